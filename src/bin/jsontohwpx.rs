@@ -4,6 +4,7 @@ use std::process;
 
 use clap::Parser;
 
+use hwpers::jsontohwpx::model::PageNumberStyle;
 use hwpers::jsontohwpx::{self, ApiResponse, JsonToHwpxError};
 
 #[derive(Parser)]
@@ -12,7 +13,7 @@ struct Cli {
     /// 입력 JSON 파일 경로 ('-'이면 stdin에서 읽기)
     input: String,
 
-    /// 출력 HWPX 파일 경로 (미지정 시 {atclId}.hwpx)
+    /// 출력 HWPX 파일 경로. 디렉터리거나 미지정이면 그 안(현재 디렉터리)에 filenameTemplate 이름으로 저장
     #[arg(short, long)]
     output: Option<PathBuf>,
 
@@ -31,6 +32,34 @@ struct Cli {
     /// 헤더 포함 강제 (JSON의 includeHeader 무시)
     #[arg(long)]
     include_header: bool,
+
+    /// 용지 크기 (A4, A3, Letter)
+    #[arg(long)]
+    page_size: Option<String>,
+
+    /// 가로 방향
+    #[arg(long)]
+    landscape: bool,
+
+    /// 꼬리말 쪽 번호 (numeric, dashed, romanLower, romanUpper, 값 없이 쓰면 dashed)
+    #[arg(long, num_args = 0..=1, default_missing_value = "dashed")]
+    page_numbers: Option<String>,
+
+    /// 헤더 라벨 언어 (ko, en)
+    #[arg(long)]
+    locale: Option<String>,
+
+    /// 그림을 읽지 않고 "[이미지: URL]" 단락으로 대신 (네트워크 없는 환경용)
+    #[arg(long)]
+    skip_images: bool,
+
+    /// 문서 끝에 생성 시각과 hwpers 버전 단락 추가 (includeProvenance 강제)
+    #[arg(long)]
+    include_provenance: bool,
+
+    /// 출력 파일 이름 템플릿 (예: "{date}_{department}_{article_id}.hwpx")
+    #[arg(long)]
+    filename_template: Option<String>,
 }
 
 fn main() {
@@ -67,12 +96,52 @@ fn run(cli: &Cli) -> Result<(), JsonToHwpxError> {
         input.options.include_header = true;
     }
 
+    // --page-size, --landscape 플래그 적용
+    if let Some(page_size) = &cli.page_size {
+        input.options.page_size = Some(page_size.clone());
+    }
+    if cli.landscape {
+        input.options.landscape = true;
+    }
+
+    // --locale 플래그 적용
+    if let Some(locale) = &cli.locale {
+        input.options.label_locale = locale.clone();
+    }
+
+    // --skip-images 플래그 적용
+    if cli.skip_images {
+        input.options.skip_images = true;
+    }
+
+    // --include-provenance 플래그 적용
+    if cli.include_provenance {
+        input.options.include_provenance = true;
+    }
+
+    // --filename-template 플래그 적용
+    if let Some(template) = &cli.filename_template {
+        input.options.filename_template = Some(template.clone());
+    }
+
+    // --page-numbers 플래그 적용
+    if let Some(value) = &cli.page_numbers {
+        let style = PageNumberStyle::parse(value).ok_or_else(|| {
+            JsonToHwpxError::Input(format!(
+                "--page-numbers는 numeric, dashed, romanLower, romanUpper 중 하나여야 합니다 ({})",
+                value
+            ))
+        })?;
+        input.options.footer_page_numbers = Some(style);
+    }
+
     // Step 2: 검증
     if cli.validate {
         log_progress(2, total_steps, "검증 중...");
         input.validate()?;
         eprintln!(
-            "검증 성공: responseCode={}, atclId={}, contents={}개",
+            "검증 성공: schemaVersion={}, responseCode={}, atclId={}, contents={}개",
+            input.schema_version()?,
             input.response_code,
             input.data.article.atcl_id,
             input.data.article.contents.len()
@@ -87,7 +156,10 @@ fn run(cli: &Cli) -> Result<(), JsonToHwpxError> {
         total_steps,
         &format!("변환 중... ({}개 콘텐츠)", content_count),
     );
-    let bytes = jsontohwpx::convert(&input, &cli.base_path)?;
+    let output = jsontohwpx::convert_to_output(&input, &cli.base_path)?;
+    for warning in &output.warnings {
+        eprintln!("[경고] {}", warning);
+    }
 
     // Step 3: 파일 저장
     let output_path = resolve_output_path(cli, &input)?;
@@ -96,7 +168,7 @@ fn run(cli: &Cli) -> Result<(), JsonToHwpxError> {
         total_steps,
         &format!("파일 저장 중... {}", output_path.display()),
     );
-    std::fs::write(&output_path, bytes)?;
+    std::fs::write(&output_path, output.bytes)?;
 
     eprintln!("변환 완료: {}", output_path.display());
     Ok(())
@@ -118,14 +190,13 @@ fn read_input(input: &str) -> Result<String, JsonToHwpxError> {
     }
 }
 
-/// 출력 경로 결정: -o 지정 시 해당 경로, 미지정 시 {atclId}.hwpx
+/// 출력 경로 결정: -o가 파일이면 그 경로, 디렉터리면 그 안에, 미지정이면 현재 디렉터리에
+/// filenameTemplate 이름 (기본 {article_id}.hwpx)
 fn resolve_output_path(cli: &Cli, input: &ApiResponse) -> Result<PathBuf, JsonToHwpxError> {
-    if let Some(ref output) = cli.output {
-        Ok(output.clone())
-    } else {
-        let atcl_id = &input.data.article.atcl_id;
-        let filename = format!("{}.hwpx", atcl_id.trim());
-        Ok(PathBuf::from(filename))
+    match &cli.output {
+        Some(output) if output.is_dir() => Ok(output.join(input.output_filename())),
+        Some(output) => Ok(output.clone()),
+        None => Ok(PathBuf::from(input.output_filename())),
     }
 }
 
@@ -140,5 +211,7 @@ fn exit_code(e: &JsonToHwpxError) -> i32 {
         JsonToHwpxError::Conversion(_) => 2,
         JsonToHwpxError::Io(_) => 3,
         JsonToHwpxError::Hwpx(_) => 2,
+        JsonToHwpxError::Encrypted(_) => 1,
+        JsonToHwpxError::LimitExceeded(..) => 1,
     }
 }
@@ -24,6 +24,7 @@ async fn main() {
         output_dir = %config.output_dir.display(),
         worker_count = config.worker_count,
         file_expiry_hours = config.file_expiry_hours,
+        limits = ?config.limits,
         "jsontohwpx-api 서버 시작"
     );
 
@@ -63,9 +64,7 @@ async fn main() {
 /// Graceful shutdown 시그널 대기
 async fn shutdown_signal() {
     let ctrl_c = async {
-        signal::ctrl_c()
-            .await
-            .expect("Ctrl+C 핸들러 설치 실패");
+        signal::ctrl_c().await.expect("Ctrl+C 핸들러 설치 실패");
     };
 
     #[cfg(unix)]
@@ -28,6 +28,9 @@ pub enum HwpError {
 
     #[error("Invalid input: {0}")]
     InvalidInput(String),
+
+    #[error("Encrypted document: {0}")]
+    Encrypted(String),
 }
 
 pub type Result<T> = std::result::Result<T, HwpError>;
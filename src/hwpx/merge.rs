@@ -0,0 +1,47 @@
+use crate::error::{HwpError, Result};
+
+use super::HwpxWriter;
+
+/// Concatenate several HWPX packages into a single document.
+///
+/// Each input keeps its own sections, which are written out as consecutive
+/// `Contents/sectionN.xml` files. Char shapes are appended per input so every
+/// section keeps its formatting, and embedded images are renumbered into one
+/// shared `BinData` directory.
+pub fn merge(docs: &[Vec<u8>]) -> Result<Vec<u8>> {
+    if docs.is_empty() {
+        return Err(HwpError::InvalidInput(
+            "No HWPX documents to merge".to_string(),
+        ));
+    }
+
+    let mut merged = HwpxWriter::new();
+    for (idx, bytes) in docs.iter().enumerate() {
+        let writer = HwpxWriter::from_hwpx_bytes(bytes).map_err(|e| {
+            HwpError::InvalidInput(format!("Failed to load document #{}: {}", idx + 1, e))
+        })?;
+        merged.append_sections_from(writer);
+    }
+
+    merged.to_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_empty_input() {
+        assert!(merge(&[]).is_err());
+    }
+
+    #[test]
+    fn test_merge_invalid_document() {
+        let mut writer = HwpxWriter::new();
+        writer.add_paragraph("ok").unwrap();
+        let docs = vec![writer.to_bytes().unwrap(), b"broken".to_vec()];
+
+        let err = merge(&docs).unwrap_err().to_string();
+        assert!(err.contains("#2"));
+    }
+}
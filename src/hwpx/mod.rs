@@ -1,11 +1,20 @@
+mod merge;
 mod reader;
 pub mod writer;
+mod xml_builder;
 mod xml_types;
 
-pub use reader::HwpxReader;
+pub use merge::merge;
+pub use reader::{
+    DocumentStatistics, ExtractOptions, ExtractedCell, ExtractedHeaderFooter, ExtractedHyperlink,
+    ExtractedImage, ExtractedNote, ExtractedRun, ExtractedTable, HwpxReader, ListKind, ListMarker,
+    OutlineParagraph, PackageIssue, PackageIssueKind, SectionText, ValidationReport,
+};
 pub use writer::{
-    CellSpan, HeaderFooterApplyTo, HwpxFooter, HwpxHeader, HwpxHyperlink, HwpxImage,
-    HwpxImageFormat, HwpxMetadata, HwpxTable, HwpxTextStyle, HwpxWriter, PageNumberFormat,
-    StyledText,
+    BorderLineStyle, CaptionPosition, CellLink, CellSpan, CellStyle, HeaderFooterApplyTo,
+    HwpxAlignment, HwpxFooter, HwpxHeader, HwpxHyperlink, HwpxImage, HwpxImageFormat, HwpxMetadata,
+    HwpxParagraphStyle, HwpxTable, HwpxTextStyle, HwpxWriter, ImageHandle, PageNumberFormat,
+    ParagraphBorder, RunDirection, StyledText, TabAlignment, TabStop, TableHandle, TextBoxStyle,
+    TextDirection, TextShadow, ValidationIssue, ValidationIssueKind,
 };
 pub use xml_types::*;
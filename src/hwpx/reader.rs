@@ -1,21 +1,328 @@
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::{BufReader, Read};
 use std::path::Path;
+use std::sync::Arc;
 use zip::ZipArchive;
 
 use crate::error::{HwpError, Result};
 use crate::model::{
     para_char_shape::{CharPositionShape, ParaCharShape},
-    CharShape, DocumentProperties, FaceName, HwpDocument, ParaShape, ParaText, Paragraph, Section,
+    CharShape, DocumentProperties, FaceName, HwpDocument, PageDef, PageLayout, PageOrientation,
+    PaperSize, ParaShape, ParaText, Paragraph, Section,
 };
 use crate::parser::body_text::BodyText;
 use crate::parser::doc_info::DocInfo;
 use crate::parser::header::FileHeader;
+use crate::preview::SummaryInfo;
 
-use super::xml_types::{self, HcfVersion, Head, Run, Section as XmlSection, XmlParagraph};
+use super::writer::{
+    CellSpan, HeaderFooterApplyTo, HwpxImage, HwpxImageFormat, HwpxMetadata, HwpxTable,
+};
+use super::xml_types::{
+    self, HcfVersion, Head, ParaPr, Run, SecPr, Section as XmlSection, SubList, XmlHeaderFooter,
+    XmlNote, XmlParagraph, XmlPicture, XmlTable, XmlTableCell,
+};
 
 pub struct HwpxReader;
 
+/// Paragraphs [`HwpxReader::preview_text`] reads from `section0.xml` when the
+/// package has no preview text
+const PREVIEW_FALLBACK_PARAGRAPHS: usize = 20;
+
+/// Columns a table read from a file may cover when its `colCnt` is missing or
+/// larger; spans reaching further are cut off there
+const MAX_TABLE_COLUMNS: u32 = 1000;
+
+/// A body table read back from `hp:tbl`, with its cell texts and merges.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedTable {
+    /// Global index of the paragraph holding the table
+    pub paragraph_index: usize,
+    pub row_count: usize,
+    pub col_count: usize,
+    /// Cells of each `hp:tr` in document order. Cells covered by a merge are not
+    /// written in HWPX and have no entry.
+    pub rows: Vec<Vec<ExtractedCell>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedCell {
+    pub row: usize,
+    pub col: usize,
+    pub span: CellSpan,
+    /// Paragraphs of the cell joined with `\n`
+    pub text: String,
+}
+
+impl ExtractedTable {
+    /// The cell whose area contains (`row`, `col`), following merges
+    pub fn cell(&self, row: usize, col: usize) -> Option<&ExtractedCell> {
+        self.rows.iter().flatten().find(|cell| {
+            (cell.row..cell.row + cell.span.row_span as usize).contains(&row)
+                && (cell.col..cell.col + cell.span.col_span as usize).contains(&col)
+        })
+    }
+
+    /// Cell texts by logical (row, col); covered cells are empty, as in
+    /// [`HwpxTable::rows`].
+    pub fn grid(&self) -> Vec<Vec<String>> {
+        let mut grid = vec![vec![String::new(); self.col_count]; self.row_count];
+        for cell in self.rows.iter().flatten() {
+            if let Some(slot) = grid.get_mut(cell.row).and_then(|r| r.get_mut(cell.col)) {
+                *slot = cell.text.clone();
+            }
+        }
+        grid
+    }
+}
+
+/// A body picture read back from `hp:pic` together with the BinData entry it
+/// references.
+#[derive(Debug, Clone)]
+pub struct ExtractedImage {
+    /// Global index of the paragraph holding the picture
+    pub paragraph_index: usize,
+    /// Path of the BinData entry in the package, e.g. `BinData/image1.png`
+    pub source_name: String,
+    pub data: Arc<Vec<u8>>,
+    pub format: HwpxImageFormat,
+    /// Placed size from `hp:orgSz`, or the pixel size at 96 DPI when it is missing
+    pub width_mm: Option<u32>,
+    pub height_mm: Option<u32>,
+}
+
+/// A hyperlink read back from an `hp:hyperlink` control and the text of its run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedHyperlink {
+    /// Global index of the paragraph holding the link; links in table cells
+    /// report the paragraph holding the table
+    pub paragraph_index: usize,
+    /// Anchor text, empty when the control has no text after it
+    pub text: String,
+    pub url: String,
+}
+
+/// A footnote or endnote read back from an `hp:footNote` / `hp:endNote` control.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedNote {
+    /// Global index of the body paragraph holding the note mark
+    pub paragraph_index: usize,
+    /// Position of the note mark in the paragraph text, in UTF-16 units: the
+    /// length of the text of the runs before the control's run
+    pub anchor_offset: usize,
+    /// Paragraphs of the note joined with `\n`
+    pub text: String,
+}
+
+/// The body text of one section from [`HwpxReader::extract_sections`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SectionText {
+    pub index: usize,
+    /// Paragraph texts in the order of [`HwpDocument::extract_text`], which is
+    /// these paragraphs of every section joined with `\n` terminators
+    pub paragraphs: Vec<String>,
+}
+
+/// A header or footer read back from the controls of a section's first paragraph.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedHeaderFooter {
+    pub section_index: usize,
+    /// From `applyPageType`. A first-page-only header is written as BOTH and
+    /// reads back as [`HeaderFooterApplyTo::All`].
+    pub apply_to: HeaderFooterApplyTo,
+    /// Paragraphs joined with `\n` and trimmed; a page number contributes no
+    /// text, so `"- "` before one reads back as `"-"`
+    pub text: String,
+    /// Whether it holds a page number (`hp:autoNum numType="PAGE"`)
+    pub has_page_number: bool,
+}
+
+/// A stretch of paragraph text with one char shape, with the attributes of its
+/// `hh:charPr` resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedRun {
+    pub text: String,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub size_pt: f32,
+    /// 0xRRGGBB
+    pub color: u32,
+}
+
+/// Kind of list a paragraph belongs to, from the `type` of its paraPr `hh:heading`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListKind {
+    /// Numbered with an `hh:numbering`
+    Numbered,
+    /// Outline heading, numbered with the section's outline numbering
+    Outline,
+    /// Marked with an `hh:bullet` character
+    Bullet,
+}
+
+/// List membership of a paragraph.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListMarker {
+    pub kind: ListKind,
+    /// Zero-based list level
+    pub level: u32,
+    /// Item number within its level; `None` for bullets
+    pub number: Option<u32>,
+    /// Text shown before the paragraph, e.g. `2.`, `가)` or `●`
+    pub label: String,
+}
+
+/// A body paragraph from [`HwpxReader::extract_outline`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutlineParagraph {
+    /// Global index of the body paragraph
+    pub paragraph_index: usize,
+    pub text: String,
+    /// `None` when the paragraph is not part of a list
+    pub list: Option<ListMarker>,
+}
+
+/// Counts from [`HwpxReader::statistics`]. Text counts cover the body and table
+/// cells but not headers, footers or captions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocumentStatistics {
+    pub section_count: usize,
+    /// Body paragraphs with text; paragraphs that only hold a table or picture
+    /// and paragraphs inside cells are not counted
+    pub paragraph_count: usize,
+    /// Characters (not bytes), including whitespace
+    pub char_count: usize,
+    pub char_count_no_spaces: usize,
+    /// Runs of non-whitespace characters
+    pub word_count: usize,
+    /// Tables, including tables nested in cells
+    pub table_count: usize,
+    /// Placed pictures, including pictures in cells
+    pub image_count: usize,
+}
+
+/// Category of a problem reported by [`HwpxReader::validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageIssueKind {
+    /// The bytes are not a readable ZIP archive, or its entries are encrypted.
+    InvalidArchive,
+    /// `mimetype`, `Contents/content.hpf`, `Contents/header.xml` or a part listed
+    /// in the spine is absent.
+    MissingPart,
+    /// A part cannot be parsed, or `mimetype` has the wrong content.
+    MalformedPart,
+    /// A picture's `binaryItemIDRef` leads to no BinData entry.
+    DanglingBinaryItem,
+    /// A run's `charPrIDRef` has no `hh:charPr` in header.xml.
+    UnknownCharShape,
+    /// Two cells of a table cover the same grid position.
+    OverlappingCells,
+}
+
+/// A single problem found by [`HwpxReader::validate`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackageIssue {
+    /// Archive path of the part the problem is in; empty for the archive itself
+    pub part: String,
+    pub kind: PackageIssueKind,
+    pub message: String,
+}
+
+impl std::fmt::Display for PackageIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.part.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.part, self.message)
+        }
+    }
+}
+
+/// Structural problems of an HWPX package, from [`HwpxReader::validate`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport {
+    pub issues: Vec<PackageIssue>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    fn push(&mut self, part: &str, kind: PackageIssueKind, message: String) {
+        self.issues.push(PackageIssue {
+            part: part.to_string(),
+            kind,
+            message,
+        });
+    }
+}
+
+/// What the parts of a package define, for checking the references to them.
+struct PackageRefs {
+    entry_names: HashSet<String>,
+    /// content.hpf manifest: item id to archive path
+    manifest: HashMap<String, String>,
+    /// `None` when header.xml could not be read
+    head: Option<Head>,
+    char_pr_ids: HashSet<u32>,
+}
+
+impl PackageRefs {
+    /// Whether a `binaryItemIDRef` leads to an entry of the archive, looking the
+    /// id up like [`HwpxReader::extract_images`] does
+    fn resolves_binary_item(&self, item_id: &str) -> bool {
+        let from_head = self
+            .head
+            .as_ref()
+            .and_then(|h| h.ref_list.as_ref())
+            .and_then(|r| r.bin_data_items.as_ref())
+            .and_then(|items| items.items.iter().find(|item| item.id == item_id))
+            .and_then(|item| item.src.as_ref());
+        if let Some(src) = self.manifest.get(item_id).or(from_head) {
+            return self.entry_names.contains(src);
+        }
+        let prefix = format!("BinData/{}.", item_id);
+        self.entry_names.iter().any(|n| n.starts_with(&prefix))
+    }
+}
+
+/// What [`HwpxReader::extract_text_with_options`] includes besides the body text.
+#[derive(Debug, Clone)]
+pub struct ExtractOptions {
+    /// Text of table cells, after the paragraph holding the table
+    pub include_tables: bool,
+    /// Header text before and footer text after each section's body
+    pub include_headers_footers: bool,
+    /// Written after every non-empty paragraph
+    pub paragraph_separator: String,
+}
+
+impl Default for ExtractOptions {
+    /// The output of [`HwpDocument::extract_text`]
+    fn default() -> Self {
+        Self {
+            include_tables: false,
+            include_headers_footers: false,
+            paragraph_separator: "\n".to_string(),
+        }
+    }
+}
+
+/// Everything [`HwpxWriter`](super::HwpxWriter) needs to re-serialize an existing package.
+///
+/// Tables and pictures have no place in the plain [`HwpDocument`] model, so they are
+/// collected separately, keyed by their global paragraph index like the writer does.
+pub(crate) struct HwpxPackageContents {
+    pub document: HwpDocument,
+    pub tables: Vec<(usize, HwpxTable)>,
+    pub images: Vec<(usize, HwpxImage)>,
+    /// hh:paraPr entries of header.xml; paragraphs refer to them by `para_shape_id`
+    pub para_properties: Vec<ParaPr>,
+}
+
 impl HwpxReader {
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<HwpDocument> {
         let file = File::open(path).map_err(HwpError::Io)?;
@@ -24,30 +331,973 @@ impl HwpxReader {
     }
 
     pub fn from_bytes(bytes: &[u8]) -> Result<HwpDocument> {
-        let cursor = std::io::Cursor::new(bytes.to_vec());
-        Self::from_reader(cursor)
+        Self::from_reader(std::io::Cursor::new(bytes))
     }
 
-    fn from_reader<R: Read + std::io::Seek>(reader: R) -> Result<HwpDocument> {
-        let mut archive = ZipArchive::new(reader)
-            .map_err(|e| HwpError::InvalidFormat(format!("Invalid HWPX archive: {}", e)))?;
+    /// Read the preview text from `Preview/PrvText.txt` without parsing any section.
+    /// A package without one falls back to the first paragraphs of `section0.xml`,
+    /// which is read only as far as needed.
+    pub fn preview_text(bytes: &[u8]) -> Result<String> {
+        let mut archive = Self::open_archive(std::io::Cursor::new(bytes))?;
+        if let Ok(mut entry) = archive.by_name("Preview/PrvText.txt") {
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data).map_err(HwpError::Io)?;
+            return Ok(String::from_utf8_lossy(&data).into_owned());
+        }
+
+        let filename = "Contents/section0.xml";
+        let file = Self::open_entry(&mut archive, filename)?;
+        let texts =
+            xml_types::leading_paragraph_texts(BufReader::new(file), PREVIEW_FALLBACK_PARAGRAPHS)
+                .map_err(|e| HwpError::ParseError(format!("Failed to parse {}: {}", filename, e)))?;
+        let texts: Vec<String> = texts.into_iter().filter(|t| !t.is_empty()).collect();
+        Ok(texts.join("\n"))
+    }
+
+    /// Check a package for the structural problems that make other tools reject
+    /// it: missing mandatory parts, pictures without data, runs with undefined char
+    /// shapes and tables with overlapping merges. Unlike the other readers this
+    /// never fails; problems that stop the check early are part of the report.
+    pub fn validate(bytes: &[u8]) -> ValidationReport {
+        use PackageIssueKind::*;
+
+        let mut report = ValidationReport::default();
+        let mut archive = match Self::open_archive(std::io::Cursor::new(bytes)) {
+            Ok(archive) => archive,
+            Err(e) => {
+                report.push("", InvalidArchive, e.to_string());
+                return report;
+            }
+        };
+
+        match Self::read_xml_file(&mut archive, "mimetype") {
+            Ok(mimetype) if mimetype.trim() == "application/hwp+zip" => {}
+            Ok(mimetype) => report.push(
+                "mimetype",
+                MalformedPart,
+                format!("unexpected mimetype {:?}", mimetype.trim()),
+            ),
+            Err(_) => report.push("mimetype", MissingPart, "not in the archive".to_string()),
+        }
+
+        let mut refs = PackageRefs {
+            entry_names: archive.file_names().map(String::from).collect(),
+            manifest: HashMap::new(),
+            head: None,
+            char_pr_ids: HashSet::new(),
+        };
+
+        // Sections come from the spine; without a readable content.hpf, from the
+        // archive's file names
+        let content_hpf = "Contents/content.hpf";
+        let mut section_names = None;
+        match Self::read_xml_file(&mut archive, content_hpf) {
+            Err(_) => report.push(content_hpf, MissingPart, "not in the archive".to_string()),
+            Ok(xml) => match xml_types::parse_content_hpf(&xml) {
+                Err(e) => report.push(content_hpf, MalformedPart, e.to_string()),
+                Ok(package) => {
+                    refs.manifest = package
+                        .manifest
+                        .into_iter()
+                        .flat_map(|m| m.items)
+                        .map(|item| (item.id, item.href))
+                        .collect();
+                    let mut names = Vec::new();
+                    for item_ref in package.spine.into_iter().flat_map(|s| s.item_refs) {
+                        let Some(href) = refs.manifest.get(&item_ref.idref) else {
+                            report.push(
+                                content_hpf,
+                                MissingPart,
+                                format!("spine item {:?} is not in the manifest", item_ref.idref),
+                            );
+                            continue;
+                        };
+                        if !refs.entry_names.contains(href) {
+                            report.push(
+                                href,
+                                MissingPart,
+                                "listed in the spine but not in the archive".to_string(),
+                            );
+                        } else if href.starts_with("Contents/section") {
+                            names.push(href.clone());
+                        }
+                    }
+                    section_names = Some(names);
+                }
+            },
+        }
+        let section_names = section_names.unwrap_or_else(|| {
+            let mut numbered: Vec<(u32, String)> = refs
+                .entry_names
+                .iter()
+                .filter_map(|name| {
+                    let idx = name
+                        .strip_prefix("Contents/section")?
+                        .strip_suffix(".xml")?
+                        .parse()
+                        .ok()?;
+                    Some((idx, name.clone()))
+                })
+                .collect();
+            numbered.sort();
+            numbered.into_iter().map(|(_, name)| name).collect()
+        });
+
+        let header = "Contents/header.xml";
+        match Self::read_header(&mut archive) {
+            Ok(head) => {
+                refs.char_pr_ids = head
+                    .ref_list
+                    .iter()
+                    .flat_map(|r| &r.char_properties)
+                    .flat_map(|props| &props.items)
+                    .map(|char_pr| char_pr.id)
+                    .collect();
+                refs.head = Some(head);
+            }
+            Err(HwpError::NotFound(_)) => {
+                report.push(header, MissingPart, "not in the archive".to_string())
+            }
+            Err(e) => report.push(header, MalformedPart, e.to_string()),
+        }
+
+        for name in &section_names {
+            let section = Self::open_entry(&mut archive, name).and_then(|file| {
+                xml_types::parse_section_from_reader(BufReader::new(file))
+                    .map_err(|e| HwpError::ParseError(e.to_string()))
+            });
+            match section {
+                Ok(section) => {
+                    let mut unknown_char_prs = HashSet::new();
+                    for (para_idx, para) in section.paragraphs.iter().enumerate() {
+                        Self::check_paragraph(
+                            name,
+                            para_idx,
+                            para,
+                            &refs,
+                            &mut unknown_char_prs,
+                            &mut report,
+                        );
+                    }
+                }
+                Err(e) => report.push(name, MalformedPart, e.to_string()),
+            }
+        }
+        report
+    }
+
+    /// Check the references of a paragraph and of everything nested in it.
+    /// Each undefined char shape is reported once per section.
+    fn check_paragraph(
+        part: &str,
+        para_idx: usize,
+        para: &XmlParagraph,
+        refs: &PackageRefs,
+        unknown_char_prs: &mut HashSet<u32>,
+        report: &mut ValidationReport,
+    ) {
+        for run in &para.runs {
+            if let Some(id) = run.char_pr_id_ref {
+                if refs.head.is_some()
+                    && !refs.char_pr_ids.contains(&id)
+                    && unknown_char_prs.insert(id)
+                {
+                    report.push(
+                        part,
+                        PackageIssueKind::UnknownCharShape,
+                        format!("paragraph {}: charPrIDRef {} is not defined", para_idx, id),
+                    );
+                }
+            }
+
+            let item_id = run
+                .picture
+                .as_ref()
+                .and_then(|pic| pic.img.as_ref())
+                .and_then(|img| img.binary_item_id_ref.as_deref());
+            if let Some(item_id) = item_id {
+                if !refs.resolves_binary_item(item_id) {
+                    report.push(
+                        part,
+                        PackageIssueKind::DanglingBinaryItem,
+                        format!(
+                            "paragraph {}: binaryItemIDRef {:?} has no BinData entry",
+                            para_idx, item_id
+                        ),
+                    );
+                }
+            }
+
+            let mut sub_lists: Vec<&SubList> = Vec::new();
+            if let Some(table) = &run.table {
+                if let Some((row, col)) = Self::overlapping_cell(table) {
+                    report.push(
+                        part,
+                        PackageIssueKind::OverlappingCells,
+                        format!(
+                            "paragraph {}: table cells overlap at row {}, column {}",
+                            para_idx, row, col
+                        ),
+                    );
+                }
+                let cells = table.rows.iter().flat_map(|r| &r.cells);
+                sub_lists.extend(cells.filter_map(|c| c.sub_list.as_ref()));
+            }
+            for ctrl in &run.ctrls {
+                let header_footer = ctrl.header.iter().chain(&ctrl.footer);
+                sub_lists.extend(header_footer.filter_map(|h| h.sub_list.as_ref()));
+                let notes = ctrl.foot_note.iter().chain(&ctrl.end_note);
+                sub_lists.extend(notes.filter_map(|n| n.sub_list.as_ref()));
+            }
+            for nested in sub_lists.into_iter().flat_map(|s| &s.paragraphs) {
+                Self::check_paragraph(part, para_idx, nested, refs, unknown_char_prs, report);
+            }
+        }
+    }
+
+    /// The first grid position, as (row, column), covered by two cells
+    fn overlapping_cell(table: &XmlTable) -> Option<(u32, u32)> {
+        let mut covered = HashSet::new();
+        for (row_idx, row) in table.rows.iter().enumerate() {
+            for (cell_idx, cell) in row.cells.iter().enumerate() {
+                let (row, col, span) = Self::cell_area(table, row_idx, cell_idx, cell);
+                for r in row..row.saturating_add(span.row_span) {
+                    for c in col..col.saturating_add(span.col_span) {
+                        if !covered.insert((r, c)) {
+                            return Some((r, c));
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Grid position (row, column) and span of the `cell_idx`-th cell in row
+    /// `row_idx`. A cell without `hp:cellAddr` sits where it is in the XML. Spans
+    /// are cut off at the last row and at [`Self::column_limit`], so values from a
+    /// damaged file cannot cover more of the grid than the table has.
+    fn cell_area(
+        table: &XmlTable,
+        row_idx: usize,
+        cell_idx: usize,
+        cell: &XmlTableCell,
+    ) -> (u32, u32, CellSpan) {
+        let addr = cell.cell_addr.as_ref();
+        let row = addr.and_then(|a| a.row_addr).unwrap_or(row_idx as u32);
+        let col = addr.and_then(|a| a.col_addr).unwrap_or(cell_idx as u32);
+        let span = cell.cell_span.as_ref();
+        let row_span = span.and_then(|s| s.row_span).or(cell.row_span).unwrap_or(1);
+        let col_span = span.and_then(|s| s.col_span).or(cell.col_span).unwrap_or(1);
+        let rows_left = (table.rows.len() as u32).saturating_sub(row);
+        let cols_left = Self::column_limit(table).saturating_sub(col);
+        let span = CellSpan {
+            col_span: col_span.min(cols_left).max(1),
+            row_span: row_span.min(rows_left).max(1),
+        };
+        (row, col, span)
+    }
+
+    /// Columns of a table read from a file: its `colCnt`, at most [`MAX_TABLE_COLUMNS`]
+    fn column_limit(table: &XmlTable) -> u32 {
+        table
+            .col_cnt
+            .unwrap_or(MAX_TABLE_COLUMNS)
+            .min(MAX_TABLE_COLUMNS)
+    }
+
+    /// Read the document metadata (title, subject, keywords, ...) from `Contents/content.hpf`.
+    pub fn metadata_from_bytes(bytes: &[u8]) -> Result<HwpxMetadata> {
+        let mut archive = Self::open_archive(std::io::Cursor::new(bytes))?;
+        Self::read_metadata(&mut archive)
+    }
+
+    /// Read the document metadata from an HWPX file; see [`HwpxReader::metadata_from_bytes`].
+    pub fn metadata_from_file<P: AsRef<Path>>(path: P) -> Result<HwpxMetadata> {
+        let file = File::open(path).map_err(HwpError::Io)?;
+        let mut archive = Self::open_archive(BufReader::new(file))?;
+        Self::read_metadata(&mut archive)
+    }
+
+    /// Read a package from any seekable source, e.g. an open `File`, without
+    /// loading the whole archive into memory first.
+    pub fn from_reader<R: Read + std::io::Seek>(reader: R) -> Result<HwpDocument> {
+        let mut archive = Self::open_archive(reader)?;
+
+        let (document, _, _) = Self::read_document(&mut archive, false)?;
+        Ok(document)
+    }
+
+    /// Like [`HwpxReader::from_bytes`], but tolerates packages from other producers
+    /// that miss non-essential parts: no or a wrong `mimetype`, no `version.xml`,
+    /// an unreadable `header.xml`, section files that fail to parse or are not
+    /// numbered consecutively. What was skipped is listed in
+    /// [`HwpDocument::warnings`]; it is an error only if no section can be read.
+    pub fn from_bytes_lenient(bytes: &[u8]) -> Result<HwpDocument> {
+        let mut archive = Self::open_archive(std::io::Cursor::new(bytes))?;
+        let (document, _, _) = Self::read_document(&mut archive, true)?;
+        Ok(document)
+    }
+
+    /// Read the tables of the body sections, which [`HwpDocument::extract_text`]
+    /// leaves out. Tables nested in cells are not listed separately.
+    pub fn extract_tables(bytes: &[u8]) -> Result<Vec<ExtractedTable>> {
+        let mut archive = Self::open_archive(std::io::Cursor::new(bytes))?;
+        Self::read_tables(&mut archive)
+    }
+
+    /// Read the tables of an HWPX file; see [`HwpxReader::extract_tables`].
+    pub fn extract_tables_from_file<P: AsRef<Path>>(path: P) -> Result<Vec<ExtractedTable>> {
+        let file = File::open(path).map_err(HwpError::Io)?;
+        let mut archive = Self::open_archive(BufReader::new(file))?;
+        Self::read_tables(&mut archive)
+    }
+
+    fn read_tables<R: Read + std::io::Seek>(
+        archive: &mut ZipArchive<R>,
+    ) -> Result<Vec<ExtractedTable>> {
+        let sections = Self::read_sections(archive)?;
+        let paragraphs = sections.iter().flat_map(|s| &s.paragraphs);
+        Ok(paragraphs
+            .enumerate()
+            .flat_map(|(para_idx, xml_para)| {
+                xml_para
+                    .runs
+                    .iter()
+                    .filter_map(|run| run.table.as_ref())
+                    .map(move |table| Self::extract_table(para_idx, table))
+            })
+            .collect())
+    }
+
+    /// Read the pictures placed in the body sections, in document order. A picture
+    /// placed several times is listed once per placement.
+    pub fn extract_images(bytes: &[u8]) -> Result<Vec<ExtractedImage>> {
+        let mut archive = Self::open_archive(std::io::Cursor::new(bytes))?;
+        Self::read_images(&mut archive)
+    }
+
+    /// Read the pictures of an HWPX file; see [`HwpxReader::extract_images`].
+    pub fn extract_images_from_file<P: AsRef<Path>>(path: P) -> Result<Vec<ExtractedImage>> {
+        let file = File::open(path).map_err(HwpError::Io)?;
+        let mut archive = Self::open_archive(BufReader::new(file))?;
+        Self::read_images(&mut archive)
+    }
+
+    fn read_images<R: Read + std::io::Seek>(
+        archive: &mut ZipArchive<R>,
+    ) -> Result<Vec<ExtractedImage>> {
+        let head = Self::read_header(archive)?;
+        let sections = Self::read_sections(archive)?;
+        let mut images = Vec::new();
+        let paragraphs = sections.iter().flat_map(|s| &s.paragraphs);
+        for (para_idx, xml_para) in paragraphs.enumerate() {
+            for picture in xml_para.runs.iter().filter_map(|run| run.picture.as_ref()) {
+                if let Some(image) = Self::read_picture(archive, &head, para_idx, picture)? {
+                    images.push(image);
+                }
+            }
+        }
+        Ok(images)
+    }
+
+    /// Read every hyperlink of the body sections, including those in table cells,
+    /// in document order.
+    pub fn extract_hyperlinks(bytes: &[u8]) -> Result<Vec<ExtractedHyperlink>> {
+        let mut archive = Self::open_archive(std::io::Cursor::new(bytes))?;
+        Self::read_hyperlinks(&mut archive)
+    }
 
-        let version = Self::read_version(&mut archive)?;
+    /// Read the hyperlinks of an HWPX file; see [`HwpxReader::extract_hyperlinks`].
+    pub fn extract_hyperlinks_from_file<P: AsRef<Path>>(
+        path: P,
+    ) -> Result<Vec<ExtractedHyperlink>> {
+        let file = File::open(path).map_err(HwpError::Io)?;
+        let mut archive = Self::open_archive(BufReader::new(file))?;
+        Self::read_hyperlinks(&mut archive)
+    }
+
+    fn read_hyperlinks<R: Read + std::io::Seek>(
+        archive: &mut ZipArchive<R>,
+    ) -> Result<Vec<ExtractedHyperlink>> {
+        let sections = Self::read_sections(archive)?;
+        let mut links = Vec::new();
+        let paragraphs = sections.iter().flat_map(|s| &s.paragraphs);
+        for (para_idx, xml_para) in paragraphs.enumerate() {
+            Self::collect_hyperlinks(para_idx, xml_para, &mut links);
+        }
+        Ok(links)
+    }
+
+    fn collect_hyperlinks(
+        paragraph_index: usize,
+        xml_para: &XmlParagraph,
+        links: &mut Vec<ExtractedHyperlink>,
+    ) {
+        for run in &xml_para.runs {
+            let urls = run
+                .ctrls
+                .iter()
+                .filter_map(|ctrl| ctrl.hyperlink.as_ref()?.url.as_deref());
+            for url in urls {
+                links.push(ExtractedHyperlink {
+                    paragraph_index,
+                    text: run.text.clone().unwrap_or_default(),
+                    url: url.to_string(),
+                });
+            }
+            let cells = run
+                .table
+                .iter()
+                .flat_map(|t| &t.rows)
+                .flat_map(|r| &r.cells);
+            let cell_paragraphs = cells
+                .filter_map(|cell| cell.sub_list.as_ref())
+                .flat_map(|sub_list| &sub_list.paragraphs);
+            for cell_para in cell_paragraphs {
+                Self::collect_hyperlinks(paragraph_index, cell_para, links);
+            }
+        }
+    }
+
+    /// Read the footnotes of the body paragraphs, in document order.
+    pub fn extract_footnotes(bytes: &[u8]) -> Result<Vec<ExtractedNote>> {
+        let mut archive = Self::open_archive(std::io::Cursor::new(bytes))?;
+        let sections = Self::read_sections(&mut archive)?;
+        Ok(Self::collect_notes(&sections, |ctrl| {
+            ctrl.foot_note.as_ref()
+        }))
+    }
+
+    /// Read the endnotes of the body paragraphs, in document order.
+    pub fn extract_endnotes(bytes: &[u8]) -> Result<Vec<ExtractedNote>> {
+        let mut archive = Self::open_archive(std::io::Cursor::new(bytes))?;
+        let sections = Self::read_sections(&mut archive)?;
+        Ok(Self::collect_notes(&sections, |ctrl| {
+            ctrl.end_note.as_ref()
+        }))
+    }
+
+    fn collect_notes(
+        sections: &[XmlSection],
+        select: impl Fn(&xml_types::XmlCtrl) -> Option<&XmlNote>,
+    ) -> Vec<ExtractedNote> {
+        let mut notes = Vec::new();
+        let paragraphs = sections.iter().flat_map(|s| &s.paragraphs);
+        for (paragraph_index, xml_para) in paragraphs.enumerate() {
+            let mut anchor_offset = 0;
+            for run in &xml_para.runs {
+                for note in run.ctrls.iter().filter_map(&select) {
+                    notes.push(ExtractedNote {
+                        paragraph_index,
+                        anchor_offset,
+                        text: Self::sub_list_text(note.sub_list.as_ref()),
+                    });
+                }
+                anchor_offset += run.text.as_deref().map_or(0, |t| t.encode_utf16().count());
+            }
+        }
+        notes
+    }
+
+    /// Plain text like [`HwpDocument::extract_text`], optionally with table cells
+    /// and headers/footers for search and indexing.
+    pub fn extract_text_with_options(bytes: &[u8], options: &ExtractOptions) -> Result<String> {
+        let mut archive = Self::open_archive(std::io::Cursor::new(bytes))?;
+        let sections = Self::read_sections(&mut archive)?;
+
+        let mut text = String::new();
+        for section in &sections {
+            let controls = section
+                .paragraphs
+                .first()
+                .into_iter()
+                .flat_map(|p| &p.runs)
+                .flat_map(|r| &r.ctrls);
+            let (mut headers, mut footers) = (Vec::new(), Vec::new());
+            if options.include_headers_footers {
+                for ctrl in controls {
+                    headers.extend(ctrl.header.iter().flat_map(|h| &h.sub_list));
+                    footers.extend(ctrl.footer.iter().flat_map(|f| &f.sub_list));
+                }
+            }
+
+            let header_paragraphs = headers.iter().flat_map(|s| &s.paragraphs);
+            let footer_paragraphs = footers.iter().flat_map(|s| &s.paragraphs);
+            for para in header_paragraphs
+                .chain(&section.paragraphs)
+                .chain(footer_paragraphs)
+            {
+                Self::push_paragraph_text(para, options, &mut text);
+            }
+        }
+        Ok(text)
+    }
+
+    fn push_paragraph_text(para: &XmlParagraph, options: &ExtractOptions, text: &mut String) {
+        let para_text = Self::paragraph_text(para);
+        if !para_text.is_empty() {
+            text.push_str(&para_text);
+            text.push_str(&options.paragraph_separator);
+        }
+        if !options.include_tables {
+            return;
+        }
+        let cells = para
+            .runs
+            .iter()
+            .filter_map(|r| r.table.as_ref())
+            .flat_map(|t| &t.rows)
+            .flat_map(|r| &r.cells);
+        for cell_para in cells.flat_map(|c| c.sub_list.iter().flat_map(|s| &s.paragraphs)) {
+            Self::push_paragraph_text(cell_para, options, text);
+        }
+    }
+
+    fn paragraph_text(para: &XmlParagraph) -> String {
+        para.runs.iter().filter_map(|r| r.text.as_deref()).collect()
+    }
+
+    /// Read the body text grouped by section.
+    pub fn extract_sections(bytes: &[u8]) -> Result<Vec<SectionText>> {
+        let document = Self::from_bytes(bytes)?;
+        Ok(document
+            .sections()
+            .enumerate()
+            .map(|(index, section)| SectionText {
+                index,
+                paragraphs: section
+                    .paragraphs
+                    .iter()
+                    .filter_map(|p| p.text.as_ref())
+                    .map(|t| t.content.clone())
+                    .collect(),
+            })
+            .collect())
+    }
+
+    /// Read the body paragraphs as styled runs, one `Vec` per paragraph in the
+    /// order of [`HwpDocument::extract_text`]. Neighbouring `hp:run`s with the same
+    /// char shape come back as one run.
+    pub fn extract_styled_paragraphs(bytes: &[u8]) -> Result<Vec<Vec<ExtractedRun>>> {
+        let document = Self::from_bytes(bytes)?;
+        let char_shapes = &document.doc_info.char_shapes;
+        Ok(document
+            .sections()
+            .flat_map(|section| &section.paragraphs)
+            .map(|para| Self::styled_runs(char_shapes, para))
+            .collect())
+    }
+
+    fn styled_runs(char_shapes: &[CharShape], para: &Paragraph) -> Vec<ExtractedRun> {
+        let text = para.text.as_ref().map_or("", |t| t.content.as_str());
+        let units: Vec<u16> = text.encode_utf16().collect();
+        let positions = para
+            .char_shapes
+            .as_ref()
+            .map(|cs| cs.char_positions.as_slice())
+            .unwrap_or_default();
+
+        // Text before the first position uses charPr 0
+        let mut starts: Vec<(usize, u16)> = Vec::with_capacity(positions.len() + 1);
+        if positions.first().is_none_or(|p| p.position > 0) {
+            starts.push((0, 0));
+        }
+        starts.extend(
+            positions
+                .iter()
+                .map(|p| ((p.position as usize).min(units.len()), p.char_shape_id)),
+        );
+
+        let default_shape = CharShape::new_default();
+        starts
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, &(start, shape_id))| {
+                let end = starts.get(idx + 1).map_or(units.len(), |&(end, _)| end);
+                if start >= end {
+                    return None;
+                }
+                let shape = char_shapes.get(shape_id as usize).unwrap_or(&default_shape);
+                Some(ExtractedRun {
+                    text: String::from_utf16_lossy(&units[start..end]),
+                    bold: shape.is_bold(),
+                    italic: shape.is_italic(),
+                    underline: shape.is_underline(),
+                    size_pt: shape.base_size as f32 / 100.0,
+                    color: shape.text_color,
+                })
+            })
+            .collect()
+    }
+
+    /// Read the body paragraphs with their list level and item number.
+    ///
+    /// Numbers are counted per numbering over the whole document, the way Hangul
+    /// shows them: an item restarts every deeper level, and paragraphs outside the
+    /// list do not interrupt the count.
+    pub fn extract_outline(bytes: &[u8]) -> Result<Vec<OutlineParagraph>> {
+        let mut archive = Self::open_archive(std::io::Cursor::new(bytes))?;
         let head = Self::read_header(&mut archive)?;
         let sections = Self::read_sections(&mut archive)?;
 
+        let ref_list = head.ref_list.as_ref();
+        let headings: HashMap<u32, &xml_types::ParaPrHeading> = ref_list
+            .and_then(|r| r.para_properties.as_ref())
+            .into_iter()
+            .flat_map(|p| &p.items)
+            .filter_map(|p| Some((p.id, p.heading.as_ref()?)))
+            .collect();
+        let numberings: HashMap<u32, &xml_types::Numbering> = ref_list
+            .and_then(|r| r.numberings.as_ref())
+            .into_iter()
+            .flat_map(|n| &n.items)
+            .map(|n| (n.id, n))
+            .collect();
+        let bullets: HashMap<u32, &str> = ref_list
+            .and_then(|r| r.bullets.as_ref())
+            .into_iter()
+            .flat_map(|b| &b.items)
+            .map(|b| (b.id, b.bullet_char.as_deref().unwrap_or_default()))
+            .collect();
+
+        let mut counters: HashMap<u32, Vec<Option<u32>>> = HashMap::new();
+        let mut outline = Vec::new();
+        for section in &sections {
+            let outline_id = section
+                .paragraphs
+                .iter()
+                .flat_map(|p| &p.runs)
+                .find_map(|r| r.sec_pr.as_ref())
+                .and_then(|sec_pr| sec_pr.outline_shape_id_ref)
+                .unwrap_or(1);
+
+            for para in &section.paragraphs {
+                let heading = para
+                    .para_pr_id_ref
+                    .and_then(|id| headings.get(&id))
+                    .filter(|h| h.heading_type.as_deref().is_some_and(|t| t != "NONE"));
+                let list = heading.and_then(|heading| {
+                    let level = heading.level.unwrap_or(0);
+                    let id_ref = heading.id_ref.unwrap_or(0);
+                    match heading.heading_type.as_deref()? {
+                        "BULLET" => Some(ListMarker {
+                            kind: ListKind::Bullet,
+                            level,
+                            number: None,
+                            label: bullets
+                                .get(&id_ref)
+                                .copied()
+                                .unwrap_or_default()
+                                .to_string(),
+                        }),
+                        kind @ ("NUMBER" | "OUTLINE") => {
+                            let (kind, numbering_id) = if kind == "OUTLINE" {
+                                (ListKind::Outline, outline_id)
+                            } else {
+                                (ListKind::Numbered, id_ref)
+                            };
+                            let numbering = numberings.get(&numbering_id).copied();
+                            let levels = counters.entry(numbering_id).or_default();
+                            let (number, label) = Self::next_list_number(numbering, levels, level);
+                            Some(ListMarker {
+                                kind,
+                                level,
+                                number: Some(number),
+                                label,
+                            })
+                        }
+                        _ => None,
+                    }
+                });
+                outline.push(OutlineParagraph {
+                    paragraph_index: outline.len(),
+                    text: Self::paragraph_text(para),
+                    list,
+                });
+            }
+        }
+        Ok(outline)
+    }
+
+    /// Advance the counter of `level` and format the item's label from the
+    /// level's `hh:paraHead`.
+    fn next_list_number(
+        numbering: Option<&xml_types::Numbering>,
+        levels: &mut Vec<Option<u32>>,
+        level: u32,
+    ) -> (u32, String) {
+        let para_head = |level: usize| {
+            numbering
+                .into_iter()
+                .flat_map(|n| &n.para_heads)
+                .find(|h| h.level == Some(level as u32 + 1))
+        };
+        let start = |level: usize| para_head(level).and_then(|h| h.start).unwrap_or(1);
+
+        let level = level as usize;
+        if levels.len() <= level {
+            levels.resize(level + 1, None);
+        }
+        let number = levels[level].map_or(start(level), |n| n + 1);
+        levels[level] = Some(number);
+        levels.truncate(level + 1);
+
+        let format_level = |idx: usize| {
+            let n = levels[idx].unwrap_or_else(|| start(idx));
+            let format = para_head(idx).and_then(|h| h.num_format.as_deref());
+            format_list_number(n, format.unwrap_or("DIGIT"))
+        };
+        let label = match para_head(level).and_then(|h| h.text.as_deref()) {
+            Some(template) => {
+                let mut label = String::new();
+                let mut chars = template.chars().peekable();
+                while let Some(ch) = chars.next() {
+                    match chars.peek().and_then(|c| c.to_digit(10)) {
+                        Some(digit @ 1..) if ch == '^' && digit as usize <= level + 1 => {
+                            chars.next();
+                            label.push_str(&format_level(digit as usize - 1));
+                        }
+                        _ => label.push(ch),
+                    }
+                }
+                label
+            }
+            None => format_level(level),
+        };
+        (number, label)
+    }
+
+    /// Count sections, paragraphs, characters, words, tables and pictures in one
+    /// pass over the body sections.
+    pub fn statistics(bytes: &[u8]) -> Result<DocumentStatistics> {
+        let mut archive = Self::open_archive(std::io::Cursor::new(bytes))?;
+        let sections = Self::read_sections(&mut archive)?;
+
+        let mut stats = DocumentStatistics {
+            section_count: sections.len(),
+            ..Default::default()
+        };
+        for para in sections.iter().flat_map(|s| &s.paragraphs) {
+            Self::count_paragraph(para, false, &mut stats);
+        }
+        Ok(stats)
+    }
+
+    fn count_paragraph(para: &XmlParagraph, in_cell: bool, stats: &mut DocumentStatistics) {
+        let text = Self::paragraph_text(para);
+        if !text.is_empty() && !in_cell {
+            stats.paragraph_count += 1;
+        }
+        for ch in text.chars() {
+            stats.char_count += 1;
+            if !ch.is_whitespace() {
+                stats.char_count_no_spaces += 1;
+            }
+        }
+        stats.word_count += text.split_whitespace().count();
+
+        for run in &para.runs {
+            if run.picture.is_some() {
+                stats.image_count += 1;
+            }
+            if let Some(table) = &run.table {
+                stats.table_count += 1;
+                let cells = table.rows.iter().flat_map(|r| &r.cells);
+                for cell_para in cells.flat_map(|c| c.sub_list.iter().flat_map(|s| &s.paragraphs)) {
+                    Self::count_paragraph(cell_para, true, stats);
+                }
+            }
+        }
+    }
+
+    /// Read the headers of every section, in section order.
+    pub fn extract_headers(bytes: &[u8]) -> Result<Vec<ExtractedHeaderFooter>> {
+        let mut archive = Self::open_archive(std::io::Cursor::new(bytes))?;
+        let sections = Self::read_sections(&mut archive)?;
+        Ok(Self::collect_headers_footers(&sections, |ctrl| {
+            ctrl.header.as_ref()
+        }))
+    }
+
+    /// Read the footers of every section, in section order.
+    pub fn extract_footers(bytes: &[u8]) -> Result<Vec<ExtractedHeaderFooter>> {
+        let mut archive = Self::open_archive(std::io::Cursor::new(bytes))?;
+        let sections = Self::read_sections(&mut archive)?;
+        Ok(Self::collect_headers_footers(&sections, |ctrl| {
+            ctrl.footer.as_ref()
+        }))
+    }
+
+    fn collect_headers_footers(
+        sections: &[XmlSection],
+        select: impl Fn(&xml_types::XmlCtrl) -> Option<&XmlHeaderFooter>,
+    ) -> Vec<ExtractedHeaderFooter> {
+        let mut found = Vec::new();
+        for (section_index, section) in sections.iter().enumerate() {
+            let Some(first) = section.paragraphs.first() else {
+                continue;
+            };
+            let controls = first.runs.iter().flat_map(|run| &run.ctrls);
+            for header_footer in controls.filter_map(&select) {
+                let sub_list = header_footer.sub_list.as_ref();
+                let has_page_number = sub_list
+                    .into_iter()
+                    .flat_map(|s| &s.paragraphs)
+                    .flat_map(|p| &p.runs)
+                    .flat_map(|r| &r.ctrls)
+                    .filter_map(|ctrl| ctrl.auto_num.as_ref())
+                    .any(|num| num.num_type.as_deref() == Some("PAGE"));
+                let apply_to = match header_footer.apply_page_type.as_deref() {
+                    Some("ODD") => HeaderFooterApplyTo::Odd,
+                    Some("EVEN") => HeaderFooterApplyTo::Even,
+                    _ => HeaderFooterApplyTo::All,
+                };
+                found.push(ExtractedHeaderFooter {
+                    section_index,
+                    apply_to,
+                    text: Self::sub_list_text(sub_list).trim().to_string(),
+                    has_page_number,
+                });
+            }
+        }
+        found
+    }
+
+    /// Read a package together with its tables and embedded images.
+    pub(crate) fn read_package(bytes: &[u8]) -> Result<HwpxPackageContents> {
+        let mut archive = Self::open_archive(std::io::Cursor::new(bytes))?;
+
+        let (document, head, sections) = Self::read_document(&mut archive, false)?;
+
+        let mut tables = Vec::new();
+        let mut images = Vec::new();
+        let paragraphs = sections.iter().flat_map(|s| &s.paragraphs);
+        for (para_idx, xml_para) in paragraphs.enumerate() {
+            for run in &xml_para.runs {
+                if let Some(table) = &run.table {
+                    tables.push((para_idx, Self::extract_table(para_idx, table).into()));
+                }
+                if let Some(picture) = &run.picture {
+                    if let Some(image) = Self::read_picture(&mut archive, &head, para_idx, picture)?
+                    {
+                        images.push((para_idx, image.into()));
+                    }
+                }
+            }
+        }
+
+        let para_properties = head
+            .ref_list
+            .as_ref()
+            .and_then(|r| r.para_properties.as_ref())
+            .map(|p| p.items.clone())
+            .unwrap_or_default();
+
+        Ok(HwpxPackageContents {
+            document,
+            tables,
+            images,
+            para_properties,
+        })
+    }
+
+    fn read_document<R: Read + std::io::Seek>(
+        archive: &mut ZipArchive<R>,
+        lenient: bool,
+    ) -> Result<(HwpDocument, Head, Vec<XmlSection>)> {
+        let mut warnings = Vec::new();
+        let (version, head, sections) = if lenient {
+            Self::check_mimetype(archive, &mut warnings);
+            let version = Self::read_version(archive).unwrap_or_else(|e| {
+                warnings.push(format!("{}; assuming version 5.0", e));
+                HcfVersion::default()
+            });
+            let head = Self::read_header(archive).unwrap_or_else(|e| {
+                warnings.push(format!("{}; using default styles", e));
+                Head::default()
+            });
+            let sections = Self::read_sections_lenient(archive, &mut warnings)?;
+            (version, head, sections)
+        } else {
+            (
+                Self::read_version(archive)?,
+                Self::read_header(archive)?,
+                Self::read_sections(archive)?,
+            )
+        };
+
         let header = Self::create_file_header(&version);
         let doc_info = Self::convert_head_to_doc_info(&head);
         let body_texts = Self::convert_sections_to_body_texts(&sections, &head);
+        // content.hpf is optional for reading the body; a package without it simply
+        // has no summary
+        let summary_info = Self::read_metadata(archive)
+            .ok()
+            .map(|metadata| Self::summary_info_from_metadata(&metadata));
 
-        Ok(HwpDocument {
+        let document = HwpDocument {
             header,
             doc_info,
             body_texts,
             preview_text: None,
             preview_image: None,
-            summary_info: None,
-        })
+            summary_info,
+            warnings,
+        };
+        Ok((document, head, sections))
+    }
+
+    fn check_mimetype<R: Read + std::io::Seek>(
+        archive: &mut ZipArchive<R>,
+        warnings: &mut Vec<String>,
+    ) {
+        match Self::read_xml_file(archive, "mimetype") {
+            Ok(mimetype) if mimetype.trim() == "application/hwp+zip" => {
+                if archive.file_names().next() != Some("mimetype") {
+                    warnings.push("mimetype is not the first entry of the archive".to_string());
+                }
+            }
+            Ok(mimetype) => warnings.push(format!("unexpected mimetype {:?}", mimetype.trim())),
+            Err(e) => warnings.push(e.to_string()),
+        }
+    }
+
+    /// Every `Contents/section{N}.xml` in numeric order, whatever order the
+    /// archive lists them in and even with gaps; sections that fail to parse are
+    /// skipped with a warning.
+    fn read_sections_lenient<R: Read + std::io::Seek>(
+        archive: &mut ZipArchive<R>,
+        warnings: &mut Vec<String>,
+    ) -> Result<Vec<XmlSection>> {
+        let mut numbered: Vec<(u32, String)> = archive
+            .file_names()
+            .filter_map(|name| {
+                let idx = name
+                    .strip_prefix("Contents/section")?
+                    .strip_suffix(".xml")?
+                    .parse()
+                    .ok()?;
+                Some((idx, name.to_string()))
+            })
+            .collect();
+        numbered.sort();
+
+        let mut sections = Vec::new();
+        for (_, filename) in numbered {
+            let file = Self::open_entry(archive, &filename)?;
+            match xml_types::parse_section_from_reader(BufReader::new(file)) {
+                Ok(section) => sections.push(section),
+                Err(e) => warnings.push(format!("skipped {}: {}", filename, e)),
+            }
+        }
+
+        if sections.is_empty() {
+            return Err(HwpError::InvalidFormat(
+                "No readable section files found in HWPX".to_string(),
+            ));
+        }
+        Ok(sections)
     }
 
     fn read_version<R: Read + std::io::Seek>(archive: &mut ZipArchive<R>) -> Result<HcfVersion> {
@@ -57,11 +1307,64 @@ impl HwpxReader {
     }
 
     fn read_header<R: Read + std::io::Seek>(archive: &mut ZipArchive<R>) -> Result<Head> {
-        let xml = Self::read_xml_file(archive, "Contents/header.xml")?;
-        xml_types::parse_head(&xml)
+        let file = Self::open_entry(archive, "Contents/header.xml")?;
+        xml_types::parse_head_from_reader(BufReader::new(file))
             .map_err(|e| HwpError::ParseError(format!("Failed to parse header.xml: {}", e)))
     }
 
+    fn read_metadata<R: Read + std::io::Seek>(archive: &mut ZipArchive<R>) -> Result<HwpxMetadata> {
+        let xml = Self::read_xml_file(archive, "Contents/content.hpf")?;
+        Self::parse_metadata(&xml)
+    }
+
+    fn parse_metadata(xml: &str) -> Result<HwpxMetadata> {
+        let package = xml_types::parse_content_hpf(xml)
+            .map_err(|e| HwpError::ParseError(format!("Failed to parse content.hpf: {}", e)))?;
+
+        let mut metadata = HwpxMetadata::default();
+        let Some(opf_metadata) = package.metadata else {
+            return Ok(metadata);
+        };
+
+        metadata.title = opf_metadata.title.unwrap_or_default();
+        for meta in opf_metadata.metas {
+            let value = meta.value.unwrap_or_default();
+            match meta.name.as_deref() {
+                Some("creator") => metadata.creator = value,
+                Some("subject") => metadata.subject = value,
+                Some("description") => metadata.description = value,
+                Some("CreatedDate") => metadata.created_date = value,
+                Some("ModifiedDate") => metadata.modified_date = value,
+                Some("keyword") => {
+                    metadata.keywords = value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|k| !k.is_empty())
+                        .map(String::from)
+                        .collect();
+                }
+                _ => {}
+            }
+        }
+
+        Ok(metadata)
+    }
+
+    /// The [`HwpDocument::summary_info`] view of content.hpf. HWPX dates are free
+    /// text rather than FILETIMEs, so they are only available through
+    /// [`HwpxReader::metadata_from_bytes`].
+    fn summary_info_from_metadata(metadata: &HwpxMetadata) -> SummaryInfo {
+        let non_empty = |s: &str| (!s.is_empty()).then(|| s.to_string());
+        SummaryInfo {
+            title: non_empty(&metadata.title),
+            subject: non_empty(&metadata.subject),
+            author: non_empty(&metadata.creator),
+            keywords: non_empty(&metadata.keywords.join(", ")),
+            comments: non_empty(&metadata.description),
+            ..Default::default()
+        }
+    }
+
     fn read_sections<R: Read + std::io::Seek>(
         archive: &mut ZipArchive<R>,
     ) -> Result<Vec<XmlSection>> {
@@ -70,16 +1373,15 @@ impl HwpxReader {
 
         loop {
             let filename = format!("Contents/section{}.xml", idx);
-            match Self::read_xml_file(archive, &filename) {
-                Ok(xml) => {
-                    let section = xml_types::parse_section(&xml).map_err(|e| {
-                        HwpError::ParseError(format!("Failed to parse {}: {}", filename, e))
-                    })?;
-                    sections.push(section);
-                    idx += 1;
-                }
-                Err(_) => break,
-            }
+            let Ok(file) = Self::open_entry(archive, &filename) else {
+                break;
+            };
+            let section =
+                xml_types::parse_section_from_reader(BufReader::new(file)).map_err(|e| {
+                    HwpError::ParseError(format!("Failed to parse {}: {}", filename, e))
+                })?;
+            sections.push(section);
+            idx += 1;
         }
 
         if sections.is_empty() {
@@ -91,20 +1393,54 @@ impl HwpxReader {
         Ok(sections)
     }
 
+    /// Open the package, rejecting one whose entries are encrypted: either with
+    /// ZIP encryption or with the `encryption-data` Hangul writes to
+    /// `META-INF/manifest.xml` for password-protected documents.
+    fn open_archive<R: Read + std::io::Seek>(reader: R) -> Result<ZipArchive<R>> {
+        let mut archive = ZipArchive::new(reader)
+            .map_err(|e| HwpError::InvalidFormat(format!("Invalid HWPX archive: {}", e)))?;
+
+        for index in 0..archive.len() {
+            let entry = archive
+                .by_index_raw(index)
+                .map_err(|e| HwpError::InvalidFormat(format!("Invalid HWPX archive: {}", e)))?;
+            if entry.encrypted() {
+                return Err(HwpError::Encrypted(format!(
+                    "archive entry {} is encrypted",
+                    entry.name()
+                )));
+            }
+        }
+        if let Ok(manifest) = Self::read_xml_file(&mut archive, "META-INF/manifest.xml") {
+            if manifest.contains("encryption-data") {
+                return Err(HwpError::Encrypted(
+                    "META-INF/manifest.xml declares encrypted entries".to_string(),
+                ));
+            }
+        }
+        Ok(archive)
+    }
+
     fn read_xml_file<R: Read + std::io::Seek>(
         archive: &mut ZipArchive<R>,
         filename: &str,
     ) -> Result<String> {
-        let mut file = archive
-            .by_name(filename)
-            .map_err(|_| HwpError::NotFound(format!("File not found in archive: {}", filename)))?;
-
+        let mut file = Self::open_entry(archive, filename)?;
         let mut contents = String::new();
         file.read_to_string(&mut contents).map_err(HwpError::Io)?;
 
         Ok(contents)
     }
 
+    fn open_entry<'a, R: Read + std::io::Seek>(
+        archive: &'a mut ZipArchive<R>,
+        filename: &str,
+    ) -> Result<zip::read::ZipFile<'a>> {
+        archive
+            .by_name(filename)
+            .map_err(|_| HwpError::NotFound(format!("File not found in archive: {}", filename)))
+    }
+
     fn create_file_header(version: &HcfVersion) -> FileHeader {
         let mut header = FileHeader::new_default();
 
@@ -162,7 +1498,7 @@ impl HwpxReader {
                 for char_pr in &char_props.items {
                     let mut char_shape = CharShape::new_default();
                     if let Some(height) = char_pr.height {
-                        char_shape.base_size = (height * 10) as i32;
+                        char_shape.base_size = height as i32;
                     }
                     if char_pr.bold == Some(true) {
                         char_shape.properties |= 0x01;
@@ -180,9 +1516,31 @@ impl HwpxReader {
                             char_shape.properties |= 0x08;
                         }
                     }
+                    if let Some(ref underline) = char_pr.underline_element {
+                        if underline.underline_type.as_deref().unwrap_or("NONE") != "NONE" {
+                            char_shape.properties |= 0x04;
+                        }
+                    }
+                    if let Some(ref strikeout) = char_pr.strikeout_element {
+                        if strikeout.shape.as_deref().unwrap_or("NONE") != "NONE" {
+                            char_shape.properties |= 0x08;
+                        }
+                    }
+                    if let Some(ref outline) = char_pr.outline {
+                        if outline.outline_type.as_deref().unwrap_or("NONE") != "NONE" {
+                            char_shape.properties |= 1 << 8;
+                        }
+                    }
+                    if char_pr.emboss.is_some() {
+                        char_shape.properties |= 1 << 13;
+                    }
+                    if char_pr.engrave.is_some() {
+                        char_shape.properties |= 1 << 14;
+                    }
                     if let Some(ref color_str) = char_pr.text_color {
                         if let Some(color) = Self::parse_color(color_str) {
                             char_shape.text_color = color;
+                            char_shape.strikethrough_color = color;
                         }
                     }
                     doc_info.char_shapes.push(char_shape);
@@ -222,29 +1580,56 @@ impl HwpxReader {
         doc_info
     }
 
-    fn convert_sections_to_body_texts(sections: &[XmlSection], _head: &Head) -> Vec<BodyText> {
+    fn convert_sections_to_body_texts(sections: &[XmlSection], head: &Head) -> Vec<BodyText> {
+        // charPr ids in the file are not guaranteed to be dense, while the document model
+        // addresses char shapes by their position in `doc_info.char_shapes`.
+        let char_shape_index: HashMap<u32, u16> = head
+            .ref_list
+            .as_ref()
+            .and_then(|r| r.char_properties.as_ref())
+            .map(|props| {
+                props
+                    .items
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, char_pr)| (char_pr.id, idx as u16))
+                    .collect()
+            })
+            .unwrap_or_default();
+
         sections
             .iter()
             .map(|xml_section| {
                 let paragraphs: Vec<Paragraph> = xml_section
                     .paragraphs
                     .iter()
-                    .map(Self::convert_paragraph)
+                    .map(|p| Self::convert_paragraph(p, &char_shape_index))
                     .collect();
 
+                let page_def = xml_section
+                    .paragraphs
+                    .iter()
+                    .flat_map(|p| &p.runs)
+                    .find_map(|r| r.sec_pr.as_ref())
+                    .map(|sec_pr| PageDef::from_layout(Self::page_layout(sec_pr)));
+
                 BodyText {
                     sections: vec![Section {
                         paragraphs,
                         section_def: None,
-                        page_def: None,
+                        page_def,
                     }],
                 }
             })
             .collect()
     }
 
-    fn convert_paragraph(xml_para: &XmlParagraph) -> Paragraph {
-        let (text_content, char_positions) = Self::extract_text_and_char_shapes(&xml_para.runs);
+    fn convert_paragraph(
+        xml_para: &XmlParagraph,
+        char_shape_index: &HashMap<u32, u16>,
+    ) -> Paragraph {
+        let (text_content, char_positions) =
+            Self::extract_text_and_char_shapes(&xml_para.runs, char_shape_index);
 
         let char_shapes = if char_positions.is_empty() {
             None
@@ -268,6 +1653,62 @@ impl HwpxReader {
         }
     }
 
+    /// Page size, orientation and margins from `hp:pagePr`. Missing values keep
+    /// the [`PageLayout`] defaults.
+    fn page_layout(sec_pr: &SecPr) -> PageLayout {
+        let mut layout = PageLayout::default();
+        let page_pr = sec_pr.page_pr.as_ref();
+        if let Some(width) = page_pr.and_then(|p| p.width) {
+            layout.width = width;
+        }
+        if let Some(height) = page_pr.and_then(|p| p.height) {
+            layout.height = height;
+        }
+        // Hangul writes WIDELY for portrait and NARROWLY for landscape pages
+        layout.orientation = match page_pr.and_then(|p| p.landscape.as_deref()) {
+            Some("NARROWLY") => PageOrientation::Landscape,
+            Some(_) => PageOrientation::Portrait,
+            None if layout.width > layout.height => PageOrientation::Landscape,
+            None => PageOrientation::Portrait,
+        };
+        // Within 1 mm of a standard size in either orientation
+        let (short, long) = (
+            layout.width.min(layout.height),
+            layout.width.max(layout.height),
+        );
+        layout.paper_size = [
+            PaperSize::A4,
+            PaperSize::A3,
+            PaperSize::A5,
+            PaperSize::Letter,
+            PaperSize::Legal,
+            PaperSize::Tabloid,
+            PaperSize::B4,
+            PaperSize::B5,
+        ]
+        .into_iter()
+        .find(|size| {
+            let (w, h) = size.dimensions_hwp_units();
+            w.abs_diff(short) <= 283 && h.abs_diff(long) <= 283
+        })
+        .unwrap_or(PaperSize::Custom);
+
+        let margin = page_pr
+            .and_then(|p| p.margin.as_ref())
+            .or(sec_pr.page_margin.as_ref());
+        if let Some(margin) = margin {
+            let margins = &mut layout.margins;
+            margins.left = margin.left.unwrap_or(margins.left);
+            margins.right = margin.right.unwrap_or(margins.right);
+            margins.top = margin.top.unwrap_or(margins.top);
+            margins.bottom = margin.bottom.unwrap_or(margins.bottom);
+            margins.header = margin.header.unwrap_or(margins.header);
+            margins.footer = margin.footer.unwrap_or(margins.footer);
+            margins.gutter = margin.gutter.unwrap_or(margins.gutter);
+        }
+        layout
+    }
+
     fn parse_color(color_str: &str) -> Option<u32> {
         let color_str = color_str.trim();
         if color_str.starts_with('#') && color_str.len() == 7 {
@@ -279,7 +1720,10 @@ impl HwpxReader {
         }
     }
 
-    fn extract_text_and_char_shapes(runs: &[Run]) -> (String, Vec<CharPositionShape>) {
+    fn extract_text_and_char_shapes(
+        runs: &[Run],
+        char_shape_index: &HashMap<u32, u16>,
+    ) -> (String, Vec<CharPositionShape>) {
         let mut text_content = String::new();
         let mut char_positions = Vec::new();
         let mut current_pos: u32 = 0;
@@ -287,32 +1731,306 @@ impl HwpxReader {
 
         for run in runs {
             if let Some(ref text) = run.text {
+                if text.is_empty() {
+                    continue;
+                }
                 let char_pr_id = run.char_pr_id_ref.unwrap_or(0);
 
-                if last_char_pr_id != Some(char_pr_id) && char_pr_id > 0 {
+                // A leading default run needs no entry; any later change (including
+                // back to charPr 0) must be recorded so the styling does not leak.
+                let is_leading_default = last_char_pr_id.is_none() && char_pr_id == 0;
+                if last_char_pr_id != Some(char_pr_id) && !is_leading_default {
                     char_positions.push(CharPositionShape {
                         position: current_pos,
-                        char_shape_id: char_pr_id as u16,
+                        char_shape_id: char_shape_index
+                            .get(&char_pr_id)
+                            .copied()
+                            .unwrap_or(char_pr_id as u16),
                     });
-                    last_char_pr_id = Some(char_pr_id);
                 }
+                last_char_pr_id = Some(char_pr_id);
 
-                current_pos += text.chars().count() as u32;
+                current_pos += text.encode_utf16().count() as u32;
                 text_content.push_str(text);
             }
         }
 
         (text_content, char_positions)
     }
+
+    fn extract_table(paragraph_index: usize, xml_table: &XmlTable) -> ExtractedTable {
+        let rows: Vec<Vec<ExtractedCell>> = xml_table
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(row_idx, row)| {
+                row.cells
+                    .iter()
+                    .enumerate()
+                    .map(|(cell_idx, cell)| {
+                        let (row, col, span) = Self::cell_area(xml_table, row_idx, cell_idx, cell);
+                        ExtractedCell {
+                            row: row as usize,
+                            col: col as usize,
+                            span,
+                            text: Self::cell_text(cell),
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let col_count = rows
+            .iter()
+            .flatten()
+            .map(|cell| cell.col + cell.span.col_span as usize)
+            .max()
+            .unwrap_or(0)
+            .min(Self::column_limit(xml_table) as usize);
+
+        ExtractedTable {
+            paragraph_index,
+            row_count: rows.len(),
+            col_count,
+            rows,
+        }
+    }
+
+    fn cell_text(cell: &XmlTableCell) -> String {
+        Self::sub_list_text(cell.sub_list.as_ref())
+    }
+
+    fn sub_list_text(sub_list: Option<&SubList>) -> String {
+        sub_list
+            .map(|sub_list| {
+                sub_list
+                    .paragraphs
+                    .iter()
+                    .map(Self::paragraph_text)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .unwrap_or_default()
+    }
+
+    fn read_picture<R: Read + std::io::Seek>(
+        archive: &mut ZipArchive<R>,
+        head: &Head,
+        paragraph_index: usize,
+        picture: &XmlPicture,
+    ) -> Result<Option<ExtractedImage>> {
+        let Some(item_id) = picture
+            .img
+            .as_ref()
+            .and_then(|img| img.binary_item_id_ref.as_deref())
+        else {
+            return Ok(None);
+        };
+
+        let declaration = head
+            .ref_list
+            .as_ref()
+            .and_then(|r| r.bin_data_items.as_ref())
+            .and_then(|items| items.items.iter().find(|item| item.id == item_id));
+        let src = declaration.and_then(|item| item.src.clone()).or_else(|| {
+            let prefix = format!("BinData/{}.", item_id);
+            archive
+                .file_names()
+                .find(|n| n.starts_with(&prefix))
+                .map(String::from)
+        });
+        let Some(src) = src else {
+            return Ok(None);
+        };
+
+        let mut data = Vec::new();
+        match archive.by_name(&src) {
+            Ok(mut file) => {
+                file.read_to_end(&mut data).map_err(HwpError::Io)?;
+            }
+            Err(_) => return Ok(None),
+        }
+
+        // EMF, WMF, OLE and other undecodable items keep their raw bytes
+        let data = Arc::new(data);
+        let mut image = HwpxImage::from_shared(Arc::clone(&data)).unwrap_or_else(|| {
+            let format = declaration
+                .and_then(|item| item.format.as_deref())
+                .or_else(|| Path::new(&src).extension().and_then(|e| e.to_str()))
+                .unwrap_or("bin");
+            HwpxImage {
+                data,
+                format: HwpxImageFormat::Other(format.to_lowercase()),
+                width_mm: None,
+                height_mm: None,
+            }
+        });
+        if let Some(size) = &picture.org_sz {
+            if let (Some(width), Some(height)) = (size.width, size.height) {
+                let hwp_scale = 7200.0 / 25.4;
+                image = image.with_size(
+                    (width as f64 / hwp_scale).round() as u32,
+                    (height as f64 / hwp_scale).round() as u32,
+                );
+            }
+        }
+        Ok(Some(ExtractedImage {
+            paragraph_index,
+            source_name: src,
+            data: image.data,
+            format: image.format,
+            width_mm: image.width_mm,
+            height_mm: image.height_mm,
+        }))
+    }
+}
+
+impl From<ExtractedImage> for HwpxImage {
+    fn from(extracted: ExtractedImage) -> Self {
+        HwpxImage {
+            data: extracted.data,
+            format: extracted.format,
+            width_mm: extracted.width_mm,
+            height_mm: extracted.height_mm,
+        }
+    }
+}
+
+impl From<ExtractedTable> for HwpxTable {
+    fn from(extracted: ExtractedTable) -> Self {
+        let mut table = HwpxTable::new(extracted.row_count, extracted.col_count);
+        for cell in extracted.rows.into_iter().flatten() {
+            table.set_cell(cell.row, cell.col, &cell.text);
+            table.set_cell_span(cell.row, cell.col, cell.span.col_span, cell.span.row_span);
+        }
+        table
+    }
+}
+
+/// Write `n` in an HWPX `numFormat`; unsupported formats fall back to digits.
+fn format_list_number(n: u32, format: &str) -> String {
+    const HANGUL_SYLLABLES: [char; 14] = [
+        '가', '나', '다', '라', '마', '바', '사', '아', '자', '차', '카', '타', '파', '하',
+    ];
+    const HANGUL_JAMO: [char; 14] = [
+        'ㄱ', 'ㄴ', 'ㄷ', 'ㄹ', 'ㅁ', 'ㅂ', 'ㅅ', 'ㅇ', 'ㅈ', 'ㅊ', 'ㅋ', 'ㅌ', 'ㅍ', 'ㅎ',
+    ];
+    let cycle = |set: &[char]| set[(n.max(1) as usize - 1) % set.len()].to_string();
+    let offset = |first: char, count: u32| {
+        (1..=count)
+            .contains(&n)
+            .then(|| char::from_u32(first as u32 + n - 1))
+            .flatten()
+    };
+    let roman = || {
+        const VALUES: [(u32, &str); 13] = [
+            (1000, "M"),
+            (900, "CM"),
+            (500, "D"),
+            (400, "CD"),
+            (100, "C"),
+            (90, "XC"),
+            (50, "L"),
+            (40, "XL"),
+            (10, "X"),
+            (9, "IX"),
+            (5, "V"),
+            (4, "IV"),
+            (1, "I"),
+        ];
+        let mut rest = n;
+        let mut out = String::new();
+        for (value, symbol) in VALUES {
+            while rest >= value {
+                out.push_str(symbol);
+                rest -= value;
+            }
+        }
+        out
+    };
+    let latin = |first: u8| ((first + ((n.max(1) - 1) % 26) as u8) as char).to_string();
+
+    match format {
+        "CIRCLED_DIGIT" => offset('①', 20).map_or_else(|| n.to_string(), String::from),
+        "ROMAN_CAPITAL" => roman(),
+        "ROMAN_SMALL" => roman().to_lowercase(),
+        "LATIN_CAPITAL" => latin(b'A'),
+        "LATIN_SMALL" => latin(b'a'),
+        "HANGUL_SYLLABLE" => cycle(&HANGUL_SYLLABLES),
+        "CIRCLED_HANGUL_SYLLABLE" => {
+            offset('㉮', 14).map_or_else(|| cycle(&HANGUL_SYLLABLES), String::from)
+        }
+        "HANGUL_JAMO" => cycle(&HANGUL_JAMO),
+        _ => n.to_string(),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_format_list_number() {
+        assert_eq!(format_list_number(3, "DIGIT"), "3");
+        assert_eq!(format_list_number(2, "CIRCLED_DIGIT"), "②");
+        assert_eq!(format_list_number(14, "ROMAN_CAPITAL"), "XIV");
+        assert_eq!(format_list_number(4, "ROMAN_SMALL"), "iv");
+        assert_eq!(format_list_number(27, "LATIN_CAPITAL"), "A");
+        assert_eq!(format_list_number(3, "HANGUL_SYLLABLE"), "다");
+        assert_eq!(format_list_number(1, "CIRCLED_HANGUL_SYLLABLE"), "㉮");
+        assert_eq!(format_list_number(5, "UNKNOWN"), "5");
+    }
+
     #[test]
     fn test_hwpx_reader_nonexistent_file() {
         let result = HwpxReader::from_file("nonexistent.hwpx");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_hyperlink_without_text() {
+        let section = xml_types::parse_section(
+            r#"<hs:sec xmlns:hs="s" xmlns:hp="p"><hp:p id="0">
+<hp:run charPrIDRef="0"><hp:ctrl><hp:hyperlink url="https://a.example"/></hp:ctrl></hp:run>
+<hp:run charPrIDRef="0"><hp:ctrl><hp:fieldBegin type="HYPERLINK"/></hp:ctrl><hp:t>plain</hp:t><hp:ctrl><hp:fieldEnd/></hp:ctrl></hp:run>
+</hp:p></hs:sec>"#,
+        )
+        .unwrap();
+        let mut links = Vec::new();
+        HwpxReader::collect_hyperlinks(0, &section.paragraphs[0], &mut links);
+        assert_eq!(
+            links,
+            [ExtractedHyperlink {
+                paragraph_index: 0,
+                text: String::new(),
+                url: "https://a.example".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_metadata_with_missing_optional_fields() {
+        let hpf = r#"<?xml version="1.0" encoding="UTF-8"?>
+<opf:package xmlns:opf="http://www.idpf.org/2007/opf/" version="" unique-identifier="" id="">
+  <opf:metadata>
+    <opf:meta name="creator" content="text">홍길동</opf:meta>
+    <opf:meta name="keyword" content="text"/>
+    <opf:meta name="unknown" content="text">ignored</opf:meta>
+  </opf:metadata>
+</opf:package>"#;
+        let metadata = HwpxReader::parse_metadata(hpf).unwrap();
+        assert_eq!(metadata.creator, "홍길동");
+        assert_eq!(metadata.title, "");
+        assert!(metadata.keywords.is_empty());
+
+        let summary = HwpxReader::summary_info_from_metadata(&metadata);
+        assert_eq!(summary.author.as_deref(), Some("홍길동"));
+        assert_eq!(summary.title, None);
+        assert_eq!(summary.keywords, None);
+
+        let no_metadata = r#"<opf:package xmlns:opf="http://www.idpf.org/2007/opf/"/>"#;
+        assert_eq!(
+            HwpxReader::parse_metadata(no_metadata).unwrap(),
+            HwpxMetadata::default()
+        );
+    }
 }
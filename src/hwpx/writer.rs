@@ -1,18 +1,30 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fs::File;
-use std::io::{Cursor, Write};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::{Cursor, Seek, SeekFrom, Write};
 use std::path::Path;
+use std::sync::Arc;
 use zip::write::SimpleFileOptions;
 use zip::ZipWriter;
 
 use crate::error::{HwpError, Result};
 use crate::model::char_shape::CharShape;
+use crate::model::hyperlink::Hyperlink;
+use crate::model::page_def::PageDef;
+use crate::model::page_layout::{mm_to_hwp_units, PageLayout, PageMargins, PageOrientation};
 use crate::model::para_char_shape::{CharPositionShape, ParaCharShape};
 use crate::model::paragraph::{ParaText, Paragraph, Section};
+use crate::model::section_def::SectionDef;
 use crate::model::HwpDocument;
 use crate::parser::body_text::BodyText;
 use crate::parser::doc_info::DocInfo;
 use crate::parser::header::FileHeader;
 
+use super::reader::HwpxReader;
+use super::xml_builder::{escape_xml, XmlBuilder};
+use super::xml_types::{ParaPr, ParaPrBreakSetting, ParaPrMargin, ParaPrMarginValue};
+
 // XML namespace declarations for HWPX 2011 format
 const HWPX_NAMESPACES: &str = concat!(
     r#"xmlns:ha="http://www.hancom.co.kr/hwpml/2011/app" "#,
@@ -32,13 +44,18 @@ const HWPX_NAMESPACES: &str = concat!(
     r#"xmlns:config="urn:oasis:names:tc:opendocument:xmlns:config:1.0""#
 );
 
-fn xml_escape(s: &str) -> String {
-    s.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-        .replace('\'', "&apos;")
-}
+const XML_DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes" ?>"#;
+
+// textWidth of the header/footer subList (A4 body width)
+const HEADER_FOOTER_TEXT_WIDTH: u32 = 42520;
+
+// borderFill ids 1-3 are fixed in header.xml; text boxes get their own from here on
+const TEXT_BOX_BORDER_FILL_BASE: u32 = 4;
+
+const DEFAULT_FONT_FACE: &str = "맑은 고딕";
+
+// Form controls are numbered from here so their ids stay clear of tables and pictures
+const FORM_FIELD_ID_BASE: u32 = 1000;
 
 /// Text style configuration for HWPX paragraphs
 #[derive(Debug, Clone, Default)]
@@ -50,6 +67,27 @@ pub struct HwpxTextStyle {
     pub underline: bool,
     pub strikethrough: bool,
     pub color: u32,
+    /// Strikeout line color; defaults to the text color
+    pub strikethrough_color: Option<u32>,
+    /// Drop shadow behind the glyphs; `None` draws no shadow
+    pub shadow: Option<TextShadow>,
+    /// Draw the glyphs as hollow outlines (외곽선)
+    pub outline: bool,
+    /// Raised relief (양각); exclusive with `engrave`
+    pub emboss: bool,
+    /// Sunken relief (음각); exclusive with `emboss`
+    pub engrave: bool,
+}
+
+/// Drop shadow for a [`HwpxTextStyle`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextShadow {
+    /// Horizontal offset in percent of the glyph size (-100..=100)
+    pub offset_x: i8,
+    /// Vertical offset in percent of the glyph size (-100..=100)
+    pub offset_y: i8,
+    /// Shadow color (RGB format: 0xRRGGBB)
+    pub color: u32,
 }
 
 impl HwpxTextStyle {
@@ -93,6 +131,43 @@ impl HwpxTextStyle {
         self
     }
 
+    /// Set the strikeout line color (RGB format: 0xRRGGBB); implies strikethrough
+    pub fn strikethrough_color(mut self, color: u32) -> Self {
+        self.strikethrough = true;
+        self.strikethrough_color = Some(color);
+        self
+    }
+
+    /// Enable a drop shadow offset by (`offset_x`, `offset_y`) percent
+    pub fn shadow(mut self, offset_x: i8, offset_y: i8, color: u32) -> Self {
+        self.shadow = Some(TextShadow {
+            offset_x,
+            offset_y,
+            color,
+        });
+        self
+    }
+
+    /// Set outline (외곽선)
+    pub fn outline(mut self) -> Self {
+        self.outline = true;
+        self
+    }
+
+    /// Set emboss (양각); replaces engrave
+    pub fn emboss(mut self) -> Self {
+        self.emboss = true;
+        self.engrave = false;
+        self
+    }
+
+    /// Set engrave (음각); replaces emboss
+    pub fn engrave(mut self) -> Self {
+        self.engrave = true;
+        self.emboss = false;
+        self
+    }
+
     /// Convert to CharShape for internal use
     fn to_char_shape(&self) -> CharShape {
         let mut properties = 0u32;
@@ -109,8 +184,24 @@ impl HwpxTextStyle {
         if self.strikethrough {
             properties |= 1 << 3; // Bit 3: Strikethrough
         }
+        if self.shadow.is_some() {
+            properties |= 1 << 11; // Bits 11-12: Shadow type (1 = drop)
+        }
+        if self.outline {
+            properties |= 1 << 8; // Bits 8-10: Outline type (1 = solid)
+        }
+        if self.emboss {
+            properties |= 1 << 13; // Bit 13: Emboss
+        } else if self.engrave {
+            properties |= 1 << 14; // Bit 14: Engrave
+        }
 
         let base_size = self.font_size.unwrap_or(10) as i32 * 100; // Convert pt to hwp units
+        let shadow = self.shadow.unwrap_or(TextShadow {
+            offset_x: 0,
+            offset_y: 0,
+            color: 0x808080,
+        });
 
         CharShape {
             face_name_ids: [0; 7],
@@ -120,13 +211,39 @@ impl HwpxTextStyle {
             char_offsets: [0; 7],
             base_size,
             properties,
-            shadow_gap_x: 0,
-            shadow_gap_y: 0,
+            shadow_gap_x: shadow.offset_x,
+            shadow_gap_y: shadow.offset_y,
             text_color: self.color,
             underline_color: self.color,
             shade_color: 0xFFFFFF,
-            shadow_color: 0x808080,
+            shadow_color: shadow.color,
             border_fill_id: 0,
+            strikethrough_color: self.strikethrough_color.unwrap_or(self.color),
+        }
+    }
+}
+
+/// Writing direction of a run inside a paragraph
+///
+/// HWPX has no per-run direction attribute, so directed runs are wrapped in
+/// Unicode directional isolates (LRI/RLI ... PDI) in `hp:t`. The marks are
+/// not part of the paragraph text and do not count towards char positions.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum RunDirection {
+    /// Follow the Unicode bidi algorithm, no marks
+    #[default]
+    Auto,
+    LeftToRight,
+    /// Arabic, Hebrew and other right-to-left scripts
+    RightToLeft,
+}
+
+impl RunDirection {
+    fn isolate<'a>(self, text: &'a str) -> Cow<'a, str> {
+        match self {
+            Self::Auto => Cow::Borrowed(text),
+            Self::LeftToRight => Cow::Owned(format!("\u{2066}{}\u{2069}", text)),
+            Self::RightToLeft => Cow::Owned(format!("\u{2067}{}\u{2069}", text)),
         }
     }
 }
@@ -136,6 +253,9 @@ impl HwpxTextStyle {
 pub struct StyledText {
     pub text: String,
     pub style: HwpxTextStyle,
+    /// Hyperlink target; the run is written as a link in its own style
+    pub url: Option<String>,
+    pub direction: RunDirection,
 }
 
 impl StyledText {
@@ -143,6 +263,8 @@ impl StyledText {
         Self {
             text: text.to_string(),
             style: HwpxTextStyle::default(),
+            url: None,
+            direction: RunDirection::Auto,
         }
     }
 
@@ -150,33 +272,64 @@ impl StyledText {
         Self {
             text: text.to_string(),
             style,
+            url: None,
+            direction: RunDirection::Auto,
         }
     }
+
+    /// Make this run a hyperlink to `url`
+    pub fn link(mut self, url: &str) -> Self {
+        self.url = Some(url.to_string());
+        self
+    }
+
+    /// Set the writing direction of this run, e.g. for Arabic inside Korean text
+    pub fn direction(mut self, direction: RunDirection) -> Self {
+        self.direction = direction;
+        self
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct HwpxImage {
-    pub data: Vec<u8>,
+    /// Encoded image bytes. Shared, so cloning an image (or reusing the same logo in
+    /// a header and the body) never copies the buffer.
+    pub data: Arc<Vec<u8>>,
     pub format: HwpxImageFormat,
     pub width_mm: Option<u32>,
     pub height_mm: Option<u32>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum HwpxImageFormat {
     Png,
     Jpeg,
     Gif,
     Bmp,
+    /// A binary item the reader cannot decode, e.g. EMF, WMF or OLE data, named
+    /// by the lowercase format of its `hh:binDataItem` declaration. Its bytes are
+    /// carried over as they are.
+    Other(String),
 }
 
 impl HwpxImageFormat {
-    pub fn extension(&self) -> &'static str {
+    pub fn extension(&self) -> &str {
         match self {
             Self::Png => "png",
             Self::Jpeg => "jpg",
             Self::Gif => "gif",
             Self::Bmp => "bmp",
+            Self::Other(format) => format,
+        }
+    }
+
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            Self::Png => "image/png",
+            Self::Jpeg => "image/jpg",
+            Self::Gif => "image/gif",
+            Self::Bmp => "image/bmp",
+            Self::Other(_) => "application/octet-stream",
         }
     }
 
@@ -200,8 +353,14 @@ impl HwpxImageFormat {
 
 impl HwpxImage {
     pub fn from_bytes(data: Vec<u8>) -> Option<Self> {
+        Self::from_shared(Arc::new(data))
+    }
+
+    /// Like [`HwpxImage::from_bytes`], but keeps a reference to a buffer the caller
+    /// already shares instead of taking ownership of a copy.
+    pub fn from_shared(data: Arc<Vec<u8>>) -> Option<Self> {
         let format = HwpxImageFormat::from_bytes(&data)?;
-        let (width_mm, height_mm) = Self::read_dimensions_mm(&data, format);
+        let (width_mm, height_mm) = Self::read_dimensions_mm(&data, &format);
         Some(Self {
             data,
             format,
@@ -217,12 +376,13 @@ impl HwpxImage {
     }
 
     /// 이미지 바이트에서 픽셀 크기를 읽고 mm로 변환 (96 DPI 기준)
-    fn read_dimensions_mm(data: &[u8], format: HwpxImageFormat) -> (Option<u32>, Option<u32>) {
+    fn read_dimensions_mm(data: &[u8], format: &HwpxImageFormat) -> (Option<u32>, Option<u32>) {
         let (w_px, h_px) = match format {
             HwpxImageFormat::Png => Self::read_png_dimensions(data),
             HwpxImageFormat::Jpeg => Self::read_jpeg_dimensions(data),
             HwpxImageFormat::Gif => Self::read_gif_dimensions(data),
             HwpxImageFormat::Bmp => Self::read_bmp_dimensions(data),
+            HwpxImageFormat::Other(_) => (None, None),
         };
         match (w_px, h_px) {
             (Some(w), Some(h)) if w > 0 && h > 0 => {
@@ -294,7 +454,7 @@ impl HwpxImage {
 }
 
 /// Cell span information for merged cells
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct CellSpan {
     pub col_span: u32,
     pub row_span: u32,
@@ -309,16 +469,65 @@ impl Default for CellSpan {
     }
 }
 
+/// Background and text color of a table cell (RGB format: 0xRRGGBB)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CellStyle {
+    pub background: Option<u32>,
+    pub text_color: Option<u32>,
+}
+
+/// A hyperlink over a byte range of a table cell's text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellLink {
+    pub range: std::ops::Range<usize>,
+    pub url: String,
+}
+
+/// Where a table caption is placed relative to the table
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CaptionPosition {
+    #[default]
+    Above,
+    Below,
+}
+
+impl CaptionPosition {
+    fn as_hwpx_side(&self) -> &'static str {
+        match self {
+            Self::Above => "TOP",
+            Self::Below => "BOTTOM",
+        }
+    }
+}
+
 pub struct HwpxTable {
     /// Grid of cell text values indexed by logical (row, col) position.
     /// Only cells that are the "origin" of a merge have text;
     /// covered cells have empty strings and are skipped in output.
+    /// A `\n` in a value starts a new paragraph inside the cell.
     pub rows: Vec<Vec<String>>,
+    /// Relative column widths, scaled to fill the content width when written.
+    /// Columns are split evenly if the length differs from the column count or
+    /// every width is 0.
     pub col_widths: Vec<u32>,
     /// Cell span info: key = (row, col) of the origin cell
     pub cell_spans: std::collections::HashMap<(usize, usize), CellSpan>,
     /// Tracks which cells are covered by another cell's span
     covered: std::collections::HashSet<(usize, usize)>,
+    /// Caption text; written as "표 N. {caption}" with N counted across the document
+    pub caption: Option<String>,
+    pub caption_position: CaptionPosition,
+    /// Number of leading rows written as header cells (repeated on each page)
+    pub header_rows: usize,
+    /// Cell colors, keyed by (row, col) of the cell (the origin of a merge)
+    pub cell_styles: std::collections::BTreeMap<(usize, usize), CellStyle>,
+    /// Hyperlinks in cell text, keyed like `cell_styles` and sorted by range.
+    /// A link that overlaps an earlier one, or whose range is not on character
+    /// boundaries of the text, is written as plain text.
+    pub cell_links: std::collections::BTreeMap<(usize, usize), Vec<CellLink>>,
+    /// Pictures placed before the text of a cell, keyed like `cell_styles`.
+    /// Each is scaled down to fit the cell width.
+    pub cell_images: std::collections::BTreeMap<(usize, usize), Vec<HwpxImage>>,
 }
 
 impl HwpxTable {
@@ -328,6 +537,12 @@ impl HwpxTable {
             col_widths: vec![8390; cols],
             cell_spans: std::collections::HashMap::new(),
             covered: std::collections::HashSet::new(),
+            caption: None,
+            caption_position: CaptionPosition::default(),
+            header_rows: 0,
+            cell_styles: std::collections::BTreeMap::new(),
+            cell_links: std::collections::BTreeMap::new(),
+            cell_images: std::collections::BTreeMap::new(),
         }
     }
 
@@ -342,7 +557,45 @@ impl HwpxTable {
             col_widths: vec![8390; cols],
             cell_spans: std::collections::HashMap::new(),
             covered: std::collections::HashSet::new(),
+            caption: None,
+            caption_position: CaptionPosition::default(),
+            header_rows: 0,
+            cell_styles: std::collections::BTreeMap::new(),
+            cell_links: std::collections::BTreeMap::new(),
+            cell_images: std::collections::BTreeMap::new(),
+        }
+    }
+
+    /// Set the caption text (without the "표 N." prefix)
+    pub fn with_caption(mut self, caption: &str, position: CaptionPosition) -> Self {
+        self.caption = Some(caption.to_string());
+        self.caption_position = position;
+        self
+    }
+
+    pub fn with_header_rows(mut self, rows: usize) -> Self {
+        self.header_rows = rows;
+        self
+    }
+
+    /// Set the relative column widths (e.g. `[20, 80]` for a 1:4 split)
+    pub fn with_col_widths(mut self, widths: Vec<u32>) -> Self {
+        self.col_widths = widths;
+        self
+    }
+
+    /// Column widths in HWPUNIT that fill `content_width` in the ratio of
+    /// `col_widths`
+    fn scaled_col_widths(&self, content_width: u32) -> Vec<u32> {
+        let col_cnt = self.rows.first().map_or(0, Vec::len);
+        let total: u64 = self.col_widths.iter().map(|&w| u64::from(w)).sum();
+        if self.col_widths.len() != col_cnt || total == 0 {
+            return vec![content_width / col_cnt.max(1) as u32; col_cnt];
         }
+        self.col_widths
+            .iter()
+            .map(|&w| (u64::from(content_width) * u64::from(w) / total) as u32)
+            .collect()
     }
 
     pub fn set_cell(&mut self, row: usize, col: usize, value: &str) {
@@ -351,15 +604,36 @@ impl HwpxTable {
         }
     }
 
+    /// Set the background and text color of a cell
+    pub fn set_cell_style(&mut self, row: usize, col: usize, style: CellStyle) {
+        if style == CellStyle::default() {
+            self.cell_styles.remove(&(row, col));
+        } else {
+            self.cell_styles.insert((row, col), style);
+        }
+    }
+
+    /// Set the hyperlinks over parts of a cell's text
+    pub fn set_cell_links(&mut self, row: usize, col: usize, links: Vec<CellLink>) {
+        if links.is_empty() {
+            self.cell_links.remove(&(row, col));
+        } else {
+            self.cell_links.insert((row, col), links);
+        }
+    }
+
+    /// Add a picture to a cell, after any added before
+    pub fn add_cell_image(&mut self, row: usize, col: usize, image: HwpxImage) {
+        self.cell_images.entry((row, col)).or_default().push(image);
+    }
+
     /// Set cell merge span and mark covered cells
     pub fn set_cell_span(&mut self, row: usize, col: usize, col_span: u32, row_span: u32) {
         if col_span <= 1 && row_span <= 1 {
             return;
         }
-        self.cell_spans.insert(
-            (row, col),
-            CellSpan { col_span, row_span },
-        );
+        self.cell_spans
+            .insert((row, col), CellSpan { col_span, row_span });
         // Mark all cells covered by this span (except the origin)
         for r in row..row + row_span as usize {
             for c in col..col + col_span as usize {
@@ -403,51 +677,122 @@ impl HwpxHyperlink {
 #[derive(Debug, Clone)]
 pub struct HwpxHeader {
     pub text: String,
+    /// Styled runs; when non-empty they replace `text`
+    pub runs: Vec<StyledText>,
+    /// Inline picture placed before the text (e.g. a logo)
+    pub image: Option<HwpxImage>,
     pub apply_to: HeaderFooterApplyTo,
+    /// Hide this header on the first page of each section (hideFirstHeader)
+    pub suppress_on_first_page: bool,
 }
 
 impl HwpxHeader {
     pub fn new(text: &str) -> Self {
         Self {
             text: text.to_string(),
+            runs: Vec::new(),
+            image: None,
             apply_to: HeaderFooterApplyTo::All,
+            suppress_on_first_page: false,
         }
     }
 
     pub fn for_odd_pages(text: &str) -> Self {
         Self {
             text: text.to_string(),
+            runs: Vec::new(),
+            image: None,
             apply_to: HeaderFooterApplyTo::Odd,
+            suppress_on_first_page: false,
         }
     }
 
     pub fn for_even_pages(text: &str) -> Self {
         Self {
             text: text.to_string(),
+            runs: Vec::new(),
+            image: None,
             apply_to: HeaderFooterApplyTo::Even,
+            suppress_on_first_page: false,
+        }
+    }
+
+    pub fn for_first_page(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            runs: Vec::new(),
+            image: None,
+            apply_to: HeaderFooterApplyTo::First,
+            suppress_on_first_page: false,
+        }
+    }
+
+    pub fn styled(runs: Vec<StyledText>) -> Self {
+        Self {
+            runs,
+            ..Self::new("")
         }
     }
+
+    pub fn with_image(mut self, image: HwpxImage) -> Self {
+        self.image = Some(image);
+        self
+    }
+
+    pub fn suppress_first(mut self) -> Self {
+        self.suppress_on_first_page = true;
+        self
+    }
 }
 
 /// Footer configuration for HWPX documents
 #[derive(Debug, Clone)]
 pub struct HwpxFooter {
     pub text: String,
+    /// Styled runs; when non-empty they replace `text`
+    pub runs: Vec<StyledText>,
+    /// Inline picture placed before the text (e.g. a logo)
+    pub image: Option<HwpxImage>,
     pub include_page_number: bool,
     pub page_number_format: PageNumberFormat,
+    /// Characters written around the page number, e.g. `-` and `-` for "- 3 -"
+    pub page_number_prefix: Option<char>,
+    pub page_number_suffix: Option<char>,
+    /// Footer paragraph alignment; `None` keeps the default body paragraph
+    pub alignment: Option<HwpxAlignment>,
     pub apply_to: HeaderFooterApplyTo,
+    /// Hide this footer on the first page of each section (hideFirstFooter)
+    pub suppress_on_first_page: bool,
 }
 
 impl HwpxFooter {
     pub fn new(text: &str) -> Self {
         Self {
             text: text.to_string(),
+            runs: Vec::new(),
+            image: None,
             include_page_number: false,
             page_number_format: PageNumberFormat::Numeric,
+            page_number_prefix: None,
+            page_number_suffix: None,
+            alignment: None,
             apply_to: HeaderFooterApplyTo::All,
+            suppress_on_first_page: false,
+        }
+    }
+
+    pub fn styled(runs: Vec<StyledText>) -> Self {
+        Self {
+            runs,
+            ..Self::new("")
         }
     }
 
+    pub fn with_image(mut self, image: HwpxImage) -> Self {
+        self.image = Some(image);
+        self
+    }
+
     pub fn with_page_number(mut self) -> Self {
         self.include_page_number = true;
         self
@@ -459,6 +804,19 @@ impl HwpxFooter {
         self
     }
 
+    /// Decorate the page number, e.g. `with_page_number_chars('-', '-')` for "- 3 -"
+    pub fn with_page_number_chars(mut self, prefix: char, suffix: char) -> Self {
+        self.include_page_number = true;
+        self.page_number_prefix = Some(prefix);
+        self.page_number_suffix = Some(suffix);
+        self
+    }
+
+    pub fn with_alignment(mut self, alignment: HwpxAlignment) -> Self {
+        self.alignment = Some(alignment);
+        self
+    }
+
     pub fn for_odd_pages(mut self) -> Self {
         self.apply_to = HeaderFooterApplyTo::Odd;
         self
@@ -468,6 +826,16 @@ impl HwpxFooter {
         self.apply_to = HeaderFooterApplyTo::Even;
         self
     }
+
+    pub fn for_first_page(mut self) -> Self {
+        self.apply_to = HeaderFooterApplyTo::First;
+        self
+    }
+
+    pub fn suppress_first(mut self) -> Self {
+        self.suppress_on_first_page = true;
+        self
+    }
 }
 
 /// Page number format for footers
@@ -497,242 +865,1898 @@ impl PageNumberFormat {
     }
 }
 
-/// Which pages the header/footer applies to
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum HeaderFooterApplyTo {
-    /// All pages
-    All,
-    /// Odd pages only
-    Odd,
-    /// Even pages only
-    Even,
-}
-
-/// Document metadata for HWPX content.hpf
-#[derive(Debug, Clone, Default)]
-pub struct HwpxMetadata {
-    pub title: String,
-    pub creator: String,
-    pub created_date: String,
+/// A file written into the HWPX zip package. Image bytes are borrowed from the
+/// writer so each buffer is copied only once, straight into the archive.
+struct PackagePart<'a> {
+    path: String,
+    media_type: &'static str,
+    data: Cow<'a, [u8]>,
+    stored: bool,
 }
 
-pub struct HwpxWriter {
-    document: HwpDocument,
-    tables: Vec<(usize, HwpxTable)>,
-    images: Vec<(usize, HwpxImage)>,
-    hyperlinks: Vec<(usize, Vec<HwpxHyperlink>)>,
-    headers: Vec<HwpxHeader>,
-    footers: Vec<HwpxFooter>,
-    next_table_id: u32,
-    next_image_id: u32,
-    metadata: HwpxMetadata,
+/// Lets [`ZipWriter`] patch each local header in place on a writer that cannot
+/// seek. The zip writer only seeks back to fill in the CRC and sizes of the entry
+/// it just finished, so everything before that point is final and passed on; at
+/// most the last entry or two are held in memory.
+struct EntryBuffer<W: Write> {
+    inner: W,
+    buffer: Vec<u8>,
+    /// Bytes already passed on to `inner`; `buffer` starts here
+    released: u64,
+    position: u64,
 }
 
-impl HwpxWriter {
-    pub fn new() -> Self {
+impl<W: Write> EntryBuffer<W> {
+    fn new(inner: W) -> Self {
         Self {
-            document: HwpDocument {
-                header: FileHeader::new_default(),
-                doc_info: DocInfo::default(),
-                body_texts: Vec::new(),
-                preview_text: None,
-                preview_image: None,
-                summary_info: None,
-            },
-            tables: Vec::new(),
-            images: Vec::new(),
-            hyperlinks: Vec::new(),
-            headers: Vec::new(),
-            footers: Vec::new(),
-            next_table_id: 1,
-            next_image_id: 1,
-            metadata: HwpxMetadata::default(),
+            inner,
+            buffer: Vec::new(),
+            released: 0,
+            position: 0,
         }
     }
 
-    pub fn set_metadata(&mut self, metadata: HwpxMetadata) {
-        self.metadata = metadata;
+    /// Pass the buffered bytes before `offset` on to `inner`
+    fn release(&mut self, offset: u64) -> std::io::Result<()> {
+        let len = (offset - self.released) as usize;
+        self.inner.write_all(&self.buffer[..len])?;
+        self.buffer.drain(..len);
+        self.released = offset;
+        Ok(())
     }
 
-    pub fn from_document(document: HwpDocument) -> Self {
-        Self {
-            document,
-            tables: Vec::new(),
-            images: Vec::new(),
-            hyperlinks: Vec::new(),
-            headers: Vec::new(),
-            footers: Vec::new(),
-            next_table_id: 1,
-            next_image_id: 1,
-            metadata: HwpxMetadata::default(),
-        }
+    fn into_inner(mut self) -> std::io::Result<W> {
+        self.release(self.released + self.buffer.len() as u64)?;
+        Ok(self.inner)
     }
+}
 
-    pub fn add_paragraph(&mut self, text: &str) -> Result<()> {
-        let paragraph = Paragraph {
-            text: Some(ParaText {
-                content: text.to_string(),
-            }),
-            ..Default::default()
-        };
+impl<W: Write> Write for EntryBuffer<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let offset = (self.position - self.released) as usize;
+        let overwritten = buf.len().min(self.buffer.len() - offset);
+        self.buffer[offset..offset + overwritten].copy_from_slice(&buf[..overwritten]);
+        self.buffer.extend_from_slice(&buf[overwritten..]);
+        self.position += buf.len() as u64;
+        Ok(buf.len())
+    }
 
-        self.push_paragraph(paragraph);
+    fn flush(&mut self) -> std::io::Result<()> {
         Ok(())
     }
+}
 
-    pub fn add_styled_paragraph(&mut self, text: &str, style: HwpxTextStyle) -> Result<()> {
-        let char_shape = style.to_char_shape();
-        let char_shape_id = self.add_char_shape(char_shape);
-
-        let paragraph = Paragraph {
-            text: Some(ParaText {
-                content: text.to_string(),
-            }),
-            char_shapes: Some(ParaCharShape {
-                char_positions: vec![CharPositionShape {
-                    position: 0,
-                    char_shape_id,
-                }],
-            }),
-            ..Default::default()
+impl<W: Write> Seek for EntryBuffer<W> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let end = self.released + self.buffer.len() as u64;
+        let target = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(delta) => end.checked_add_signed(delta),
+            SeekFrom::Current(delta) => self.position.checked_add_signed(delta),
         };
-
-        self.push_paragraph(paragraph);
-        Ok(())
+        match target {
+            Some(target) if (self.released..=end).contains(&target) => {
+                if target < self.position {
+                    self.release(target)?;
+                }
+                self.position = target;
+                Ok(target)
+            }
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "cannot seek into data already written",
+            )),
+        }
     }
+}
 
-    pub fn add_mixed_styled_paragraph(&mut self, runs: Vec<StyledText>) -> Result<()> {
-        let mut full_text = String::new();
-        let mut char_positions = Vec::new();
-        let mut position: u32 = 0;
-
-        for run in runs {
-            let char_shape = run.style.to_char_shape();
-            let char_shape_id = self.add_char_shape(char_shape);
+/// Horizontal paragraph alignment
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum HwpxAlignment {
+    #[default]
+    Justify,
+    Left,
+    Center,
+    Right,
+}
 
-            char_positions.push(CharPositionShape {
-                position,
-                char_shape_id,
-            });
+impl HwpxAlignment {
+    fn as_hwpx_align(self) -> &'static str {
+        match self {
+            Self::Justify => "JUSTIFY",
+            Self::Left => "LEFT",
+            Self::Center => "CENTER",
+            Self::Right => "RIGHT",
+        }
+    }
 
-            position += run.text.chars().count() as u32;
-            full_text.push_str(&run.text);
+    /// Alignment for an hh:align `horizontal` value; distributed alignments have
+    /// no counterpart and read as `None`
+    fn from_hwpx_align(value: &str) -> Option<Self> {
+        match value {
+            "JUSTIFY" => Some(Self::Justify),
+            "LEFT" => Some(Self::Left),
+            "CENTER" => Some(Self::Center),
+            "RIGHT" => Some(Self::Right),
+            _ => None,
         }
+    }
+}
 
-        let paragraph = Paragraph {
-            text: Some(ParaText { content: full_text }),
-            char_shapes: Some(ParaCharShape { char_positions }),
-            ..Default::default()
-        };
+/// Alignment of text at a tab stop
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TabAlignment {
+    #[default]
+    Left,
+    Right,
+    Center,
+    /// Align numbers on their decimal point
+    Decimal,
+}
 
-        self.push_paragraph(paragraph);
-        Ok(())
+impl TabAlignment {
+    fn as_hwpx(self) -> &'static str {
+        match self {
+            Self::Left => "LEFT",
+            Self::Right => "RIGHT",
+            Self::Center => "CENTER",
+            Self::Decimal => "DECIMAL",
+        }
     }
+}
 
-    pub fn add_table(&mut self, table: HwpxTable) -> Result<()> {
-        let para_idx = self.current_paragraph_count();
-        self.tables.push((para_idx, table));
+/// A tab stop, measured from the left edge of the paragraph
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TabStop {
+    pub position_mm: f32,
+    pub alignment: TabAlignment,
+}
 
-        let paragraph = Paragraph {
-            text: Some(ParaText {
-                content: String::new(),
-            }),
-            ..Default::default()
-        };
-        self.push_paragraph(paragraph);
-        Ok(())
+/// Line style of a paragraph border
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum BorderLineStyle {
+    #[default]
+    Solid,
+    Dash,
+    Dot,
+    Double,
+    /// No line; only the background is drawn
+    None,
+}
+
+impl BorderLineStyle {
+    fn as_hwpx(self) -> &'static str {
+        match self {
+            Self::Solid => "SOLID",
+            Self::Dash => "DASH",
+            Self::Dot => "DOT",
+            Self::Double => "DOUBLE_SLIM",
+            Self::None => "NONE",
+        }
     }
+}
 
-    pub fn add_image(&mut self, image: HwpxImage) -> Result<()> {
-        let para_idx = self.current_paragraph_count();
-        self.images.push((para_idx, image));
+/// Border and shading drawn around a paragraph (문단 테두리/배경)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParagraphBorder {
+    pub line_style: BorderLineStyle,
+    /// Line color (RGB format: 0xRRGGBB)
+    pub color: u32,
+    /// Background color (RGB format: 0xRRGGBB), `None` for transparent
+    pub fill_color: Option<u32>,
+    /// Gap between the border and the text in mm: left, right, top, bottom
+    pub offsets_mm: [f32; 4],
+    /// Draw only the left line, e.g. as the bar of a quotation
+    pub left_only: bool,
+}
 
+impl Default for ParagraphBorder {
+    fn default() -> Self {
+        Self {
+            line_style: BorderLineStyle::Solid,
+            color: 0x000000,
+            fill_color: None,
+            offsets_mm: [0.0; 4],
+            left_only: false,
+        }
+    }
+}
+
+impl ParagraphBorder {
+    /// Thin solid black box
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Background only, without a line
+    pub fn shading(fill_color: u32) -> Self {
+        Self {
+            line_style: BorderLineStyle::None,
+            fill_color: Some(fill_color),
+            ..Self::default()
+        }
+    }
+
+    pub fn line_style(mut self, line_style: BorderLineStyle) -> Self {
+        self.line_style = line_style;
+        self
+    }
+
+    /// Set the line color (RGB format: 0xRRGGBB)
+    pub fn color(mut self, color: u32) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Set the background color (RGB format: 0xRRGGBB)
+    pub fn fill(mut self, fill_color: u32) -> Self {
+        self.fill_color = Some(fill_color);
+        self
+    }
+
+    /// Set the gap between the border and the text in mm
+    pub fn offsets_mm(mut self, left: f32, right: f32, top: f32, bottom: f32) -> Self {
+        self.offsets_mm = [left, right, top, bottom];
+        self
+    }
+
+    /// Keep the left line only
+    pub fn left_only(mut self) -> Self {
+        self.left_only = true;
+        self
+    }
+}
+
+/// Paragraph style configuration for HWPX paragraphs
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HwpxParagraphStyle {
+    pub alignment: HwpxAlignment,
+    /// Custom tab stops; without them tabs advance to the section's default interval
+    pub tab_stops: Vec<TabStop>,
+    /// Box and/or background around the paragraph
+    pub border: Option<ParagraphBorder>,
+    /// Keep the paragraph on the same page as the next one (다음 문단과 함께)
+    pub keep_with_next: bool,
+    /// Never split the paragraph across pages (문단 보호)
+    pub keep_lines: bool,
+    /// Start the paragraph on a new page (문단 앞에서 항상 쪽 나눔)
+    pub page_break_before: bool,
+    /// Left margin (왼쪽 여백) in millimeters
+    pub left_margin_mm: f32,
+}
+
+impl HwpxParagraphStyle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set horizontal alignment
+    pub fn alignment(mut self, alignment: HwpxAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Add a tab stop at `position_mm`
+    pub fn tab_stop(mut self, position_mm: f32, alignment: TabAlignment) -> Self {
+        self.tab_stops.push(TabStop {
+            position_mm,
+            alignment,
+        });
+        self
+    }
+
+    /// Draw `border` around the paragraph
+    pub fn border(mut self, border: ParagraphBorder) -> Self {
+        self.border = Some(border);
+        self
+    }
+
+    /// Keep on the same page as the next paragraph, e.g. for headings
+    pub fn keep_with_next(mut self) -> Self {
+        self.keep_with_next = true;
+        self
+    }
+
+    /// Keep all lines of the paragraph on one page
+    pub fn keep_lines(mut self) -> Self {
+        self.keep_lines = true;
+        self
+    }
+
+    /// Always start the paragraph on a new page
+    pub fn page_break_before(mut self) -> Self {
+        self.page_break_before = true;
+        self
+    }
+
+    /// Indent the whole paragraph by `mm` from the left
+    pub fn left_margin(mut self, mm: f32) -> Self {
+        self.left_margin_mm = mm;
+        self
+    }
+}
+
+/// Paragraph properties written as an hh:paraPr entry. The writer keeps a
+/// deduplicated list whose index is the paraPr id; id 0 is the default.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ParaPrDef {
+    align: HwpxAlignment,
+    /// Space above the paragraph in HWPUNIT (hc:prev)
+    space_before: u32,
+    /// Space below the paragraph in HWPUNIT (hc:next)
+    space_after: u32,
+    /// Left margin in HWPUNIT (hc:left)
+    left_margin: u32,
+    /// Right margin in HWPUNIT (hc:right)
+    right_margin: u32,
+    /// First-line indent in HWPUNIT (hc:intent), negative for a hanging indent
+    indent: i32,
+    /// Line spacing in percent
+    line_spacing: u32,
+    /// hh:tabPr id; 0 has no custom tab stops
+    tab_pr: u32,
+    /// 1-based index into the writer's paragraph borders; 0 has no border
+    border: u32,
+    keep_with_next: bool,
+    keep_lines: bool,
+    page_break_before: bool,
+}
+
+impl Default for ParaPrDef {
+    fn default() -> Self {
+        Self {
+            align: HwpxAlignment::Justify,
+            space_before: 0,
+            space_after: 0,
+            left_margin: 0,
+            right_margin: 0,
+            indent: 0,
+            line_spacing: 160,
+            tab_pr: 0,
+            border: 0,
+            keep_with_next: false,
+            keep_lines: false,
+            page_break_before: false,
+        }
+    }
+}
+
+impl ParaPrDef {
+    /// A paraPr read from another package. Its tab stops and border refer to
+    /// definitions that are not carried over, so both are reset; line spacing
+    /// other than PERCENT falls back to the default.
+    fn from_xml(para_pr: &ParaPr) -> Self {
+        let default = Self::default();
+        let (margin, line_spacing) = para_pr.spacing();
+        let margin_value = |select: fn(&ParaPrMargin) -> &Option<ParaPrMarginValue>| {
+            margin
+                .and_then(|m| select(m).as_ref())
+                .and_then(|v| v.value)
+                .unwrap_or(0)
+        };
+        let non_negative = |value: i32| value.max(0) as u32;
+        let flag = |select: fn(&ParaPrBreakSetting) -> Option<u32>| {
+            para_pr.break_setting.as_ref().and_then(select) == Some(1)
+        };
+        Self {
+            align: para_pr
+                .alignment
+                .as_ref()
+                .and_then(|a| a.horizontal.as_deref())
+                .and_then(HwpxAlignment::from_hwpx_align)
+                .unwrap_or(default.align),
+            space_before: non_negative(margin_value(|m| &m.prev)),
+            space_after: non_negative(margin_value(|m| &m.next)),
+            left_margin: non_negative(margin_value(|m| &m.left)),
+            right_margin: non_negative(margin_value(|m| &m.right)),
+            indent: margin_value(|m| &m.intent),
+            line_spacing: line_spacing
+                .filter(|l| l.spacing_type.as_deref().unwrap_or("PERCENT") == "PERCENT")
+                .and_then(|l| l.value)
+                .unwrap_or(default.line_spacing),
+            keep_with_next: flag(|b| b.keep_with_next),
+            keep_lines: flag(|b| b.keep_lines),
+            page_break_before: flag(|b| b.page_break_before),
+            ..default
+        }
+    }
+}
+
+/// Centered paraPr used for table captions
+const CAPTION_PARA_PR: ParaPrDef = ParaPrDef {
+    align: HwpxAlignment::Center,
+    space_before: 0,
+    space_after: 0,
+    left_margin: 0,
+    right_margin: 0,
+    indent: 0,
+    line_spacing: 160,
+    tab_pr: 0,
+    border: 0,
+    keep_with_next: false,
+    keep_lines: false,
+    page_break_before: false,
+};
+
+/// Style of a text box (글상자)
+///
+/// The box is written as an inline single-cell table with its own borderFill,
+/// which Hangul renders as a bordered callout around the text.
+#[derive(Debug, Clone)]
+pub struct TextBoxStyle {
+    /// Box width in mm (clamped to the body width)
+    pub width_mm: u32,
+    /// Draw a solid border around the box
+    pub border: bool,
+    /// Background color (RGB format: 0xRRGGBB), `None` for transparent
+    pub fill_color: Option<u32>,
+    /// Inner padding between border and text in mm
+    pub padding_mm: u32,
+}
+
+impl Default for TextBoxStyle {
+    fn default() -> Self {
+        Self {
+            width_mm: 150,
+            border: true,
+            fill_color: None,
+            padding_mm: 2,
+        }
+    }
+}
+
+impl TextBoxStyle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set box width in mm
+    pub fn width(mut self, width_mm: u32) -> Self {
+        self.width_mm = width_mm;
+        self
+    }
+
+    /// Remove the border
+    pub fn no_border(mut self) -> Self {
+        self.border = false;
+        self
+    }
+
+    /// Set background color (RGB format: 0xRRGGBB)
+    pub fn fill(mut self, color: u32) -> Self {
+        self.fill_color = Some(color);
+        self
+    }
+
+    /// Set inner padding in mm
+    pub fn padding(mut self, padding_mm: u32) -> Self {
+        self.padding_mm = padding_mm;
+        self
+    }
+}
+
+/// Horizontal rule drawn as an hp:line across part of the text width
+#[derive(Debug, Clone, Copy)]
+struct HorizontalRule {
+    width_percent: u32,
+    thickness_mm: f64,
+    color: u32,
+}
+
+/// Which pages the header/footer applies to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HeaderFooterApplyTo {
+    /// All pages
+    All,
+    /// Odd pages only
+    Odd,
+    /// Even pages only
+    Even,
+    /// First section only. OWPML has no first-page-only apply type, so this is
+    /// written as BOTH into the first section alone; keep the cover page in its
+    /// own section (see `add_section`) to limit it to that page.
+    First,
+}
+
+/// Category of a problem reported by [`HwpxWriter::validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationIssueKind {
+    /// A char shape run or hyperlink lies past the end of the paragraph text, or
+    /// runs are not in ascending order.
+    CharPositionOutOfRange,
+    /// A run references a char shape id that is not registered.
+    UnknownCharShape,
+    /// Table rows have different lengths, or the table has no cells.
+    MalformedTable,
+    /// A merged cell extends past the last row or column.
+    TableSpanOutOfGrid,
+    /// An image has a zero width or height.
+    ZeroSizedImage,
+}
+
+/// A single inconsistency found by [`HwpxWriter::validate`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationIssue {
+    /// Document-wide paragraph index, or `None` for header/footer content
+    pub paragraph_index: Option<usize>,
+    pub kind: ValidationIssueKind,
+    pub message: String,
+}
+
+impl std::fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.paragraph_index {
+            Some(idx) => write!(f, "paragraph {}: {}", idx, self.message),
+            None => write!(f, "header/footer: {}", self.message),
+        }
+    }
+}
+
+/// Text direction of a section (글자 방향)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TextDirection {
+    #[default]
+    Horizontal,
+    /// 세로쓰기: characters run top to bottom, lines advance right to left
+    VerticalRightToLeft,
+}
+
+impl TextDirection {
+    fn as_hwpx(&self) -> &'static str {
+        match self {
+            Self::Horizontal => "HORIZONTAL",
+            Self::VerticalRightToLeft => "VERTICAL",
+        }
+    }
+
+    fn of_section(section: &Section) -> Self {
+        match section.section_def.as_ref().map(|def| def.text_direction()) {
+            Some(1) => Self::VerticalRightToLeft,
+            _ => Self::Horizontal,
+        }
+    }
+}
+
+/// Form control (양식 개체) placed in a paragraph of its own
+#[derive(Debug, Clone, PartialEq)]
+enum FormField {
+    CheckBox { label: String, checked: bool },
+    TextField { name: String, placeholder: String },
+}
+
+/// Line numbers printed in the margin of a section (hp:lineNumberShape)
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LineNumberShape {
+    count_by: u16,
+    start: u16,
+    /// Gap between the numbers and the text, in HWPUNIT
+    distance: u32,
+}
+
+/// Document metadata for HWPX content.hpf
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HwpxMetadata {
+    pub title: String,
+    pub creator: String,
+    pub created_date: String,
+    pub subject: String,
+    pub description: String,
+    /// Written as a single comma-separated keyword entry
+    pub keywords: Vec<String>,
+    /// Falls back to `created_date` when empty
+    pub modified_date: String,
+}
+
+/// Refers to a table added with [`HwpxWriter::add_table`], for later changes
+/// through [`HwpxWriter::table_mut`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TableHandle(usize);
+
+/// Refers to a picture added with [`HwpxWriter::add_image`], for later changes
+/// through [`HwpxWriter::image_mut`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ImageHandle(usize);
+
+pub struct HwpxWriter {
+    document: HwpDocument,
+    tables: Vec<(usize, HwpxTable)>,
+    images: Vec<(usize, HwpxImage)>,
+    text_boxes: Vec<(usize, String, TextBoxStyle)>,
+    horizontal_rules: Vec<(usize, HorizontalRule)>,
+    form_fields: Vec<(usize, FormField)>,
+    /// Directed runs as (paragraph, start, length, direction), in UTF-16 units
+    run_directions: Vec<(usize, u32, u32, RunDirection)>,
+    /// Headers/footers with the charPr ids registered for their styled runs
+    headers: Vec<(HwpxHeader, Vec<u16>)>,
+    footers: Vec<(HwpxFooter, Vec<u16>)>,
+    para_properties: Vec<ParaPrDef>,
+    /// Tab stop sets indexed by hh:tabPr id; id 0 is the empty default
+    tab_properties: Vec<Vec<TabStop>>,
+    /// Deduplicated paragraph borders; their borderFills follow the text boxes'
+    paragraph_borders: Vec<ParagraphBorder>,
+    /// Distinct picture bytes in BinData order, filled by `intern_image`
+    bin_data: Vec<HwpxImage>,
+    /// Content hash of each `bin_data` entry to its indices
+    bin_data_index: HashMap<u64, Vec<usize>>,
+    metadata: HwpxMetadata,
+    deterministic: bool,
+    strict: bool,
+    /// Space requested by `add_vertical_space`, applied to the next paragraph
+    pending_space_before: u32,
+    /// Set by `add_page_break`; the next paragraph starts a new page
+    pending_page_break: bool,
+    /// Line numbering keyed by document-wide section index
+    line_numbers: Vec<(usize, LineNumberShape)>,
+    /// charPr 0 is the shape of unstyled text rather than of the first styled run
+    has_default_char_shape: bool,
+    /// Face written for font 0; `None` keeps [`DEFAULT_FONT_FACE`]
+    default_font: Option<String>,
+    /// Faces of styled runs' `font_name`, written as font 1, 2, ...
+    fonts: Vec<String>,
+}
+
+impl HwpxWriter {
+    pub fn new() -> Self {
+        Self {
+            document: HwpDocument {
+                header: FileHeader::new_default(),
+                doc_info: DocInfo::default(),
+                body_texts: Vec::new(),
+                preview_text: None,
+                preview_image: None,
+                summary_info: None,
+                warnings: Vec::new(),
+            },
+            tables: Vec::new(),
+            images: Vec::new(),
+            text_boxes: Vec::new(),
+            horizontal_rules: Vec::new(),
+            form_fields: Vec::new(),
+            run_directions: Vec::new(),
+            headers: Vec::new(),
+            footers: Vec::new(),
+            para_properties: vec![ParaPrDef::default()],
+            tab_properties: vec![Vec::new()],
+            paragraph_borders: Vec::new(),
+            bin_data: Vec::new(),
+            bin_data_index: HashMap::new(),
+            metadata: HwpxMetadata::default(),
+            deterministic: true,
+            strict: false,
+            pending_space_before: 0,
+            pending_page_break: false,
+            line_numbers: Vec::new(),
+            has_default_char_shape: false,
+            default_font: None,
+            fonts: Vec::new(),
+        }
+    }
+
+    pub fn set_metadata(&mut self, metadata: HwpxMetadata) {
+        self.metadata = metadata;
+    }
+
+    /// Set `ModifiedDate` in content.hpf. When empty, the created date is used.
+    pub fn set_modified_date(&mut self, date: &str) {
+        self.metadata.modified_date = date.to_string();
+    }
+
+    /// Stamp every zip entry with a fixed mtime (1980-01-01) so the same content
+    /// always produces byte-identical output. On by default; when disabled,
+    /// entries carry the current time.
+    pub fn set_deterministic(&mut self, deterministic: bool) {
+        self.deterministic = deterministic;
+    }
+
+    /// Run [`HwpxWriter::validate`] before every write and fail with
+    /// [`HwpError::ParseError`] listing the issues instead of producing a
+    /// package Hangul cannot open. Off by default.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Check the document for inconsistencies that would produce a corrupt or
+    /// unreadable package: run positions past the text, unknown char shapes,
+    /// merged cells outside the table grid and zero-sized images.
+    ///
+    /// Issues are returned in document order; an empty list means the document
+    /// is consistent.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let char_shape_count = self.document.doc_info.char_shapes.len().max(1);
+
+        let paragraphs = self
+            .document
+            .body_texts
+            .iter()
+            .flat_map(|body| &body.sections)
+            .flat_map(|section| &section.paragraphs);
+        for (idx, para) in paragraphs.enumerate() {
+            let text_len = para
+                .text
+                .as_ref()
+                .map_or(0, |t| t.content.encode_utf16().count());
+            for link in &para.hyperlinks {
+                let end = link.start_position as usize + link.length as usize;
+                if end > text_len {
+                    issues.push(ValidationIssue {
+                        paragraph_index: Some(idx),
+                        kind: ValidationIssueKind::CharPositionOutOfRange,
+                        message: format!(
+                            "hyperlink to {} ends at {} but the text has {} UTF-16 units",
+                            link.target_url, end, text_len
+                        ),
+                    });
+                }
+            }
+            let Some(char_shapes) = &para.char_shapes else {
+                continue;
+            };
+            let mut last_pos = 0;
+            for run in &char_shapes.char_positions {
+                let pos = run.position as usize;
+                if pos > text_len {
+                    issues.push(ValidationIssue {
+                        paragraph_index: Some(idx),
+                        kind: ValidationIssueKind::CharPositionOutOfRange,
+                        message: format!(
+                            "run starts at {} but the text has {} UTF-16 units",
+                            pos, text_len
+                        ),
+                    });
+                } else if pos < last_pos {
+                    issues.push(ValidationIssue {
+                        paragraph_index: Some(idx),
+                        kind: ValidationIssueKind::CharPositionOutOfRange,
+                        message: format!("run at {} comes after a run at {}", pos, last_pos),
+                    });
+                }
+                last_pos = last_pos.max(pos);
+                if run.char_shape_id as usize >= char_shape_count {
+                    issues.push(ValidationIssue {
+                        paragraph_index: Some(idx),
+                        kind: ValidationIssueKind::UnknownCharShape,
+                        message: format!(
+                            "char shape {} is not defined ({} registered)",
+                            run.char_shape_id, char_shape_count
+                        ),
+                    });
+                }
+            }
+        }
+
+        for (idx, table) in &self.tables {
+            Self::validate_table(*idx, table, &mut issues);
+        }
+
+        let header_images = self.headers.iter().filter_map(|(h, _)| h.image.as_ref());
+        let footer_images = self.footers.iter().filter_map(|(f, _)| f.image.as_ref());
+        let images = self
+            .images
+            .iter()
+            .map(|(idx, image)| (Some(*idx), image))
+            .chain(
+                header_images
+                    .chain(footer_images)
+                    .map(|image| (None, image)),
+            );
+        for (idx, image) in images {
+            if image.width_mm == Some(0) || image.height_mm == Some(0) {
+                issues.push(ValidationIssue {
+                    paragraph_index: idx,
+                    kind: ValidationIssueKind::ZeroSizedImage,
+                    message: format!(
+                        "image is {}x{} mm",
+                        image.width_mm.unwrap_or(50),
+                        image.height_mm.unwrap_or(50)
+                    ),
+                });
+            }
+        }
+
+        issues.sort_by_key(|issue| issue.paragraph_index.unwrap_or(usize::MAX));
+        issues
+    }
+
+    fn validate_table(idx: usize, table: &HwpxTable, issues: &mut Vec<ValidationIssue>) {
+        let row_count = table.rows.len();
+        let col_count = table.rows.first().map_or(0, |r| r.len());
+        if col_count == 0 {
+            issues.push(ValidationIssue {
+                paragraph_index: Some(idx),
+                kind: ValidationIssueKind::MalformedTable,
+                message: "table has no cells".to_string(),
+            });
+            return;
+        }
+        if let Some(row) = table.rows.iter().position(|r| r.len() != col_count) {
+            issues.push(ValidationIssue {
+                paragraph_index: Some(idx),
+                kind: ValidationIssueKind::MalformedTable,
+                message: format!(
+                    "table row {} has {} cells, expected {}",
+                    row,
+                    table.rows[row].len(),
+                    col_count
+                ),
+            });
+        }
+
+        let mut spans: Vec<_> = table.cell_spans.iter().collect();
+        spans.sort_by_key(|(pos, _)| **pos);
+        for (&(row, col), span) in spans {
+            if row + span.row_span as usize > row_count || col + span.col_span as usize > col_count
+            {
+                issues.push(ValidationIssue {
+                    paragraph_index: Some(idx),
+                    kind: ValidationIssueKind::TableSpanOutOfGrid,
+                    message: format!(
+                        "cell ({}, {}) spans {}x{} in a {}x{} table",
+                        row, col, span.row_span, span.col_span, row_count, col_count
+                    ),
+                });
+            }
+        }
+    }
+
+    pub fn from_document(document: HwpDocument) -> Self {
+        let has_default_char_shape = !document.doc_info.char_shapes.is_empty();
+        Self {
+            document,
+            tables: Vec::new(),
+            images: Vec::new(),
+            text_boxes: Vec::new(),
+            horizontal_rules: Vec::new(),
+            form_fields: Vec::new(),
+            run_directions: Vec::new(),
+            headers: Vec::new(),
+            footers: Vec::new(),
+            para_properties: vec![ParaPrDef::default()],
+            tab_properties: vec![Vec::new()],
+            paragraph_borders: Vec::new(),
+            bin_data: Vec::new(),
+            bin_data_index: HashMap::new(),
+            metadata: HwpxMetadata::default(),
+            deterministic: true,
+            strict: false,
+            pending_space_before: 0,
+            pending_page_break: false,
+            line_numbers: Vec::new(),
+            has_default_char_shape,
+            default_font: None,
+            fonts: Vec::new(),
+        }
+    }
+
+    /// Load an existing HWPX package so that subsequent `add_*` calls append to it.
+    ///
+    /// Char shapes, sections, tables and embedded images are carried over; BinData items
+    /// are renumbered on output and new char shapes are appended after the existing ones,
+    /// so ids never collide. Paragraph shapes keep their alignment, margins, indent,
+    /// spacing and keep/page-break settings and are renumbered into this writer's list;
+    /// their tab stops, borders and numbering are dropped, and every paragraph uses
+    /// style 0 since styles are not carried over. The first section's header and footer texts are carried
+    /// over as plain headers/footers (run styles and header pictures are not).
+    ///
+    /// Loaded text keeps its char shapes when [`HwpxWriter::set_default_text_style`]
+    /// is called afterwards: the new default is inserted as charPr 0 instead of
+    /// replacing the package's first shape.
+    pub fn from_hwpx_bytes(bytes: &[u8]) -> Result<Self> {
+        let contents = HwpxReader::read_package(bytes)?;
+        let mut writer = Self::from_document(contents.document);
+
+        // paraPr ids of the source header map to the writer's deduplicated list;
+        // unknown ids fall back to the default paraPr.
+        let para_pr_ids: HashMap<u32, u32> = contents
+            .para_properties
+            .iter()
+            .map(|para_pr| (para_pr.id, writer.add_para_pr(ParaPrDef::from_xml(para_pr))))
+            .collect();
+
+        // Only style 0 is emitted in header.xml, so style references into the
+        // source header would dangle. Text on charPr 0 is pinned to it explicitly
+        // so that a later default shape does not restyle it.
+        for paragraph in writer
+            .document
+            .body_texts
+            .iter_mut()
+            .flat_map(|b| &mut b.sections)
+            .flat_map(|s| &mut s.paragraphs)
+        {
+            paragraph.para_shape_id = para_pr_ids
+                .get(&u32::from(paragraph.para_shape_id))
+                .map_or(0, |&id| id as u16);
+            paragraph.style_id = 0;
+            if paragraph
+                .text
+                .as_ref()
+                .is_some_and(|t| !t.content.is_empty())
+            {
+                let char_shapes = paragraph.char_shapes.get_or_insert_with(|| ParaCharShape {
+                    char_positions: Vec::new(),
+                });
+                if char_shapes.char_positions.first().map(|p| p.position) != Some(0) {
+                    char_shapes.char_positions.insert(
+                        0,
+                        CharPositionShape {
+                            position: 0,
+                            char_shape_id: 0,
+                        },
+                    );
+                }
+            }
+        }
+        writer.has_default_char_shape = false;
+
+        for header in HwpxReader::extract_headers(bytes)? {
+            if header.section_index == 0 {
+                writer.add_header_config(HwpxHeader {
+                    apply_to: header.apply_to,
+                    ..HwpxHeader::new(&header.text)
+                });
+            }
+        }
+        for footer in HwpxReader::extract_footers(bytes)? {
+            if footer.section_index == 0 {
+                writer.add_footer_config(HwpxFooter {
+                    apply_to: footer.apply_to,
+                    include_page_number: footer.has_page_number,
+                    ..HwpxFooter::new(&footer.text)
+                });
+            }
+        }
+
+        for (para_idx, mut table) in contents.tables {
+            writer.intern_cell_images(&mut table);
+            writer.tables.push((para_idx, table));
+        }
+        for (para_idx, image) in contents.images {
+            let image = writer.intern_image(image);
+            writer.images.push((para_idx, image));
+        }
+        Ok(writer)
+    }
+
+    /// Load an existing HWPX file; see [`HwpxWriter::from_hwpx_bytes`].
+    pub fn from_hwpx_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let bytes = std::fs::read(path).map_err(HwpError::Io)?;
+        Self::from_hwpx_bytes(&bytes)
+    }
+
+    /// Replace the paragraph whose trimmed text is `marker` with what `fill` adds.
+    ///
+    /// The paragraphs after the marker, with their tables, images and other
+    /// objects, follow the new content. Only the last section is searched; when
+    /// no paragraph matches, `fill` is not called and `Ok(false)` is returned.
+    pub fn insert_at_marker<F, E>(&mut self, marker: &str, fill: F) -> std::result::Result<bool, E>
+    where
+        F: FnOnce(&mut Self) -> std::result::Result<(), E>,
+    {
+        let total = self.current_paragraph_count();
+        let section = self
+            .document
+            .body_texts
+            .last_mut()
+            .and_then(|body| body.sections.last_mut());
+        let Some(section) = section else {
+            return Ok(false);
+        };
+        let Some(local) = section
+            .paragraphs
+            .iter()
+            .position(|p| p.text.as_ref().is_some_and(|t| t.content.trim() == marker))
+        else {
+            return Ok(false);
+        };
+        let marker_idx = total - section.paragraphs.len() + local;
+        let tail = section.paragraphs.split_off(local + 1);
+        section.paragraphs.pop();
+
+        fn split_after<T>(items: &mut Vec<(usize, T)>, idx: usize) -> Vec<(usize, T)> {
+            let (after, before) = std::mem::take(items)
+                .into_iter()
+                .partition(|(para, _)| *para > idx);
+            *items = before;
+            after
+        }
+        let tables = split_after(&mut self.tables, marker_idx);
+        let images = split_after(&mut self.images, marker_idx);
+        let (text_boxes, kept) = std::mem::take(&mut self.text_boxes)
+            .into_iter()
+            .partition(|(para, ..)| *para > marker_idx);
+        self.text_boxes = kept;
+        let rules = split_after(&mut self.horizontal_rules, marker_idx);
+        let form_fields = split_after(&mut self.form_fields, marker_idx);
+        let (directions, kept) = std::mem::take(&mut self.run_directions)
+            .into_iter()
+            .partition(|(para, ..)| *para > marker_idx);
+        self.run_directions = kept;
+
+        fill(self)?;
+
+        // Tail paragraph `marker_idx + 1 + k` now sits at `start + k`
+        let start = self.current_paragraph_count();
+        let shift = |para: usize| para - (marker_idx + 1) + start;
+        self.current_section_mut().paragraphs.extend(tail);
+        self.tables
+            .extend(tables.into_iter().map(|(p, t)| (shift(p), t)));
+        self.images
+            .extend(images.into_iter().map(|(p, i)| (shift(p), i)));
+        self.text_boxes.extend(
+            text_boxes
+                .into_iter()
+                .map(|(p, text, style): (usize, String, TextBoxStyle)| (shift(p), text, style)),
+        );
+        self.horizontal_rules
+            .extend(rules.into_iter().map(|(p, r)| (shift(p), r)));
+        self.form_fields
+            .extend(form_fields.into_iter().map(|(p, f)| (shift(p), f)));
+        self.run_directions.extend(directions.into_iter().map(
+            |(p, offset, len, dir): (usize, u32, u32, RunDirection)| (shift(p), offset, len, dir),
+        ));
+        Ok(true)
+    }
+
+    /// Append every section of `other` after the sections of this writer.
+    ///
+    /// Char shape ids, paragraph-keyed tables/images and BinData items of `other` are
+    /// shifted so that they keep pointing at their own definitions.
+    pub(crate) fn append_sections_from(&mut self, mut other: HwpxWriter) {
+        let char_shape_offset = self.document.doc_info.char_shapes.len() as u16;
+        let para_offset = self.current_paragraph_count();
+
+        // Runs without an explicit char shape fall back to charPr 0, which after the
+        // shift belongs to another document; pin them to `other`'s own default.
+        if other.document.doc_info.char_shapes.is_empty() {
+            other
+                .document
+                .doc_info
+                .char_shapes
+                .push(CharShape::new_default());
+        }
+        for paragraph in other
+            .document
+            .body_texts
+            .iter_mut()
+            .flat_map(|b| &mut b.sections)
+            .flat_map(|s| &mut s.paragraphs)
+        {
+            let has_text = paragraph
+                .text
+                .as_ref()
+                .is_some_and(|t| !t.content.is_empty());
+            if !has_text {
+                continue;
+            }
+            let char_shapes = paragraph.char_shapes.get_or_insert_with(|| ParaCharShape {
+                char_positions: Vec::new(),
+            });
+            if char_shapes.char_positions.first().map(|p| p.position) != Some(0) {
+                char_shapes.char_positions.insert(
+                    0,
+                    CharPositionShape {
+                        position: 0,
+                        char_shape_id: 0,
+                    },
+                );
+            }
+            for pos in &mut char_shapes.char_positions {
+                pos.char_shape_id += char_shape_offset;
+            }
+        }
+
+        self.document
+            .doc_info
+            .char_shapes
+            .append(&mut other.document.doc_info.char_shapes);
+        self.document
+            .body_texts
+            .append(&mut other.document.body_texts);
+        for (idx, mut table) in other.tables {
+            self.intern_cell_images(&mut table);
+            self.tables.push((idx + para_offset, table));
+        }
+        for (idx, image) in other.images {
+            let image = self.intern_image(image);
+            self.images.push((idx + para_offset, image));
+        }
+        self.text_boxes.extend(
+            other
+                .text_boxes
+                .into_iter()
+                .map(|(idx, text, style)| (idx + para_offset, text, style)),
+        );
+        self.horizontal_rules.extend(
+            other
+                .horizontal_rules
+                .into_iter()
+                .map(|(idx, rule)| (idx + para_offset, rule)),
+        );
+        self.form_fields.extend(
+            other
+                .form_fields
+                .into_iter()
+                .map(|(idx, field)| (idx + para_offset, field)),
+        );
+        self.run_directions.extend(
+            other
+                .run_directions
+                .into_iter()
+                .map(|(idx, start, len, dir)| (idx + para_offset, start, len, dir)),
+        );
+    }
+
+    /// Apply a page layout (paper size, margins, columns) to the current section.
+    pub fn set_page_layout(&mut self, layout: PageLayout) -> Result<()> {
+        self.current_section_mut().page_def = Some(PageDef::from_layout(layout));
+        Ok(())
+    }
+
+    /// Number of body paragraphs so far, not counting paragraphs inside table cells
+    pub fn paragraph_count(&self) -> usize {
+        self.current_paragraph_count()
+    }
+
+    /// Total bytes of the distinct pictures embedded so far (BinData entries)
+    pub fn embedded_image_bytes(&self) -> usize {
+        self.bin_images().iter().map(|image| image.data.len()).sum()
+    }
+
+    /// Plain text of the body as written so far, the same as
+    /// [`HwpxReader::extract_text_with_options`](super::HwpxReader::extract_text_with_options)
+    /// on the serialized document but without building or re-reading the package.
+    ///
+    /// Every non-empty paragraph is followed by `\n`; with `include_tables`, the
+    /// text of each table's cells follows the paragraph holding the table.
+    /// Headers, footers, text boxes and table captions are not included.
+    pub fn extract_text(&self, include_tables: bool) -> String {
+        let mut text = String::new();
+        let paragraphs = self
+            .document
+            .body_texts
+            .iter()
+            .flat_map(|b| &b.sections)
+            .flat_map(|s| &s.paragraphs);
+        for (para_idx, para) in paragraphs.enumerate() {
+            let content = para.text.as_ref().map_or("", |t| t.content.as_str());
+            let table = self
+                .get_table_for_paragraph(para_idx)
+                .map(|(_, table)| table);
+            let para_text = if para.char_shapes.is_some() || !para.hyperlinks.is_empty() {
+                let directions = self.run_directions_for(para_idx);
+                let runs = paragraph_runs(content, para, &directions);
+                if runs.is_empty() && para.char_shapes.is_none() {
+                    content.to_string()
+                } else {
+                    runs.iter()
+                        .map(|run| run.direction.isolate(&run.text))
+                        .collect()
+                }
+            } else if table.is_some()
+                || self.get_image_for_paragraph(para_idx).is_some()
+                || self.get_text_box_for_paragraph(para_idx).is_some()
+                || self.get_horizontal_rule_for_paragraph(para_idx).is_some()
+                || self.get_form_field_for_paragraph(para_idx).is_some()
+            {
+                String::new()
+            } else {
+                content.to_string()
+            };
+            if !para_text.is_empty() {
+                text.push_str(&para_text);
+                text.push('\n');
+            }
+
+            let Some(table) = table.filter(|_| include_tables) else {
+                continue;
+            };
+            let col_count = table.rows.first().map_or(0, Vec::len);
+            for (row_idx, row) in table.rows.iter().enumerate() {
+                for (col_idx, cell) in row.iter().enumerate().take(col_count) {
+                    if table.is_covered(row_idx, col_idx) {
+                        continue;
+                    }
+                    for line in cell.split('\n').filter(|line| !line.is_empty()) {
+                        text.push_str(line);
+                        text.push('\n');
+                    }
+                }
+            }
+        }
+        text
+    }
+
+    /// Column width of the current section in mm, the widest a table or picture
+    /// is drawn
+    pub fn column_width_mm(&self) -> f64 {
+        let layout = self
+            .document
+            .body_texts
+            .last()
+            .and_then(|body| body.sections.last())
+            .and_then(|section| section.page_def.as_ref())
+            .and_then(|page_def| page_def.layout.clone())
+            .unwrap_or_else(default_page_layout);
+        layout.column_width() as f64 * 25.4 / 7200.0
+    }
+
+    /// Start a new section; subsequent content and [`HwpxWriter::set_page_layout`]
+    /// calls apply to it.
+    pub fn add_section(&mut self) -> Result<()> {
+        self.push_section();
+        Ok(())
+    }
+
+    fn push_section(&mut self) {
+        let section = Section {
+            paragraphs: Vec::new(),
+            section_def: None,
+            page_def: None,
+        };
+        match self.document.body_texts.last_mut() {
+            Some(body) => body.sections.push(section),
+            None => self.document.body_texts.push(BodyText {
+                sections: vec![section],
+            }),
+        }
+    }
+
+    pub fn add_paragraph(&mut self, text: &str) -> Result<()> {
+        let paragraph = Paragraph {
+            text: Some(ParaText {
+                content: text.to_string(),
+            }),
+            ..Default::default()
+        };
+
+        self.push_paragraph(paragraph);
+        Ok(())
+    }
+
+    /// Leave `pt` points of blank space above the next paragraph.
+    ///
+    /// Unlike an empty paragraph this adds nothing the reader has to delete later:
+    /// the space becomes the next paragraph's space-before (its own paraPr).
+    /// Consecutive calls add up; space requested after the last paragraph is
+    /// dropped. Fails with [`HwpError::InvalidInput`] if the total does not fit
+    /// hc:prev, a signed 32-bit HWPUNIT value.
+    pub fn add_vertical_space(&mut self, pt: u32) -> Result<()> {
+        self.pending_space_before = pt
+            .checked_mul(100)
+            .and_then(|space| self.pending_space_before.checked_add(space))
+            .filter(|&space| space <= i32::MAX as u32)
+            .ok_or_else(|| {
+                HwpError::InvalidInput(format!("vertical space of {pt}pt is too large"))
+            })?;
+        Ok(())
+    }
+
+    /// Start the next paragraph on a new page.
+    ///
+    /// The break is written as `pageBreak="1"` on that paragraph. A second call
+    /// before any content adds an empty paragraph, so each break yields a page;
+    /// a break after the last paragraph is dropped.
+    pub fn add_page_break(&mut self) -> Result<()> {
+        if self.pending_page_break {
+            self.add_paragraph("")?;
+        }
+        self.pending_page_break = true;
+        Ok(())
+    }
+
+    /// Append `n` literal empty paragraphs.
+    pub fn add_empty_paragraphs(&mut self, n: usize) -> Result<()> {
+        for _ in 0..n {
+            self.add_paragraph("")?;
+        }
+        Ok(())
+    }
+
+    /// Add a plain-text paragraph with its own paragraph style. Tab characters
+    /// in `text` jump to the style's tab stops.
+    pub fn add_paragraph_with_style(
+        &mut self,
+        text: &str,
+        style: &HwpxParagraphStyle,
+    ) -> Result<()> {
+        let para_shape_id = self.add_paragraph_style(style) as u16;
         let paragraph = Paragraph {
             text: Some(ParaText {
-                content: String::new(),
+                content: text.to_string(),
+            }),
+            para_shape_id,
+            ..Default::default()
+        };
+
+        self.push_paragraph(paragraph);
+        Ok(())
+    }
+
+    /// Register the paraPr (and tabPr) for a paragraph style and return its id
+    fn add_paragraph_style(&mut self, style: &HwpxParagraphStyle) -> u32 {
+        let tab_pr = if style.tab_stops.is_empty() {
+            0
+        } else if let Some(id) = self
+            .tab_properties
+            .iter()
+            .position(|stops| *stops == style.tab_stops)
+        {
+            id as u32
+        } else {
+            self.tab_properties.push(style.tab_stops.clone());
+            (self.tab_properties.len() - 1) as u32
+        };
+        let border = match style.border {
+            None => 0,
+            Some(border) => match self.paragraph_borders.iter().position(|b| *b == border) {
+                Some(idx) => idx as u32 + 1,
+                None => {
+                    self.paragraph_borders.push(border);
+                    self.paragraph_borders.len() as u32
+                }
+            },
+        };
+        self.add_para_pr(ParaPrDef {
+            align: style.alignment,
+            tab_pr,
+            border,
+            keep_with_next: style.keep_with_next,
+            keep_lines: style.keep_lines,
+            page_break_before: style.page_break_before,
+            left_margin: mm_to_hwp_units(style.left_margin_mm.max(0.0)),
+            ..Default::default()
+        })
+    }
+
+    pub fn add_styled_paragraph(&mut self, text: &str, style: HwpxTextStyle) -> Result<()> {
+        let char_shape = self.char_shape_for(&style);
+        let char_shape_id = self.add_char_shape(char_shape);
+
+        let paragraph = Paragraph {
+            text: Some(ParaText {
+                content: text.to_string(),
+            }),
+            char_shapes: Some(ParaCharShape {
+                char_positions: vec![CharPositionShape {
+                    position: 0,
+                    char_shape_id,
+                }],
+            }),
+            ..Default::default()
+        };
+
+        self.push_paragraph(paragraph);
+        Ok(())
+    }
+
+    /// Add a paragraph in a single text style with its own paragraph style
+    pub fn add_styled_paragraph_with_style(
+        &mut self,
+        text: &str,
+        style: HwpxTextStyle,
+        para_style: &HwpxParagraphStyle,
+    ) -> Result<()> {
+        let para_shape_id = self.add_paragraph_style(para_style) as u16;
+        let char_shape = self.char_shape_for(&style);
+        let char_shape_id = self.add_char_shape(char_shape);
+
+        let paragraph = Paragraph {
+            text: Some(ParaText {
+                content: text.to_string(),
+            }),
+            para_shape_id,
+            char_shapes: Some(ParaCharShape {
+                char_positions: vec![CharPositionShape {
+                    position: 0,
+                    char_shape_id,
+                }],
             }),
             ..Default::default()
         };
+
+        self.push_paragraph(paragraph);
+        Ok(())
+    }
+
+    pub fn add_mixed_styled_paragraph(&mut self, runs: Vec<StyledText>) -> Result<()> {
+        self.push_mixed_paragraph(runs, 0)
+    }
+
+    /// Add a paragraph of styled runs with its own paragraph style
+    pub fn add_mixed_styled_paragraph_with_style(
+        &mut self,
+        runs: Vec<StyledText>,
+        para_style: &HwpxParagraphStyle,
+    ) -> Result<()> {
+        let para_shape_id = self.add_paragraph_style(para_style) as u16;
+        self.push_mixed_paragraph(runs, para_shape_id)
+    }
+
+    fn push_mixed_paragraph(&mut self, runs: Vec<StyledText>, para_shape_id: u16) -> Result<()> {
+        let mut full_text = String::new();
+        let mut char_positions = Vec::new();
+        let mut position: u32 = 0;
+
+        let mut hyperlinks = Vec::new();
+        let para_idx = self.current_paragraph_count();
+
+        for run in runs {
+            let char_shape = self.char_shape_for(&run.style);
+            let char_shape_id = self.add_char_shape(char_shape);
+
+            char_positions.push(CharPositionShape {
+                position,
+                char_shape_id,
+            });
+
+            let length = run.text.encode_utf16().count() as u32;
+            if run.direction != RunDirection::Auto {
+                self.run_directions
+                    .push((para_idx, position, length, run.direction));
+            }
+            if let Some(url) = &run.url {
+                hyperlinks.push(
+                    Hyperlink::new_url(&run.text, url)
+                        .with_position(position)
+                        .with_length(length),
+                );
+            }
+            position += length;
+            full_text.push_str(&run.text);
+        }
+
+        let paragraph = Paragraph {
+            text: Some(ParaText { content: full_text }),
+            para_shape_id,
+            char_shapes: Some(ParaCharShape { char_positions }),
+            hyperlinks,
+            ..Default::default()
+        };
+
         self.push_paragraph(paragraph);
         Ok(())
     }
 
-    pub fn add_image_from_file<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<()> {
+    pub fn add_table(&mut self, mut table: HwpxTable) -> Result<TableHandle> {
+        self.intern_cell_images(&mut table);
+        let para_idx = self.current_paragraph_count();
+        let handle = TableHandle(self.tables.len());
+        self.tables.push((para_idx, table));
+
+        let paragraph = Paragraph {
+            text: Some(ParaText {
+                content: String::new(),
+            }),
+            ..Default::default()
+        };
+        self.push_paragraph(paragraph);
+        Ok(handle)
+    }
+
+    pub fn add_image(&mut self, image: HwpxImage) -> Result<ImageHandle> {
+        self.push_image(image, 0)
+    }
+
+    /// Add a picture in a paragraph with its own paragraph style, e.g. centered.
+    /// The picture sits in the line like a character, so the alignment places it.
+    pub fn add_image_with_style(
+        &mut self,
+        image: HwpxImage,
+        para_style: &HwpxParagraphStyle,
+    ) -> Result<ImageHandle> {
+        let para_shape_id = self.add_paragraph_style(para_style) as u16;
+        self.push_image(image, para_shape_id)
+    }
+
+    fn push_image(&mut self, image: HwpxImage, para_shape_id: u16) -> Result<ImageHandle> {
+        let para_idx = self.current_paragraph_count();
+        let image = self.intern_image(image);
+        let handle = ImageHandle(self.images.len());
+        self.images.push((para_idx, image));
+
+        let paragraph = Paragraph {
+            text: Some(ParaText {
+                content: String::new(),
+            }),
+            para_shape_id,
+            ..Default::default()
+        };
+        self.push_paragraph(paragraph);
+        Ok(handle)
+    }
+
+    /// A table added earlier, e.g. to set its caption once later content fixes the
+    /// numbering. A caption set this way is still centered like one passed to
+    /// `add_table`. `None` if the handle comes from another writer.
+    pub fn table_mut(&mut self, handle: TableHandle) -> Option<&mut HwpxTable> {
+        self.tables.get_mut(handle.0).map(|(_, table)| table)
+    }
+
+    /// A picture added earlier, e.g. to resize it. `None` if the handle comes from
+    /// another writer. Replacing `data` stores the new bytes as another BinData item;
+    /// the item for the old bytes stays in the package.
+    pub fn image_mut(&mut self, handle: ImageHandle) -> Option<&mut HwpxImage> {
+        self.images.get_mut(handle.0).map(|(_, image)| image)
+    }
+
+    /// Add a picture read from `path`. The file is read once into the image's shared
+    /// buffer, which is written into the package without further copies; the bytes
+    /// are not streamed from disk, so the whole file stays in memory until the writer
+    /// is dropped.
+    pub fn add_image_from_file<P: AsRef<std::path::Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<ImageHandle> {
         let data = std::fs::read(path).map_err(HwpError::Io)?;
         let image = HwpxImage::from_bytes(data)
             .ok_or_else(|| HwpError::ParseError("Unsupported image format".to_string()))?;
         self.add_image(image)
     }
 
+    /// Add a plain paragraph in which each link's text is found (in order) and
+    /// turned into a hyperlink. Links whose text does not occur are ignored.
+    #[deprecated(
+        note = "locates links by searching the text; build the paragraph from runs with \
+                `add_mixed_styled_paragraph` and `StyledText::link` instead"
+    )]
     pub fn add_paragraph_with_hyperlinks(
         &mut self,
         text: &str,
         links: Vec<HwpxHyperlink>,
     ) -> Result<()> {
-        let para_idx = self.current_paragraph_count();
-        self.hyperlinks.push((para_idx, links));
+        let mut hyperlinks = Vec::new();
+        let mut search_from = 0;
+        for link in links {
+            if let Some(offset) = text[search_from..].find(&link.text) {
+                let start = search_from + offset;
+                let length = link.text.encode_utf16().count() as u32;
+                hyperlinks.push(
+                    Hyperlink::new_url(&link.text, &link.url)
+                        .with_position(text[..start].encode_utf16().count() as u32)
+                        .with_length(length),
+                );
+                search_from = start + link.text.len();
+            }
+        }
+        self.push_linked_paragraph(text, hyperlinks);
+        Ok(())
+    }
 
+    /// Add a paragraph that consists of a single link
+    pub fn add_hyperlink(&mut self, display_text: &str, url: &str) -> Result<()> {
+        let length = display_text.encode_utf16().count() as u32;
+        let link = Hyperlink::new_url(display_text, url)
+            .with_position(0)
+            .with_length(length);
+        self.push_linked_paragraph(display_text, vec![link]);
+        Ok(())
+    }
+
+    /// Plain-text paragraph (no char shapes) carrying hyperlink ranges
+    fn push_linked_paragraph(&mut self, text: &str, hyperlinks: Vec<Hyperlink>) {
         let paragraph = Paragraph {
             text: Some(ParaText {
                 content: text.to_string(),
             }),
+            hyperlinks,
+            ..Default::default()
+        };
+        self.push_paragraph(paragraph);
+    }
+
+    /// Add a bordered text box (글상자) holding `text`; newlines start new paragraphs
+    /// inside the box. The box is written as a single-cell table, not a drawing
+    /// object, so [`HwpxReader::extract_tables`] lists it among the tables.
+    pub fn add_text_box(&mut self, text: &str, style: TextBoxStyle) -> Result<()> {
+        let para_idx = self.current_paragraph_count();
+        self.text_boxes.push((para_idx, text.to_string(), style));
+
+        let paragraph = Paragraph {
+            text: Some(ParaText {
+                content: String::new(),
+            }),
             ..Default::default()
         };
         self.push_paragraph(paragraph);
         Ok(())
     }
 
-    pub fn add_hyperlink(&mut self, display_text: &str, url: &str) -> Result<()> {
-        self.add_paragraph_with_hyperlinks(
-            display_text,
-            vec![HwpxHyperlink::new(display_text, url)],
-        )
+    /// Add a horizontal line spanning `width_percent` of the text width.
+    ///
+    /// `thickness_mm` is the stroke width and `color` is RGB (0xRRGGBB).
+    pub fn add_horizontal_rule(
+        &mut self,
+        width_percent: u32,
+        thickness_mm: f64,
+        color: u32,
+    ) -> Result<()> {
+        let para_idx = self.current_paragraph_count();
+        self.horizontal_rules.push((
+            para_idx,
+            HorizontalRule {
+                width_percent: width_percent.clamp(1, 100),
+                thickness_mm,
+                color,
+            },
+        ));
+
+        let paragraph = Paragraph {
+            text: Some(ParaText {
+                content: String::new(),
+            }),
+            ..Default::default()
+        };
+        self.push_paragraph(paragraph);
+        Ok(())
+    }
+
+    /// Add a paragraph holding a check box (확인란) captioned with `label`.
+    pub fn add_checkbox_paragraph(&mut self, label: &str, checked: bool) -> Result<()> {
+        self.add_form_field(FormField::CheckBox {
+            label: label.to_string(),
+            checked,
+        })
+    }
+
+    /// Add a paragraph holding a single-line input box (입력 상자) named `name`,
+    /// pre-filled with `placeholder`.
+    pub fn add_text_field(&mut self, name: &str, placeholder: &str) -> Result<()> {
+        self.add_form_field(FormField::TextField {
+            name: name.to_string(),
+            placeholder: placeholder.to_string(),
+        })
+    }
+
+    fn add_form_field(&mut self, field: FormField) -> Result<()> {
+        let para_idx = self.current_paragraph_count();
+        self.form_fields.push((para_idx, field));
+
+        let paragraph = Paragraph {
+            text: Some(ParaText {
+                content: String::new(),
+            }),
+            ..Default::default()
+        };
+        self.push_paragraph(paragraph);
+        Ok(())
     }
 
     pub fn add_header(&mut self, text: &str) {
-        self.headers.push(HwpxHeader::new(text));
+        self.add_header_config(HwpxHeader::new(text));
     }
 
-    pub fn add_header_config(&mut self, header: HwpxHeader) {
-        self.headers.push(header);
+    pub fn add_header_config(&mut self, mut header: HwpxHeader) {
+        header.image = header.image.map(|image| self.intern_image(image));
+        let run_shapes = self.add_run_char_shapes(&header.runs);
+        self.headers.push((header, run_shapes));
     }
 
     pub fn add_footer(&mut self, text: &str) {
-        self.footers.push(HwpxFooter::new(text));
+        self.add_footer_config(HwpxFooter::new(text));
     }
 
     pub fn add_footer_with_page_number(&mut self, prefix: &str) {
-        self.footers
-            .push(HwpxFooter::new(prefix).with_page_number());
+        self.add_footer_config(HwpxFooter::new(prefix).with_page_number());
+    }
+
+    pub fn add_footer_config(&mut self, mut footer: HwpxFooter) {
+        footer.image = footer.image.map(|image| self.intern_image(image));
+        let run_shapes = self.add_run_char_shapes(&footer.runs);
+        if let Some(align) = footer.alignment {
+            self.add_para_pr(ParaPrDef {
+                align,
+                ..Default::default()
+            });
+        }
+        self.footers.push((footer, run_shapes));
+    }
+
+    fn add_para_pr(&mut self, def: ParaPrDef) -> u32 {
+        if let Some(id) = self.para_properties.iter().position(|p| *p == def) {
+            return id as u32;
+        }
+        self.para_properties.push(def);
+        (self.para_properties.len() - 1) as u32
+    }
+
+    /// Id of a paraPr in [`Self::written_para_properties`], 0 if it is not written
+    fn para_pr_id(&self, def: &ParaPrDef) -> u32 {
+        self.written_para_properties()
+            .iter()
+            .position(|p| p == def)
+            .unwrap_or(0) as u32
+    }
+
+    /// The paraPrs written to header.xml: those registered through `add_para_pr`,
+    /// then the caption style if a table has a caption and no registered paraPr
+    /// matches it. Captions can be set after the table is added (through
+    /// [`Self::table_mut`]), so their style is only known here.
+    fn written_para_properties(&self) -> Cow<'_, [ParaPrDef]> {
+        let has_caption = self.tables.iter().any(|(_, t)| t.caption.is_some());
+        if has_caption && !self.para_properties.contains(&CAPTION_PARA_PR) {
+            let mut defs = self.para_properties.clone();
+            defs.push(CAPTION_PARA_PR);
+            Cow::Owned(defs)
+        } else {
+            Cow::Borrowed(&self.para_properties)
+        }
+    }
+
+    /// Number pages of the current section from `page` (hp:startNum). In the first
+    /// section this is also the document's hh:beginNum.
+    pub fn set_start_page(&mut self, page: u16) -> Result<()> {
+        let section = self.current_section_mut();
+        let mut layout = section
+            .page_def
+            .as_ref()
+            .and_then(|page_def| page_def.layout.clone())
+            .unwrap_or_else(default_page_layout);
+        layout.start_page_number = page;
+        section.page_def = Some(PageDef::from_layout(layout));
+        Ok(())
+    }
+
+    /// Set the text direction of the current section. Tables and text boxes in
+    /// the section follow it; headers and footers stay horizontal.
+    pub fn set_text_direction(&mut self, direction: TextDirection) -> Result<()> {
+        let section_def = self
+            .current_section_mut()
+            .section_def
+            .get_or_insert_with(SectionDef::new_default);
+        let bits = match direction {
+            TextDirection::Horizontal => 0,
+            TextDirection::VerticalRightToLeft => 1,
+        };
+        section_def.properties = (section_def.properties & !(0x7 << 16)) | (bits << 16);
+        Ok(())
+    }
+
+    /// Use `style` for all unstyled text: plain paragraphs, table cells and header/footer
+    /// text written without runs. Its size and attributes become charPr 0 and its font,
+    /// if any, becomes the document font. Styled runs keep their own shapes.
+    pub fn set_default_text_style(&mut self, style: HwpxTextStyle) {
+        let char_shape = style.to_char_shape();
+        if self.has_default_char_shape {
+            self.document.doc_info.char_shapes[0] = char_shape;
+        } else {
+            // Shapes registered so far belong to styled runs; make room at id 0
+            for pos in self
+                .document
+                .body_texts
+                .iter_mut()
+                .flat_map(|b| &mut b.sections)
+                .flat_map(|s| &mut s.paragraphs)
+                .filter_map(|p| p.char_shapes.as_mut())
+                .flat_map(|c| &mut c.char_positions)
+            {
+                pos.char_shape_id += 1;
+            }
+            let run_shapes = self
+                .headers
+                .iter_mut()
+                .map(|(_, shapes)| shapes)
+                .chain(self.footers.iter_mut().map(|(_, shapes)| shapes));
+            for id in run_shapes.flatten() {
+                *id += 1;
+            }
+            self.document.doc_info.char_shapes.insert(0, char_shape);
+            self.has_default_char_shape = true;
+        }
+        if style.font_name.is_some() {
+            self.default_font = style.font_name;
+        }
+    }
+
+    /// Print line numbers in the current section: every `count_by`-th line is
+    /// numbered, counting from `start`, `distance_mm` away from the text.
+    pub fn enable_line_numbers(
+        &mut self,
+        count_by: u16,
+        start: u16,
+        distance_mm: u32,
+    ) -> Result<()> {
+        if count_by == 0 {
+            return Err(HwpError::InvalidInput(
+                "line numbers must count by at least 1".to_string(),
+            ));
+        }
+        self.current_section_mut();
+        let section_idx = self
+            .document
+            .body_texts
+            .iter()
+            .map(|body| body.sections.len())
+            .sum::<usize>()
+            - 1;
+        let shape = LineNumberShape {
+            count_by,
+            start,
+            distance: mm_to_hwp_units(distance_mm as f32),
+        };
+        self.line_numbers.retain(|(idx, _)| *idx != section_idx);
+        self.line_numbers.push((section_idx, shape));
+        Ok(())
+    }
+
+    /// Registers a charPr per header/footer run. Unstyled text in headers and
+    /// footers refers to charPr 0, so a default shape is put there first when the
+    /// document has none yet.
+    fn add_run_char_shapes(&mut self, runs: &[StyledText]) -> Vec<u16> {
+        if runs.is_empty() {
+            return Vec::new();
+        }
+        if self.document.doc_info.char_shapes.is_empty() {
+            self.add_char_shape(HwpxTextStyle::default().to_char_shape());
+            self.has_default_char_shape = true;
+        }
+        runs.iter()
+            .map(|run| {
+                let char_shape = self.char_shape_for(&run.style);
+                self.add_char_shape(char_shape)
+            })
+            .collect()
+    }
+
+    fn current_paragraph_count(&self) -> usize {
+        self.document
+            .body_texts
+            .iter()
+            .flat_map(|b| &b.sections)
+            .flat_map(|s| &s.paragraphs)
+            .count()
     }
 
-    pub fn add_footer_config(&mut self, footer: HwpxFooter) {
-        self.footers.push(footer);
+    fn push_paragraph(&mut self, mut paragraph: Paragraph) {
+        if self.pending_space_before > 0 {
+            let mut def = self
+                .para_properties
+                .get(paragraph.para_shape_id as usize)
+                .copied()
+                .unwrap_or_default();
+            def.space_before = def
+                .space_before
+                .saturating_add(std::mem::take(&mut self.pending_space_before))
+                .min(i32::MAX as u32);
+            paragraph.para_shape_id = self.add_para_pr(def) as u16;
+        }
+        if std::mem::take(&mut self.pending_page_break) {
+            paragraph.column_type |= BREAK_PAGE;
+        }
+        self.current_section_mut().paragraphs.push(paragraph);
     }
 
-    fn current_paragraph_count(&self) -> usize {
+    fn current_section_mut(&mut self) -> &mut Section {
+        let has_section = self
+            .document
+            .body_texts
+            .last()
+            .is_some_and(|body| !body.sections.is_empty());
+        if !has_section {
+            self.push_section();
+        }
         self.document
             .body_texts
-            .iter()
-            .flat_map(|b| &b.sections)
-            .flat_map(|s| &s.paragraphs)
-            .count()
+            .last_mut()
+            .and_then(|body| body.sections.last_mut())
+            .expect("section was just ensured")
     }
 
-    fn push_paragraph(&mut self, paragraph: Paragraph) {
-        if self.document.body_texts.is_empty() {
-            self.document.body_texts.push(BodyText {
-                sections: vec![Section {
-                    paragraphs: vec![paragraph],
-                    section_def: None,
-                    page_def: None,
-                }],
-            });
-        } else if let Some(body) = self.document.body_texts.first_mut() {
-            if let Some(section) = body.sections.first_mut() {
-                section.paragraphs.push(paragraph);
-            }
+    /// Char shape of `style`, registering its `font_name` as a font face
+    fn char_shape_for(&mut self, style: &HwpxTextStyle) -> CharShape {
+        let mut char_shape = style.to_char_shape();
+        if let Some(face) = &style.font_name {
+            // Kept apart from font 0 even when the names match, since
+            // set_default_text_style may still change the document font
+            let id = match self.fonts.iter().position(|f| f == face) {
+                Some(idx) => idx + 1,
+                None => {
+                    self.fonts.push(face.clone());
+                    self.fonts.len()
+                }
+            };
+            char_shape.face_name_ids = [id as u16; 7];
         }
+        char_shape
     }
 
     fn add_char_shape(&mut self, char_shape: CharShape) -> u16 {
@@ -742,7 +2766,10 @@ impl HwpxWriter {
     }
 
     pub fn to_bytes(&self) -> Result<Vec<u8>> {
-        let mut buffer = Cursor::new(Vec::new());
+        // Images are stored uncompressed; reserving for them up front avoids
+        // regrowing (and briefly doubling) the buffer for image-heavy documents.
+        let image_bytes: usize = self.bin_images().iter().map(|i| i.data.len()).sum();
+        let mut buffer = Cursor::new(Vec::with_capacity(image_bytes + 64 * 1024));
         self.write_to(&mut buffer)?;
         Ok(buffer.into_inner())
     }
@@ -752,120 +2779,159 @@ impl HwpxWriter {
         self.write_to(file)
     }
 
-    fn write_to<W: Write + std::io::Seek>(&self, writer: W) -> Result<()> {
-        let mut zip = ZipWriter::new(writer);
-        let stored =
-            SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
-        let deflated =
-            SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
-
-        // mimetype must be first and uncompressed (per ODF spec)
-        zip.start_file("mimetype", stored)
-            .map_err(|e| HwpError::Io(std::io::Error::other(e)))?;
-        zip.write_all(b"application/hwp+zip")
-            .map_err(HwpError::Io)?;
-
-        // version.xml
-        zip.start_file("version.xml", deflated)
-            .map_err(|e| HwpError::Io(std::io::Error::other(e)))?;
-        zip.write_all(self.generate_version_xml().as_bytes())
+    /// Write the HWPX package into any non-seekable writer (socket, pipe, HTTP body).
+    ///
+    /// Each entry is held back only until its CRC and sizes are filled into its local
+    /// header, then passed on to `writer`, so the whole archive is never buffered and
+    /// the bytes are identical to [`HwpxWriter::to_bytes`]. Prefer
+    /// [`HwpxWriter::write_to`] when the destination is seekable.
+    pub fn write_to_writer<W: Write>(&self, writer: W) -> Result<()> {
+        let mut writer = self
+            .write_package(ZipWriter::new(EntryBuffer::new(writer)))?
+            .into_inner()
             .map_err(HwpError::Io)?;
+        writer.flush().map_err(HwpError::Io)
+    }
 
-        // Contents directory
-        zip.add_directory("Contents", deflated)
-            .map_err(|e| HwpError::Io(std::io::Error::other(e)))?;
-
-        // Contents/header.xml
-        zip.start_file("Contents/header.xml", deflated)
-            .map_err(|e| HwpError::Io(std::io::Error::other(e)))?;
-        zip.write_all(self.generate_header_xml().as_bytes())
-            .map_err(HwpError::Io)?;
+    /// Write the HWPX package directly into a seekable writer (file, cursor, ...).
+    ///
+    /// This is the primitive used by [`HwpxWriter::to_bytes`] and
+    /// [`HwpxWriter::save_to_file`]; the zip archive is streamed into `writer`
+    /// entry by entry.
+    pub fn write_to<W: Write + std::io::Seek>(&self, writer: W) -> Result<()> {
+        self.write_package(ZipWriter::new(writer))?;
+        Ok(())
+    }
 
-        // Contents/section0.xml (and more if multiple sections)
-        for (idx, section_xml) in self.generate_section_xmls().iter().enumerate() {
-            let filename = format!("Contents/section{}.xml", idx);
-            zip.start_file(&filename, deflated)
+    /// Write every package part into `zip` and finish the archive
+    fn write_package<W: Write + std::io::Seek>(&self, mut zip: ZipWriter<W>) -> Result<W> {
+        if self.strict {
+            let issues = self.validate();
+            if !issues.is_empty() {
+                let report: Vec<String> = issues.iter().map(|i| i.to_string()).collect();
+                return Err(HwpError::ParseError(format!(
+                    "document failed validation: {}",
+                    report.join("; ")
+                )));
+            }
+        }
+        let options = if self.deterministic {
+            SimpleFileOptions::default().last_modified_time(zip::DateTime::default())
+        } else {
+            SimpleFileOptions::default()
+        };
+        let stored = options.compression_method(zip::CompressionMethod::Stored);
+        let deflated = options.compression_method(zip::CompressionMethod::Deflated);
+
+        let mut directories: Vec<&str> = Vec::new();
+        for part in &self.package_parts() {
+            if let Some((dir, _)) = part.path.rsplit_once('/') {
+                if !directories.contains(&dir) {
+                    zip.add_directory(dir, deflated)
+                        .map_err(|e| HwpError::Io(std::io::Error::other(e)))?;
+                    directories.push(dir);
+                }
+            }
+            let file_options = if part.stored { stored } else { deflated };
+            zip.start_file(part.path.as_str(), file_options)
                 .map_err(|e| HwpError::Io(std::io::Error::other(e)))?;
-            zip.write_all(section_xml.as_bytes())
-                .map_err(HwpError::Io)?;
+            zip.write_all(&part.data).map_err(HwpError::Io)?;
         }
 
-        // Preview directory
-        zip.add_directory("Preview", deflated)
-            .map_err(|e| HwpError::Io(std::io::Error::other(e)))?;
-
-        // Preview/PrvText.txt
-        zip.start_file("Preview/PrvText.txt", deflated)
-            .map_err(|e| HwpError::Io(std::io::Error::other(e)))?;
-        zip.write_all(self.generate_preview_text().as_bytes())
-            .map_err(HwpError::Io)?;
-
-        // Scripts directory
-        zip.add_directory("Scripts", deflated)
-            .map_err(|e| HwpError::Io(std::io::Error::other(e)))?;
-
-        // Scripts/headerScripts (empty but required)
-        zip.start_file("Scripts/headerScripts", deflated)
-            .map_err(|e| HwpError::Io(std::io::Error::other(e)))?;
-        zip.write_all(&self.generate_header_scripts())
-            .map_err(HwpError::Io)?;
-
-        // Scripts/sourceScripts (empty but required)
-        zip.start_file("Scripts/sourceScripts", deflated)
-            .map_err(|e| HwpError::Io(std::io::Error::other(e)))?;
-        zip.write_all(&self.generate_source_scripts())
-            .map_err(HwpError::Io)?;
-
-        // settings.xml
-        zip.start_file("settings.xml", deflated)
-            .map_err(|e| HwpError::Io(std::io::Error::other(e)))?;
-        zip.write_all(self.generate_settings_xml().as_bytes())
-            .map_err(HwpError::Io)?;
-
-        // META-INF directory
-        zip.add_directory("META-INF", deflated)
-            .map_err(|e| HwpError::Io(std::io::Error::other(e)))?;
-
-        // META-INF/container.xml
-        zip.start_file("META-INF/container.xml", deflated)
-            .map_err(|e| HwpError::Io(std::io::Error::other(e)))?;
-        zip.write_all(self.generate_container_xml().as_bytes())
-            .map_err(HwpError::Io)?;
-
-        // META-INF/manifest.xml
-        zip.start_file("META-INF/manifest.xml", deflated)
-            .map_err(|e| HwpError::Io(std::io::Error::other(e)))?;
-        zip.write_all(self.generate_manifest_xml().as_bytes())
-            .map_err(HwpError::Io)?;
-
-        // META-INF/container.rdf
-        zip.start_file("META-INF/container.rdf", deflated)
-            .map_err(|e| HwpError::Io(std::io::Error::other(e)))?;
-        zip.write_all(self.generate_container_rdf().as_bytes())
-            .map_err(HwpError::Io)?;
-
-        // Contents/content.hpf (must be after sections are known)
-        zip.start_file("Contents/content.hpf", deflated)
-            .map_err(|e| HwpError::Io(std::io::Error::other(e)))?;
-        zip.write_all(self.generate_content_hpf().as_bytes())
-            .map_err(HwpError::Io)?;
+        zip.finish()
+            .map_err(|e| HwpError::Io(std::io::Error::other(e)))
+    }
 
-        if !self.images.is_empty() {
-            zip.add_directory("BinData", deflated)
-                .map_err(|e| HwpError::Io(std::io::Error::other(e)))?;
+    /// Every file of the package in archive order. META-INF/manifest.xml is generated
+    /// from this same list, so it always matches what is written.
+    fn package_parts(&self) -> Vec<PackagePart<'_>> {
+        let xml = |path: &str, media_type: &'static str, content: String| PackagePart {
+            path: path.to_string(),
+            media_type,
+            data: Cow::Owned(content.into_bytes()),
+            stored: false,
+        };
 
-            for (idx, (_, image)) in self.images.iter().enumerate() {
-                let filename = format!("BinData/image{}.{}", idx + 1, image.format.extension());
-                zip.start_file(&filename, stored)
-                    .map_err(|e| HwpError::Io(std::io::Error::other(e)))?;
-                zip.write_all(&image.data).map_err(HwpError::Io)?;
-            }
+        // mimetype must be first and uncompressed (per ODF spec)
+        let mut parts = vec![PackagePart {
+            path: "mimetype".to_string(),
+            media_type: "text/plain",
+            data: Cow::Borrowed(b"application/hwp+zip"),
+            stored: true,
+        }];
+        parts.push(xml(
+            "version.xml",
+            "application/xml",
+            self.generate_version_xml(),
+        ));
+        parts.push(xml(
+            "Contents/header.xml",
+            "application/xml",
+            self.generate_header_xml(),
+        ));
+        for (idx, section_xml) in self.generate_section_xmls().into_iter().enumerate() {
+            parts.push(xml(
+                &format!("Contents/section{}.xml", idx),
+                "application/xml",
+                section_xml,
+            ));
+        }
+        parts.push(xml(
+            "Preview/PrvText.txt",
+            "text/plain",
+            self.generate_preview_text(),
+        ));
+        // Scripts are empty but required
+        parts.push(PackagePart {
+            path: "Scripts/headerScripts".to_string(),
+            media_type: "application/x-javascript",
+            data: Cow::Owned(self.generate_header_scripts()),
+            stored: false,
+        });
+        parts.push(PackagePart {
+            path: "Scripts/sourceScripts".to_string(),
+            media_type: "application/x-javascript",
+            data: Cow::Owned(self.generate_source_scripts()),
+            stored: false,
+        });
+        parts.push(xml(
+            "settings.xml",
+            "application/xml",
+            self.generate_settings_xml(),
+        ));
+        parts.push(xml(
+            "META-INF/container.xml",
+            "application/xml",
+            self.generate_container_xml(),
+        ));
+        let manifest_idx = parts.len();
+        parts.push(xml(
+            "META-INF/container.rdf",
+            "application/rdf+xml",
+            self.generate_container_rdf(),
+        ));
+        // content.hpf must be after sections are known
+        parts.push(xml(
+            "Contents/content.hpf",
+            "application/hwpml-package+xml",
+            self.generate_content_hpf(),
+        ));
+        for (idx, image) in self.bin_images().into_iter().enumerate() {
+            parts.push(PackagePart {
+                path: format!("BinData/image{}.{}", idx + 1, image.format.extension()),
+                media_type: image.format.media_type(),
+                data: Cow::Borrowed(image.data.as_slice()),
+                stored: true,
+            });
         }
 
-        zip.finish()
-            .map_err(|e| HwpError::Io(std::io::Error::other(e)))?;
-
-        Ok(())
+        let manifest = xml(
+            "META-INF/manifest.xml",
+            "application/xml",
+            Self::generate_manifest_xml(&parts),
+        );
+        parts.insert(manifest_idx, manifest);
+        parts
     }
 
     fn generate_version_xml(&self) -> String {
@@ -904,12 +2970,21 @@ impl HwpxWriter {
         .to_string()
     }
 
-    fn generate_manifest_xml(&self) -> String {
-        concat!(
+    /// The manifest lists every other part of the package; it never lists itself.
+    fn generate_manifest_xml(parts: &[PackagePart]) -> String {
+        let mut xml = String::from(concat!(
             r#"<?xml version="1.0" encoding="UTF-8" standalone="yes" ?>"#,
-            r#"<odf:manifest xmlns:odf="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0"/>"#
-        )
-        .to_string()
+            r#"<odf:manifest xmlns:odf="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0">"#
+        ));
+        for part in parts {
+            xml.push_str(&format!(
+                r#"<odf:file-entry odf:full-path="{}" odf:media-type="{}"/>"#,
+                escape_xml(&part.path),
+                part.media_type
+            ));
+        }
+        xml.push_str("</odf:manifest>");
+        xml
     }
 
     fn generate_container_rdf(&self) -> String {
@@ -964,24 +3039,27 @@ impl HwpxWriter {
         }
 
         let mut images_manifest = String::new();
-        for (idx, (_, image)) in self.images.iter().enumerate() {
+        for (idx, image) in self.bin_images().iter().enumerate() {
             let item_id = format!("image{}", idx + 1);
             let href = format!("BinData/image{}.{}", idx + 1, image.format.extension());
-            let media_type = match image.format {
-                HwpxImageFormat::Png => "image/png",
-                HwpxImageFormat::Jpeg => "image/jpg",
-                HwpxImageFormat::Gif => "image/gif",
-                HwpxImageFormat::Bmp => "image/bmp",
-            };
+            let media_type = image.format.media_type();
             images_manifest.push_str(&format!(
                 r#"<opf:item id="{}" href="{}" media-type="{}" isEmbeded="1"/>"#,
                 item_id, href, media_type
             ));
         }
 
-        let title = xml_escape(&self.metadata.title);
-        let creator = xml_escape(&self.metadata.creator);
-        let created_date = xml_escape(&self.metadata.created_date);
+        let title = escape_xml(&self.metadata.title);
+        let creator = escape_xml(&self.metadata.creator);
+        let created_date = escape_xml(&self.metadata.created_date);
+        let modified_date = if self.metadata.modified_date.is_empty() {
+            created_date.clone()
+        } else {
+            escape_xml(&self.metadata.modified_date)
+        };
+        let subject = Self::format_opf_meta("subject", &self.metadata.subject);
+        let description = Self::format_opf_meta("description", &self.metadata.description);
+        let keyword = Self::format_opf_meta("keyword", &self.metadata.keywords.join(", "));
 
         format!(
             concat!(
@@ -991,13 +3069,13 @@ impl HwpxWriter {
                 r#"<opf:title>{}</opf:title>"#,
                 r#"<opf:language>ko</opf:language>"#,
                 r#"<opf:meta name="creator" content="text">{}</opf:meta>"#,
-                r#"<opf:meta name="subject" content="text"/>"#,
-                r#"<opf:meta name="description" content="text"/>"#,
+                r#"{}"#,
+                r#"{}"#,
                 r#"<opf:meta name="lastsaveby" content="text">{}</opf:meta>"#,
                 r#"<opf:meta name="CreatedDate" content="text">{}</opf:meta>"#,
                 r#"<opf:meta name="ModifiedDate" content="text">{}</opf:meta>"#,
                 r#"<opf:meta name="date" content="text">{}</opf:meta>"#,
-                r#"<opf:meta name="keyword" content="text"/>"#,
+                r#"{}"#,
                 r#"</opf:metadata>"#,
                 r#"<opf:manifest>"#,
                 r#"<opf:item id="header" href="Contents/header.xml" media-type="application/xml"/>"#,
@@ -1015,201 +3093,506 @@ impl HwpxWriter {
                 r#"</opf:spine></opf:package>"#
             ),
             HWPX_NAMESPACES,
-            title,          // opf:title
-            creator,        // creator
-            creator,        // lastsaveby
-            created_date,   // CreatedDate
-            created_date,   // ModifiedDate
-            created_date,   // date
+            title,         // opf:title
+            creator,       // creator
+            subject,       // subject
+            description,   // description
+            creator,       // lastsaveby
+            created_date,  // CreatedDate
+            modified_date, // ModifiedDate
+            created_date,  // date
+            keyword,       // keyword
             sections_manifest,
             images_manifest,
             sections_spine
         )
     }
 
+    fn format_opf_meta(name: &str, value: &str) -> String {
+        if value.is_empty() {
+            format!(r#"<opf:meta name="{}" content="text"/>"#, name)
+        } else {
+            format!(
+                r#"<opf:meta name="{}" content="text">{}</opf:meta>"#,
+                name,
+                escape_xml(value)
+            )
+        }
+    }
+
     fn generate_header_xml(&self) -> String {
-        let mut xml = String::new();
-        xml.push_str(r#"<?xml version="1.0" encoding="UTF-8" standalone="yes" ?>"#);
-        xml.push_str("<hh:head ");
-        xml.push_str(HWPX_NAMESPACES);
-        xml.push_str(r#" version="1.5" secCnt="1">"#);
-        xml.push_str(
-            r#"<hh:beginNum page="1" footnote="1" endnote="1" pic="1" tbl="1" equation="1"/>"#,
-        );
-        xml.push_str("<hh:refList>");
-
-        // fontfaces
-        xml.push_str(r#"<hh:fontfaces itemCnt="7">"#);
-        xml.push_str(r#"<hh:fontface lang="HANGUL" fontCnt="1"><hh:font id="0" face="맑은 고딕" type="TTF" isEmbedded="0"><hh:typeInfo weight="26" proportion="26" contrast="26" strokeVariation="26" armStyle="26" letterform="26" midline="26" xHeight="26"/></hh:font></hh:fontface>"#);
-        xml.push_str(r#"<hh:fontface lang="LATIN" fontCnt="1"><hh:font id="0" face="맑은 고딕" type="TTF" isEmbedded="0"><hh:typeInfo familyType="FCAT_UNKNOWN" weight="0" proportion="0" contrast="0" strokeVariation="0" armStyle="0" letterform="0" midline="252" xHeight="255"/></hh:font></hh:fontface>"#);
-        xml.push_str(r#"<hh:fontface lang="HANJA" fontCnt="1"><hh:font id="0" face="맑은 고딕" type="TTF" isEmbedded="0"><hh:typeInfo familyType="FCAT_UNKNOWN" weight="0" proportion="0" contrast="0" strokeVariation="0" armStyle="0" letterform="0" midline="252" xHeight="255"/></hh:font></hh:fontface>"#);
-        xml.push_str(r#"<hh:fontface lang="JAPANESE" fontCnt="1"><hh:font id="0" face="맑은 고딕" type="TTF" isEmbedded="0"><hh:typeInfo familyType="FCAT_UNKNOWN" weight="0" proportion="0" contrast="0" strokeVariation="0" armStyle="0" letterform="0" midline="252" xHeight="255"/></hh:font></hh:fontface>"#);
-        xml.push_str(r#"<hh:fontface lang="OTHER" fontCnt="1"><hh:font id="0" face="맑은 고딕" type="TTF" isEmbedded="0"><hh:typeInfo familyType="FCAT_UNKNOWN" weight="0" proportion="0" contrast="0" strokeVariation="0" armStyle="0" letterform="0" midline="252" xHeight="255"/></hh:font></hh:fontface>"#);
-        xml.push_str(r#"<hh:fontface lang="SYMBOL" fontCnt="1"><hh:font id="0" face="맑은 고딕" type="TTF" isEmbedded="0"><hh:typeInfo familyType="FCAT_UNKNOWN" weight="0" proportion="0" contrast="0" strokeVariation="0" armStyle="0" letterform="0" midline="252" xHeight="255"/></hh:font></hh:fontface>"#);
-        xml.push_str(r#"<hh:fontface lang="USER" fontCnt="1"><hh:font id="0" face="맑은 고딕" type="TTF" isEmbedded="0"><hh:typeInfo familyType="FCAT_UNKNOWN" weight="0" proportion="0" contrast="0" strokeVariation="0" armStyle="0" letterform="0" midline="252" xHeight="255"/></hh:font></hh:fontface>"#);
-        xml.push_str("</hh:fontfaces>");
-
-        xml.push_str(r#"<hh:borderFills itemCnt="3">"#);
-        xml.push_str(r#"<hh:borderFill id="1" threeD="0" shadow="0" centerLine="NONE" breakCellSeparateLine="0">"#);
-        xml.push_str(r#"<hh:slash type="NONE" Crooked="0" isCounter="0"/><hh:backSlash type="NONE" Crooked="0" isCounter="0"/>"#);
-        xml.push_str("<hh:leftBorder type=\"NONE\" width=\"0.1 mm\" color=\"#000000\"/><hh:rightBorder type=\"NONE\" width=\"0.1 mm\" color=\"#000000\"/>");
-        xml.push_str("<hh:topBorder type=\"NONE\" width=\"0.1 mm\" color=\"#000000\"/><hh:bottomBorder type=\"NONE\" width=\"0.1 mm\" color=\"#000000\"/>");
-        xml.push_str(
-            "<hh:diagonal type=\"SOLID\" width=\"0.1 mm\" color=\"#000000\"/></hh:borderFill>",
+        let mut b = XmlBuilder::new();
+        b.raw(XML_DECLARATION);
+        b.open("hh:head")
+            .raw_attrs(HWPX_NAMESPACES)
+            .attr("version", "1.5")
+            .attr("secCnt", self.get_section_count());
+        let begin_page = self
+            .document
+            .body_texts
+            .iter()
+            .flat_map(|body| &body.sections)
+            .next()
+            .and_then(|section| section.page_def.as_ref())
+            .and_then(|page_def| page_def.layout.as_ref())
+            .map(|layout| layout.start_page_number.max(1))
+            .unwrap_or(1);
+        b.open("hh:beginNum")
+            .attr("page", begin_page)
+            .attr("footnote", 1)
+            .attr("endnote", 1)
+            .attr("pic", 1)
+            .attr("tbl", 1)
+            .attr("equation", 1)
+            .close();
+
+        b.open("hh:refList");
+
+        // fontfaces: font 0 is the document font, then the faces of styled runs
+        const LATIN_TYPE_INFO: &str = r#"<hh:typeInfo familyType="FCAT_UNKNOWN" weight="0" proportion="0" contrast="0" strokeVariation="0" armStyle="0" letterform="0" midline="252" xHeight="255"/>"#;
+        const FONT_LANGS: [(&str, &str); 7] = [
+            (
+                "HANGUL",
+                r#"<hh:typeInfo weight="26" proportion="26" contrast="26" strokeVariation="26" armStyle="26" letterform="26" midline="26" xHeight="26"/>"#,
+            ),
+            ("LATIN", LATIN_TYPE_INFO),
+            ("HANJA", LATIN_TYPE_INFO),
+            ("JAPANESE", LATIN_TYPE_INFO),
+            ("OTHER", LATIN_TYPE_INFO),
+            ("SYMBOL", LATIN_TYPE_INFO),
+            ("USER", LATIN_TYPE_INFO),
+        ];
+        let document_font = self.default_font.as_deref().unwrap_or(DEFAULT_FONT_FACE);
+        let faces: Vec<&str> = std::iter::once(document_font)
+            .chain(self.fonts.iter().map(String::as_str))
+            .collect();
+        b.open("hh:fontfaces").attr("itemCnt", FONT_LANGS.len());
+        for (lang, type_info) in FONT_LANGS {
+            b.open("hh:fontface")
+                .attr("lang", lang)
+                .attr("fontCnt", faces.len());
+            for (id, face) in faces.iter().enumerate() {
+                b.open("hh:font")
+                    .attr("id", id)
+                    .attr("face", face)
+                    .attr("type", "TTF")
+                    .attr("isEmbedded", 0)
+                    .raw(type_info)
+                    .close();
+            }
+            b.close();
+        }
+        b.close();
+
+        let cell_fills = self.cell_colors(|style| style.background);
+        b.open("hh:borderFills").attr(
+            "itemCnt",
+            3 + self.text_boxes.len() + self.paragraph_borders.len() + cell_fills.len(),
         );
-        xml.push_str(r#"<hh:borderFill id="2" threeD="0" shadow="0" centerLine="NONE" breakCellSeparateLine="0">"#);
-        xml.push_str(r#"<hh:slash type="NONE" Crooked="0" isCounter="0"/><hh:backSlash type="NONE" Crooked="0" isCounter="0"/>"#);
-        xml.push_str("<hh:leftBorder type=\"NONE\" width=\"0.1 mm\" color=\"#000000\"/><hh:rightBorder type=\"NONE\" width=\"0.1 mm\" color=\"#000000\"/>");
-        xml.push_str("<hh:topBorder type=\"NONE\" width=\"0.1 mm\" color=\"#000000\"/><hh:bottomBorder type=\"NONE\" width=\"0.1 mm\" color=\"#000000\"/>");
-        xml.push_str("<hh:diagonal type=\"SOLID\" width=\"0.1 mm\" color=\"#000000\"/>");
-        xml.push_str("<hc:fillBrush><hc:winBrush faceColor=\"none\" hatchColor=\"#999999\" alpha=\"0\"/></hc:fillBrush></hh:borderFill>");
+        const NO_LINES: [&str; 4] = ["NONE"; 4];
+        border_fill(&mut b, 1, NO_LINES, "0.1 mm", 0, "SOLID", None);
+        border_fill(&mut b, 2, NO_LINES, "0.1 mm", 0, "SOLID", Some("none"));
         // id="3": 테이블 셀용 (실선 테두리)
-        xml.push_str(r#"<hh:borderFill id="3" threeD="0" shadow="0" centerLine="NONE" breakCellSeparateLine="0">"#);
-        xml.push_str(r#"<hh:slash type="NONE" Crooked="0" isCounter="0"/><hh:backSlash type="NONE" Crooked="0" isCounter="0"/>"#);
-        xml.push_str("<hh:leftBorder type=\"SOLID\" width=\"0.12 mm\" color=\"#000000\"/><hh:rightBorder type=\"SOLID\" width=\"0.12 mm\" color=\"#000000\"/>");
-        xml.push_str("<hh:topBorder type=\"SOLID\" width=\"0.12 mm\" color=\"#000000\"/><hh:bottomBorder type=\"SOLID\" width=\"0.12 mm\" color=\"#000000\"/>");
-        xml.push_str("<hh:diagonal type=\"NONE\" width=\"0.1 mm\" color=\"#000000\"/></hh:borderFill>");
-        xml.push_str("</hh:borderFills>");
-
-        xml.push_str(&self.generate_char_properties());
+        border_fill(&mut b, 3, ["SOLID"; 4], "0.12 mm", 0, "NONE", None);
+        // id="4"부터: 글상자마다 전용 borderFill
+        for (idx, (_, _, style)) in self.text_boxes.iter().enumerate() {
+            let id = TEXT_BOX_BORDER_FILL_BASE + idx as u32;
+            let line = if style.border { "SOLID" } else { "NONE" };
+            let fill = style.fill_color.map(hex_color);
+            border_fill(&mut b, id, [line; 4], "0.12 mm", 0, "NONE", fill.as_deref());
+        }
+        // 그 다음: 문단 테두리마다 전용 borderFill
+        for (idx, border) in self.paragraph_borders.iter().enumerate() {
+            let id = self.paragraph_border_fill_id(idx);
+            let mut lines = [border.line_style.as_hwpx(); 4];
+            if border.left_only {
+                lines[1..].fill(BorderLineStyle::None.as_hwpx());
+            }
+            let fill = border.fill_color.map(hex_color);
+            border_fill(
+                &mut b,
+                id,
+                lines,
+                "0.12 mm",
+                border.color,
+                "NONE",
+                fill.as_deref(),
+            );
+        }
+        // 그 다음: 표 셀 배경색마다 하나
+        for (idx, &color) in cell_fills.iter().enumerate() {
+            let id = self.cell_border_fill_base() + idx as u32;
+            let fill = hex_color(color);
+            border_fill(&mut b, id, ["SOLID"; 4], "0.12 mm", 0, "NONE", Some(&fill));
+        }
+        b.close();
 
-        // tabProperties
-        xml.push_str(r#"<hh:tabProperties itemCnt="1"><hh:tabPr id="0" autoTabLeft="0" autoTabRight="0"/></hh:tabProperties>"#);
+        self.write_char_properties(&mut b);
+        self.write_tab_properties(&mut b);
 
         // numberings
-        xml.push_str(r#"<hh:numberings itemCnt="1"><hh:numbering id="1" start="0">"#);
-        xml.push_str(r#"<hh:paraHead start="1" level="1" align="LEFT" useInstWidth="1" autoIndent="1" widthAdjust="0" textOffsetType="PERCENT" textOffset="50" numFormat="DIGIT" charPrIDRef="4294967295" checkable="0">^1.</hh:paraHead>"#);
-        xml.push_str(r#"<hh:paraHead start="1" level="2" align="LEFT" useInstWidth="1" autoIndent="1" widthAdjust="0" textOffsetType="PERCENT" textOffset="50" numFormat="HANGUL_SYLLABLE" charPrIDRef="4294967295" checkable="0">^2.</hh:paraHead>"#);
-        xml.push_str(r#"<hh:paraHead start="1" level="3" align="LEFT" useInstWidth="1" autoIndent="1" widthAdjust="0" textOffsetType="PERCENT" textOffset="50" numFormat="DIGIT" charPrIDRef="4294967295" checkable="0">^3)</hh:paraHead>"#);
-        xml.push_str(r#"<hh:paraHead start="1" level="4" align="LEFT" useInstWidth="1" autoIndent="1" widthAdjust="0" textOffsetType="PERCENT" textOffset="50" numFormat="HANGUL_SYLLABLE" charPrIDRef="4294967295" checkable="0">^4)</hh:paraHead>"#);
-        xml.push_str(r#"<hh:paraHead start="1" level="5" align="LEFT" useInstWidth="1" autoIndent="1" widthAdjust="0" textOffsetType="PERCENT" textOffset="50" numFormat="DIGIT" charPrIDRef="4294967295" checkable="0">(^5)</hh:paraHead>"#);
-        xml.push_str(r#"<hh:paraHead start="1" level="6" align="LEFT" useInstWidth="1" autoIndent="1" widthAdjust="0" textOffsetType="PERCENT" textOffset="50" numFormat="HANGUL_SYLLABLE" charPrIDRef="4294967295" checkable="0">(^6)</hh:paraHead>"#);
-        xml.push_str(r#"<hh:paraHead start="1" level="7" align="LEFT" useInstWidth="1" autoIndent="1" widthAdjust="0" textOffsetType="PERCENT" textOffset="50" numFormat="CIRCLED_DIGIT" charPrIDRef="4294967295" checkable="1">^7</hh:paraHead>"#);
-        xml.push_str(r#"<hh:paraHead start="1" level="8" align="LEFT" useInstWidth="1" autoIndent="1" widthAdjust="0" textOffsetType="PERCENT" textOffset="50" numFormat="CIRCLED_HANGUL_SYLLABLE" charPrIDRef="4294967295" checkable="1">^8</hh:paraHead>"#);
-        xml.push_str(r#"<hh:paraHead start="1" level="9" align="LEFT" useInstWidth="1" autoIndent="1" widthAdjust="0" textOffsetType="PERCENT" textOffset="50" numFormat="HANGUL_JAMO" charPrIDRef="4294967295" checkable="0"/>"#);
-        xml.push_str(r#"<hh:paraHead start="1" level="10" align="LEFT" useInstWidth="1" autoIndent="1" widthAdjust="0" textOffsetType="PERCENT" textOffset="50" numFormat="ROMAN_SMALL" charPrIDRef="4294967295" checkable="1"/>"#);
-        xml.push_str("</hh:numbering></hh:numberings>");
+        b.open("hh:numberings").attr("itemCnt", 1);
+        b.open("hh:numbering").attr("id", 1).attr("start", 0);
+        b.raw(r#"<hh:paraHead start="1" level="1" align="LEFT" useInstWidth="1" autoIndent="1" widthAdjust="0" textOffsetType="PERCENT" textOffset="50" numFormat="DIGIT" charPrIDRef="4294967295" checkable="0">^1.</hh:paraHead>"#);
+        b.raw(r#"<hh:paraHead start="1" level="2" align="LEFT" useInstWidth="1" autoIndent="1" widthAdjust="0" textOffsetType="PERCENT" textOffset="50" numFormat="HANGUL_SYLLABLE" charPrIDRef="4294967295" checkable="0">^2.</hh:paraHead>"#);
+        b.raw(r#"<hh:paraHead start="1" level="3" align="LEFT" useInstWidth="1" autoIndent="1" widthAdjust="0" textOffsetType="PERCENT" textOffset="50" numFormat="DIGIT" charPrIDRef="4294967295" checkable="0">^3)</hh:paraHead>"#);
+        b.raw(r#"<hh:paraHead start="1" level="4" align="LEFT" useInstWidth="1" autoIndent="1" widthAdjust="0" textOffsetType="PERCENT" textOffset="50" numFormat="HANGUL_SYLLABLE" charPrIDRef="4294967295" checkable="0">^4)</hh:paraHead>"#);
+        b.raw(r#"<hh:paraHead start="1" level="5" align="LEFT" useInstWidth="1" autoIndent="1" widthAdjust="0" textOffsetType="PERCENT" textOffset="50" numFormat="DIGIT" charPrIDRef="4294967295" checkable="0">(^5)</hh:paraHead>"#);
+        b.raw(r#"<hh:paraHead start="1" level="6" align="LEFT" useInstWidth="1" autoIndent="1" widthAdjust="0" textOffsetType="PERCENT" textOffset="50" numFormat="HANGUL_SYLLABLE" charPrIDRef="4294967295" checkable="0">(^6)</hh:paraHead>"#);
+        b.raw(r#"<hh:paraHead start="1" level="7" align="LEFT" useInstWidth="1" autoIndent="1" widthAdjust="0" textOffsetType="PERCENT" textOffset="50" numFormat="CIRCLED_DIGIT" charPrIDRef="4294967295" checkable="1">^7</hh:paraHead>"#);
+        b.raw(r#"<hh:paraHead start="1" level="8" align="LEFT" useInstWidth="1" autoIndent="1" widthAdjust="0" textOffsetType="PERCENT" textOffset="50" numFormat="CIRCLED_HANGUL_SYLLABLE" charPrIDRef="4294967295" checkable="1">^8</hh:paraHead>"#);
+        b.raw(r#"<hh:paraHead start="1" level="9" align="LEFT" useInstWidth="1" autoIndent="1" widthAdjust="0" textOffsetType="PERCENT" textOffset="50" numFormat="HANGUL_JAMO" charPrIDRef="4294967295" checkable="0"/>"#);
+        b.raw(r#"<hh:paraHead start="1" level="10" align="LEFT" useInstWidth="1" autoIndent="1" widthAdjust="0" textOffsetType="PERCENT" textOffset="50" numFormat="ROMAN_SMALL" charPrIDRef="4294967295" checkable="1"/>"#);
+        b.close().close();
 
         // paraProperties
-        xml.push_str(r#"<hh:paraProperties itemCnt="1">"#);
-        xml.push_str(r#"<hh:paraPr id="0" tabPrIDRef="0" condense="0" fontLineHeight="0" snapToGrid="1" suppressLineNumbers="0" checked="0">"#);
-        xml.push_str(r#"<hh:align horizontal="JUSTIFY" vertical="BASELINE"/>"#);
-        xml.push_str(r#"<hh:heading type="NONE" idRef="0" level="0"/>"#);
-        xml.push_str(r#"<hh:breakSetting breakLatinWord="KEEP_WORD" breakNonLatinWord="KEEP_WORD" widowOrphan="0" keepWithNext="0" keepLines="0" pageBreakBefore="0" lineWrap="BREAK"/>"#);
-        xml.push_str(r#"<hh:autoSpacing eAsianEng="0" eAsianNum="0"/>"#);
-        xml.push_str(r#"<hp:switch><hp:case hp:required-namespace="http://www.hancom.co.kr/hwpml/2016/HwpUnitChar">"#);
-        xml.push_str(r#"<hh:margin><hc:intent value="0" unit="HWPUNIT"/><hc:left value="0" unit="HWPUNIT"/><hc:right value="0" unit="HWPUNIT"/><hc:prev value="0" unit="HWPUNIT"/><hc:next value="0" unit="HWPUNIT"/></hh:margin>"#);
-        xml.push_str(r#"<hh:lineSpacing type="PERCENT" value="160" unit="HWPUNIT"/></hp:case>"#);
-        xml.push_str(r#"<hp:default><hh:margin><hc:intent value="0" unit="HWPUNIT"/><hc:left value="0" unit="HWPUNIT"/><hc:right value="0" unit="HWPUNIT"/><hc:prev value="0" unit="HWPUNIT"/><hc:next value="0" unit="HWPUNIT"/></hh:margin>"#);
-        xml.push_str(r#"<hh:lineSpacing type="PERCENT" value="160" unit="HWPUNIT"/></hp:default></hp:switch>"#);
-        xml.push_str(r#"<hh:border borderFillIDRef="2" offsetLeft="0" offsetRight="0" offsetTop="0" offsetBottom="0" connect="0" ignoreMargin="0"/>"#);
-        xml.push_str("</hh:paraPr></hh:paraProperties>");
+        let para_properties = self.written_para_properties();
+        b.open("hh:paraProperties")
+            .attr("itemCnt", para_properties.len());
+        for (id, def) in para_properties.iter().enumerate() {
+            self.write_para_pr(&mut b, id, def);
+        }
+        b.close();
 
         // styles
-        xml.push_str(r#"<hh:styles itemCnt="1">"#);
-        xml.push_str(r#"<hh:style id="0" type="PARA" name="바탕글" engName="Normal" paraPrIDRef="0" charPrIDRef="0" nextStyleIDRef="0" langID="1042" lockForm="0"/>"#);
-        xml.push_str("</hh:styles>");
+        b.open("hh:styles").attr("itemCnt", 1);
+        b.open("hh:style")
+            .attr("id", 0)
+            .attr("type", "PARA")
+            .attr("name", "바탕글")
+            .attr("engName", "Normal")
+            .attr("paraPrIDRef", 0)
+            .attr("charPrIDRef", 0)
+            .attr("nextStyleIDRef", 0)
+            .attr("langID", 1042)
+            .attr("lockForm", 0)
+            .close();
+        b.close();
+
+        self.write_bin_data_items(&mut b);
+
+        b.close(); // hh:refList
+
+        // compatibleDocument, docOption and trackchangeConfig are static
+        b.raw(r#"<hh:compatibleDocument targetProgram="HWP201X"><hh:layoutCompatibility/></hh:compatibleDocument>"#);
+        b.raw(r#"<hh:docOption><hh:linkinfo path="" pageInherit="0" footnoteInherit="0"/></hh:docOption>"#);
+        b.raw(r#"<hh:trackchageConfig flags="56">"#);
+        b.raw(r#"<config:config-item-set name="TrackChangePasswordInfo">"#);
+        b.raw(
+            r#"<config:config-item name="algorithm-name" type="string">SHA1</config:config-item>"#,
+        );
+        b.raw("</config:config-item-set></hh:trackchageConfig>");
+        b.close();
 
-        xml.push_str(&self.generate_bin_data_items());
+        b.finish()
+    }
 
-        xml.push_str("</hh:refList>");
+    fn write_tab_properties(&self, b: &mut XmlBuilder) {
+        let hwp_scale: f32 = 7200.0 / 25.4;
+        b.open("hh:tabProperties")
+            .attr("itemCnt", self.tab_properties.len());
+        for (id, stops) in self.tab_properties.iter().enumerate() {
+            b.open("hh:tabPr")
+                .attr("id", id)
+                .attr("autoTabLeft", 0)
+                .attr("autoTabRight", 0);
+            for stop in stops {
+                b.open("hh:tabItem")
+                    .attr("pos", (stop.position_mm * hwp_scale).round() as u32)
+                    .attr("type", stop.alignment.as_hwpx())
+                    .attr("leader", "NONE")
+                    .close();
+            }
+            b.close();
+        }
+        b.close();
+    }
 
-        // compatibleDocument
-        xml.push_str(r#"<hh:compatibleDocument targetProgram="HWP201X"><hh:layoutCompatibility/></hh:compatibleDocument>"#);
+    fn write_para_pr(&self, b: &mut XmlBuilder, id: usize, def: &ParaPrDef) {
+        b.open("hh:paraPr")
+            .attr("id", id)
+            .attr("tabPrIDRef", def.tab_pr)
+            .attr("condense", 0)
+            .attr("fontLineHeight", 0)
+            .attr("snapToGrid", 1)
+            .attr("suppressLineNumbers", 0)
+            .attr("checked", 0);
+        b.open("hh:align")
+            .attr("horizontal", def.align.as_hwpx_align())
+            .attr("vertical", "BASELINE")
+            .close();
+        b.raw(r#"<hh:heading type="NONE" idRef="0" level="0"/>"#);
+        b.open("hh:breakSetting")
+            .attr("breakLatinWord", "KEEP_WORD")
+            .attr("breakNonLatinWord", "KEEP_WORD")
+            .attr("widowOrphan", 0)
+            .attr("keepWithNext", def.keep_with_next as u8)
+            .attr("keepLines", def.keep_lines as u8)
+            .attr("pageBreakBefore", def.page_break_before as u8)
+            .attr("lineWrap", "BREAK")
+            .close();
+        b.raw(r#"<hh:autoSpacing eAsianEng="0" eAsianNum="0"/>"#);
+        let margin_and_spacing = |b: &mut XmlBuilder| {
+            b.open("hh:margin");
+            for (name, value) in [
+                ("hc:intent", i64::from(def.indent)),
+                ("hc:left", i64::from(def.left_margin)),
+                ("hc:right", i64::from(def.right_margin)),
+                ("hc:prev", i64::from(def.space_before)),
+                ("hc:next", i64::from(def.space_after)),
+            ] {
+                b.open(name)
+                    .attr("value", value)
+                    .attr("unit", "HWPUNIT")
+                    .close();
+            }
+            b.close();
+            b.open("hh:lineSpacing")
+                .attr("type", "PERCENT")
+                .attr("value", def.line_spacing)
+                .attr("unit", "HWPUNIT")
+                .close();
+        };
+        b.open("hp:switch");
+        b.open("hp:case").attr(
+            "hp:required-namespace",
+            "http://www.hancom.co.kr/hwpml/2016/HwpUnitChar",
+        );
+        margin_and_spacing(b);
+        b.close();
+        b.open("hp:default");
+        margin_and_spacing(b);
+        b.close().close();
+        let (border_fill_id, [left, right, top, bottom]) = match def.border {
+            0 => (2, [0; 4]),
+            n => {
+                let border = &self.paragraph_borders[n as usize - 1];
+                (
+                    self.paragraph_border_fill_id(n as usize - 1),
+                    border.offsets_mm.map(mm_to_hwp_units),
+                )
+            }
+        };
+        b.open("hh:border")
+            .attr("borderFillIDRef", border_fill_id)
+            .attr("offsetLeft", left)
+            .attr("offsetRight", right)
+            .attr("offsetTop", top)
+            .attr("offsetBottom", bottom)
+            .attr("connect", 0)
+            .attr("ignoreMargin", 0)
+            .close();
+        b.close();
+    }
 
-        // docOption
-        xml.push_str(r#"<hh:docOption><hh:linkinfo path="" pageInherit="0" footnoteInherit="0"/></hh:docOption>"#);
+    /// Every picture placement: body images first, then header and footer images.
+    /// hp:pic ids follow this order.
+    fn placed_images(&self) -> impl Iterator<Item = &HwpxImage> {
+        self.images
+            .iter()
+            .map(|(_, image)| image)
+            .chain(self.headers.iter().filter_map(|(h, _)| h.image.as_ref()))
+            .chain(self.footers.iter().filter_map(|(f, _)| f.image.as_ref()))
+            .chain(
+                self.tables
+                    .iter()
+                    .flat_map(|(_, table)| table.cell_images.values().flatten()),
+            )
+    }
 
-        // trackchangeConfig
-        xml.push_str(r#"<hh:trackchageConfig flags="56">"#);
-        xml.push_str(r#"<config:config-item-set name="TrackChangePasswordInfo">"#);
-        xml.push_str(
-            r#"<config:config-item name="algorithm-name" type="string">SHA1</config:config-item>"#,
-        );
-        xml.push_str("</config:config-item-set></hh:trackchageConfig>");
-        xml.push_str("</hh:head>");
+    /// Distinct embedded pictures in BinData order; a picture placed several times
+    /// is stored once. `image{N}` item ids follow this order: the interned pictures
+    /// first, then any whose bytes were replaced after being added (through
+    /// [`Self::image_mut`] or [`Self::table_mut`]).
+    fn bin_images(&self) -> Vec<&HwpxImage> {
+        let mut bin_images: Vec<&HwpxImage> = self.bin_data.iter().collect();
+        let interned = bin_images.len();
+        for image in self.placed_images() {
+            if self.interned_index(image).is_none()
+                && !bin_images[interned..]
+                    .iter()
+                    .any(|stored| same_image_data(stored, image))
+            {
+                bin_images.push(image);
+            }
+        }
+        bin_images
+    }
 
-        xml
+    /// BinData item id (`image{N}`) holding the bytes of `image`
+    fn bin_item_id(&self, image: &HwpxImage) -> String {
+        let idx = self.interned_index(image).unwrap_or_else(|| {
+            self.bin_images()
+                .iter()
+                .position(|stored| same_image_data(stored, image))
+                .expect("placed images are always in BinData")
+        });
+        format!("image{}", idx + 1)
     }
 
-    fn generate_bin_data_items(&self) -> String {
-        if self.images.is_empty() {
-            return String::new();
+    /// Position of `image`'s bytes in `bin_data`
+    fn interned_index(&self, image: &HwpxImage) -> Option<usize> {
+        self.bin_data_index
+            .get(&image_data_hash(image))?
+            .iter()
+            .copied()
+            .find(|&idx| same_image_data(&self.bin_data[idx], image))
+    }
+
+    /// Share the buffer of an already added picture with the same bytes, so both
+    /// placements refer to one BinData item without keeping two copies around.
+    fn intern_image(&mut self, mut image: HwpxImage) -> HwpxImage {
+        match self.interned_index(&image) {
+            Some(idx) => image.data = Arc::clone(&self.bin_data[idx].data),
+            None => {
+                self.bin_data_index
+                    .entry(image_data_hash(&image))
+                    .or_default()
+                    .push(self.bin_data.len());
+                self.bin_data.push(image.clone());
+            }
         }
+        image
+    }
 
-        let mut xml = format!(r#"<hh:binDataItems itemCnt="{}">"#, self.images.len());
-        for (idx, (_, image)) in self.images.iter().enumerate() {
-            let item_id = format!("image{}", idx + 1);
-            let src = format!("BinData/image{}.{}", idx + 1, image.format.extension());
-            let format = image.format.extension().to_uppercase();
-            xml.push_str(&format!(
-                r#"<hh:binDataItem id="{}" src="{}" format="{}" isEmbeded="1"/>"#,
-                item_id, src, format
-            ));
+    fn intern_cell_images(&mut self, table: &mut HwpxTable) {
+        for images in table.cell_images.values_mut() {
+            for image in images.iter_mut() {
+                *image = self.intern_image(image.clone());
+            }
         }
-        xml.push_str("</hh:binDataItems>");
-        xml
     }
 
-    fn generate_char_properties(&self) -> String {
+    fn write_bin_data_items(&self, b: &mut XmlBuilder) {
+        let bin_images = self.bin_images();
+        if bin_images.is_empty() {
+            return;
+        }
+
+        b.open("hh:binDataItems").attr("itemCnt", bin_images.len());
+        for (idx, image) in bin_images.iter().enumerate() {
+            b.open("hh:binDataItem")
+                .attr("id", format!("image{}", idx + 1))
+                .attr(
+                    "src",
+                    format!("BinData/image{}.{}", idx + 1, image.format.extension()),
+                )
+                .attr("format", image.format.extension().to_uppercase())
+                .attr("isEmbeded", 1)
+                .close();
+        }
+        b.close();
+    }
+
+    fn write_char_properties(&self, b: &mut XmlBuilder) {
         let char_shapes = &self.document.doc_info.char_shapes;
-        let count = char_shapes.len().max(1);
+        let cell_text_colors = self.cell_colors(|style| style.text_color);
+        let count = char_shapes.len().max(1) + cell_text_colors.len();
 
-        let mut xml = format!(r#"<hh:charProperties itemCnt="{}">"#, count);
+        b.open("hh:charProperties").attr("itemCnt", count);
 
         if char_shapes.is_empty() {
-            xml.push_str(&self.format_char_pr(0, &CharShape::new_default()));
+            self.write_char_pr(b, 0, &CharShape::new_default());
         } else {
             for (id, cs) in char_shapes.iter().enumerate() {
-                xml.push_str(&self.format_char_pr(id as u32, cs));
+                self.write_char_pr(b, id as u32, cs);
             }
         }
+        // Table cell text colors: the default shape in each color
+        for (idx, &color) in cell_text_colors.iter().enumerate() {
+            let mut cs = char_shapes
+                .first()
+                .cloned()
+                .unwrap_or_else(CharShape::new_default);
+            cs.text_color = color;
+            self.write_char_pr(b, self.cell_char_pr_base() + idx as u32, &cs);
+        }
 
-        xml.push_str("</hh:charProperties>");
-        xml
+        b.close();
     }
 
-    fn format_char_pr(&self, id: u32, cs: &CharShape) -> String {
-        let height = cs.base_size;
-        let text_color = format!("#{:06X}", cs.text_color & 0xFFFFFF);
-        let underline_color = format!("#{:06X}", cs.underline_color & 0xFFFFFF);
-        let shadow_color = format!("#{:06X}", cs.shadow_color & 0xFFFFFF);
+    fn write_char_pr(&self, b: &mut XmlBuilder, id: u32, cs: &CharShape) {
+        // Faces of a converted binary document are not written; they fall back to font 0
+        let font = cs.face_name_ids.map(|id| {
+            if id as usize <= self.fonts.len() {
+                id
+            } else {
+                0
+            }
+        });
 
-        let bold_attr = if cs.is_bold() { r#" bold="1""# } else { "" };
-        let italic_attr = if cs.is_italic() { r#" italic="1""# } else { "" };
-        let underline_type = if cs.is_underline() { "BOTTOM" } else { "NONE" };
-        let strikeout_shape = if cs.is_strikethrough() {
+        // HwpxTextStyle and HwpxReader keep underline in bit 2 and strikeout in bit 3
+        let underline_type = if cs.properties & 0x04 != 0 {
+            "BOTTOM"
+        } else {
+            "NONE"
+        };
+        let strikeout_shape = if cs.properties & 0x08 != 0 {
             "CONTINUOUS"
         } else {
             "NONE"
         };
+        let (shadow_type, offset_x, offset_y) = match cs.get_shadow_type() {
+            0 => ("NONE", 10, 10),
+            2 => ("CONTINUOUS", cs.shadow_gap_x, cs.shadow_gap_y),
+            _ => ("DROP", cs.shadow_gap_x, cs.shadow_gap_y),
+        };
+        let outline_type = match cs.get_outline_type() {
+            0 => "NONE",
+            2 => "DOT",
+            3 => "THICK",
+            4 => "DASH",
+            5 => "DASH_DOT",
+            6 => "DASH_DOT_DOT",
+            _ => "SOLID",
+        };
 
-        format!(
-            concat!(
-                r#"<hh:charPr id="{}" height="{}"{}{} textColor="{}" shadeColor="none" "#,
-                r#"useFontSpace="0" useKerning="0" symMark="NONE" borderFillIDRef="2">"#,
-                r#"<hh:fontRef hangul="0" latin="0" hanja="0" japanese="0" other="0" symbol="0" user="0"/>"#,
-                r#"<hh:ratio hangul="100" latin="100" hanja="100" japanese="100" other="100" symbol="100" user="100"/>"#,
-                r#"<hh:spacing hangul="0" latin="0" hanja="0" japanese="0" other="0" symbol="0" user="0"/>"#,
-                r#"<hh:relSz hangul="100" latin="100" hanja="100" japanese="100" other="100" symbol="100" user="100"/>"#,
-                r#"<hh:offset hangul="0" latin="0" hanja="0" japanese="0" other="0" symbol="0" user="0"/>"#,
-                r#"<hh:underline type="{}" shape="SOLID" color="{}"/>"#,
-                r#"<hh:strikeout shape="{}" color="{}"/>"#,
-                r#"<hh:outline type="NONE"/>"#,
-                r#"<hh:shadow type="NONE" color="{}" offsetX="10" offsetY="10"/>"#,
-                r#"</hh:charPr>"#
-            ),
-            id,
-            height,
-            bold_attr,
-            italic_attr,
-            text_color,
-            underline_type,
-            underline_color,
-            strikeout_shape,
-            text_color,
-            shadow_color
-        )
+        b.open("hh:charPr")
+            .attr("id", id)
+            .attr("height", cs.base_size);
+        if cs.is_bold() {
+            b.attr("bold", 1);
+        }
+        if cs.is_italic() {
+            b.attr("italic", 1);
+        }
+        b.attr("textColor", hex_color(cs.text_color))
+            .attr("shadeColor", "none")
+            .attr("useFontSpace", 0)
+            .attr("useKerning", 0)
+            .attr("symMark", "NONE")
+            .attr("borderFillIDRef", 2);
+        b.open("hh:fontRef");
+        for (lang, id) in FONT_REF_LANGS.iter().zip(font) {
+            b.attr(lang, id);
+        }
+        b.close();
+        b.raw(r#"<hh:ratio hangul="100" latin="100" hanja="100" japanese="100" other="100" symbol="100" user="100"/>"#);
+        b.raw(r#"<hh:spacing hangul="0" latin="0" hanja="0" japanese="0" other="0" symbol="0" user="0"/>"#);
+        b.raw(r#"<hh:relSz hangul="100" latin="100" hanja="100" japanese="100" other="100" symbol="100" user="100"/>"#);
+        b.raw(r#"<hh:offset hangul="0" latin="0" hanja="0" japanese="0" other="0" symbol="0" user="0"/>"#);
+        b.open("hh:underline")
+            .attr("type", underline_type)
+            .attr("shape", "SOLID")
+            .attr("color", hex_color(cs.underline_color))
+            .close();
+        b.open("hh:strikeout")
+            .attr("shape", strikeout_shape)
+            .attr("color", hex_color(cs.strikethrough_color))
+            .close();
+        b.open("hh:outline").attr("type", outline_type).close();
+        b.open("hh:shadow")
+            .attr("type", shadow_type)
+            .attr("color", hex_color(cs.shadow_color))
+            .attr("offsetX", offset_x)
+            .attr("offsetY", offset_y)
+            .close();
+        if cs.is_emboss() {
+            b.open("hh:emboss").close();
+        } else if cs.is_engrave() {
+            b.open("hh:engrave").close();
+        }
+        b.close();
     }
 
     fn generate_header_scripts(&self) -> Vec<u8> {
@@ -1247,43 +3630,119 @@ impl HwpxWriter {
             return vec![self.generate_empty_section()];
         }
 
+        // Tables, images and hyperlinks are keyed by document-wide paragraph index
+        let mut para_offset = 0;
         self.document
             .body_texts
             .iter()
             .flat_map(|body| &body.sections)
-            .map(|section| self.generate_section_xml(section))
+            .enumerate()
+            .map(|(section_idx, section)| {
+                let xml = self.generate_section_xml(section, para_offset, section_idx);
+                para_offset += section.paragraphs.len();
+                xml
+            })
             .collect()
     }
 
     fn generate_empty_section(&self) -> String {
-        self.generate_section_xml_with_paragraphs(&[])
+        self.generate_section_xml_with_paragraphs(
+            &[],
+            0,
+            &default_page_layout(),
+            TextDirection::Horizontal,
+            None,
+            true,
+        )
     }
 
-    fn generate_section_xml(&self, section: &crate::model::Section) -> String {
+    fn generate_section_xml(
+        &self,
+        section: &crate::model::Section,
+        para_offset: usize,
+        section_idx: usize,
+    ) -> String {
         let paragraphs: Vec<_> = section.paragraphs.iter().collect();
-        self.generate_section_xml_with_paragraphs(&paragraphs)
+        let layout = section
+            .page_def
+            .as_ref()
+            .and_then(|page_def| page_def.layout.clone())
+            .unwrap_or_else(default_page_layout);
+        self.generate_section_xml_with_paragraphs(
+            &paragraphs,
+            para_offset,
+            &layout,
+            TextDirection::of_section(section),
+            self.line_numbers
+                .iter()
+                .find(|(idx, _)| *idx == section_idx)
+                .map(|(_, shape)| shape),
+            section_idx == 0,
+        )
     }
 
     fn generate_section_xml_with_paragraphs(
         &self,
         paragraphs: &[&crate::model::Paragraph],
+        para_offset: usize,
+        layout: &PageLayout,
+        direction: TextDirection,
+        line_numbers: Option<&LineNumberShape>,
+        first_section: bool,
     ) -> String {
+        // Tables and images are clamped to a single column
+        let content_width = layout.column_width();
+
         let mut sec_pr = String::new();
-        sec_pr.push_str(
-            r#"<hp:secPr id="" textDirection="HORIZONTAL" spaceColumns="1134" tabStop="8000" tabStopVal="4000" tabStopUnit="HWPUNIT" outlineShapeIDRef="1" memoShapeIDRef="0" textVerticalWidthHead="0" masterPageCnt="0">"#
-        );
+        sec_pr.push_str(&format!(
+            r#"<hp:secPr id="" textDirection="{}" spaceColumns="1134" tabStop="8000" tabStopVal="4000" tabStopUnit="HWPUNIT" outlineShapeIDRef="1" memoShapeIDRef="0" textVerticalWidthHead="0" masterPageCnt="0">"#,
+            direction.as_hwpx()
+        ));
         sec_pr.push_str(r#"<hp:grid lineGrid="0" charGrid="0" wonggojiFormat="0"/>"#);
-        sec_pr.push_str(
-            r#"<hp:startNum pageStartsOn="BOTH" page="0" pic="0" tbl="0" equation="0"/>"#,
-        );
-        sec_pr.push_str(r#"<hp:visibility hideFirstHeader="0" hideFirstFooter="0" hideFirstMasterPage="0" border="SHOW_ALL" fill="SHOW_ALL" hideFirstPageNum="0" hideFirstEmptyLine="0" showLineNumber="0"/>"#);
-        sec_pr.push_str(
-            r#"<hp:lineNumberShape restartType="0" countBy="0" distance="0" startNumber="0"/>"#,
-        );
-        sec_pr.push_str(
-            r#"<hp:pagePr landscape="WIDELY" width="59528" height="84186" gutterType="LEFT_ONLY">"#,
-        );
-        sec_pr.push_str(r#"<hp:margin header="4252" footer="4252" gutter="0" left="8504" right="8504" top="5668" bottom="4252"/></hp:pagePr>"#);
+        // page="0" continues numbering from the previous section
+        let start_page = if layout.start_page_number > 1 {
+            layout.start_page_number
+        } else {
+            0
+        };
+        sec_pr.push_str(&format!(
+            r#"<hp:startNum pageStartsOn="BOTH" page="{}" pic="0" tbl="0" equation="0"/>"#,
+            start_page
+        ));
+        let hide_first_header = self.headers.iter().any(|(h, _)| h.suppress_on_first_page);
+        let hide_first_footer = self.footers.iter().any(|(f, _)| f.suppress_on_first_page);
+        sec_pr.push_str(&format!(
+            r#"<hp:visibility hideFirstHeader="{}" hideFirstFooter="{}" hideFirstMasterPage="0" border="SHOW_ALL" fill="SHOW_ALL" hideFirstPageNum="0" hideFirstEmptyLine="0" showLineNumber="{}"/>"#,
+            hide_first_header as u8,
+            hide_first_footer as u8,
+            line_numbers.is_some() as u8
+        ));
+        let (count_by, distance, start_number) = line_numbers
+            .map(|shape| (shape.count_by, shape.distance, shape.start))
+            .unwrap_or_default();
+        sec_pr.push_str(&format!(
+            r#"<hp:lineNumberShape restartType="0" countBy="{}" distance="{}" startNumber="{}"/>"#,
+            count_by, distance, start_number
+        ));
+        let landscape = match layout.orientation {
+            PageOrientation::Portrait => "WIDELY",
+            PageOrientation::Landscape => "NARROWLY",
+        };
+        sec_pr.push_str(&format!(
+            r#"<hp:pagePr landscape="{}" width="{}" height="{}" gutterType="LEFT_ONLY">"#,
+            landscape, layout.width, layout.height
+        ));
+        let margins = &layout.margins;
+        sec_pr.push_str(&format!(
+            r#"<hp:margin header="{}" footer="{}" gutter="{}" left="{}" right="{}" top="{}" bottom="{}"/></hp:pagePr>"#,
+            margins.header,
+            margins.footer,
+            margins.gutter,
+            margins.left,
+            margins.right,
+            margins.top,
+            margins.bottom
+        ));
         sec_pr.push_str(r#"<hp:footNotePr><hp:autoNumFormat type="DIGIT" userChar="" prefixChar="" suffixChar=")" supscript="0"/>"#);
         sec_pr.push_str(
             "<hp:noteLine length=\"-1\" type=\"SOLID\" width=\"0.12 mm\" color=\"#000000\"/>",
@@ -1309,206 +3768,340 @@ impl HwpxWriter {
             r#"<hp:offset left="1417" right="1417" top="1417" bottom="1417"/></hp:pageBorderFill>"#,
         );
         sec_pr.push_str("</hp:secPr>");
-        sec_pr.push_str(r#"<hp:ctrl><hp:colPr id="" type="NEWSPAPER" layout="LEFT" colCount="1" sameSz="1" sameGap="0"/></hp:ctrl>"#);
+        sec_pr.push_str(&Self::format_col_pr(layout));
 
-        let mut xml = format!(
-            concat!(
-                r#"<?xml version="1.0" encoding="UTF-8" standalone="yes" ?>"#,
-                r#"<hs:sec {}>"#
-            ),
-            HWPX_NAMESPACES
-        );
+        let mut b = XmlBuilder::new();
+        b.raw(XML_DECLARATION);
+        b.open("hs:sec").raw_attrs(HWPX_NAMESPACES);
 
         let has_headers = !self.headers.is_empty();
         let has_footers = !self.footers.is_empty();
-
-        // First paragraph with section properties
-        if paragraphs.is_empty() {
-            xml.push_str(concat!(
-                r#"<hp:p id="0" paraPrIDRef="0" styleIDRef="0" pageBreak="0" columnBreak="0" merged="0">"#,
-                r#"<hp:run charPrIDRef="0">"#
-            ));
-            xml.push_str(&sec_pr);
-            xml.push_str(r#"</hp:run>"#);
+        let section_controls = |b: &mut XmlBuilder| {
+            b.open("hp:run").attr("charPrIDRef", 0).raw(&sec_pr).close();
             if has_headers {
-                xml.push_str(r#"<hp:run charPrIDRef="0">"#);
-                xml.push_str(&self.generate_header_ctrl_xml());
-                xml.push_str(r#"<hp:t/></hp:run>"#);
+                b.open("hp:run").attr("charPrIDRef", 0);
+                self.write_header_ctrls(b, first_section);
+                b.open("hp:t").close();
+                b.close();
             }
             if has_footers {
-                xml.push_str(r#"<hp:run charPrIDRef="0">"#);
-                xml.push_str(&self.generate_footer_ctrl_xml());
-                xml.push_str(r#"<hp:t/></hp:run>"#);
+                b.open("hp:run").attr("charPrIDRef", 0);
+                self.write_footer_ctrls(b, first_section);
+                b.open("hp:t").close();
+                b.close();
             }
-            xml.push_str(r#"<hp:run charPrIDRef="0"><hp:t></hp:t></hp:run></hp:p>"#);
+        };
+
+        // First paragraph with section properties
+        if paragraphs.is_empty() {
+            open_paragraph(&mut b, 0, 0, false);
+            section_controls(&mut b);
+            text_run(&mut b, 0, "");
+            b.close();
         } else {
             for (idx, para) in paragraphs.iter().enumerate() {
-                let para_pr_id = para.para_shape_id;
-
-                xml.push_str(&format!(
-                    r#"<hp:p id="{}" paraPrIDRef="{}" styleIDRef="0" pageBreak="0" columnBreak="0" merged="0">"#,
-                    idx, para_pr_id
-                ));
+                open_paragraph(
+                    &mut b,
+                    idx,
+                    para.para_shape_id,
+                    para.column_type & BREAK_PAGE != 0,
+                );
 
                 if idx == 0 {
-                    xml.push_str(r#"<hp:run charPrIDRef="0">"#);
-                    xml.push_str(&sec_pr);
-                    xml.push_str(r#"</hp:run>"#);
-                    if has_headers {
-                        xml.push_str(r#"<hp:run charPrIDRef="0">"#);
-                        xml.push_str(&self.generate_header_ctrl_xml());
-                        xml.push_str(r#"<hp:t/></hp:run>"#);
-                    }
-                    if has_footers {
-                        xml.push_str(r#"<hp:run charPrIDRef="0">"#);
-                        xml.push_str(&self.generate_footer_ctrl_xml());
-                        xml.push_str(r#"<hp:t/></hp:run>"#);
-                    }
+                    section_controls(&mut b);
                 }
 
                 let text = para.text.as_ref().map(|t| t.content.as_str()).unwrap_or("");
 
-                if let Some(char_shapes) = &para.char_shapes {
-                    let mut last_pos = 0;
-                    for (i, pos_shape) in char_shapes.char_positions.iter().enumerate() {
-                        let start = pos_shape.position as usize;
-                        let end = char_shapes
-                            .char_positions
-                            .get(i + 1)
-                            .map(|p| p.position as usize)
-                            .unwrap_or(text.chars().count());
-
-                        if start > last_pos {
-                            let segment: String =
-                                text.chars().skip(last_pos).take(start - last_pos).collect();
-                            if !segment.is_empty() {
-                                xml.push_str(&format!(
-                                    r#"<hp:run charPrIDRef="0"><hp:t>{}</hp:t></hp:run>"#,
-                                    escape_xml(&segment)
-                                ));
-                            }
-                        }
-
-                        let segment: String = text.chars().skip(start).take(end - start).collect();
-                        if !segment.is_empty() {
-                            xml.push_str(&format!(
-                                r#"<hp:run charPrIDRef="{}"><hp:t>{}</hp:t></hp:run>"#,
-                                pos_shape.char_shape_id,
-                                escape_xml(&segment)
-                            ));
-                        }
-                        last_pos = end;
+                if para.char_shapes.is_some() || !para.hyperlinks.is_empty() {
+                    let directions = self.run_directions_for(para_offset + idx);
+                    let runs = paragraph_runs(text, para, &directions);
+                    for run in &runs {
+                        let text = run.direction.isolate(&run.text);
+                        match run.url {
+                            Some(url) => hyperlink_run(&mut b, run.char_pr_id, url, &text),
+                            None => text_run(&mut b, run.char_pr_id, &text),
+                        };
                     }
-
-                    if last_pos < text.chars().count() {
-                        let remaining: String = text.chars().skip(last_pos).collect();
-                        xml.push_str(&format!(
-                            r#"<hp:run charPrIDRef="0"><hp:t>{}</hp:t></hp:run>"#,
-                            escape_xml(&remaining)
-                        ));
+                    if runs.is_empty() && para.char_shapes.is_none() {
+                        text_run(&mut b, 0, text);
                     }
-                } else if let Some(table) = self.get_table_for_paragraph(idx) {
-                    xml.push_str(r#"<hp:run charPrIDRef="0">"#);
-                    xml.push_str(&self.format_table(table));
-                    xml.push_str("<hp:t/>");
-                    xml.push_str("</hp:run>");
-                } else if let Some((img_idx, image)) = self.get_image_for_paragraph(idx) {
-                    xml.push_str(r#"<hp:run charPrIDRef="0">"#);
-                    xml.push_str(&self.format_picture(img_idx, image));
-                    xml.push_str("<hp:t/>");
-                    xml.push_str("</hp:run>");
-                } else if let Some(links) = self.get_hyperlinks_for_paragraph(idx) {
-                    xml.push_str(&self.format_hyperlinks(text, links));
+                } else if let Some((table_idx, table)) =
+                    self.get_table_for_paragraph(para_offset + idx)
+                {
+                    let number = self.table_number(para_offset + idx);
+                    object_run(
+                        &mut b,
+                        &self.format_table(table_idx, table, number, content_width, direction),
+                    );
+                } else if let Some((img_idx, image)) =
+                    self.get_image_for_paragraph(para_offset + idx)
+                {
+                    object_run(&mut b, &self.format_picture(img_idx, image, content_width));
+                } else if let Some((box_idx, text, style)) =
+                    self.get_text_box_for_paragraph(para_offset + idx)
+                {
+                    object_run(
+                        &mut b,
+                        &self.format_text_box(box_idx, text, style, content_width, direction),
+                    );
+                } else if let Some(rule) = self.get_horizontal_rule_for_paragraph(para_offset + idx)
+                {
+                    object_run(&mut b, &self.format_horizontal_rule(rule, content_width));
+                } else if let Some((field_idx, field)) =
+                    self.get_form_field_for_paragraph(para_offset + idx)
+                {
+                    object_run(&mut b, &format_form_field(field_idx, field, content_width));
                 } else {
-                    xml.push_str(&format!(
-                        r#"<hp:run charPrIDRef="0"><hp:t>{}</hp:t></hp:run>"#,
-                        escape_xml(text)
-                    ));
+                    text_run(&mut b, 0, text);
                 }
 
-                xml.push_str("</hp:p>");
+                b.close();
             }
         }
 
-        xml.push_str("</hs:sec>");
+        b.close();
+        b.finish()
+    }
+
+    /// Column definition control; columns always have equal widths.
+    fn format_col_pr(layout: &PageLayout) -> String {
+        let col_count = layout.columns.max(1);
+        if col_count == 1 {
+            return r#"<hp:ctrl><hp:colPr id="" type="NEWSPAPER" layout="LEFT" colCount="1" sameSz="1" sameGap="0"/></hp:ctrl>"#.to_string();
+        }
+
+        let mut xml = format!(
+            r#"<hp:ctrl><hp:colPr id="" type="NEWSPAPER" layout="LEFT" colCount="{}" sameSz="1" sameGap="{}">"#,
+            col_count, layout.column_spacing
+        );
+        if layout.column_line {
+            xml.push_str(r##"<hp:colLine type="SOLID" width="0.12 mm" color="#000000"/>"##);
+        }
+        xml.push_str("</hp:colPr></hp:ctrl>");
+        xml
+    }
+
+    /// (start, len, direction) of the direction runs of paragraph `para_idx`
+    fn run_directions_for(&self, para_idx: usize) -> Vec<(u32, u32, RunDirection)> {
+        self.run_directions
+            .iter()
+            .filter(|(idx, ..)| *idx == para_idx)
+            .map(|&(_, start, len, dir)| (start, len, dir))
+            .collect()
+    }
+
+    fn get_table_for_paragraph(&self, para_idx: usize) -> Option<(usize, &HwpxTable)> {
+        self.tables
+            .iter()
+            .enumerate()
+            .find(|(_, (idx, _))| *idx == para_idx)
+            .map(|(table_idx, (_, table))| (table_idx, table))
+    }
+
+    fn get_image_for_paragraph(&self, para_idx: usize) -> Option<(usize, &HwpxImage)> {
+        self.images
+            .iter()
+            .enumerate()
+            .find(|(_, (idx, _))| *idx == para_idx)
+            .map(|(img_idx, (_, image))| (img_idx, image))
+    }
+
+    fn get_text_box_for_paragraph(&self, para_idx: usize) -> Option<(usize, &str, &TextBoxStyle)> {
+        self.text_boxes
+            .iter()
+            .enumerate()
+            .find(|(_, (idx, _, _))| *idx == para_idx)
+            .map(|(box_idx, (_, text, style))| (box_idx, text.as_str(), style))
+    }
+
+    fn get_horizontal_rule_for_paragraph(&self, para_idx: usize) -> Option<&HorizontalRule> {
+        self.horizontal_rules
+            .iter()
+            .find(|(idx, _)| *idx == para_idx)
+            .map(|(_, rule)| rule)
+    }
+
+    fn get_form_field_for_paragraph(&self, para_idx: usize) -> Option<(usize, &FormField)> {
+        self.form_fields
+            .iter()
+            .enumerate()
+            .find(|(_, (idx, _))| *idx == para_idx)
+            .map(|(field_idx, (_, field))| (field_idx, field))
+    }
+
+    fn format_horizontal_rule(&self, rule: &HorizontalRule, content_width: u32) -> String {
+        let hwp_scale: f64 = 7200.0 / 25.4;
+
+        let width = content_width * rule.width_percent / 100;
+        let thickness = ((rule.thickness_mm * hwp_scale).round() as u32).max(1);
+        let color = format!("#{:06X}", rule.color & 0xFFFFFF);
+
+        let mut xml = String::new();
+        xml.push_str(concat!(
+            r#"<hp:line id="0" zOrder="0" numberingType="NONE" textWrap="TOP_AND_BOTTOM" "#,
+            r#"textFlow="BOTH_SIDES" lock="0" dropcapstyle="None" href="" groupLevel="0" "#,
+            r#"instid="0" isReverseHV="0">"#
+        ));
+        xml.push_str(r#"<hp:offset x="0" y="0"/>"#);
+        xml.push_str(&format!(r#"<hp:orgSz width="{}" height="0"/>"#, width));
+        xml.push_str(&format!(r#"<hp:curSz width="{}" height="0"/>"#, width));
+        xml.push_str(r#"<hp:flip horizontal="0" vertical="0"/>"#);
+        xml.push_str(&format!(
+            r#"<hp:rotationInfo angle="0" centerX="{}" centerY="0" rotateimage="1"/>"#,
+            width / 2
+        ));
+        xml.push_str("<hp:renderingInfo>");
+        xml.push_str(r#"<hc:transMatrix e1="1" e2="0" e3="0" e4="0" e5="1" e6="0"/>"#);
+        xml.push_str(r#"<hc:scaMatrix e1="1" e2="0" e3="0" e4="0" e5="1" e6="0"/>"#);
+        xml.push_str(r#"<hc:rotMatrix e1="1" e2="0" e3="0" e4="0" e5="1" e6="0"/>"#);
+        xml.push_str("</hp:renderingInfo>");
+        xml.push_str(&format!(
+            concat!(
+                r#"<hp:lineShape color="{}" width="{}" style="SOLID" endCap="FLAT" "#,
+                r#"headStyle="NORMAL" tailStyle="NORMAL" headfill="1" tailfill="1" "#,
+                r#"headSz="MEDIUM_MEDIUM" tailSz="MEDIUM_MEDIUM" outlineStyle="NORMAL" alpha="0"/>"#
+            ),
+            color, thickness
+        ));
+        xml.push_str(r#"<hc:startPt x="0" y="0"/>"#);
+        xml.push_str(&format!(r#"<hc:endPt x="{}" y="0"/>"#, width));
+        xml.push_str(&format!(
+            r#"<hp:sz width="{}" widthRelTo="ABSOLUTE" height="0" heightRelTo="ABSOLUTE" protect="0"/>"#,
+            width
+        ));
+        xml.push_str(concat!(
+            r#"<hp:pos treatAsChar="1" affectLSpacing="0" flowWithText="1" allowOverlap="0" "#,
+            r#"holdAnchorAndSO="0" vertRelTo="PARA" horzRelTo="PARA" vertAlign="TOP" "#,
+            r#"horzAlign="LEFT" vertOffset="0" horzOffset="0"/>"#
+        ));
+        xml.push_str(r#"<hp:outMargin left="0" right="0" top="0" bottom="0"/>"#);
+        xml.push_str("</hp:line>");
         xml
     }
 
-    fn get_table_for_paragraph(&self, para_idx: usize) -> Option<&HwpxTable> {
-        self.tables
-            .iter()
-            .find(|(idx, _)| *idx == para_idx)
-            .map(|(_, table)| table)
+    fn paragraph_border_fill_id(&self, border_idx: usize) -> u32 {
+        TEXT_BOX_BORDER_FILL_BASE + (self.text_boxes.len() + border_idx) as u32
     }
 
-    fn get_image_for_paragraph(&self, para_idx: usize) -> Option<(usize, &HwpxImage)> {
-        self.images
-            .iter()
-            .enumerate()
-            .find(|(_, (idx, _))| *idx == para_idx)
-            .map(|(img_idx, (_, image))| (img_idx, image))
+    /// borderFill id of the first cell background, after the paragraph borders
+    fn cell_border_fill_base(&self) -> u32 {
+        self.paragraph_border_fill_id(self.paragraph_borders.len())
     }
 
-    fn get_hyperlinks_for_paragraph(&self, para_idx: usize) -> Option<&Vec<HwpxHyperlink>> {
-        self.hyperlinks
-            .iter()
-            .find(|(idx, _)| *idx == para_idx)
-            .map(|(_, links)| links)
+    /// charPr id of the first cell text color, after the registered char shapes
+    fn cell_char_pr_base(&self) -> u32 {
+        self.document.doc_info.char_shapes.len().max(1) as u32
     }
 
-    fn format_hyperlinks(&self, text: &str, links: &[HwpxHyperlink]) -> String {
-        let mut xml = String::new();
-        let mut last_end = 0usize;
-
-        for link in links {
-            if let Some(start) = text.find(&link.text) {
-                if start > last_end {
-                    let prefix: String =
-                        text.chars().skip(last_end).take(start - last_end).collect();
-                    xml.push_str(&format!(
-                        r#"<hp:run charPrIDRef="0"><hp:t>{}</hp:t></hp:run>"#,
-                        escape_xml(&prefix)
-                    ));
-                }
-
-                xml.push_str(&format!(
-                    concat!(
-                        r#"<hp:run charPrIDRef="0">"#,
-                        r#"<hp:ctrl>"#,
-                        r#"<hp:hyperlink url="{}" visited="0" visited_style="0" new_window="0"/>"#,
-                        r#"</hp:ctrl>"#,
-                        r#"<hp:t>{}</hp:t>"#,
-                        r#"</hp:run>"#
-                    ),
-                    escape_xml(&link.url),
-                    escape_xml(&link.text)
-                ));
-
-                last_end = start + link.text.chars().count();
+    /// Distinct colors picked from the cell styles of all tables, in table order
+    fn cell_colors(&self, pick: impl Fn(&CellStyle) -> Option<u32>) -> Vec<u32> {
+        let mut colors = Vec::new();
+        let styles = self.tables.iter().flat_map(|(_, t)| t.cell_styles.values());
+        for color in styles.filter_map(pick).map(|c| c & 0xFFFFFF) {
+            if !colors.contains(&color) {
+                colors.push(color);
             }
         }
+        colors
+    }
 
-        if last_end < text.chars().count() {
-            let suffix: String = text.chars().skip(last_end).collect();
-            xml.push_str(&format!(
-                r#"<hp:run charPrIDRef="0"><hp:t>{}</hp:t></hp:run>"#,
-                escape_xml(&suffix)
-            ));
-        }
+    /// Tables are numbered after every placed picture, in table order
+    fn table_id(&self, table_idx: usize) -> u32 {
+        (1 + self.placed_images().count() + table_idx) as u32
+    }
 
-        if xml.is_empty() {
-            xml.push_str(&format!(
-                r#"<hp:run charPrIDRef="0"><hp:t>{}</hp:t></hp:run>"#,
-                escape_xml(text)
-            ));
-        }
+    /// Text boxes are written as single-cell tables, numbered after the real ones
+    fn text_box_id(&self, box_idx: usize) -> u32 {
+        self.table_id(self.tables.len() + box_idx)
+    }
 
-        xml
+    fn format_text_box(
+        &self,
+        box_idx: usize,
+        text: &str,
+        style: &TextBoxStyle,
+        content_width: u32,
+        direction: TextDirection,
+    ) -> String {
+        let hwp_scale: f64 = 7200.0 / 25.4;
+        let line_height: u32 = 1000;
+
+        let width = ((style.width_mm as f64 * hwp_scale) as u32).min(content_width);
+        let padding = (style.padding_mm as f64 * hwp_scale) as u32;
+        let lines: Vec<&str> = text.split('\n').collect();
+        let height = line_height * lines.len() as u32 + padding * 2;
+        let border_fill_id = TEXT_BOX_BORDER_FILL_BASE + box_idx as u32;
+
+        let mut b = XmlBuilder::new();
+        b.open("hp:tbl")
+            .attr("id", self.text_box_id(box_idx))
+            .attr("zOrder", 0)
+            .attr("numberingType", "NONE")
+            .attr("textWrap", "TOP_AND_BOTTOM")
+            .attr("textFlow", "BOTH_SIDES")
+            .attr("lock", 0)
+            .attr("dropcapstyle", "None")
+            .attr("pageBreak", "CELL")
+            .attr("repeatHeader", 0)
+            .attr("rowCnt", 1)
+            .attr("colCnt", 1)
+            .attr("cellSpacing", 0)
+            .attr("borderFillIDRef", border_fill_id)
+            .attr("noAdjust", 0);
+        b.open("hp:sz")
+            .attr("width", width)
+            .attr("widthRelTo", "ABSOLUTE")
+            .attr("height", height)
+            .attr("heightRelTo", "ABSOLUTE")
+            .attr("protect", 0)
+            .close();
+        b.raw(concat!(
+            r#"<hp:pos treatAsChar="1" affectLSpacing="0" flowWithText="1" allowOverlap="0" "#,
+            r#"holdAnchorAndSO="0" vertRelTo="PARA" horzRelTo="PARA" vertAlign="TOP" "#,
+            r#"horzAlign="LEFT" vertOffset="0" horzOffset="0"/>"#,
+            r#"<hp:outMargin left="0" right="0" top="0" bottom="0"/>"#
+        ));
+        margin(&mut b, "hp:inMargin", padding, padding, padding, padding);
+        b.open("hp:tr");
+        b.open("hp:tc")
+            .attr("name", "")
+            .attr("header", 0)
+            .attr("hasMargin", 1)
+            .attr("protect", 0)
+            .attr("editable", 0)
+            .attr("dirty", 0)
+            .attr("borderFillIDRef", border_fill_id);
+        b.open("hp:subList")
+            .attr("id", "")
+            .attr("textDirection", direction.as_hwpx())
+            .attr("lineWrap", "BREAK")
+            .attr("vertAlign", "CENTER")
+            .attr("linkListIDRef", 0)
+            .attr("linkListNextIDRef", 0)
+            .attr("textWidth", 0)
+            .attr("textHeight", 0)
+            .attr("hasTextRef", 0)
+            .attr("hasNumRef", 0);
+        for (line_idx, line) in lines.iter().enumerate() {
+            open_paragraph(&mut b, line_idx, 0, false);
+            text_run(&mut b, 0, line);
+            b.close();
+        }
+        b.close();
+        b.raw(r#"<hp:cellAddr colAddr="0" rowAddr="0"/><hp:cellSpan colSpan="1" rowSpan="1"/>"#);
+        b.open("hp:cellSz")
+            .attr("width", width)
+            .attr("height", height)
+            .close();
+        margin(&mut b, "hp:cellMargin", padding, padding, padding, padding);
+        b.close().close().close();
+        b.finish()
     }
 
-    fn format_picture(&self, img_idx: usize, image: &HwpxImage) -> String {
+    /// `img_idx` is the picture's position in [`Self::placed_images`]
+    fn format_picture(&self, img_idx: usize, image: &HwpxImage, content_width: u32) -> String {
         let hwp_scale: f64 = 7200.0 / 25.4;
-        let content_width: u32 = 42520;
 
         let org_width = (image.width_mm.unwrap_or(50) as f64 * hwp_scale) as u32;
         let org_height = (image.height_mm.unwrap_or(50) as f64 * hwp_scale) as u32;
@@ -1521,36 +4114,11 @@ impl HwpxWriter {
             (org_width, org_height)
         };
 
-        let item_id = format!("image{}", img_idx + 1);
-        let pic_id = self.next_image_id + img_idx as u32;
+        let item_id = self.bin_item_id(image);
+        let pic_id = 1 + img_idx as u32;
         let center_x = cur_width / 2;
         let center_y = cur_height / 2;
 
-        let mut xml = String::new();
-        xml.push_str(&format!(
-            concat!(
-                r#"<hp:pic id="{}" zOrder="{}" numberingType="PICTURE" textWrap="TOP_AND_BOTTOM" "#,
-                r#"textFlow="BOTH_SIDES" lock="0" dropcapstyle="None" href="" groupLevel="0" "#,
-                r#"instid="{}" reverse="0">"#
-            ),
-            pic_id, img_idx, pic_id
-        ));
-
-        xml.push_str(r#"<hp:offset x="0" y="0"/>"#);
-        xml.push_str(&format!(
-            r#"<hp:orgSz width="{}" height="{}"/>"#,
-            org_width, org_height
-        ));
-        xml.push_str(&format!(
-            r#"<hp:curSz width="{}" height="{}"/>"#,
-            cur_width, cur_height
-        ));
-        xml.push_str(r#"<hp:flip horizontal="0" vertical="0"/>"#);
-        xml.push_str(&format!(
-            r#"<hp:rotationInfo angle="0" centerX="{}" centerY="{}" rotateimage="1"/>"#,
-            center_x, center_y
-        ));
-
         // renderingInfo
         let sca_x = if org_width > 0 {
             cur_width as f64 / org_width as f64
@@ -1562,89 +4130,233 @@ impl HwpxWriter {
         } else {
             1.0
         };
-        xml.push_str("<hp:renderingInfo>");
-        xml.push_str(r#"<hc:transMatrix e1="1" e2="0" e3="0" e4="0" e5="1" e6="0"/>"#);
-        xml.push_str(&format!(
-            r#"<hc:scaMatrix e1="{:.6}" e2="0" e3="0" e4="0" e5="{:.6}" e6="0"/>"#,
-            sca_x, sca_y
-        ));
-        xml.push_str(r#"<hc:rotMatrix e1="1" e2="0" e3="0" e4="0" e5="1" e6="0"/>"#);
-        xml.push_str("</hp:renderingInfo>");
+
+        let mut b = XmlBuilder::new();
+        b.open("hp:pic")
+            .attr("id", pic_id)
+            .attr("zOrder", img_idx)
+            .attr("numberingType", "PICTURE")
+            .attr("textWrap", "TOP_AND_BOTTOM")
+            .attr("textFlow", "BOTH_SIDES")
+            .attr("lock", 0)
+            .attr("dropcapstyle", "None")
+            .attr("href", "")
+            .attr("groupLevel", 0)
+            .attr("instid", pic_id)
+            .attr("reverse", 0);
+        b.open("hp:offset").attr("x", 0).attr("y", 0).close();
+        b.open("hp:orgSz")
+            .attr("width", org_width)
+            .attr("height", org_height)
+            .close();
+        b.open("hp:curSz")
+            .attr("width", cur_width)
+            .attr("height", cur_height)
+            .close();
+        b.open("hp:flip")
+            .attr("horizontal", 0)
+            .attr("vertical", 0)
+            .close();
+        b.open("hp:rotationInfo")
+            .attr("angle", 0)
+            .attr("centerX", center_x)
+            .attr("centerY", center_y)
+            .attr("rotateimage", 1)
+            .close();
+
+        b.open("hp:renderingInfo");
+        matrix(&mut b, "hc:transMatrix", "1", "1");
+        matrix(
+            &mut b,
+            "hc:scaMatrix",
+            &format!("{:.6}", sca_x),
+            &format!("{:.6}", sca_y),
+        );
+        matrix(&mut b, "hc:rotMatrix", "1", "1");
+        b.close();
 
         // hc:img (핵심: hp:img 아닌 hc:img 네임스페이스)
-        xml.push_str(&format!(
-            r#"<hc:img binaryItemIDRef="{}" bright="0" contrast="0" effect="REAL_PIC" alpha="0"/>"#,
-            item_id
-        ));
+        b.open("hc:img")
+            .attr("binaryItemIDRef", &item_id)
+            .attr("bright", 0)
+            .attr("contrast", 0)
+            .attr("effect", "REAL_PIC")
+            .attr("alpha", 0)
+            .close();
 
         // imgRect
-        xml.push_str("<hp:imgRect>");
-        xml.push_str(r#"<hc:pt0 x="0" y="0"/>"#);
-        xml.push_str(&format!(r#"<hc:pt1 x="{}" y="0"/>"#, org_width));
-        xml.push_str(&format!(
-            r#"<hc:pt2 x="{}" y="{}"/>"#,
-            org_width, org_height
-        ));
-        xml.push_str(&format!(r#"<hc:pt3 x="0" y="{}"/>"#, org_height));
-        xml.push_str("</hp:imgRect>");
-
-        xml.push_str(&format!(
-            r#"<hp:imgClip left="0" right="{}" top="0" bottom="{}"/>"#,
-            org_width, org_height
-        ));
-        xml.push_str(r#"<hp:inMargin left="0" right="0" top="0" bottom="0"/>"#);
-        xml.push_str(&format!(
-            r#"<hp:imgDim dimwidth="{}" dimheight="{}"/>"#,
-            org_width, org_height
-        ));
-        xml.push_str("<hp:effects/>");
+        b.open("hp:imgRect");
+        for (name, x, y) in [
+            ("hc:pt0", 0, 0),
+            ("hc:pt1", org_width, 0),
+            ("hc:pt2", org_width, org_height),
+            ("hc:pt3", 0, org_height),
+        ] {
+            b.open(name).attr("x", x).attr("y", y).close();
+        }
+        b.close();
+
+        b.open("hp:imgClip")
+            .attr("left", 0)
+            .attr("right", org_width)
+            .attr("top", 0)
+            .attr("bottom", org_height)
+            .close();
+        b.open("hp:inMargin")
+            .attr("left", 0)
+            .attr("right", 0)
+            .attr("top", 0)
+            .attr("bottom", 0)
+            .close();
+        b.open("hp:imgDim")
+            .attr("dimwidth", org_width)
+            .attr("dimheight", org_height)
+            .close();
+        b.open("hp:effects").close();
 
         // sz, pos, outMargin (참조 파일에서는 하단에 위치)
-        xml.push_str(&format!(
-            r#"<hp:sz width="{}" widthRelTo="ABSOLUTE" height="{}" heightRelTo="ABSOLUTE" protect="0"/>"#,
-            cur_width, cur_height
-        ));
-        xml.push_str(concat!(
-            r#"<hp:pos treatAsChar="1" affectLSpacing="0" flowWithText="1" allowOverlap="0" "#,
-            r#"holdAnchorAndSO="0" vertRelTo="PARA" horzRelTo="PARA" vertAlign="TOP" "#,
-            r#"horzAlign="LEFT" vertOffset="0" horzOffset="0"/>"#
-        ));
-        xml.push_str(r#"<hp:outMargin left="0" right="0" top="0" bottom="0"/>"#);
-        xml.push_str("</hp:pic>");
+        b.open("hp:sz")
+            .attr("width", cur_width)
+            .attr("widthRelTo", "ABSOLUTE")
+            .attr("height", cur_height)
+            .attr("heightRelTo", "ABSOLUTE")
+            .attr("protect", 0)
+            .close();
+        b.open("hp:pos")
+            .attr("treatAsChar", 1)
+            .attr("affectLSpacing", 0)
+            .attr("flowWithText", 1)
+            .attr("allowOverlap", 0)
+            .attr("holdAnchorAndSO", 0)
+            .attr("vertRelTo", "PARA")
+            .attr("horzRelTo", "PARA")
+            .attr("vertAlign", "TOP")
+            .attr("horzAlign", "LEFT")
+            .attr("vertOffset", 0)
+            .attr("horzOffset", 0)
+            .close();
+        b.open("hp:outMargin")
+            .attr("left", 0)
+            .attr("right", 0)
+            .attr("top", 0)
+            .attr("bottom", 0)
+            .close();
+        b.close();
+
+        b.finish()
+    }
 
-        xml
+    /// 1-based number of the captioned table at `para_idx`, counting captioned
+    /// tables in document order.
+    fn table_number(&self, para_idx: usize) -> usize {
+        self.tables
+            .iter()
+            .filter(|(idx, table)| *idx <= para_idx && table.caption.is_some())
+            .count()
     }
 
-    fn format_table(&self, table: &HwpxTable) -> String {
+    fn format_table(
+        &self,
+        table_idx: usize,
+        table: &HwpxTable,
+        number: usize,
+        content_width: u32,
+        direction: TextDirection,
+    ) -> String {
         let row_cnt = table.rows.len();
         let col_cnt = table.rows.first().map(|r| r.len()).unwrap_or(0);
         if row_cnt == 0 || col_cnt == 0 {
             return String::new();
         }
 
-        let content_width: u32 = 42520;
-        let col_width = content_width / col_cnt as u32;
-        let total_width = col_width * col_cnt as u32;
+        let col_widths = table.scaled_col_widths(content_width);
+        // Cell pictures are numbered after the body, header and footer images,
+        // in table order
+        let mut img_idx = self.images.len()
+            + self
+                .headers
+                .iter()
+                .filter(|(h, _)| h.image.is_some())
+                .count()
+            + self
+                .footers
+                .iter()
+                .filter(|(f, _)| f.image.is_some())
+                .count()
+            + self
+                .tables
+                .iter()
+                .take_while(|(_, t)| !std::ptr::eq(t, table))
+                .map(|(_, t)| t.cell_images.values().map(Vec::len).sum::<usize>())
+                .sum::<usize>();
+        let cell_fills = self.cell_colors(|style| style.background);
+        let cell_text_colors = self.cell_colors(|style| style.text_color);
+        let total_width: u32 = col_widths.iter().sum();
         let cell_height: u32 = 1000;
 
-        let mut xml = format!(
-            concat!(
-                r#"<hp:tbl id="{}" zOrder="0" numberingType="TABLE" textWrap="TOP_AND_BOTTOM" "#,
-                r#"textFlow="BOTH_SIDES" lock="0" dropcapstyle="None" pageBreak="CELL" "#,
-                r#"repeatHeader="1" rowCnt="{}" colCnt="{}" cellSpacing="0" borderFillIDRef="3" noAdjust="0">"#,
-                r#"<hp:sz width="{}" widthRelTo="ABSOLUTE" height="{}" heightRelTo="ABSOLUTE" protect="0"/>"#,
-                r#"<hp:pos treatAsChar="0" affectLSpacing="0" flowWithText="1" allowOverlap="0" "#,
-                r#"holdAnchorAndSO="0" vertRelTo="PARA" horzRelTo="PARA" vertAlign="TOP" "#,
-                r#"horzAlign="LEFT" vertOffset="0" horzOffset="0"/>"#,
-                r#"<hp:outMargin left="283" right="283" top="283" bottom="283"/>"#,
-                r#"<hp:inMargin left="510" right="510" top="142" bottom="142"/>"#
-            ),
-            self.next_table_id, row_cnt, col_cnt, total_width,
-            cell_height * row_cnt as u32
-        );
+        let mut b = XmlBuilder::new();
+        b.open("hp:tbl")
+            .attr("id", self.table_id(table_idx))
+            .attr("zOrder", 0)
+            .attr("numberingType", "TABLE")
+            .attr("textWrap", "TOP_AND_BOTTOM")
+            .attr("textFlow", "BOTH_SIDES")
+            .attr("lock", 0)
+            .attr("dropcapstyle", "None")
+            .attr("pageBreak", "CELL")
+            .attr("repeatHeader", 1)
+            .attr("rowCnt", row_cnt)
+            .attr("colCnt", col_cnt)
+            .attr("cellSpacing", 0)
+            .attr("borderFillIDRef", 3)
+            .attr("noAdjust", 0);
+        b.open("hp:sz")
+            .attr("width", total_width)
+            .attr("widthRelTo", "ABSOLUTE")
+            .attr("height", cell_height * row_cnt as u32)
+            .attr("heightRelTo", "ABSOLUTE")
+            .attr("protect", 0)
+            .close();
+        b.open("hp:pos")
+            .attr("treatAsChar", 0)
+            .attr("affectLSpacing", 0)
+            .attr("flowWithText", 1)
+            .attr("allowOverlap", 0)
+            .attr("holdAnchorAndSO", 0)
+            .attr("vertRelTo", "PARA")
+            .attr("horzRelTo", "PARA")
+            .attr("vertAlign", "TOP")
+            .attr("horzAlign", "LEFT")
+            .attr("vertOffset", 0)
+            .attr("horzOffset", 0)
+            .close();
+        margin(&mut b, "hp:outMargin", 283, 283, 283, 283);
+        if let Some(caption) = &table.caption {
+            b.open("hp:caption")
+                .attr("side", table.caption_position.as_hwpx_side())
+                .attr("fullSz", 0)
+                .attr("width", 8504)
+                .attr("gap", 850)
+                .attr("lastWidth", total_width);
+            b.open("hp:subList")
+                .attr("id", "")
+                .attr("textDirection", direction.as_hwpx())
+                .attr("lineWrap", "BREAK")
+                .attr("vertAlign", "TOP")
+                .attr("linkListIDRef", 0)
+                .attr("linkListNextIDRef", 0)
+                .attr("textWidth", 0)
+                .attr("textHeight", 0)
+                .attr("hasTextRef", 0)
+                .attr("hasNumRef", 0);
+            open_paragraph(&mut b, 0, self.para_pr_id(&CAPTION_PARA_PR), false);
+            text_run(&mut b, 0, &format!("표 {}. {}", number, caption));
+            b.close().close().close();
+        }
+        margin(&mut b, "hp:inMargin", 510, 510, 142, 142);
 
         for row_idx in 0..row_cnt {
-            xml.push_str("<hp:tr>");
+            b.open("hp:tr");
             for col_idx in 0..col_cnt {
                 // Skip cells covered by another cell's span
                 if table.is_covered(row_idx, col_idx) {
@@ -1653,136 +4365,171 @@ impl HwpxWriter {
 
                 let cell_text = &table.rows[row_idx][col_idx];
                 let span = table.get_cell_span(row_idx, col_idx);
-                let cell_w = col_width * span.col_span;
+                let style = table.cell_styles.get(&(row_idx, col_idx));
+                let border_fill = style
+                    .and_then(|s| s.background)
+                    .and_then(|c| cell_fills.iter().position(|&f| f == c & 0xFFFFFF))
+                    .map_or(3, |idx| self.cell_border_fill_base() + idx as u32);
+                let char_pr = style
+                    .and_then(|s| s.text_color)
+                    .and_then(|c| cell_text_colors.iter().position(|&t| t == c & 0xFFFFFF))
+                    .map_or(0, |idx| self.cell_char_pr_base() + idx as u32);
+                let cell_w: u32 = col_widths
+                    .iter()
+                    .skip(col_idx)
+                    .take(span.col_span as usize)
+                    .sum();
                 let cell_h = cell_height * span.row_span;
 
-                xml.push_str(&format!(
-                    concat!(
-                        r#"<hp:tc name="" header="0" hasMargin="0" protect="0" editable="0" dirty="0" borderFillIDRef="3">"#,
-                        r#"<hp:subList id="" textDirection="HORIZONTAL" lineWrap="BREAK" vertAlign="CENTER" "#,
-                        r#"linkListIDRef="0" linkListNextIDRef="0" textWidth="0" textHeight="0" hasTextRef="0" hasNumRef="0">"#,
-                        r#"<hp:p id="0" paraPrIDRef="0" styleIDRef="0" pageBreak="0" columnBreak="0" merged="0">"#,
-                        r#"<hp:run charPrIDRef="0"><hp:t>{}</hp:t></hp:run>"#,
-                        r#"</hp:p></hp:subList>"#,
-                        r#"<hp:cellAddr colAddr="{}" rowAddr="{}"/>"#,
-                        r#"<hp:cellSpan colSpan="{}" rowSpan="{}"/>"#,
-                        r#"<hp:cellSz width="{}" height="{}"/>"#,
-                        r#"<hp:cellMargin left="510" right="510" top="142" bottom="142"/>"#,
-                        r#"</hp:tc>"#
-                    ),
-                    escape_xml(cell_text),
-                    col_idx,
-                    row_idx,
-                    span.col_span,
-                    span.row_span,
-                    cell_w,
-                    cell_h
-                ));
+                b.open("hp:tc")
+                    .attr("name", "")
+                    .attr("header", u8::from(row_idx < table.header_rows))
+                    .attr("hasMargin", 0)
+                    .attr("protect", 0)
+                    .attr("editable", 0)
+                    .attr("dirty", 0)
+                    .attr("borderFillIDRef", border_fill);
+                b.open("hp:subList")
+                    .attr("id", "")
+                    .attr("textDirection", direction.as_hwpx())
+                    .attr("lineWrap", "BREAK")
+                    .attr("vertAlign", "CENTER")
+                    .attr("linkListIDRef", 0)
+                    .attr("linkListNextIDRef", 0)
+                    .attr("textWidth", 0)
+                    .attr("textHeight", 0)
+                    .attr("hasTextRef", 0)
+                    .attr("hasNumRef", 0);
+                // One paragraph per line, so line breaks survive in Hangul
+                let links = table
+                    .cell_links
+                    .get(&(row_idx, col_idx))
+                    .map_or(&[][..], Vec::as_slice);
+                let images = table
+                    .cell_images
+                    .get(&(row_idx, col_idx))
+                    .map_or(&[][..], Vec::as_slice);
+                let mut line_start = 0;
+                for line in cell_text.split('\n') {
+                    open_paragraph(&mut b, 0, 0, false);
+                    if line_start == 0 {
+                        // Inline pictures placed before the text
+                        for image in images {
+                            let width = cell_w.saturating_sub(1020).max(1);
+                            object_run(&mut b, &self.format_picture(img_idx, image, width));
+                            img_idx += 1;
+                        }
+                    }
+                    cell_line_runs(&mut b, char_pr, line, line_start, links);
+                    b.close();
+                    line_start += line.len() + 1;
+                }
+                b.close();
+                b.open("hp:cellAddr")
+                    .attr("colAddr", col_idx)
+                    .attr("rowAddr", row_idx)
+                    .close();
+                b.open("hp:cellSpan")
+                    .attr("colSpan", span.col_span)
+                    .attr("rowSpan", span.row_span)
+                    .close();
+                b.open("hp:cellSz")
+                    .attr("width", cell_w)
+                    .attr("height", cell_h)
+                    .close();
+                margin(&mut b, "hp:cellMargin", 510, 510, 142, 142);
+                b.close();
             }
-            xml.push_str("</hp:tr>");
+            b.close();
         }
 
-        xml.push_str("</hp:tbl>");
-        xml
+        b.close();
+        b.finish()
     }
 
-    fn generate_header_ctrl_xml(&self) -> String {
-        let mut xml = String::new();
-
-        for (idx, header) in self.headers.iter().enumerate() {
-            let apply_type = match header.apply_to {
-                HeaderFooterApplyTo::All => "BOTH",
-                HeaderFooterApplyTo::Odd => "ODD",
-                HeaderFooterApplyTo::Even => "EVEN",
-            };
+    fn write_header_ctrls(&self, b: &mut XmlBuilder, first_section: bool) {
+        // Header pictures are numbered after the body images
+        let mut img_idx = self.images.len();
 
-            let content = if header.text.is_empty() {
-                concat!(
-                    r#"<hp:ctrl>"#,
-                    r#"<hp:autoNum num="1" numType="PAGE">"#,
-                    r#"<hp:autoNumFormat type="DIGIT" userChar="" prefixChar="" suffixChar="" supscript="0"/>"#,
-                    r#"</hp:autoNum>"#,
-                    r#"</hp:ctrl>"#,
-                    r#"<hp:t/>"#
-                ).to_string()
-            } else {
-                format!(r#"<hp:t>{}</hp:t>"#, escape_xml(&header.text))
-            };
+        for (idx, (header, run_shapes)) in self.headers.iter().enumerate() {
+            let picture_idx = header.image.as_ref().map(|_| {
+                img_idx += 1;
+                img_idx - 1
+            });
+            if header.apply_to == HeaderFooterApplyTo::First && !first_section {
+                continue;
+            }
 
-            xml.push_str(&format!(
-                concat!(
-                    r#"<hp:ctrl>"#,
-                    r#"<hp:header id="{}" applyPageType="{}">"#,
-                    r#"<hp:subList id="" textDirection="HORIZONTAL" lineWrap="BREAK" vertAlign="TOP" "#,
-                    r#"linkListIDRef="0" linkListNextIDRef="0" textWidth="42520" textHeight="4252" "#,
-                    r#"hasTextRef="0" hasNumRef="0">"#,
-                    r#"<hp:p id="0" paraPrIDRef="0" styleIDRef="0" pageBreak="0" columnBreak="0" merged="0">"#,
-                    r#"<hp:run charPrIDRef="0">{}</hp:run>"#,
-                    r#"</hp:p>"#,
-                    r#"</hp:subList>"#,
-                    r#"</hp:header>"#,
-                    r#"</hp:ctrl>"#
-                ),
-                idx + 1,
-                apply_type,
-                content
-            ));
+            open_header_footer(b, "hp:header", idx + 1, header.apply_to, 0);
+            if let (Some(image), Some(picture_idx)) = (&header.image, picture_idx) {
+                object_run(
+                    b,
+                    &self.format_picture(picture_idx, image, HEADER_FOOTER_TEXT_WIDTH),
+                );
+            }
+            if !header.runs.is_empty() {
+                header_footer_runs(b, &header.runs, run_shapes);
+            } else if header.text.is_empty() && header.image.is_none() {
+                page_number_run(b, "DIGIT", None, None);
+            } else if !header.text.is_empty() {
+                b.open("hp:run").attr("charPrIDRef", 0);
+                b.text_element("hp:t", &header.text);
+                b.close();
+            }
+            close_header_footer(b);
         }
-
-        xml
     }
 
-    fn generate_footer_ctrl_xml(&self) -> String {
-        let mut xml = String::new();
-
-        for (idx, footer) in self.footers.iter().enumerate() {
-            let apply_type = match footer.apply_to {
-                HeaderFooterApplyTo::All => "BOTH",
-                HeaderFooterApplyTo::Odd => "ODD",
-                HeaderFooterApplyTo::Even => "EVEN",
-            };
-
-            let content = if footer.include_page_number {
-                let format_type = footer.page_number_format.as_hwpx_format();
-                format!(
-                    concat!(
-                        r#"<hp:t>{}</hp:t>"#,
-                        r#"</hp:run><hp:run charPrIDRef="0">"#,
-                        r#"<hp:ctrl>"#,
-                        r#"<hp:autoNum num="1" numType="PAGE">"#,
-                        r#"<hp:autoNumFormat type="{}" userChar="" prefixChar="" suffixChar="" supscript="0"/>"#,
-                        r#"</hp:autoNum>"#,
-                        r#"</hp:ctrl>"#,
-                        r#"<hp:t/>"#
-                    ),
-                    escape_xml(&footer.text),
-                    format_type
-                )
-            } else {
-                format!(r#"<hp:t>{}</hp:t>"#, escape_xml(&footer.text))
-            };
+    fn write_footer_ctrls(&self, b: &mut XmlBuilder, first_section: bool) {
+        let header_images = self
+            .headers
+            .iter()
+            .filter(|(h, _)| h.image.is_some())
+            .count();
+        let mut img_idx = self.images.len() + header_images;
+
+        for (idx, (footer, run_shapes)) in self.footers.iter().enumerate() {
+            let picture_idx = footer.image.as_ref().map(|_| {
+                img_idx += 1;
+                img_idx - 1
+            });
+            if footer.apply_to == HeaderFooterApplyTo::First && !first_section {
+                continue;
+            }
 
-            xml.push_str(&format!(
-                concat!(
-                    r#"<hp:ctrl>"#,
-                    r#"<hp:footer id="{}" applyPageType="{}">"#,
-                    r#"<hp:subList id="" textDirection="HORIZONTAL" lineWrap="BREAK" vertAlign="TOP" "#,
-                    r#"linkListIDRef="0" linkListNextIDRef="0" textWidth="42520" textHeight="4252" "#,
-                    r#"hasTextRef="0" hasNumRef="0">"#,
-                    r#"<hp:p id="0" paraPrIDRef="0" styleIDRef="0" pageBreak="0" columnBreak="0" merged="0">"#,
-                    r#"<hp:run charPrIDRef="0">{}</hp:run>"#,
-                    r#"</hp:p>"#,
-                    r#"</hp:subList>"#,
-                    r#"</hp:footer>"#,
-                    r#"</hp:ctrl>"#
-                ),
-                idx + 1,
-                apply_type,
-                content
-            ));
+            let para_pr_id = footer
+                .alignment
+                .map(|align| {
+                    self.para_pr_id(&ParaPrDef {
+                        align,
+                        ..Default::default()
+                    })
+                })
+                .unwrap_or(0);
+            open_header_footer(b, "hp:footer", idx + 1, footer.apply_to, para_pr_id);
+            if let (Some(image), Some(picture_idx)) = (&footer.image, picture_idx) {
+                object_run(
+                    b,
+                    &self.format_picture(picture_idx, image, HEADER_FOOTER_TEXT_WIDTH),
+                );
+            }
+            if !footer.runs.is_empty() {
+                header_footer_runs(b, &footer.runs, run_shapes);
+            } else if !footer.text.is_empty() || !footer.include_page_number {
+                b.open("hp:run").attr("charPrIDRef", 0);
+                b.text_element("hp:t", &footer.text);
+                b.close();
+            }
+            if footer.include_page_number {
+                page_number_run(
+                    b,
+                    footer.page_number_format.as_hwpx_format(),
+                    footer.page_number_prefix,
+                    footer.page_number_suffix,
+                );
+            }
+            close_header_footer(b);
         }
-
-        xml
     }
 
     fn get_section_count(&self) -> usize {
@@ -1799,18 +4546,464 @@ impl HwpxWriter {
     }
 }
 
+/// Page layout used when a section has none: A4 portrait with the margins
+/// Hangul uses for new documents.
+fn default_page_layout() -> PageLayout {
+    PageLayout {
+        height: 84186,
+        margins: PageMargins {
+            top: 5668,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
 impl Default for HwpxWriter {
     fn default() -> Self {
         Self::new()
     }
 }
 
-fn escape_xml(s: &str) -> String {
-    s.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-        .replace('\'', "&apos;")
+/// Page-break bit of a paragraph's break type (`Paragraph::column_type`)
+const BREAK_PAGE: u8 = 0x04;
+
+fn open_paragraph(
+    b: &mut XmlBuilder,
+    id: usize,
+    para_pr_id: impl std::fmt::Display,
+    page_break: bool,
+) {
+    b.open("hp:p")
+        .attr("id", id)
+        .attr("paraPrIDRef", para_pr_id)
+        .attr("styleIDRef", 0)
+        .attr("pageBreak", page_break as u8)
+        .attr("columnBreak", 0)
+        .attr("merged", 0);
+}
+
+/// Languages of `hh:fontRef` and the other per-language charPr attributes, in order
+const FONT_REF_LANGS: [&str; 7] = [
+    "hangul", "latin", "hanja", "japanese", "other", "symbol", "user",
+];
+
+/// `#RRGGBB` of an RGB color
+fn hex_color(color: u32) -> String {
+    format!("#{:06X}", color & 0xFFFFFF)
+}
+
+/// `hh:borderFill` with the given line type on the left, right, top and bottom
+/// sides, and a background when `face_color` is set
+fn border_fill(
+    b: &mut XmlBuilder,
+    id: u32,
+    lines: [&str; 4],
+    width: &str,
+    color: u32,
+    diagonal: &str,
+    face_color: Option<&str>,
+) {
+    b.open("hh:borderFill")
+        .attr("id", id)
+        .attr("threeD", 0)
+        .attr("shadow", 0)
+        .attr("centerLine", "NONE")
+        .attr("breakCellSeparateLine", 0);
+    b.raw(r#"<hh:slash type="NONE" Crooked="0" isCounter="0"/><hh:backSlash type="NONE" Crooked="0" isCounter="0"/>"#);
+    let sides = [
+        "hh:leftBorder",
+        "hh:rightBorder",
+        "hh:topBorder",
+        "hh:bottomBorder",
+    ];
+    for (side, line) in sides.into_iter().zip(lines) {
+        b.open(side)
+            .attr("type", line)
+            .attr("width", width)
+            .attr("color", hex_color(color))
+            .close();
+    }
+    b.open("hh:diagonal")
+        .attr("type", diagonal)
+        .attr("width", "0.1 mm")
+        .attr("color", "#000000")
+        .close();
+    if let Some(face_color) = face_color {
+        b.open("hc:fillBrush");
+        b.open("hc:winBrush")
+            .attr("faceColor", face_color)
+            .attr("hatchColor", "#999999")
+            .attr("alpha", 0)
+            .close();
+        b.close();
+    }
+    b.close();
+}
+
+/// Open `hp:ctrl` > `name` (`hp:header`/`hp:footer`) > `hp:subList` > `hp:p`; the
+/// paragraph's runs follow and [`close_header_footer`] ends all four.
+fn open_header_footer(
+    b: &mut XmlBuilder,
+    name: &'static str,
+    id: usize,
+    apply_to: HeaderFooterApplyTo,
+    para_pr_id: u32,
+) {
+    let apply_type = match apply_to {
+        HeaderFooterApplyTo::All => "BOTH",
+        HeaderFooterApplyTo::Odd => "ODD",
+        HeaderFooterApplyTo::Even => "EVEN",
+        HeaderFooterApplyTo::First => "BOTH",
+    };
+    b.open("hp:ctrl");
+    b.open(name)
+        .attr("id", id)
+        .attr("applyPageType", apply_type);
+    b.open("hp:subList")
+        .attr("id", "")
+        .attr("textDirection", "HORIZONTAL")
+        .attr("lineWrap", "BREAK")
+        .attr("vertAlign", "TOP")
+        .attr("linkListIDRef", 0)
+        .attr("linkListNextIDRef", 0)
+        .attr("textWidth", HEADER_FOOTER_TEXT_WIDTH)
+        .attr("textHeight", 4252)
+        .attr("hasTextRef", 0)
+        .attr("hasNumRef", 0);
+    open_paragraph(b, 0, para_pr_id, false);
+}
+
+fn close_header_footer(b: &mut XmlBuilder) {
+    b.close().close().close().close();
+}
+
+/// Styled header/footer runs with the charPr ids registered for them
+fn header_footer_runs(b: &mut XmlBuilder, runs: &[StyledText], run_shapes: &[u16]) {
+    for (run, char_shape_id) in runs.iter().zip(run_shapes) {
+        let text = run.direction.isolate(&run.text);
+        match &run.url {
+            Some(url) => hyperlink_run(b, char_shape_id, url, &text),
+            None => text_run(b, char_shape_id, &text),
+        };
+    }
+}
+
+/// A run holding the current page number (`hp:autoNum`)
+fn page_number_run(
+    b: &mut XmlBuilder,
+    format_type: &str,
+    prefix: Option<char>,
+    suffix: Option<char>,
+) {
+    b.open("hp:run").attr("charPrIDRef", 0);
+    b.open("hp:ctrl");
+    b.open("hp:autoNum").attr("num", 1).attr("numType", "PAGE");
+    b.open("hp:autoNumFormat")
+        .attr("type", format_type)
+        .attr("userChar", "")
+        .attr("prefixChar", prefix.map(String::from).unwrap_or_default())
+        .attr("suffixChar", suffix.map(String::from).unwrap_or_default())
+        .attr("supscript", 0)
+        .close();
+    b.close().close();
+    b.open("hp:t").close();
+    b.close();
+}
+
+/// `<hp:run charPrIDRef=".."><hp:t>text</hp:t></hp:run>`
+fn text_run(b: &mut XmlBuilder, char_pr_id: impl std::fmt::Display, text: &str) {
+    b.open("hp:run").attr("charPrIDRef", char_pr_id);
+    text_element(b, text);
+    b.close();
+}
+
+/// `hp:t` with every tab character written as an `hp:tab` control between the
+/// text segments. The width is only a layout hint; Hangul recomputes it from the
+/// paragraph's tab stops.
+fn text_element(b: &mut XmlBuilder, text: &str) {
+    if !text.contains('\t') {
+        b.text_element("hp:t", text);
+        return;
+    }
+    b.open("hp:t");
+    for (i, segment) in text.split('\t').enumerate() {
+        if i > 0 {
+            b.open("hp:tab")
+                .attr("width", 4000)
+                .attr("leader", 0)
+                .attr("type", 1)
+                .close();
+        }
+        b.text(segment);
+    }
+    b.close();
+}
+
+/// Runs of one line of a table cell starting at byte `offset` of the cell text,
+/// with the parts covered by `links` written as hyperlink runs
+fn cell_line_runs(
+    b: &mut XmlBuilder,
+    char_pr_id: u32,
+    line: &str,
+    offset: usize,
+    links: &[CellLink],
+) {
+    let mut pos = 0;
+    for link in links {
+        let start = link.range.start.max(offset) - offset;
+        let end = link
+            .range
+            .end
+            .min(offset + line.len())
+            .saturating_sub(offset);
+        if start >= end
+            || start < pos
+            || !line.is_char_boundary(start)
+            || !line.is_char_boundary(end)
+        {
+            continue;
+        }
+        if start > pos {
+            text_run(b, char_pr_id, &line[pos..start]);
+        }
+        hyperlink_run(b, char_pr_id, &link.url, &line[start..end]);
+        pos = end;
+    }
+    if pos < line.len() || pos == 0 {
+        text_run(b, char_pr_id, &line[pos..]);
+    }
+}
+
+/// A text run that is also a hyperlink; the link control sits inside the run so
+/// the link keeps the run's char shape.
+fn hyperlink_run(b: &mut XmlBuilder, char_pr_id: impl std::fmt::Display, url: &str, text: &str) {
+    b.open("hp:run").attr("charPrIDRef", char_pr_id);
+    b.open("hp:ctrl");
+    b.open("hp:hyperlink")
+        .attr("url", url)
+        .attr("visited", 0)
+        .attr("visited_style", 0)
+        .attr("new_window", 0)
+        .close();
+    b.close();
+    text_element(b, text);
+    b.close();
+}
+
+/// One `hp:run` of a paragraph: a stretch of text with a single char shape,
+/// at most one link and one direction.
+struct ParagraphRun<'a> {
+    text: String,
+    char_pr_id: u16,
+    url: Option<&'a str>,
+    direction: RunDirection,
+}
+
+/// Split a paragraph into runs at every char shape change, hyperlink and
+/// direction boundary, in text order. Text before the first char shape position
+/// uses charPr 0. Positions count UTF-16 code units, as HWPX does; one that
+/// falls inside a surrogate pair is moved back to the start of that character,
+/// so a run never splits a scalar value.
+fn paragraph_runs<'a>(
+    text: &str,
+    para: &'a Paragraph,
+    directions: &[(u32, u32, RunDirection)],
+) -> Vec<ParagraphRun<'a>> {
+    // (UTF-16 offset, byte offset) of every char boundary, including the end
+    let mut offsets: Vec<(usize, usize)> = Vec::with_capacity(text.len() + 1);
+    let mut unit = 0;
+    for (byte, ch) in text.char_indices() {
+        offsets.push((unit, byte));
+        unit += ch.len_utf16();
+    }
+    offsets.push((unit, text.len()));
+    let len = unit;
+    let snap = |pos: usize| {
+        let pos = pos.min(len);
+        offsets[offsets.partition_point(|&(u, _)| u <= pos) - 1]
+    };
+
+    let positions = para
+        .char_shapes
+        .as_ref()
+        .map(|cs| cs.char_positions.as_slice())
+        .unwrap_or_default();
+
+    let mut bounds: Vec<(usize, usize)> = vec![snap(0), snap(len)];
+    bounds.extend(positions.iter().map(|p| snap(p.position as usize)));
+    for link in &para.hyperlinks {
+        let start = link.start_position as usize;
+        bounds.push(snap(start));
+        bounds.push(snap(start + link.length as usize));
+    }
+    for &(start, length, _) in directions {
+        bounds.push(snap(start as usize));
+        bounds.push(snap((start + length) as usize));
+    }
+    bounds.sort_unstable();
+    bounds.dedup();
+
+    bounds
+        .windows(2)
+        .map(|w| {
+            let ((start, start_byte), (_, end_byte)) = (w[0], w[1]);
+            let char_pr_id = positions
+                .iter()
+                .take_while(|p| snap(p.position as usize).0 <= start)
+                .last()
+                .map_or(0, |p| p.char_shape_id);
+            let url = para
+                .hyperlinks
+                .iter()
+                .find(|l| {
+                    let link_start = snap(l.start_position as usize).0;
+                    let link_end = snap(l.start_position as usize + l.length as usize).0;
+                    link_start <= start && start < link_end
+                })
+                .map(|l| l.target_url.as_str());
+            let direction = directions
+                .iter()
+                .find(|&&(dir_start, length, _)| {
+                    snap(dir_start as usize).0 <= start
+                        && start < snap((dir_start + length) as usize).0
+                })
+                .map_or(RunDirection::Auto, |&(_, _, dir)| dir);
+            ParagraphRun {
+                text: text[start_byte..end_byte].to_string(),
+                char_pr_id,
+                url,
+                direction,
+            }
+        })
+        .collect()
+}
+
+/// hp:checkBtn / hp:edit placed inline like a character. `field_idx` is the
+/// control's position among the document's form fields and makes its id and
+/// default name unique.
+fn format_form_field(field_idx: usize, field: &FormField, content_width: u32) -> String {
+    let id = FORM_FIELD_ID_BASE + field_idx as u32;
+    let height: u32 = 1500;
+    let (element, name, width) = match field {
+        FormField::CheckBox { label, .. } => (
+            "hp:checkBtn",
+            format!("CheckBox{}", field_idx + 1),
+            // Box plus roughly one em per caption character
+            (height + 1000 * label.chars().count() as u32).min(content_width),
+        ),
+        FormField::TextField { name, .. } => (
+            "hp:edit",
+            name.clone(),
+            mm_to_hwp_units(50.0).min(content_width),
+        ),
+    };
+
+    let mut b = XmlBuilder::new();
+    b.open(element)
+        .attr("id", id)
+        .attr("zOrder", field_idx)
+        .attr("numberingType", "NONE")
+        .attr("textWrap", "TOP_AND_BOTTOM")
+        .attr("textFlow", "BOTH_SIDES")
+        .attr("lock", 0)
+        .attr("dropcapstyle", "None")
+        .attr("name", name)
+        .attr("foreColor", "#000000")
+        .attr("backColor", "#FFFFFF")
+        .attr("groupName", "")
+        .attr("tabStop", 1)
+        .attr("editable", 1)
+        .attr("tabOrder", field_idx + 1)
+        .attr("enabled", 1)
+        .attr("borderTypeIDRef", 0)
+        .attr("drawFrame", 1)
+        .attr("printable", 1)
+        .attr("command", "");
+    match field {
+        FormField::CheckBox { label, checked } => {
+            b.attr("caption", label)
+                .attr("value", if *checked { "CHECKED" } else { "UNCHECKED" })
+                .attr("radioGroupName", "")
+                .attr("triState", 0)
+                .attr("backStyle", "TRANSPARENT");
+        }
+        FormField::TextField { .. } => {
+            b.attr("multiLine", 0)
+                .attr("passwordChar", "")
+                .attr("maxLength", 2147483647)
+                .attr("scrollBars", "NONE")
+                .attr("tabKeyBehavior", "NEXT_OBJECT")
+                .attr("numOnly", 0)
+                .attr("readOnly", 0)
+                .attr("alignText", "LEFT");
+        }
+    }
+    b.open("hp:sz")
+        .attr("width", width)
+        .attr("widthRelTo", "ABSOLUTE")
+        .attr("height", height)
+        .attr("heightRelTo", "ABSOLUTE")
+        .attr("protect", 0)
+        .close();
+    b.raw(concat!(
+        r#"<hp:pos treatAsChar="1" affectLSpacing="0" flowWithText="1" allowOverlap="0" "#,
+        r#"holdAnchorAndSO="0" vertRelTo="PARA" horzRelTo="PARA" vertAlign="TOP" "#,
+        r#"horzAlign="LEFT" vertOffset="0" horzOffset="0"/>"#
+    ));
+    margin(&mut b, "hp:outMargin", 0, 0, 0, 0);
+    b.open("hp:formCharPr")
+        .attr("charPrIDRef", 0)
+        .attr("followContext", 0)
+        .attr("autoSz", 0)
+        .attr("wordWrap", 0)
+        .close();
+    if let FormField::TextField { placeholder, .. } = field {
+        b.text_element("hp:text", placeholder);
+    }
+    b.close();
+    b.finish()
+}
+
+/// Key of a picture's bytes in the BinData index
+fn image_data_hash(image: &HwpxImage) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    image.format.extension().hash(&mut hasher);
+    image.data.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Whether two pictures are stored as the same BinData item
+fn same_image_data(a: &HwpxImage, b: &HwpxImage) -> bool {
+    a.format == b.format && (Arc::ptr_eq(&a.data, &b.data) || a.data == b.data)
+}
+
+/// A run holding an inline object (table, picture, ...) followed by an empty `hp:t`
+fn object_run(b: &mut XmlBuilder, object_xml: &str) {
+    b.open("hp:run").attr("charPrIDRef", 0).raw(object_xml);
+    b.open("hp:t").close();
+    b.close();
+}
+
+fn margin(b: &mut XmlBuilder, name: &'static str, left: u32, right: u32, top: u32, bottom: u32) {
+    b.open(name)
+        .attr("left", left)
+        .attr("right", right)
+        .attr("top", top)
+        .attr("bottom", bottom)
+        .close();
+}
+
+/// 2x3 affine matrix with only the scale terms (e1, e5) set
+fn matrix(b: &mut XmlBuilder, name: &'static str, e1: &str, e5: &str) {
+    b.open(name)
+        .attr("e1", e1)
+        .attr("e2", 0)
+        .attr("e3", 0)
+        .attr("e4", 0)
+        .attr("e5", e5)
+        .attr("e6", 0)
+        .close();
 }
 
 #[cfg(test)]
@@ -1836,6 +5029,145 @@ mod tests {
         );
     }
 
+    fn test_png() -> HwpxImage {
+        HwpxImage::from_bytes(vec![0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A]).unwrap()
+    }
+
+    #[test]
+    fn test_validate_clean_document() {
+        let mut writer = HwpxWriter::new();
+        writer.add_paragraph("plain").unwrap();
+        writer
+            .add_mixed_styled_paragraph(vec![StyledText::new("bold "), StyledText::new("text")])
+            .unwrap();
+        writer
+            .add_table(HwpxTable::from_data(vec![vec!["a", "b"]]))
+            .unwrap();
+        writer.add_image(test_png()).unwrap();
+        assert!(writer.validate().is_empty());
+    }
+
+    #[test]
+    fn test_validate_char_position_past_text() {
+        let mut writer = HwpxWriter::new();
+        writer.add_paragraph("first").unwrap();
+        writer
+            .add_styled_paragraph("abc", HwpxTextStyle::new().bold())
+            .unwrap();
+        let para = &mut writer.document.body_texts[0].sections[0].paragraphs[1];
+        para.char_shapes.as_mut().unwrap().char_positions[0].position = 10;
+
+        let issues = writer.validate();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].paragraph_index, Some(1));
+        assert_eq!(issues[0].kind, ValidationIssueKind::CharPositionOutOfRange);
+    }
+
+    #[test]
+    fn test_validate_hyperlink_past_text() {
+        let mut writer = HwpxWriter::new();
+        writer.add_hyperlink("link", "https://example.com").unwrap();
+        assert!(writer.validate().is_empty());
+
+        let para = &mut writer.document.body_texts[0].sections[0].paragraphs[0];
+        para.hyperlinks[0].length = 9;
+        let issues = writer.validate();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, ValidationIssueKind::CharPositionOutOfRange);
+        assert!(issues[0].message.contains("https://example.com"));
+    }
+
+    #[test]
+    fn test_paragraph_runs_never_split_a_surrogate_pair() {
+        let mut writer = HwpxWriter::new();
+        writer
+            .add_mixed_styled_paragraph(vec![
+                StyledText::new("a😀"),
+                StyledText::with_style("b", HwpxTextStyle::new().bold()),
+            ])
+            .unwrap();
+        let para = &mut writer.document.body_texts[0].sections[0].paragraphs[0];
+        let positions = &mut para.char_shapes.as_mut().unwrap().char_positions;
+        assert_eq!(positions[1].position, 3);
+
+        // A position between the two halves of 😀 starts the run at the emoji
+        positions[1].position = 2;
+        let runs = paragraph_runs("a😀b", para, &[]);
+        let texts: Vec<&str> = runs.iter().map(|r| r.text.as_str()).collect();
+        assert_eq!(texts, ["a", "😀b"]);
+    }
+
+    #[test]
+    fn test_validate_unknown_char_shape() {
+        let mut writer = HwpxWriter::new();
+        writer
+            .add_styled_paragraph("abc", HwpxTextStyle::new().bold())
+            .unwrap();
+        let para = &mut writer.document.body_texts[0].sections[0].paragraphs[0];
+        para.char_shapes.as_mut().unwrap().char_positions[0].char_shape_id = 99;
+
+        let issues = writer.validate();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, ValidationIssueKind::UnknownCharShape);
+    }
+
+    #[test]
+    fn test_validate_table_span_out_of_grid() {
+        let mut writer = HwpxWriter::new();
+        let mut table = HwpxTable::new(2, 2);
+        table.set_cell_span(1, 1, 2, 1);
+        writer.add_table(table).unwrap();
+        writer
+            .add_table(HwpxTable::from_data(vec![vec!["a", "b"], vec!["c"]]))
+            .unwrap();
+
+        let kinds: Vec<_> = writer.validate().iter().map(|i| i.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ValidationIssueKind::TableSpanOutOfGrid,
+                ValidationIssueKind::MalformedTable
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_zero_sized_image() {
+        let mut writer = HwpxWriter::new();
+        writer.add_paragraph("before").unwrap();
+        writer.add_image(test_png().with_size(0, 20)).unwrap();
+        writer.add_footer_config(HwpxFooter::new("").with_image(test_png().with_size(10, 0)));
+
+        let issues = writer.validate();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].paragraph_index, Some(1));
+        assert_eq!(issues[1].paragraph_index, None);
+        assert!(issues
+            .iter()
+            .all(|i| i.kind == ValidationIssueKind::ZeroSizedImage));
+    }
+
+    #[test]
+    fn test_strict_mode_rejects_invalid_document() {
+        let mut writer = HwpxWriter::new();
+        let mut table = HwpxTable::new(1, 1);
+        table.set_cell_span(0, 0, 3, 1);
+        writer.add_table(table).unwrap();
+        assert!(writer.to_bytes().is_ok());
+
+        writer.set_strict(true);
+        match writer.to_bytes() {
+            Err(HwpError::ParseError(msg)) => {
+                assert!(msg.contains("paragraph 0"), "{}", msg);
+                assert!(msg.contains("spans 1x3"), "{}", msg);
+            }
+            other => panic!(
+                "expected validation error, got {:?}",
+                other.map(|b| b.len())
+            ),
+        }
+    }
+
     #[test]
     fn test_escape_xml() {
         assert_eq!(escape_xml("<test>"), "&lt;test&gt;");
@@ -0,0 +1,145 @@
+//! Minimal streaming XML builder for the HWPX writer.
+//!
+//! All attribute values and text go through [`escape_xml`], and every element
+//! opened must be closed in order, so user data can never break the markup.
+//! Static fragments that contain no user data may still be appended with
+//! [`XmlBuilder::raw`].
+
+use std::fmt::Display;
+
+/// Escape text or an attribute value. Characters that XML 1.0 does not allow at
+/// all (C0 controls other than tab/newline/carriage return, U+FFFE, U+FFFF) are
+/// dropped, since no escape can represent them.
+pub(crate) fn escape_xml(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\t' | '\n' | '\r' => out.push(ch),
+            c if (c as u32) < 0x20 || c == '\u{FFFE}' || c == '\u{FFFF}' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[derive(Default)]
+pub(crate) struct XmlBuilder {
+    out: String,
+    open: Vec<&'static str>,
+    in_start_tag: bool,
+}
+
+impl XmlBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start `<name`; attributes may follow until content is written.
+    pub fn open(&mut self, name: &'static str) -> &mut Self {
+        self.end_start_tag();
+        self.out.push('<');
+        self.out.push_str(name);
+        self.open.push(name);
+        self.in_start_tag = true;
+        self
+    }
+
+    pub fn attr(&mut self, key: &str, value: impl Display) -> &mut Self {
+        debug_assert!(self.in_start_tag, "attribute {} outside a start tag", key);
+        self.out.push(' ');
+        self.out.push_str(key);
+        self.out.push_str("=\"");
+        self.out.push_str(&escape_xml(&value.to_string()));
+        self.out.push('"');
+        self
+    }
+
+    /// Append trusted attributes verbatim, e.g. namespace declarations.
+    pub fn raw_attrs(&mut self, attrs: &str) -> &mut Self {
+        debug_assert!(self.in_start_tag, "raw_attrs outside a start tag");
+        self.out.push(' ');
+        self.out.push_str(attrs);
+        self
+    }
+
+    pub fn text(&mut self, text: &str) -> &mut Self {
+        self.end_start_tag();
+        self.out.push_str(&escape_xml(text));
+        self
+    }
+
+    /// Append a trusted, already well-formed fragment.
+    pub fn raw(&mut self, xml: &str) -> &mut Self {
+        self.end_start_tag();
+        self.out.push_str(xml);
+        self
+    }
+
+    /// Close the innermost element; an element without content becomes `<name/>`.
+    pub fn close(&mut self) -> &mut Self {
+        let name = self.open.pop().expect("close() without an open element");
+        if self.in_start_tag {
+            self.out.push_str("/>");
+            self.in_start_tag = false;
+        } else {
+            self.out.push_str("</");
+            self.out.push_str(name);
+            self.out.push('>');
+        }
+        self
+    }
+
+    /// `<name>text</name>`
+    pub fn text_element(&mut self, name: &'static str, text: &str) -> &mut Self {
+        self.open(name).text(text).close()
+    }
+
+    pub fn finish(mut self) -> String {
+        self.end_start_tag();
+        debug_assert!(self.open.is_empty(), "unclosed elements: {:?}", self.open);
+        self.out
+    }
+
+    fn end_start_tag(&mut self) {
+        if self.in_start_tag {
+            self.out.push('>');
+            self.in_start_tag = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_xml_drops_invalid_chars() {
+        assert_eq!(escape_xml("a\u{0}b\u{1b}c\td"), "abc\td");
+        assert_eq!(escape_xml(r#"<"'&>"#), "&lt;&quot;&apos;&amp;&gt;");
+    }
+
+    #[test]
+    fn test_builder_nesting_and_empty_elements() {
+        let mut b = XmlBuilder::new();
+        b.open("hp:run").attr("charPrIDRef", 3);
+        b.text_element("hp:t", "a<b");
+        b.open("hp:t").close();
+        b.close();
+        assert_eq!(
+            b.finish(),
+            r#"<hp:run charPrIDRef="3"><hp:t>a&lt;b</hp:t><hp:t/></hp:run>"#
+        );
+    }
+
+    #[test]
+    fn test_builder_escapes_attribute_values() {
+        let mut b = XmlBuilder::new();
+        b.open("hp:a").attr("href", r#"x" onload="y"#).close();
+        assert_eq!(b.finish(), r#"<hp:a href="x&quot; onload=&quot;y"/>"#);
+    }
+}
@@ -1,7 +1,8 @@
-use quick_xml::de::from_str;
-use serde::Deserialize;
+use quick_xml::de::{from_reader, from_str};
+use serde::{Deserialize, Deserializer};
+use std::io::{BufRead, BufReader, Read};
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct HcfVersion {
     #[serde(rename = "@version", default)]
     pub version: Option<String>,
@@ -17,7 +18,7 @@ pub struct HcfVersion {
     pub application: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 #[serde(rename = "head")]
 pub struct Head {
     #[serde(rename = "beginNum", default)]
@@ -50,6 +51,28 @@ pub struct RefList {
     pub char_properties: Option<CharProperties>,
     #[serde(rename = "paraProperties", default)]
     pub para_properties: Option<ParaProperties>,
+    #[serde(rename = "numberings", default)]
+    pub numberings: Option<Numberings>,
+    #[serde(rename = "bullets", default)]
+    pub bullets: Option<Bullets>,
+    #[serde(rename = "binDataItems", default)]
+    pub bin_data_items: Option<BinDataItems>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BinDataItems {
+    #[serde(rename = "binDataItem", default)]
+    pub items: Vec<BinDataItem>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BinDataItem {
+    #[serde(rename = "@id")]
+    pub id: String,
+    #[serde(rename = "@src", default)]
+    pub src: Option<String>,
+    #[serde(rename = "@format", default)]
+    pub format: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -98,8 +121,40 @@ pub struct CharPr {
     pub underline: Option<String>,
     #[serde(rename = "@strikeout", default)]
     pub strikeout: Option<String>,
+    #[serde(rename = "underline", default)]
+    pub underline_element: Option<CharPrUnderline>,
+    #[serde(rename = "strikeout", default)]
+    pub strikeout_element: Option<CharPrStrikeout>,
+    #[serde(rename = "outline", default)]
+    pub outline: Option<CharPrOutline>,
+    #[serde(rename = "emboss", default)]
+    pub emboss: Option<CharPrFlag>,
+    #[serde(rename = "engrave", default)]
+    pub engrave: Option<CharPrFlag>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct CharPrUnderline {
+    #[serde(rename = "@type", default)]
+    pub underline_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CharPrStrikeout {
+    #[serde(rename = "@shape", default)]
+    pub shape: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CharPrOutline {
+    #[serde(rename = "@type", default)]
+    pub outline_type: Option<String>,
+}
+
+/// Element whose presence alone switches an effect on, e.g. `hh:emboss`
+#[derive(Debug, Clone, Deserialize)]
+pub struct CharPrFlag {}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct ParaProperties {
     #[serde(rename = "paraPr", default)]
@@ -116,6 +171,169 @@ pub struct ParaPr {
     pub line_spacing: Option<String>,
     #[serde(rename = "@tabPrIDRef", default)]
     pub tab_pr_id_ref: Option<u32>,
+    #[serde(rename = "heading", default)]
+    pub heading: Option<ParaPrHeading>,
+    /// `hh:align`
+    #[serde(rename = "align", default)]
+    pub alignment: Option<ParaPrAlign>,
+    #[serde(rename = "breakSetting", default)]
+    pub break_setting: Option<ParaPrBreakSetting>,
+    /// `hh:margin` written directly under the paraPr
+    #[serde(rename = "margin", default)]
+    pub margin: Option<ParaPrMargin>,
+    /// `hh:lineSpacing` written directly under the paraPr
+    #[serde(rename = "lineSpacing", default)]
+    pub line_spacing_setting: Option<ParaPrLineSpacing>,
+    /// `hp:switch` holding margin and line spacing, as Hangul writes them
+    #[serde(rename = "switch", default)]
+    pub switch: Option<ParaPrSwitch>,
+}
+
+impl ParaPr {
+    /// Margins and line spacing of this paraPr: the `hp:case` branch of `hp:switch`
+    /// first, then its `hp:default` branch, then the elements directly under the paraPr
+    pub fn spacing(&self) -> (Option<&ParaPrMargin>, Option<&ParaPrLineSpacing>) {
+        let mut branches = self
+            .switch
+            .iter()
+            .flat_map(|s| [s.case.as_ref(), s.default.as_ref()])
+            .flatten();
+        let margin = branches
+            .clone()
+            .find_map(|b| b.margin.as_ref())
+            .or(self.margin.as_ref());
+        let line_spacing = branches
+            .find_map(|b| b.line_spacing.as_ref())
+            .or(self.line_spacing_setting.as_ref());
+        (margin, line_spacing)
+    }
+}
+
+/// `hh:align` of a paraPr
+#[derive(Debug, Clone, Deserialize)]
+pub struct ParaPrAlign {
+    /// JUSTIFY, LEFT, RIGHT, CENTER, DISTRIBUTE or DISTRIBUTE_SPACE
+    #[serde(rename = "@horizontal", default)]
+    pub horizontal: Option<String>,
+}
+
+/// `hh:breakSetting` of a paraPr; flags are 0 or 1
+#[derive(Debug, Clone, Deserialize)]
+pub struct ParaPrBreakSetting {
+    #[serde(rename = "@keepWithNext", default)]
+    pub keep_with_next: Option<u32>,
+    #[serde(rename = "@keepLines", default)]
+    pub keep_lines: Option<u32>,
+    #[serde(rename = "@pageBreakBefore", default)]
+    pub page_break_before: Option<u32>,
+}
+
+/// `hp:switch` of a paraPr
+#[derive(Debug, Clone, Deserialize)]
+pub struct ParaPrSwitch {
+    #[serde(rename = "case", default)]
+    pub case: Option<ParaPrSpacing>,
+    #[serde(rename = "default", default)]
+    pub default: Option<ParaPrSpacing>,
+}
+
+/// Margin and line spacing inside an `hp:case` or `hp:default` branch
+#[derive(Debug, Clone, Deserialize)]
+pub struct ParaPrSpacing {
+    #[serde(rename = "margin", default)]
+    pub margin: Option<ParaPrMargin>,
+    #[serde(rename = "lineSpacing", default)]
+    pub line_spacing: Option<ParaPrLineSpacing>,
+}
+
+/// `hh:margin` of a paraPr; values are in HWPUNIT
+#[derive(Debug, Clone, Deserialize)]
+pub struct ParaPrMargin {
+    /// First-line indent, negative for a hanging indent
+    #[serde(rename = "intent", default)]
+    pub intent: Option<ParaPrMarginValue>,
+    #[serde(rename = "left", default)]
+    pub left: Option<ParaPrMarginValue>,
+    #[serde(rename = "right", default)]
+    pub right: Option<ParaPrMarginValue>,
+    /// Space above the paragraph
+    #[serde(rename = "prev", default)]
+    pub prev: Option<ParaPrMarginValue>,
+    /// Space below the paragraph
+    #[serde(rename = "next", default)]
+    pub next: Option<ParaPrMarginValue>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ParaPrMarginValue {
+    #[serde(rename = "@value", default)]
+    pub value: Option<i32>,
+}
+
+/// `hh:lineSpacing` of a paraPr
+#[derive(Debug, Clone, Deserialize)]
+pub struct ParaPrLineSpacing {
+    /// PERCENT, FIXED, BETWEEN_LINES or AT_LEAST
+    #[serde(rename = "@type", default)]
+    pub spacing_type: Option<String>,
+    #[serde(rename = "@value", default)]
+    pub value: Option<u32>,
+}
+
+/// `hh:heading`: list membership of paragraphs using this paraPr
+#[derive(Debug, Clone, Deserialize)]
+pub struct ParaPrHeading {
+    /// NONE, OUTLINE, NUMBER or BULLET
+    #[serde(rename = "@type", default)]
+    pub heading_type: Option<String>,
+    #[serde(rename = "@idRef", default)]
+    pub id_ref: Option<u32>,
+    /// Zero-based list level
+    #[serde(rename = "@level", default)]
+    pub level: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Numberings {
+    #[serde(rename = "numbering", default)]
+    pub items: Vec<Numbering>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Numbering {
+    #[serde(rename = "@id")]
+    pub id: u32,
+    #[serde(rename = "paraHead", default)]
+    pub para_heads: Vec<ParaHead>,
+}
+
+/// Number format of one list level, e.g. `^1.` with `numFormat="DIGIT"`
+#[derive(Debug, Clone, Deserialize)]
+pub struct ParaHead {
+    #[serde(rename = "@start", default)]
+    pub start: Option<u32>,
+    /// One-based list level
+    #[serde(rename = "@level", default)]
+    pub level: Option<u32>,
+    #[serde(rename = "@numFormat", default)]
+    pub num_format: Option<String>,
+    /// `^n` stands for the number of level n
+    #[serde(rename = "$text", default)]
+    pub text: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Bullets {
+    #[serde(rename = "bullet", default)]
+    pub items: Vec<Bullet>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Bullet {
+    #[serde(rename = "@id")]
+    pub id: u32,
+    #[serde(rename = "@char", default)]
+    pub bullet_char: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -141,7 +359,7 @@ pub struct XmlParagraph {
 pub struct Run {
     #[serde(rename = "@charPrIDRef", default)]
     pub char_pr_id_ref: Option<u32>,
-    #[serde(rename = "t", default)]
+    #[serde(rename = "t", default, deserialize_with = "deserialize_run_text")]
     pub text: Option<String>,
     #[serde(rename = "secPr", default)]
     pub sec_pr: Option<SecPr>,
@@ -149,6 +367,88 @@ pub struct Run {
     pub table: Option<XmlTable>,
     #[serde(rename = "pic", default)]
     pub picture: Option<XmlPicture>,
+    #[serde(rename = "ctrl", default)]
+    pub ctrls: Vec<XmlCtrl>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct XmlCtrl {
+    #[serde(rename = "hyperlink", default)]
+    pub hyperlink: Option<XmlHyperlink>,
+    #[serde(rename = "header", default)]
+    pub header: Option<XmlHeaderFooter>,
+    #[serde(rename = "footer", default)]
+    pub footer: Option<XmlHeaderFooter>,
+    #[serde(rename = "autoNum", default)]
+    pub auto_num: Option<XmlAutoNum>,
+    #[serde(rename = "footNote", default)]
+    pub foot_note: Option<XmlNote>,
+    #[serde(rename = "endNote", default)]
+    pub end_note: Option<XmlNote>,
+}
+
+/// `hp:header` / `hp:footer`
+#[derive(Debug, Clone, Deserialize)]
+pub struct XmlHeaderFooter {
+    #[serde(rename = "@applyPageType", default)]
+    pub apply_page_type: Option<String>,
+    #[serde(rename = "subList", default)]
+    pub sub_list: Option<SubList>,
+}
+
+/// `hp:footNote` / `hp:endNote`
+#[derive(Debug, Clone, Deserialize)]
+pub struct XmlNote {
+    #[serde(rename = "subList", default)]
+    pub sub_list: Option<SubList>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct XmlAutoNum {
+    #[serde(rename = "@numType", default)]
+    pub num_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct XmlHyperlink {
+    #[serde(rename = "@url", default)]
+    pub url: Option<String>,
+}
+
+/// `hp:t` is mixed content: text interleaved with inline controls such as
+/// `hp:tab`. Tabs become `\t`; other inline controls are dropped, as are the
+/// directional isolates the writer wraps around right-to-left runs.
+fn deserialize_run_text<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    #[derive(Deserialize)]
+    struct RunText {
+        #[serde(rename = "$value", default)]
+        items: Vec<RunTextItem>,
+    }
+
+    #[derive(Deserialize)]
+    enum RunTextItem {
+        #[serde(rename = "$text")]
+        Text(String),
+        #[serde(rename = "tab")]
+        Tab,
+        #[serde(other)]
+        Other,
+    }
+
+    let run_text = RunText::deserialize(deserializer)?;
+    let mut text = String::new();
+    for item in run_text.items {
+        match item {
+            RunTextItem::Text(s) => {
+                text.extend(s.chars().filter(|c| !('\u{2066}'..='\u{2069}').contains(c)))
+            }
+            RunTextItem::Tab => text.push('\t'),
+            RunTextItem::Other => {}
+        }
+    }
+    Ok(Some(text))
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -157,6 +457,9 @@ pub struct SecPr {
     pub text_direction: Option<String>,
     #[serde(rename = "@spaceColumns", default)]
     pub space_columns: Option<u32>,
+    /// Numbering used by OUTLINE headings in this section
+    #[serde(rename = "@outlineShapeIDRef", default)]
+    pub outline_shape_id_ref: Option<u32>,
     #[serde(rename = "pageMargin", default)]
     pub page_margin: Option<PageMargin>,
     #[serde(rename = "pagePr", default)]
@@ -197,6 +500,8 @@ pub struct PagePr {
 pub struct XmlTable {
     #[serde(rename = "@id", default)]
     pub id: Option<u32>,
+    #[serde(rename = "@colCnt", default)]
+    pub col_cnt: Option<u32>,
     #[serde(rename = "tr", default)]
     pub rows: Vec<XmlTableRow>,
 }
@@ -215,6 +520,8 @@ pub struct XmlTableCell {
     pub row_span: Option<u32>,
     #[serde(rename = "cellAddr", default)]
     pub cell_addr: Option<CellAddr>,
+    #[serde(rename = "cellSpan", default)]
+    pub cell_span: Option<CellSpanElement>,
     #[serde(rename = "subList", default)]
     pub sub_list: Option<SubList>,
 }
@@ -227,6 +534,14 @@ pub struct CellAddr {
     pub row_addr: Option<u32>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct CellSpanElement {
+    #[serde(rename = "@colSpan", default)]
+    pub col_span: Option<u32>,
+    #[serde(rename = "@rowSpan", default)]
+    pub row_span: Option<u32>,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct SubList {
     #[serde(rename = "p", default)]
@@ -237,12 +552,22 @@ pub struct SubList {
 pub struct XmlPicture {
     #[serde(rename = "@id", default)]
     pub id: Option<u32>,
+    #[serde(rename = "orgSz", default)]
+    pub org_sz: Option<XmlSize>,
     #[serde(rename = "imgRect", default)]
     pub img_rect: Option<ImgRect>,
     #[serde(rename = "img", default)]
     pub img: Option<Img>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct XmlSize {
+    #[serde(rename = "@width", default)]
+    pub width: Option<u32>,
+    #[serde(rename = "@height", default)]
+    pub height: Option<u32>,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct ImgRect {
     #[serde(rename = "@x", default)]
@@ -261,6 +586,59 @@ pub struct Img {
     pub binary_item_id_ref: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename = "package")]
+pub struct OpfPackage {
+    #[serde(rename = "metadata", default)]
+    pub metadata: Option<OpfMetadata>,
+    #[serde(rename = "manifest", default)]
+    pub manifest: Option<OpfManifest>,
+    #[serde(rename = "spine", default)]
+    pub spine: Option<OpfSpine>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpfManifest {
+    #[serde(rename = "item", default)]
+    pub items: Vec<OpfItem>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpfItem {
+    #[serde(rename = "@id", default)]
+    pub id: String,
+    #[serde(rename = "@href", default)]
+    pub href: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpfSpine {
+    #[serde(rename = "itemref", default)]
+    pub item_refs: Vec<OpfItemRef>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpfItemRef {
+    #[serde(rename = "@idref", default)]
+    pub idref: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpfMetadata {
+    #[serde(rename = "title", default)]
+    pub title: Option<String>,
+    #[serde(rename = "meta", default)]
+    pub metas: Vec<OpfMeta>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OpfMeta {
+    #[serde(rename = "@name", default)]
+    pub name: Option<String>,
+    #[serde(rename = "$text", default)]
+    pub value: Option<String>,
+}
+
 pub fn parse_version(xml: &str) -> Result<HcfVersion, quick_xml::DeError> {
     from_str(xml)
 }
@@ -269,7 +647,192 @@ pub fn parse_head(xml: &str) -> Result<Head, quick_xml::DeError> {
     from_str(xml)
 }
 
+/// Like [`parse_head`], decoding straight from the archive entry
+pub fn parse_head_from_reader<R: BufRead>(reader: R) -> Result<Head, quick_xml::DeError> {
+    from_reader(reader)
+}
+
 pub fn parse_section(xml: &str) -> Result<Section, quick_xml::DeError> {
+    let mut escaper = EdgeSpaceEscaper::default();
+    let mut escaped = Vec::with_capacity(xml.len());
+    for &byte in xml.as_bytes() {
+        escaper.push(byte, &mut escaped);
+    }
+    escaper.finish(&mut escaped);
+    from_str(&String::from_utf8(escaped).expect("only ASCII spaces are rewritten"))
+}
+
+/// Like [`parse_section`], decoding straight from the archive entry
+pub fn parse_section_from_reader<R: BufRead>(reader: R) -> Result<Section, quick_xml::DeError> {
+    from_reader(BufReader::new(KeepEdgeSpaces {
+        inner: reader,
+        escaper: EdgeSpaceEscaper::default(),
+        out: Vec::new(),
+        pos: 0,
+        eof: false,
+    }))
+}
+
+/// Rewrites spaces at the edges of `t` text as `&#32;`.
+///
+/// quick-xml's deserializer trims text content, which drops code indentation
+/// and the space between differently styled runs ("이번 주 " + "핵심"). It
+/// trims the raw bytes before unescaping, so character references survive.
+/// Only text directly inside `t` elements is touched; whitespace-only text is
+/// left alone.
+#[derive(Default)]
+struct EdgeSpaceEscaper {
+    in_tag: bool,
+    /// Name of the tag being read, up to the first space
+    tag: Vec<u8>,
+    tag_name_done: bool,
+    in_t: bool,
+    /// Whether the current text node has non-space content yet
+    text_started: bool,
+    pending_spaces: usize,
+}
+
+impl EdgeSpaceEscaper {
+    fn push(&mut self, byte: u8, out: &mut Vec<u8>) {
+        if self.in_tag {
+            if byte == b'>' {
+                self.in_tag = false;
+                self.end_tag();
+            } else if !self.tag_name_done {
+                if byte.is_ascii_whitespace() {
+                    self.tag_name_done = true;
+                } else {
+                    self.tag.push(byte);
+                }
+            }
+            out.push(byte);
+            return;
+        }
+
+        match byte {
+            b'<' => {
+                // trailing spaces before the next tag, unless the text is only spaces
+                self.flush_spaces(self.text_started, out);
+                self.in_tag = true;
+                self.tag.clear();
+                self.tag_name_done = false;
+                self.text_started = false;
+                out.push(byte);
+            }
+            b' ' if self.in_t => self.pending_spaces += 1,
+            b'\t' | b'\n' | b'\r' => {
+                self.flush_spaces(false, out);
+                out.push(byte);
+            }
+            _ => {
+                // leading spaces before the first content are escaped, inner ones are kept
+                self.flush_spaces(!self.text_started, out);
+                self.text_started = true;
+                out.push(byte);
+            }
+        }
+    }
+
+    fn finish(&mut self, out: &mut Vec<u8>) {
+        self.flush_spaces(false, out);
+    }
+
+    fn end_tag(&mut self) {
+        let name = self.tag.strip_suffix(b"/").unwrap_or(&self.tag);
+        let is_t = |name: &[u8]| name == b"t" || name.ends_with(b":t");
+        if let Some(closing) = name.strip_prefix(b"/") {
+            if is_t(closing) {
+                self.in_t = false;
+            }
+        } else if is_t(name) && !self.tag.ends_with(b"/") {
+            self.in_t = true;
+        }
+    }
+
+    fn flush_spaces(&mut self, escape: bool, out: &mut Vec<u8>) {
+        let space: &[u8] = if escape { b"&#32;" } else { b" " };
+        for _ in 0..std::mem::take(&mut self.pending_spaces) {
+            out.extend_from_slice(space);
+        }
+    }
+}
+
+/// [`EdgeSpaceEscaper`] over a reader
+struct KeepEdgeSpaces<R> {
+    inner: R,
+    escaper: EdgeSpaceEscaper,
+    out: Vec<u8>,
+    pos: usize,
+    eof: bool,
+}
+
+impl<R: BufRead> Read for KeepEdgeSpaces<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.pos == self.out.len() && !self.eof {
+            self.out.clear();
+            self.pos = 0;
+            let chunk = self.inner.fill_buf()?;
+            if chunk.is_empty() {
+                self.escaper.finish(&mut self.out);
+                self.eof = true;
+                break;
+            }
+            for &byte in chunk {
+                self.escaper.push(byte, &mut self.out);
+            }
+            let len = chunk.len();
+            self.inner.consume(len);
+        }
+        let len = (self.out.len() - self.pos).min(buf.len());
+        buf[..len].copy_from_slice(&self.out[self.pos..self.pos + len]);
+        self.pos += len;
+        Ok(len)
+    }
+}
+
+/// Text of the first `limit` top-level paragraphs of a section, read event by
+/// event so the rest of the file is neither parsed nor read. Paragraphs nested in
+/// tables or other sub-lists are skipped.
+pub fn leading_paragraph_texts<R: BufRead>(
+    reader: R,
+    limit: usize,
+) -> Result<Vec<String>, quick_xml::Error> {
+    use quick_xml::events::Event;
+
+    let mut reader = quick_xml::Reader::from_reader(reader);
+    let mut buf = Vec::new();
+    let mut texts = Vec::new();
+    let mut current = String::new();
+    let mut p_depth = 0usize;
+    let mut in_text = false;
+    while texts.len() < limit {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(e) => match e.local_name().as_ref() {
+                b"p" => p_depth += 1,
+                b"t" if p_depth == 1 => in_text = true,
+                _ => {}
+            },
+            Event::Empty(e) if in_text && e.local_name().as_ref() == b"tab" => current.push('\t'),
+            Event::Text(t) if in_text => current.push_str(&t.unescape()?),
+            Event::End(e) => match e.local_name().as_ref() {
+                b"t" => in_text = false,
+                b"p" => {
+                    p_depth = p_depth.saturating_sub(1);
+                    if p_depth == 0 {
+                        texts.push(std::mem::take(&mut current));
+                    }
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    Ok(texts)
+}
+
+pub fn parse_content_hpf(xml: &str) -> Result<OpfPackage, quick_xml::DeError> {
     from_str(xml)
 }
 
@@ -305,4 +868,27 @@ mod tests {
             Some("Hello World".to_string())
         );
     }
+
+    #[test]
+    fn test_parse_section_keeps_text_edge_spaces() {
+        let xml = "<sec>\n  <p id=\"0\">\n    <run><t>    indented </t></run>\n    \
+                   <run><t>  </t></run><run><t>a  b</t></run>\n  </p>\n</sec>";
+        let texts = |section: Section| -> Vec<Option<String>> {
+            section.paragraphs[0]
+                .runs
+                .iter()
+                .map(|r| r.text.clone())
+                .collect()
+        };
+        let expected = vec![
+            Some("    indented ".to_string()),
+            Some(String::new()),
+            Some("a  b".to_string()),
+        ];
+        assert_eq!(texts(parse_section(xml).unwrap()), expected);
+        let from_reader =
+            parse_section_from_reader(std::io::BufReader::with_capacity(3, xml.as_bytes()))
+                .unwrap();
+        assert_eq!(texts(from_reader), expected);
+    }
 }
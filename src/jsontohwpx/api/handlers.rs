@@ -1,8 +1,9 @@
 use std::sync::Arc;
 
-use axum::extract::{Path, State};
-use axum::http::{header, StatusCode};
-use axum::response::IntoResponse;
+use axum::body::Body;
+use axum::extract::{Path, Query, State};
+use axum::http::{header, HeaderName, StatusCode};
+use axum::response::{AppendHeaders, IntoResponse};
 use axum::Json;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -11,11 +12,18 @@ use uuid::Uuid;
 use super::jobs::{AsyncConvertResponse, JobResponse, JobStats, JobStatus};
 use super::queue::ConvertJob;
 use super::AppState;
-use crate::jsontohwpx::{self, ApiResponse, JsonToHwpxError};
+use crate::jsontohwpx::model::OnError;
+use crate::jsontohwpx::{self, ApiResponse, ConvertWarning, JsonToHwpxError};
+
+/// 동기 변환의 경고를 담는 응답 헤더 (`ConvertWarning` JSON 배열)
+pub const WARNINGS_HEADER: &str = "x-conversion-warnings";
 
 // --- 요청/응답 스키마 ---
 
 /// 변환 요청 바디 (OpenAPI 문서용)
+///
+/// 게시판 API 응답 모양 외에 평평한 문서 모양
+/// (`{"article_id", "title", "metadata": {"author", "department", "created_at"}, "contents"}`)도 받는다.
 #[derive(Deserialize, ToSchema)]
 #[schema(example = json!({
     "responseCode": "0",
@@ -30,6 +38,9 @@ use crate::jsontohwpx::{self, ApiResponse, JsonToHwpxError};
     }
 }))]
 pub struct ConvertRequest {
+    /// 입력 스키마 버전 (예: "1.2", 없으면 현재 버전)
+    #[serde(rename = "schemaVersion", default)]
+    pub schema_version: Option<String>,
     /// 응답 코드 ("0"이면 정상)
     #[serde(rename = "responseCode")]
     pub response_code: String,
@@ -40,6 +51,95 @@ pub struct ConvertRequest {
     pub data: serde_json::Value,
 }
 
+/// 변환 쿼리 파라미터
+#[derive(Deserialize, Default)]
+pub struct ConvertQuery {
+    /// 콘텐츠 변환 실패 처리 ("fail", "skip"). JSON의 options.onError보다 우선
+    pub on_error: Option<String>,
+    /// 그림을 읽지 않고 "[이미지: URL]" 단락으로 대신. JSON의 options.skipImages보다 우선
+    pub skip_images: Option<bool>,
+    /// 문서 끝에 생성 시각과 hwpers 버전 단락 추가. JSON의 options.includeProvenance보다 우선
+    pub include_provenance: Option<bool>,
+}
+
+impl ConvertQuery {
+    /// 쿼리를 입력 옵션에 적용
+    fn apply(&self, input: &mut ApiResponse) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+        if let Some(skip_images) = self.skip_images {
+            input.options.skip_images = skip_images;
+        }
+        if let Some(include_provenance) = self.include_provenance {
+            input.options.include_provenance = include_provenance;
+        }
+        let Some(value) = &self.on_error else {
+            return Ok(());
+        };
+        match OnError::parse(value) {
+            Some(on_error) => {
+                input.options.on_error = on_error;
+                Ok(())
+            }
+            None => {
+                let e = JsonToHwpxError::Input(format!(
+                    "on_error는 fail, skip 중 하나여야 합니다 (on_error={})",
+                    value
+                ));
+                let resp = ErrorResponse {
+                    error: ErrorDetail {
+                        code: e.error_code().to_string(),
+                        message: e.to_string(),
+                        details: Vec::new(),
+                    },
+                };
+                Err((StatusCode::BAD_REQUEST, Json(resp)))
+            }
+        }
+    }
+}
+
+/// 입력 검증. 콘텐츠 문제는 `details`에 콘텐츠마다 하나씩 담는다
+fn validate_input(input: &ApiResponse) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    let Err(e) = input.validate() else {
+        return Ok(());
+    };
+    let details = match input.validate_document() {
+        Ok(()) => input
+            .content_issues()
+            .into_iter()
+            .map(|issue| ErrorItem {
+                path: format!("contents/{}", issue.content_index),
+                message: issue.message,
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    let resp = ErrorResponse {
+        error: ErrorDetail {
+            code: e.error_code().to_string(),
+            message: e.to_string(),
+            details,
+        },
+    };
+    Err((StatusCode::BAD_REQUEST, Json(resp)))
+}
+
+/// 경고 목록을 헤더 값으로 쓸 수 있게 ASCII JSON 배열로 (한글은 \uXXXX)
+fn warnings_header_value(warnings: &[ConvertWarning]) -> String {
+    let json = serde_json::to_string(warnings).unwrap_or_default();
+    let mut out = String::with_capacity(json.len());
+    for c in json.chars() {
+        if c.is_ascii() {
+            out.push(c);
+        } else {
+            let mut units = [0u16; 2];
+            for unit in c.encode_utf16(&mut units) {
+                out.push_str(&format!("\\u{:04x}", unit));
+            }
+        }
+    }
+    out
+}
+
 /// 에러 응답 구조
 #[derive(Serialize, ToSchema)]
 #[schema(example = json!({
@@ -58,7 +158,7 @@ pub struct ErrorDetail {
     pub code: String,
     /// 에러 메시지
     pub message: String,
-    /// 상세 에러 목록
+    /// 상세 에러 목록 (입력 검증 실패 시 콘텐츠별 문제)
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub details: Vec<ErrorItem>,
 }
@@ -73,10 +173,13 @@ pub struct ErrorItem {
 
 /// validate 응답 구조
 #[derive(Serialize, ToSchema)]
-#[schema(example = json!({ "valid": true, "errors": [] }))]
+#[schema(example = json!({ "valid": true, "schemaVersion": "1.2" }))]
 pub struct ValidateResponse {
     /// 유효성 결과
     pub valid: bool,
+    /// 확인한 입력 스키마 버전 (schemaVersion이 없으면 현재 버전)
+    #[serde(rename = "schemaVersion", skip_serializing_if = "Option::is_none")]
+    pub schema_version: Option<String>,
     /// 에러 목록 (유효하지 않은 경우)
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub errors: Vec<String>,
@@ -118,9 +221,15 @@ pub struct WorkerInfo {
 /// JSON을 HWPX 문서로 변환 (동기)
 ///
 /// JSON API 응답을 받아 HWPX(한글 문서) 바이너리 파일로 변환하여 즉시 반환합니다.
+/// `on_error=skip`이면 실패한 콘텐츠를 건너뛰고 경고를 `X-Conversion-Warnings` 헤더로 돌려줍니다.
 #[utoipa::path(
     post,
     path = "/api/v1/convert",
+    params(
+        ("on_error" = Option<String>, Query, description = "콘텐츠 변환 실패 처리 (fail, skip)"),
+        ("skip_images" = Option<bool>, Query, description = "그림 대신 \"[이미지: URL]\" 단락 삽입"),
+        ("include_provenance" = Option<bool>, Query, description = "문서 끝에 생성 시각과 hwpers 버전 단락 추가"),
+    ),
     request_body(content = ConvertRequest, content_type = "application/json"),
     responses(
         (status = 200, description = "변환 성공 (HWPX 바이너리)", content_type = "application/vnd.hancom.hwpx"),
@@ -131,9 +240,10 @@ pub struct WorkerInfo {
 )]
 pub async fn convert(
     State(state): State<Arc<AppState>>,
+    Query(query): Query<ConvertQuery>,
     body: String,
-) -> Result<impl IntoResponse, impl IntoResponse> {
-    let input: ApiResponse = serde_json::from_str(&body).map_err(|e| {
+) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let mut input: ApiResponse = serde_json::from_str(&body).map_err(|e| {
         let resp = ErrorResponse {
             error: ErrorDetail {
                 code: "INVALID_JSON".to_string(),
@@ -143,65 +253,72 @@ pub async fn convert(
         };
         (StatusCode::BAD_REQUEST, Json(resp))
     })?;
+    query.apply(&mut input)?;
+    input.options.limits = state.limits;
 
-    if let Err(e) = input.validate() {
-        let resp = ErrorResponse {
-            error: ErrorDetail {
-                code: e.error_code().to_string(),
-                message: e.to_string(),
-                details: Vec::new(),
-            },
-        };
-        return Err((StatusCode::BAD_REQUEST, Json(resp)));
-    }
+    validate_input(&input)?;
 
-    let atcl_id = input.data.article.atcl_id.trim().to_string();
+    let filename = input.output_filename();
     let base_path = state.base_path.clone();
 
     // spawn_blocking으로 감싸서 blocking reqwest와 tokio 런타임 충돌 방지
-    let convert_result = tokio::task::spawn_blocking(move || {
-        jsontohwpx::convert(&input, &base_path)
-    })
-    .await
-    .map_err(|e| {
-        let resp = ErrorResponse {
-            error: ErrorDetail {
-                code: "INTERNAL_ERROR".to_string(),
-                message: format!("변환 작업 실행 실패: {}", e),
-                details: Vec::new(),
-            },
-        };
-        (StatusCode::INTERNAL_SERVER_ERROR, Json(resp))
-    })?;
-
-    let bytes = convert_result.map_err(|e| {
+    let convert_result =
+        tokio::task::spawn_blocking(move || jsontohwpx::convert_to_output(&input, &base_path))
+            .await
+            .map_err(|e| {
+                let resp = ErrorResponse {
+                    error: ErrorDetail {
+                        code: "INTERNAL_ERROR".to_string(),
+                        message: format!("변환 작업 실행 실패: {}", e),
+                        details: Vec::new(),
+                    },
+                };
+                (StatusCode::INTERNAL_SERVER_ERROR, Json(resp))
+            })?;
+
+    let output = convert_result.map_err(|e| {
         let (status, code) = match &e {
-            JsonToHwpxError::Input(_) => (StatusCode::BAD_REQUEST, e.error_code()),
+            JsonToHwpxError::Input(_)
+            | JsonToHwpxError::Encrypted(_)
+            | JsonToHwpxError::LimitExceeded(..) => (StatusCode::BAD_REQUEST, e.error_code()),
             _ => (StatusCode::INTERNAL_SERVER_ERROR, e.error_code()),
         };
+        // 콘텐츠 하나의 변환 실패면 어느 콘텐츠인지 details에 담는다
+        let details = e
+            .content_index()
+            .map(|idx| ErrorItem {
+                path: format!("contents/{}", idx),
+                message: e.to_string(),
+            })
+            .into_iter()
+            .collect();
         let resp = ErrorResponse {
             error: ErrorDetail {
                 code: code.to_string(),
                 message: e.to_string(),
-                details: Vec::new(),
+                details,
             },
         };
         (status, Json(resp))
     })?;
 
-    let filename = format!("{}.hwpx", atcl_id);
     let headers = [
         (
             header::CONTENT_TYPE,
             "application/vnd.hancom.hwpx".to_string(),
         ),
-        (
-            header::CONTENT_DISPOSITION,
-            format!("attachment; filename=\"{}\"", filename),
-        ),
+        (header::CONTENT_DISPOSITION, content_disposition(&filename)),
     ];
+    let warning_headers = if output.warnings.is_empty() {
+        Vec::new()
+    } else {
+        vec![(
+            HeaderName::from_static(WARNINGS_HEADER),
+            warnings_header_value(&output.warnings),
+        )]
+    };
 
-    Ok((headers, bytes))
+    Ok((headers, AppendHeaders(warning_headers), output.bytes))
 }
 
 /// JSON을 HWPX 문서로 변환 (비동기)
@@ -211,6 +328,11 @@ pub async fn convert(
 #[utoipa::path(
     post,
     path = "/api/v1/convert/async",
+    params(
+        ("on_error" = Option<String>, Query, description = "콘텐츠 변환 실패 처리 (fail, skip)"),
+        ("skip_images" = Option<bool>, Query, description = "그림 대신 \"[이미지: URL]\" 단락 삽입"),
+        ("include_provenance" = Option<bool>, Query, description = "문서 끝에 생성 시각과 hwpers 버전 단락 추가"),
+    ),
     request_body(content = ConvertRequest, content_type = "application/json"),
     responses(
         (status = 202, description = "작업 등록 완료", body = AsyncConvertResponse),
@@ -221,9 +343,10 @@ pub async fn convert(
 )]
 pub async fn convert_async(
     State(state): State<Arc<AppState>>,
+    Query(query): Query<ConvertQuery>,
     body: String,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
-    let input: ApiResponse = serde_json::from_str(&body).map_err(|e| {
+    let mut input: ApiResponse = serde_json::from_str(&body).map_err(|e| {
         let resp = ErrorResponse {
             error: ErrorDetail {
                 code: "INVALID_JSON".to_string(),
@@ -233,17 +356,10 @@ pub async fn convert_async(
         };
         (StatusCode::BAD_REQUEST, Json(resp))
     })?;
+    query.apply(&mut input)?;
+    input.options.limits = state.limits;
 
-    if let Err(e) = input.validate() {
-        let resp = ErrorResponse {
-            error: ErrorDetail {
-                code: e.error_code().to_string(),
-                message: e.to_string(),
-                details: Vec::new(),
-            },
-        };
-        return Err((StatusCode::BAD_REQUEST, Json(resp)));
-    }
+    validate_input(&input)?;
 
     let job_id = Uuid::new_v4().to_string();
     let job = state.job_store.create_job(job_id.clone()).await;
@@ -317,6 +433,7 @@ pub async fn get_job(
         completed_at: job.completed_at,
         download_url,
         error: job.error_message,
+        warnings: job.warnings,
     };
 
     Ok(Json(resp))
@@ -372,36 +489,68 @@ pub async fn download_job(
         (StatusCode::NOT_FOUND, Json(resp))
     })?;
 
-    let bytes = tokio::fs::read(&file_path).await.map_err(|e| {
+    // 파일 전체를 메모리에 올리지 않고 읽는 대로 응답 본문으로 흘려보낸다
+    let file = tokio::fs::File::open(&file_path).await.map_err(|e| {
         let resp = ErrorResponse {
             error: ErrorDetail {
                 code: "IO_ERROR".to_string(),
-                message: format!("파일 읽기 실패: {}", e),
+                message: format!("파일 열기 실패: {}", e),
                 details: Vec::new(),
             },
         };
         (StatusCode::INTERNAL_SERVER_ERROR, Json(resp))
     })?;
 
-    let atcl_id = job.atcl_id.unwrap_or_else(|| id.clone());
-    let filename = format!("{}.hwpx", atcl_id);
+    let filename = job.file_name.unwrap_or_else(|| format!("{}.hwpx", id));
     let headers = [
         (
             header::CONTENT_TYPE,
             "application/vnd.hancom.hwpx".to_string(),
         ),
-        (
-            header::CONTENT_DISPOSITION,
-            format!("attachment; filename=\"{}\"", filename),
-        ),
+        (header::CONTENT_DISPOSITION, content_disposition(&filename)),
     ];
 
-    Ok((headers, bytes))
+    Ok((
+        headers,
+        Body::from_stream(tokio_util::io::ReaderStream::new(file)),
+    ))
+}
+
+/// 다운로드 파일 이름 헤더. ASCII가 아닌 이름(한글 제목 등)은 `filename`에 `_`로 바꾼
+/// 이름을 두고 원래 이름을 `filename*` (RFC 6266)로 함께 보낸다.
+fn content_disposition(filename: &str) -> String {
+    let fallback: String = filename
+        .chars()
+        .map(|c| match c {
+            ' ' => ' ',
+            '"' | '\\' => '_',
+            c if c.is_ascii_graphic() => c,
+            _ => '_',
+        })
+        .collect();
+    if fallback == filename {
+        return format!("attachment; filename=\"{}\"", filename);
+    }
+
+    let encoded: String = filename
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect();
+    format!(
+        "attachment; filename=\"{}\"; filename*=UTF-8''{}",
+        fallback, encoded
+    )
 }
 
 /// JSON 입력 유효성 검증
 ///
 /// JSON 데이터의 구조와 필수 필드를 검증합니다. 변환은 수행하지 않습니다.
+/// 콘텐츠 문제(빈 표, 원본이 없는 그림, 디코딩할 수 없는 base64 등)는 `contents[n]: 메시지`로 모두 돌려줍니다.
 #[utoipa::path(
     post,
     path = "/api/v1/validate",
@@ -417,28 +566,29 @@ pub async fn validate(body: String) -> impl IntoResponse {
         Err(e) => {
             let resp = ValidateResponse {
                 valid: false,
+                schema_version: None,
                 errors: vec![format!("JSON 파싱 실패: {}", e)],
             };
             return (StatusCode::OK, Json(resp));
         }
     };
 
-    match input.validate() {
-        Ok(()) => {
-            let resp = ValidateResponse {
-                valid: true,
-                errors: Vec::new(),
-            };
-            (StatusCode::OK, Json(resp))
-        }
-        Err(e) => {
-            let resp = ValidateResponse {
-                valid: false,
-                errors: vec![e.to_string()],
-            };
-            (StatusCode::OK, Json(resp))
-        }
-    }
+    let schema_version = input.schema_version().ok().map(|v| v.to_string());
+    // 문서 단위 문제가 없으면 콘텐츠 문제를 모두 모은다
+    let errors: Vec<String> = match input.validate_document() {
+        Ok(()) => input
+            .content_issues()
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        Err(e) => vec![e.to_string()],
+    };
+    let resp = ValidateResponse {
+        valid: errors.is_empty(),
+        schema_version,
+        errors,
+    };
+    (StatusCode::OK, Json(resp))
 }
 
 /// 서버 상태 확인
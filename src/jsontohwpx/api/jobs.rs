@@ -7,6 +7,8 @@ use serde::Serialize;
 use tokio::sync::RwLock;
 use utoipa::ToSchema;
 
+use crate::jsontohwpx::ConvertWarning;
+
 /// 작업 상태
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
@@ -25,8 +27,13 @@ pub struct Job {
     pub created_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
     pub file_path: Option<PathBuf>,
-    pub atcl_id: Option<String>,
+    /// 내려받을 때의 파일 이름 (filenameTemplate으로 만든 이름)
+    pub file_name: Option<String>,
     pub error_message: Option<String>,
+    /// 변환은 되었지만 건너뛴 콘텐츠 등의 경고
+    pub warnings: Vec<ConvertWarning>,
+    /// 변환한 문서의 본문 텍스트 (미리보기용)
+    pub text: Option<String>,
 }
 
 /// 작업 상태 조회 응답
@@ -51,6 +58,9 @@ pub struct JobResponse {
     pub download_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[schema(value_type = Vec<Object>)]
+    pub warnings: Vec<ConvertWarning>,
 }
 
 /// 비동기 변환 요청 응답
@@ -95,8 +105,10 @@ impl JobStore {
             created_at: Utc::now(),
             completed_at: None,
             file_path: None,
-            atcl_id: None,
+            file_name: None,
             error_message: None,
+            warnings: Vec::new(),
+            text: None,
         };
         self.jobs.write().await.insert(id, job.clone());
         job
@@ -110,12 +122,21 @@ impl JobStore {
     }
 
     /// 작업 완료 처리
-    pub async fn set_completed(&self, id: &str, file_path: PathBuf, atcl_id: String) {
+    pub async fn set_completed(
+        &self,
+        id: &str,
+        file_path: PathBuf,
+        file_name: String,
+        warnings: Vec<ConvertWarning>,
+        text: String,
+    ) {
         if let Some(job) = self.jobs.write().await.get_mut(id) {
             job.status = JobStatus::Completed;
             job.completed_at = Some(Utc::now());
             job.file_path = Some(file_path);
-            job.atcl_id = Some(atcl_id);
+            job.file_name = Some(file_name);
+            job.warnings = warnings;
+            job.text = Some(text);
         }
     }
 
@@ -19,6 +19,8 @@ use handlers::{
 };
 use jobs::{AsyncConvertResponse, JobResponse, JobStats, JobStatus};
 
+use super::ConvertLimits;
+
 /// OpenAPI 문서 정의
 #[derive(OpenApi)]
 #[openapi(
@@ -65,6 +67,8 @@ pub struct AppState {
     pub output_dir: PathBuf,
     pub job_store: jobs::JobStore,
     pub queue: queue::JobQueue,
+    /// 모든 변환에 적용할 한도
+    pub limits: ConvertLimits,
 }
 
 /// API 서버 설정
@@ -76,6 +80,7 @@ pub struct ServerConfig {
     pub output_dir: PathBuf,
     pub worker_count: u64,
     pub file_expiry_hours: u64,
+    pub limits: ConvertLimits,
 }
 
 impl Default for ServerConfig {
@@ -88,6 +93,7 @@ impl Default for ServerConfig {
             output_dir: PathBuf::from("./output"),
             worker_count: 4,
             file_expiry_hours: 24,
+            limits: ConvertLimits::DEFAULT,
         }
     }
 }
@@ -126,6 +132,26 @@ impl ServerConfig {
                 config.file_expiry_hours = h;
             }
         }
+        if let Ok(count) = std::env::var("MAX_CONTENTS") {
+            if let Ok(c) = count.parse() {
+                config.limits.max_contents = c;
+            }
+        }
+        if let Ok(count) = std::env::var("MAX_TABLE_CELLS") {
+            if let Ok(c) = count.parse() {
+                config.limits.max_table_cells = c;
+            }
+        }
+        if let Ok(size) = std::env::var("MAX_TOTAL_IMAGE_BYTES") {
+            if let Ok(s) = size.parse() {
+                config.limits.max_total_image_bytes = s;
+            }
+        }
+        if let Ok(count) = std::env::var("MAX_PARAGRAPHS") {
+            if let Ok(c) = count.parse() {
+                config.limits.max_paragraphs = c;
+            }
+        }
 
         config
     }
@@ -142,6 +168,7 @@ pub fn build_state(config: &ServerConfig) -> Arc<AppState> {
         output_dir: config.output_dir.clone(),
         job_store,
         queue,
+        limits: config.limits,
     })
 }
 
@@ -101,13 +101,14 @@ async fn process_job(store: &JobStore, job: ConvertJob, worker_id: u64) {
     let jid = job_id.clone();
 
     let result = tokio::task::spawn_blocking(move || {
-        let atcl_id = input.data.article.atcl_id.trim().to_string();
-        match jsontohwpx::convert(&input, &base_path) {
-            Ok(bytes) => {
-                let file_path = output_dir.join(format!("{}.hwpx", jid));
+        let file_name = input.output_filename();
+        match jsontohwpx::convert_to_output(&input, &base_path) {
+            Ok(output) => {
+                // 같은 이름의 작업이 겹치지 않도록 작업 ID를 앞에 붙인다
+                let file_path = output_dir.join(format!("{}_{}", jid, file_name));
                 std::fs::create_dir_all(&output_dir).ok();
-                match std::fs::write(&file_path, bytes) {
-                    Ok(()) => Ok((file_path, atcl_id)),
+                match std::fs::write(&file_path, output.bytes) {
+                    Ok(()) => Ok((file_path, file_name, output.warnings, output.text)),
                     Err(e) => Err(format!("파일 저장 실패: {}", e)),
                 }
             }
@@ -117,8 +118,10 @@ async fn process_job(store: &JobStore, job: ConvertJob, worker_id: u64) {
     .await;
 
     match result {
-        Ok(Ok((file_path, atcl_id))) => {
-            store.set_completed(&job_id, file_path, atcl_id).await;
+        Ok(Ok((file_path, file_name, warnings, text))) => {
+            store
+                .set_completed(&job_id, file_path, file_name, warnings, text)
+                .await;
             tracing::info!(worker_id, job_id = %job_id, "작업 완료");
         }
         Ok(Err(e)) => {
@@ -0,0 +1,143 @@
+use std::path::Path;
+
+use crate::hwpx::HwpxWriter;
+
+use super::converter::{
+    add_article, add_provenance, convert_article, finish, new_writer, normalize_reg_dt,
+    setup_document, ConvertOutput, ConvertWarning, CONTENTS_MARKER,
+};
+use super::error::{JsonToHwpxError, Result};
+use super::model::{ApiResponse, Options};
+use super::text;
+
+/// 여러 글을 변환하는 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchMode {
+    /// 한 HWPX 문서로 합친다. 글 사이에 쪽을 나누고, `headings`면 글마다
+    /// 제목(subject)을 titleStyle 대신 1수준 제목으로 먼저 넣는다
+    Combined { headings: bool },
+    /// 글마다 HWPX 문서를 따로 만든다
+    Separate,
+}
+
+/// 여러 글의 변환 결과
+#[derive(Debug, Clone)]
+pub enum BatchOutput {
+    /// 합친 문서 하나. 경고에는 글의 atclId가 붙는다
+    Combined(ConvertOutput),
+    /// 입력 순서대로 (atclId, 문서)
+    Separate(Vec<(String, ConvertOutput)>),
+}
+
+/// 여러 글을 한 번에 HWPX로 변환
+///
+/// 각 입력의 `options` 대신 `options`를 모든 글에 쓰고, 입력 검증도
+/// [`ApiResponse::validate_with`]로 `options`에 맞춰 한다 (입력의 `options`는 보지 않음).
+/// 합친 문서의 메타데이터와 머리말/꼬리말 템플릿은 첫 글의 값으로 채우고, HWPX
+/// 템플릿이 있으면 모든 글을 그 `{{CONTENTS}}` 자리에 차례로 넣는다. 입력 검증에
+/// 실패하면 몇 번째 글인지(`articles[n]`)와 함께 `Input` 에러를 돌려준다.
+///
+/// 입력은 글만이 아니라 ApiResponse 전체(`&[ApiResponse]`)를 받고, 따로 만든 문서는
+/// `(atclId, 바이트)` 대신 경고와 본문 텍스트도 담은 `(atclId, ConvertOutput)`으로 돌려준다.
+pub fn convert_batch(
+    inputs: &[ApiResponse],
+    options: &Options,
+    base_path: &Path,
+    mode: BatchMode,
+) -> Result<BatchOutput> {
+    if inputs.is_empty() {
+        return Err(JsonToHwpxError::Input("변환할 글이 없습니다".to_string()));
+    }
+    options.validate()?;
+    for (idx, input) in inputs.iter().enumerate() {
+        input.validate_with(options).map_err(|e| match e {
+            JsonToHwpxError::Input(msg) => {
+                JsonToHwpxError::Input(format!("articles[{}]: {}", idx, msg))
+            }
+            e => e,
+        })?;
+    }
+
+    match mode {
+        BatchMode::Separate => inputs
+            .iter()
+            .map(|input| {
+                let article = &input.data.article;
+                let output = convert_article(article, options, base_path)?;
+                Ok((article.atcl_id.trim().to_string(), output))
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(BatchOutput::Separate),
+        BatchMode::Combined { headings } => {
+            combine(inputs, options, base_path, headings).map(BatchOutput::Combined)
+        }
+    }
+}
+
+fn combine(
+    inputs: &[ApiResponse],
+    options: &Options,
+    base_path: &Path,
+    headings: bool,
+) -> Result<ConvertOutput> {
+    let mut writer = new_writer(options, base_path)?;
+    let body_size = options.default_font_size.unwrap_or(10);
+
+    // 글마다 (dateFormat으로 맞춘 작성일시, 경고)
+    let mut articles: Vec<(Option<String>, Vec<ConvertWarning>)> = inputs
+        .iter()
+        .map(|input| {
+            let mut warnings = Vec::new();
+            let reg_dt = normalize_reg_dt(&input.data.article, options, &mut warnings);
+            (reg_dt, warnings)
+        })
+        .collect();
+
+    let (reg_dt, warnings) = &mut articles[0];
+    setup_document(
+        &mut writer,
+        &inputs[0].data.article,
+        options,
+        reg_dt.as_deref(),
+        warnings,
+    )?;
+
+    let mut body = |writer: &mut HwpxWriter| -> Result<()> {
+        for (idx, (input, (reg_dt, warnings))) in inputs.iter().zip(&mut articles).enumerate() {
+            let article = &input.data.article;
+            if idx > 0 {
+                writer.add_page_break()?;
+            }
+            if headings && !article.subject.trim().is_empty() {
+                text::add_heading(writer, &article.subject, 1, body_size)?;
+            }
+            add_article(
+                writer,
+                article,
+                options,
+                reg_dt.as_deref(),
+                base_path,
+                !headings,
+                warnings,
+            )?;
+        }
+        Ok(())
+    };
+    if !writer.insert_at_marker(CONTENTS_MARKER, &mut body)? {
+        body(&mut writer)?;
+    }
+    add_provenance(&mut writer, options)?;
+
+    let warnings = inputs
+        .iter()
+        .zip(articles)
+        .flat_map(|(input, (_, warnings))| {
+            let id = input.data.article.atcl_id.trim().to_string();
+            warnings.into_iter().map(move |mut warning| {
+                warning.article_id = Some(id.clone());
+                warning
+            })
+        })
+        .collect();
+    finish(writer, warnings)
+}
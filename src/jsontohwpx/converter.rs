@@ -1,20 +1,232 @@
-use std::path::Path;
+use std::borrow::Cow;
+use std::fmt;
+use std::path::{Path, PathBuf};
 
-use crate::hwpx::{HwpxMetadata, HwpxTextStyle, HwpxWriter, StyledText};
+use serde::Serialize;
 
-use super::error::Result;
-use super::image;
-use super::model::{ApiResponse, Content};
+use crate::error::HwpError;
+use crate::hwpx::{
+    HwpxAlignment, HwpxFooter, HwpxMetadata, HwpxParagraphStyle, HwpxTable, HwpxTextStyle,
+    HwpxWriter, PageNumberFormat, StyledText,
+};
+use crate::model::page_layout::mm_to_hwp_units;
+use crate::model::{PageLayout, PageOrientation, PaperSize};
+
+use super::error::{ConvertLimit, JsonToHwpxError, Result};
+use super::file;
+use super::html;
+use super::image::{self, ImageOptions};
+use super::markdown;
+use super::model::{
+    parse_page_size, ApiResponse, Article, Attachment, Comment, Content, ContentStyle,
+    ConvertLimits, OnError, Options, PageNumberStyle, TableContent, TextValue, TitleStyle,
+};
 use super::table;
+use super::template;
 use super::text;
 
-/// JSON ApiResponse를 HWPX 바이트로 변환
+/// 변환 결과
+#[derive(Debug, Clone)]
+pub struct ConvertOutput {
+    /// HWPX 파일 바이트
+    pub bytes: Vec<u8>,
+    /// 변환은 끝냈지만 알려야 할 문제 (나온 순서대로)
+    pub warnings: Vec<ConvertWarning>,
+    /// 본문 텍스트 (단락, 표 셀, 캡션). 색인용으로 문서를 다시 읽지 않도록
+    /// 변환하면서 모은다 ([`HwpxWriter::extract_text`])
+    pub text: String,
+}
+
+/// 변환 경고
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertWarning {
+    /// 여러 글을 한 문서로 합칠 때 경고가 난 글의 atclId
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub article_id: Option<String>,
+    /// 경고가 난 contents 항목. 문서 전체에 대한 경고면 `None`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_index: Option<usize>,
+    /// 경고 종류 (`EMPTY_CONTENTS`, `CONTENT_SKIPPED` 등)
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl ConvertWarning {
+    fn new(content_index: Option<usize>, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            article_id: None,
+            content_index,
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ConvertWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(id) = &self.article_id {
+            write!(f, "[{}] ", id)?;
+        }
+        match self.content_index {
+            Some(idx) => write!(f, "contents[{}]: {}", idx, self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+/// 템플릿에서 본문을 넣을 자리를 표시하는 단락
+pub const CONTENTS_MARKER: &str = "{{CONTENTS}}";
+
+/// JSON ApiResponse를 HWPX 바이트로 변환 (경고는 버린다)
 pub fn convert(input: &ApiResponse, base_path: &Path) -> Result<Vec<u8>> {
-    input.validate()?;
+    convert_to_output(input, base_path).map(|output| output.bytes)
+}
+
+/// JSON ApiResponse를 HWPX 바이트로 변환하고, 변환 경고를 문자열로 함께 돌려준다
+/// (onError가 skip일 때 건너뛴 콘텐츠 등). 코드가 필요하면 [`convert_to_output`]을 쓴다.
+pub fn convert_with_warnings(
+    input: &ApiResponse,
+    base_path: &Path,
+) -> Result<(Vec<u8>, Vec<String>)> {
+    convert_to_output(input, base_path).map(|output| {
+        let warnings = output.warnings.iter().map(|w| w.to_string()).collect();
+        (output.bytes, warnings)
+    })
+}
+
+/// JSON ApiResponse를 HWPX 바이트와 본문 텍스트로 변환 (경고는 버린다)
+///
+/// 텍스트는 `HwpxReader::extract_text_with_options`에 `include_tables`를 켜고
+/// 읽은 것과 같다.
+pub fn convert_with_text(input: &ApiResponse, base_path: &Path) -> Result<(Vec<u8>, String)> {
+    convert_to_output(input, base_path).map(|output| (output.bytes, output.text))
+}
+
+/// JSON ApiResponse를 HWPX로 변환하고 변환 중의 경고를 함께 돌려준다
+///
+/// 경고는 출력하지 않는다. CLI는 stderr로, API는 응답 헤더나 작업 기록으로 알린다.
+pub fn convert_to_output(input: &ApiResponse, base_path: &Path) -> Result<ConvertOutput> {
+    let mut writer = new_writer(&input.options, base_path)?;
+    let warnings = convert_into(&mut writer, input, &input.options, base_path)?;
+    finish(writer, warnings)
+}
+
+/// 이미 내용이 있는 HwpxWriter에 글 하나를 변환해 넣는다
+///
+/// 표지나 머리말을 먼저 넣은 문서에 본문을 넣고, 그 뒤에 부록을 더 붙인 다음
+/// `to_bytes`로 직렬화하는 식으로 쓴다. 입력의 `options` 대신 `options`를 쓰며,
+/// 입력 검증도 [`ApiResponse::validate_with`]로 `options`에 맞춰 한다. 메타데이터,
+/// 쪽 설정, 머리말/꼬리말 같은 문서 설정도 `options`대로 writer에 적용한다.
+/// 본문은 writer에 `{{CONTENTS}}` 단락이 있으면 그 자리에, 없으면 끝에 붙는다.
+///
+/// writer는 이미 만들어져 있으므로 `options`의 templatePath, templateBytes는 쓰지
+/// 않는다. 템플릿에 넣으려면 `HwpxWriter::from_hwpx_bytes`로 writer를 만든다.
+pub fn convert_into(
+    writer: &mut HwpxWriter,
+    input: &ApiResponse,
+    options: &Options,
+    base_path: &Path,
+) -> Result<Vec<ConvertWarning>> {
+    input.validate_with(options)?;
+    write_article(writer, &input.data.article, options, base_path)
+}
 
-    let mut writer = HwpxWriter::new();
-    let article = &input.data.article;
+/// 검증을 마친 글 하나를 HWPX로 변환
+pub(super) fn convert_article(
+    article: &Article,
+    options: &Options,
+    base_path: &Path,
+) -> Result<ConvertOutput> {
+    let mut writer = new_writer(options, base_path)?;
+    let warnings = write_article(&mut writer, article, options, base_path)?;
+    finish(writer, warnings)
+}
 
+/// 검증을 마친 글 하나를 writer에 넣는다 (문서 설정과 본문)
+fn write_article(
+    writer: &mut HwpxWriter,
+    article: &Article,
+    options: &Options,
+    base_path: &Path,
+) -> Result<Vec<ConvertWarning>> {
+    let mut warnings = Vec::new();
+    let reg_dt = normalize_reg_dt(article, options, &mut warnings);
+    setup_document(writer, article, options, reg_dt.as_deref(), &mut warnings)?;
+    let mut body = |writer: &mut HwpxWriter| {
+        add_article(
+            writer,
+            article,
+            options,
+            reg_dt.as_deref(),
+            base_path,
+            true,
+            &mut warnings,
+        )
+    };
+    if !writer.insert_at_marker(CONTENTS_MARKER, &mut body)? {
+        body(writer)?;
+    }
+    add_provenance(writer, options)?;
+    Ok(warnings)
+}
+
+/// 변환을 시작할 문서. templateBytes나 templatePath가 있으면 그 HWPX를 읽어
+/// 글꼴, 글자 모양, 머리말/꼬리말과 본문을 그대로 두고, 없으면 빈 문서
+///
+/// 본문은 템플릿의 `{{CONTENTS}}` 단락 자리에 들어가고, 그런 단락이 없으면
+/// 템플릿 끝에 붙는다.
+pub(super) fn new_writer(options: &Options, base_path: &Path) -> Result<HwpxWriter> {
+    let bytes = match (&options.template_bytes, &options.template_path) {
+        (Some(bytes), _) => Cow::Borrowed(bytes.as_slice()),
+        (None, Some(path)) => {
+            let path = base_path.join(path);
+            Cow::Owned(std::fs::read(&path).map_err(|e| {
+                JsonToHwpxError::Conversion(format!(
+                    "템플릿 파일 읽기 실패: {} ({})",
+                    path.display(),
+                    e
+                ))
+            })?)
+        }
+        (None, None) => return Ok(HwpxWriter::new()),
+    };
+    HwpxWriter::from_hwpx_bytes(&bytes).map_err(|e| match e {
+        HwpError::Encrypted(_) => e.into(),
+        e => JsonToHwpxError::Conversion(format!("템플릿을 HWPX로 읽을 수 없습니다: {}", e)),
+    })
+}
+
+/// 작성일시를 dateFormat으로 맞춘다 (읽을 수 없으면 경고와 함께 그대로)
+pub(super) fn normalize_reg_dt(
+    article: &Article,
+    options: &Options,
+    warnings: &mut Vec<ConvertWarning>,
+) -> Option<String> {
+    let date_format = options
+        .date_format
+        .as_deref()
+        .unwrap_or(template::DEFAULT_DATE_FORMAT);
+    article.reg_dt.as_deref().map(|raw| {
+        template::format_date(raw, date_format).unwrap_or_else(|| {
+            warnings.push(ConvertWarning::new(
+                None,
+                "INVALID_DATE",
+                format!("regDt를 날짜로 읽을 수 없어 그대로 씁니다 (regDt={})", raw),
+            ));
+            raw.to_string()
+        })
+    })
+}
+
+/// 문서 전체 설정: 메타데이터, 본문 글자 모양, 쪽 설정, 머리말/꼬리말, 줄 번호
+pub(super) fn setup_document(
+    writer: &mut HwpxWriter,
+    article: &Article,
+    options: &Options,
+    reg_dt: Option<&str>,
+    warnings: &mut Vec<ConvertWarning>,
+) -> Result<()> {
     // 문서 메타데이터 설정
     let creator = match (&article.reg_emp_name, &article.reg_dept_name) {
         (Some(name), Some(dept)) => format!("{} ({})", name, dept),
@@ -24,65 +236,416 @@ pub fn convert(input: &ApiResponse, base_path: &Path) -> Result<Vec<u8>> {
     writer.set_metadata(HwpxMetadata {
         title: article.subject.clone(),
         creator,
-        created_date: article.reg_dt.clone().unwrap_or_default(),
+        created_date: reg_dt.unwrap_or_default().to_string(),
+        subject: article.board_name.clone().unwrap_or_default(),
+        keywords: article.tags.clone(),
+        ..Default::default()
+    });
+
+    // 본문 글자 모양을 charPr 0에 먼저 고정한다. 그렇지 않으면 처음 추가된
+    // 제목이나 머리 항목의 굵은 글자 모양이 charPr 0이 되어 본문에 적용된다.
+    writer.set_default_text_style(HwpxTextStyle {
+        font_name: options.default_font.clone(),
+        font_size: options.default_font_size,
+        ..Default::default()
     });
 
+    if options.page_size.is_some() || options.landscape || options.margins_mm.is_some() {
+        writer.set_page_layout(page_layout(options))?;
+    }
+
+    // 머리말/꼬리말 템플릿
+    if let Some(template) = &options.header_template {
+        let header = render_template("headerTemplate", template, article, options, warnings);
+        writer.add_header(&header);
+    }
+    let footer_text = match &options.footer_template {
+        Some(template) => render_template("footerTemplate", template, article, options, warnings),
+        None => options.footer_text.clone().unwrap_or_default(),
+    };
+    if let Some(footer) = footer(options, &footer_text) {
+        writer.add_footer_config(footer);
+    }
+
+    if let Some(line_numbers) = &options.line_numbers {
+        writer.enable_line_numbers(
+            line_numbers.count_by,
+            line_numbers.start,
+            line_numbers.distance_mm,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// 글 하나의 본문: 머리 항목(includeHeader), contents, 첨부파일
+pub(super) fn add_article(
+    writer: &mut HwpxWriter,
+    article: &Article,
+    options: &Options,
+    reg_dt: Option<&str>,
+    base_path: &Path,
+    with_title: bool,
+    warnings: &mut Vec<ConvertWarning>,
+) -> Result<()> {
+    let body_size = options.default_font_size.unwrap_or(10);
+    let mut has_prev = false;
+    check_contents(article, &options.limits)?;
+
+    // titleStyle 옵션 처리 (기본은 includeHeader가 꺼져 있을 때만 제목을 넣음)
+    if let TitleStyle::Heading {
+        size_pt,
+        bold,
+        align,
+    } = options.effective_title_style()
+    {
+        if with_title && !article.subject.trim().is_empty() {
+            let style = ContentStyle {
+                align: Some(align),
+                font_size: Some(size_pt),
+                bold,
+                ..Default::default()
+            };
+            let subject = TextValue::Plain(article.subject.clone());
+            text::add_styled_text(writer, &subject, &[], &style, body_size)?;
+            has_prev = true;
+        }
+    }
+
     // includeHeader 옵션 처리
-    if input.options.include_header {
-        add_header_section(&mut writer, input)?;
+    if options.include_header {
+        add_header_section(writer, article, options, reg_dt)?;
+        has_prev = false;
     }
 
-    // 빈 contents 경고
+    // 빈 contents 처리: 예전 글은 contentHtml에만 본문이 있다
     if article.contents.is_empty() {
-        eprintln!("[경고] contents가 비어있습니다. 빈 문서를 생성합니다.");
+        match article
+            .content_html
+            .as_deref()
+            .filter(|h| !h.trim().is_empty())
+        {
+            Some(value) => {
+                if options.use_content_html_fallback {
+                    let html_warnings = html::add_html(
+                        writer,
+                        value,
+                        base_path,
+                        body_size,
+                        options.skip_images,
+                        &options.limits,
+                    )?;
+                    warnings.extend(
+                        html_warnings
+                            .into_iter()
+                            .map(|(code, message)| ConvertWarning::new(None, code, message)),
+                    );
+                    check_paragraphs(writer, &options.limits)?;
+                    has_prev = true;
+                }
+            }
+            None => warnings.push(ConvertWarning::new(
+                None,
+                "EMPTY_CONTENTS",
+                "contents가 비어있습니다. 빈 문서를 생성합니다.",
+            )),
+        }
     }
 
     // contents 순회하며 변환
-    let mut has_prev = false;
+    for (idx, content) in article.contents.iter().enumerate() {
+        // 각 콘텐츠 항목 사이에 빈 단락(개행) 추가 (쪽 나누기 앞뒤는 제외)
+        let is_page_break = matches!(content, Content::PageBreak);
+        if has_prev && !is_page_break {
+            text::add_separator_paragraph(writer, options.separator_spacing)?;
+        }
 
-    for content in &article.contents {
-        // 각 콘텐츠 항목 사이에 빈 단락(개행) 추가
+        let result = add_content(writer, idx, content, options, base_path, warnings)
+            .and_then(|()| check_paragraphs(writer, &options.limits));
+        if let Err(e) = result {
+            // 한도를 넘은 입력은 onError와 관계없이 멈춘다
+            if options.on_error == OnError::Fail || matches!(e, JsonToHwpxError::LimitExceeded(..))
+            {
+                return Err(e.in_content(idx, content.type_name()));
+            }
+            // 실패한 콘텐츠 자리에 눈에 보이는 표시를 남긴다
+            writer.add_paragraph(&failure_placeholder(content))?;
+            warnings.push(ConvertWarning::new(
+                Some(idx),
+                "CONTENT_SKIPPED",
+                format!("변환 실패로 건너뛰었습니다 ({})", e),
+            ));
+        }
+        has_prev = !is_page_break;
+    }
+
+    if options.include_attachments && !article.attachments.is_empty() {
         if has_prev {
-            text::add_separator_paragraph(&mut writer)?;
+            text::add_separator_paragraph(writer, options.separator_spacing)?;
         }
+        add_attachments(writer, &article.attachments, body_size)?;
+        has_prev = true;
+    }
+
+    if options.include_comments && !article.comments.is_empty() {
+        if has_prev {
+            text::add_separator_paragraph(writer, options.separator_spacing)?;
+        }
+        add_comments(writer, &article.comments, body_size)?;
+    }
+
+    Ok(())
+}
+
+/// contents 개수가 한도를 넘으면 `LimitExceeded` 에러
+fn check_contents(article: &Article, limits: &ConvertLimits) -> Result<()> {
+    let count = article.contents.len();
+    if count > limits.max_contents {
+        return Err(JsonToHwpxError::LimitExceeded(
+            ConvertLimit::Contents,
+            format!(
+                "contents가 너무 많습니다 ({}개, 최대 {}개)",
+                count, limits.max_contents
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// 지금까지 쓴 본문 단락 수가 한도를 넘으면 `LimitExceeded` 에러
+fn check_paragraphs(writer: &HwpxWriter, limits: &ConvertLimits) -> Result<()> {
+    let count = writer.paragraph_count();
+    if count > limits.max_paragraphs {
+        return Err(JsonToHwpxError::LimitExceeded(
+            ConvertLimit::Paragraphs,
+            format!(
+                "단락이 너무 많습니다 ({}개, 최대 {}개)",
+                count, limits.max_paragraphs
+            ),
+        ));
+    }
+    Ok(())
+}
 
-        match content {
-            Content::Text { value } => {
-                text::add_text_paragraphs(&mut writer, value)?;
+/// 문서를 검증하여 바이트로
+pub(super) fn finish(
+    mut writer: HwpxWriter,
+    warnings: Vec<ConvertWarning>,
+) -> Result<ConvertOutput> {
+    // 일관성이 깨진 문서는 손상된 파일 대신 검증 오류로 돌려준다
+    writer.set_strict(true);
+    let bytes = writer.to_bytes()?;
+    let text = writer.extract_text(true);
+    Ok(ConvertOutput {
+        bytes,
+        warnings,
+        text,
+    })
+}
+
+/// 콘텐츠 항목 하나를 변환하여 추가
+fn add_content(
+    writer: &mut HwpxWriter,
+    idx: usize,
+    content: &Content,
+    options: &Options,
+    base_path: &Path,
+    warnings: &mut Vec<ConvertWarning>,
+) -> Result<()> {
+    let body_size = options.default_font_size.unwrap_or(10);
+    match content {
+        Content::Text {
+            value,
+            links,
+            style: Some(style),
+        } => {
+            for key in style.unknown.keys() {
+                warnings.push(ConvertWarning::new(
+                    Some(idx),
+                    "UNKNOWN_STYLE_KEY",
+                    format!("알 수 없는 style 키 '{}'를 무시했습니다", key),
+                ));
             }
-            Content::Image {
-                url,
-                base64,
-                format,
-            } => {
-                if let Some(b64_data) = base64 {
-                    image::add_image_from_base64(&mut writer, b64_data, format.as_deref())?;
-                } else if let Some(url_str) = url {
-                    image::add_image_from_url(&mut writer, url_str, base_path)?;
-                }
+            text::add_styled_text(writer, value, links, style, body_size)?;
+        }
+        Content::Text {
+            value: TextValue::Plain(value),
+            links,
+            ..
+        } if links.is_empty() => {
+            text::add_text_paragraphs(writer, value, &options.text_normalization)?;
+        }
+        Content::Text {
+            value: TextValue::Plain(value),
+            links,
+            ..
+        } => {
+            text::add_linked_text_paragraphs(writer, value, links)?;
+        }
+        Content::Text {
+            value: TextValue::Runs(runs),
+            ..
+        } => {
+            text::add_text_runs(writer, runs, body_size)?;
+        }
+        Content::Quote { value } => {
+            text::add_quote(writer, value, body_size)?;
+        }
+        Content::Code { value, .. } => {
+            text::add_code_block(writer, value, body_size)?;
+        }
+        Content::Link { text, url } => {
+            writer.add_hyperlink(text, url)?;
+        }
+        Content::Heading { value, level } => {
+            text::add_heading(writer, value, *level, body_size)?;
+        }
+        Content::Image { url, base64, .. } if options.skip_images => {
+            if url.is_some() || base64.is_some() {
+                let text = image::skipped_image_text(url.as_deref(), base64.is_some());
+                writer.add_paragraph(&text)?;
             }
-            Content::Table { value } => {
-                table::add_table_from_html(&mut writer, value)?;
+        }
+        Content::Image {
+            url,
+            base64,
+            format,
+            width,
+            height,
+            align,
+            caption,
+        } => {
+            let image_options = ImageOptions {
+                width: width.as_deref(),
+                height: height.as_deref(),
+                align: *align,
+                caption: caption.as_deref(),
+                body_size_pt: body_size,
+                max_total_bytes: options.limits.max_total_image_bytes,
+            };
+            if let Some(b64_data) = base64 {
+                image::add_image_from_base64(writer, b64_data, format.as_deref(), &image_options)?;
+            } else if let Some(url_str) = url {
+                image::add_image_from_url(writer, url_str, base_path, &image_options)?;
             }
         }
-        has_prev = true;
+        Content::Table(TableContent::Html { value }) => {
+            let images = table::CellImageOptions {
+                base_path,
+                skip: options.skip_images,
+                max_total_bytes: options.limits.max_total_image_bytes,
+            };
+            let table_warnings = table::add_table_from_html(
+                writer,
+                value,
+                options.limits.max_table_cells,
+                Some(&images),
+            )?;
+            warnings.extend(
+                table_warnings
+                    .into_iter()
+                    .map(|(code, message)| ConvertWarning::new(Some(idx), code, message)),
+            );
+        }
+        Content::Table(TableContent::Rows(rows)) => {
+            table::add_table_from_rows(writer, rows, options.limits.max_table_cells)?;
+        }
+        Content::Divider => {
+            add_divider(writer)?;
+        }
+        Content::PageBreak => {
+            writer.add_page_break()?;
+        }
+        Content::Checkbox { label, checked } => {
+            writer.add_checkbox_paragraph(label, *checked)?;
+        }
+        Content::List { ordered, items } => {
+            text::add_list(writer, *ordered, items)?;
+        }
+        Content::Html { value } => {
+            let html_warnings = html::add_html(
+                writer,
+                value,
+                base_path,
+                body_size,
+                options.skip_images,
+                &options.limits,
+            )?;
+            warnings.extend(
+                html_warnings
+                    .into_iter()
+                    .map(|(code, message)| ConvertWarning::new(Some(idx), code, message)),
+            );
+        }
+        Content::Markdown { value } => {
+            let markdown_warnings =
+                markdown::add_markdown(writer, value, body_size, options.limits.max_table_cells)?;
+            warnings.extend(
+                markdown_warnings
+                    .into_iter()
+                    .map(|(code, message)| ConvertWarning::new(Some(idx), code, message)),
+            );
+        }
+        Content::File { url, render } => {
+            file::add_file(
+                writer,
+                url,
+                *render,
+                base_path,
+                options.limits.max_table_cells,
+                &options.text_normalization,
+            )?;
+        }
     }
+    Ok(())
+}
 
-    let bytes = writer.to_bytes()?;
-    Ok(bytes)
+/// onError가 skip일 때 실패한 콘텐츠 자리에 넣는 단락
+fn failure_placeholder(content: &Content) -> String {
+    match content {
+        Content::Image { url: Some(url), .. } => format!("[이미지 로드 실패: {}]", url),
+        Content::Image { .. } => "[이미지 로드 실패]".to_string(),
+        Content::Table(_) => "[표 변환 실패]".to_string(),
+        Content::File { url, .. } => format!("[파일 로드 실패: {}]", url),
+        _ => "[콘텐츠 변환 실패]".to_string(),
+    }
 }
 
 /// JSON ApiResponse를 HWPX 파일로 변환하여 저장
-pub fn convert_to_file(input: &ApiResponse, base_path: &Path, output: &Path) -> Result<()> {
+///
+/// `output`이 디렉터리면 그 안에 [`ApiResponse::output_filename`] 이름으로 저장한다.
+/// 저장한 파일 경로를 돌려준다.
+pub fn convert_to_file(input: &ApiResponse, base_path: &Path, output: &Path) -> Result<PathBuf> {
     let bytes = convert(input, base_path)?;
-    std::fs::write(output, bytes)?;
-    Ok(())
+    let path = if output.is_dir() {
+        output.join(input.output_filename())
+    } else {
+        output.to_path_buf()
+    };
+    std::fs::write(&path, bytes)?;
+    Ok(path)
 }
 
+/// 헤더 라벨 (labelLocale별)
+const HEADER_LABELS: [(&str, &str, &str); 4] = [
+    ("subject", "제목", "Title"),
+    ("regEmpName", "작성자", "Author"),
+    ("regDeptName", "부서", "Department"),
+    ("regDt", "작성일", "Date"),
+];
+
 /// includeHeader 옵션에 따라 메타데이터를 본문 상단에 삽입
-fn add_header_section(writer: &mut HwpxWriter, input: &ApiResponse) -> Result<()> {
-    let article = &input.data.article;
-    let fields = &input.options.header_fields;
+///
+/// headerFields가 있으면 그 순서대로 넣고, 기본 필드가 아닌 키는 article의
+/// 추가 필드(`extra`)에서 찾아 키 이름을 라벨로 쓴다. 라벨은 labelOverrides,
+/// labelLocale 순으로 정한다. 작성일시는 dateFormat으로 맞춘 `reg_dt`를 쓴다.
+fn add_header_section(
+    writer: &mut HwpxWriter,
+    article: &Article,
+    options: &Options,
+    reg_dt: Option<&str>,
+) -> Result<()> {
+    let fields = &options.header_fields;
 
     let bold_style = HwpxTextStyle::new().bold();
 
@@ -90,43 +653,268 @@ fn add_header_section(writer: &mut HwpxWriter, input: &ApiResponse) -> Result<()
         ("subject", Some(article.subject.as_str())),
         ("regEmpName", article.reg_emp_name.as_deref()),
         ("regDeptName", article.reg_dept_name.as_deref()),
-        ("regDt", article.reg_dt.as_deref()),
+        ("regDt", reg_dt),
     ];
 
-    let labels = [
-        ("subject", "제목"),
-        ("regEmpName", "작성자"),
-        ("regDeptName", "부서"),
-        ("regDt", "작성일"),
-    ];
+    let keys: Vec<&str> = if fields.is_empty() {
+        field_entries.iter().map(|(key, _)| *key).collect()
+    } else {
+        fields.iter().map(String::as_str).collect()
+    };
 
-    for (field_key, value) in &field_entries {
-        if !fields.is_empty() && !fields.iter().any(|f| f == *field_key) {
+    let mut written: Vec<&str> = Vec::new();
+    for key in keys {
+        if written.contains(&key) {
             continue;
         }
+        written.push(key);
 
-        if let Some(val) = value {
-            let label = labels
+        let value = match field_entries.iter().find(|(k, _)| *k == key) {
+            Some((_, value)) => value.map(str::to_string),
+            None => article.extra.get(key).and_then(header_value),
+        };
+        let label = match options.label_overrides.get(key) {
+            Some(label) => label.as_str(),
+            None => HEADER_LABELS
                 .iter()
-                .find(|(k, _)| k == field_key)
-                .map(|(_, l)| *l)
-                .unwrap_or(*field_key);
+                .find(|(k, _, _)| *k == key)
+                .map(|(_, ko, en)| {
+                    if options.label_locale == "en" {
+                        *en
+                    } else {
+                        *ko
+                    }
+                })
+                .unwrap_or(key),
+        };
 
+        if let Some(val) = value {
             let runs = vec![
                 StyledText::with_style(&format!("{}: ", label), bold_style.clone()),
-                StyledText::new(val),
+                StyledText::new(&val),
             ];
             writer.add_mixed_styled_paragraph(runs)?;
         }
     }
 
     // 구분선
-    writer.add_paragraph("─────────────────────────")?;
+    add_divider(writer)?;
     writer.add_paragraph("")?;
 
     Ok(())
 }
 
+/// pageSize, landscape, marginsMm 옵션으로 만든 쪽 설정
+fn page_layout(options: &Options) -> PageLayout {
+    let paper = options
+        .page_size
+        .as_deref()
+        .and_then(parse_page_size)
+        .unwrap_or(PaperSize::A4);
+    let orientation = if options.landscape {
+        PageOrientation::Landscape
+    } else {
+        PageOrientation::Portrait
+    };
+
+    let mut layout = PageLayout::new(paper, orientation);
+    if let Some(margins) = &options.margins_mm {
+        let sides = [
+            (&mut layout.margins.top, margins.top),
+            (&mut layout.margins.bottom, margins.bottom),
+            (&mut layout.margins.left, margins.left),
+            (&mut layout.margins.right, margins.right),
+        ];
+        for (side, mm) in sides {
+            if let Some(mm) = mm {
+                *side = mm_to_hwp_units(mm);
+            }
+        }
+    }
+    layout
+}
+
+/// 템플릿을 article 값으로 채우고 경고를 모은다
+fn render_template(
+    option: &str,
+    template: &str,
+    article: &Article,
+    options: &Options,
+    warnings: &mut Vec<ConvertWarning>,
+) -> String {
+    let date_format = options
+        .date_format
+        .as_deref()
+        .unwrap_or(template::DEFAULT_DATE_FORMAT);
+    let (text, template_warnings) = template::render(template, article, date_format);
+    warnings.extend(template_warnings.into_iter().map(|message| {
+        ConvertWarning::new(
+            None,
+            "UNKNOWN_PLACEHOLDER",
+            format!("{}: {}", option, message),
+        )
+    }));
+    text
+}
+
+/// footerPageNumbers 옵션과 꼬리말 텍스트로 만든 가운데 정렬 꼬리말
+fn footer(options: &Options, text: &str) -> Option<HwpxFooter> {
+    let text = text.trim();
+    let footer = match options.footer_page_numbers {
+        Some(style) => {
+            // 텍스트와 쪽 번호 사이를 한 칸 띄운다
+            let prefix = if text.is_empty() {
+                String::new()
+            } else {
+                format!("{} ", text)
+            };
+            let footer = HwpxFooter::new(&prefix);
+            match style {
+                PageNumberStyle::Numeric => footer.with_page_number(),
+                PageNumberStyle::Dashed => footer.with_page_number_chars('-', '-'),
+                PageNumberStyle::RomanLower => {
+                    footer.with_page_number_format(PageNumberFormat::RomanLower)
+                }
+                PageNumberStyle::RomanUpper => {
+                    footer.with_page_number_format(PageNumberFormat::RomanUpper)
+                }
+            }
+        }
+        None if !text.is_empty() => HwpxFooter::new(text),
+        None => return None,
+    };
+    Some(footer.with_alignment(HwpxAlignment::Center))
+}
+
+/// 추가 필드 값을 헤더에 쓸 텍스트로 (null이면 생략)
+fn header_value(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::Null => None,
+        serde_json::Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+/// includeAttachments 옵션에 따라 첨부파일 목록을 제목과 표로 삽입
+fn add_attachments(
+    writer: &mut HwpxWriter,
+    attachments: &[Attachment],
+    body_size_pt: u32,
+) -> Result<()> {
+    text::add_heading(writer, "첨부파일", 2, body_size_pt)?;
+
+    let mut rows = vec![vec![
+        "파일명".to_string(),
+        "크기".to_string(),
+        "등록일".to_string(),
+    ]];
+    for attachment in attachments {
+        rows.push(vec![
+            attachment.file_name.clone().unwrap_or_default(),
+            attachment
+                .file_size
+                .map(format_file_size)
+                .unwrap_or_default(),
+            attachment.reg_dt.clone().unwrap_or_default(),
+        ]);
+    }
+    let data = rows
+        .iter()
+        .map(|row| row.iter().map(String::as_str).collect())
+        .collect();
+    writer.add_table(HwpxTable::from_data(data))?;
+    Ok(())
+}
+
+/// 댓글 들여쓰기 (mm)
+const COMMENT_INDENT_MM: f32 = 5.0;
+
+/// includeComments 옵션에 따라 댓글을 제목과 들여쓴 단락으로 삽입
+///
+/// 댓글마다 "작성자 · 작성일시"를 굵게 한 줄, 본문을 줄마다 한 단락으로 쓴다.
+fn add_comments(writer: &mut HwpxWriter, comments: &[Comment], body_size_pt: u32) -> Result<()> {
+    text::add_heading(writer, "댓글", 2, body_size_pt)?;
+
+    let para_style = HwpxParagraphStyle::new().left_margin(COMMENT_INDENT_MM);
+    let body_style = HwpxTextStyle::new().size(body_size_pt);
+    for comment in comments {
+        let byline = [comment.author.as_deref(), comment.created_at.as_deref()]
+            .into_iter()
+            .flatten()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join(" · ");
+        if !byline.is_empty() {
+            writer.add_styled_paragraph_with_style(
+                &byline,
+                body_style.clone().bold(),
+                &para_style,
+            )?;
+        }
+        for line in comment.body.split('\n') {
+            writer.add_styled_paragraph_with_style(line, body_style.clone(), &para_style)?;
+        }
+    }
+    Ok(())
+}
+
+/// 생성 기록 글자 색
+const PROVENANCE_COLOR: u32 = 0x808080;
+
+/// includeProvenance 옵션에 따라 문서 끝에 생성 기록을 작은 회색 단락으로 넣는다
+///
+/// content.hpf의 ModifiedDate도 작성일시 대신 생성 시각(dateFormat)으로 바꾼다.
+pub(super) fn add_provenance(writer: &mut HwpxWriter, options: &Options) -> Result<()> {
+    if !options.include_provenance {
+        return Ok(());
+    }
+    let now = chrono::Local::now();
+    let date_format = options
+        .date_format
+        .as_deref()
+        .unwrap_or(template::DEFAULT_DATE_FORMAT);
+    writer.set_modified_date(&now.format(date_format).to_string());
+
+    let body_size = options.default_font_size.unwrap_or(10);
+    let style = HwpxTextStyle::new()
+        .size(body_size.saturating_sub(2).max(1))
+        .color(PROVENANCE_COLOR);
+    text::add_separator_paragraph(writer, options.separator_spacing)?;
+    writer.add_styled_paragraph(&provenance_text(&now), style)?;
+    Ok(())
+}
+
+/// "본 문서는 2026-01-25 09:30에 hwpers v0.5.0로 생성되었습니다"
+fn provenance_text(generated_at: &chrono::DateTime<chrono::Local>) -> String {
+    format!(
+        "본 문서는 {}에 hwpers v{}로 생성되었습니다",
+        generated_at.format("%Y-%m-%d %H:%M"),
+        env!("CARGO_PKG_VERSION")
+    )
+}
+
+/// 바이트 수 → "512 B", "1.5 KB", "12.0 MB"
+fn format_file_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// 본문 너비 전체에 걸친 구분선 추가
+fn add_divider(writer: &mut HwpxWriter) -> Result<()> {
+    writer.add_horizontal_rule(100, 0.12, 0x000000)?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -194,4 +982,13 @@ mod tests {
         let result = convert(&input, &base_path());
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_format_file_size() {
+        assert_eq!(format_file_size(0), "0 B");
+        assert_eq!(format_file_size(1023), "1023 B");
+        assert_eq!(format_file_size(1536), "1.5 KB");
+        assert_eq!(format_file_size(12 * 1024 * 1024), "12.0 MB");
+        assert_eq!(format_file_size(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
 }
@@ -18,6 +18,41 @@ pub enum JsonToHwpxError {
     /// HwpxWriter 내부 에러
     #[error("HWPX 에러: {0}")]
     Hwpx(String),
+
+    /// 암호 또는 DRM으로 보호된 문서 (exit code 1)
+    #[error("암호화된 문서: {0}")]
+    Encrypted(String),
+
+    /// 입력이 변환 한도([`ConvertLimits`](super::ConvertLimits))를 넘음 (exit code 1)
+    ///
+    /// 입력 에러의 한 종류지만, 한도마다 에러 코드가 다르고 `onError: skip`으로 건너뛰지 않는다.
+    #[error("입력 에러: {1}")]
+    LimitExceeded(ConvertLimit, String),
+}
+
+/// 넘은 변환 한도의 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConvertLimit {
+    /// contents 개수 (`max_contents`)
+    Contents,
+    /// 표 하나의 셀 수 (`max_table_cells`)
+    TableCells,
+    /// 문서에 넣은 그림 바이트 합계 (`max_total_image_bytes`)
+    TotalImageBytes,
+    /// 본문 단락 수 (`max_paragraphs`)
+    Paragraphs,
+}
+
+impl ConvertLimit {
+    /// 에러 코드 문자열
+    pub fn error_code(self) -> &'static str {
+        match self {
+            Self::Contents => "CONTENTS_LIMIT_EXCEEDED",
+            Self::TableCells => "TABLE_CELLS_LIMIT_EXCEEDED",
+            Self::TotalImageBytes => "IMAGE_BYTES_LIMIT_EXCEEDED",
+            Self::Paragraphs => "PARAGRAPHS_LIMIT_EXCEEDED",
+        }
+    }
 }
 
 impl JsonToHwpxError {
@@ -28,6 +63,8 @@ impl JsonToHwpxError {
             Self::Conversion(_) => 2,
             Self::Io(_) => 3,
             Self::Hwpx(_) => 2,
+            Self::Encrypted(_) => 1,
+            Self::LimitExceeded(..) => 1,
         }
     }
 
@@ -38,13 +75,43 @@ impl JsonToHwpxError {
             Self::Conversion(_) => "CONVERSION_ERROR",
             Self::Io(_) => "IO_ERROR",
             Self::Hwpx(_) => "HWPX_ERROR",
+            Self::Encrypted(_) => "DOCUMENT_ENCRYPTED",
+            Self::LimitExceeded(limit, _) => limit.error_code(),
+        }
+    }
+
+    /// 에러가 난 콘텐츠 위치. 메시지가 `contents[n]`으로 시작하는 입력, 변환, 한도 에러만
+    pub fn content_index(&self) -> Option<usize> {
+        let (Self::Input(message) | Self::Conversion(message) | Self::LimitExceeded(_, message)) =
+            self
+        else {
+            return None;
+        };
+        let (index, _) = message.strip_prefix("contents[")?.split_once(']')?;
+        index.parse().ok()
+    }
+
+    /// 콘텐츠 하나의 변환 실패를 위치와 type이 붙은 `Conversion` 에러로
+    /// (예: "contents[12] (table): 테이블에 행이 없습니다"). 한도 에러는 종류를 그대로 둔다
+    pub(crate) fn in_content(self, index: usize, content_type: &str) -> Self {
+        let prefix =
+            |message: String| format!("contents[{}] ({}): {}", index, content_type, message);
+        match self {
+            Self::LimitExceeded(limit, m) => Self::LimitExceeded(limit, prefix(m)),
+            Self::Io(e) => Self::Conversion(prefix(e.to_string())),
+            Self::Input(m) | Self::Conversion(m) | Self::Hwpx(m) | Self::Encrypted(m) => {
+                Self::Conversion(prefix(m))
+            }
         }
     }
 }
 
 impl From<crate::error::HwpError> for JsonToHwpxError {
     fn from(err: crate::error::HwpError) -> Self {
-        Self::Hwpx(err.to_string())
+        match err {
+            crate::error::HwpError::Encrypted(msg) => Self::Encrypted(msg),
+            err => Self::Hwpx(err.to_string()),
+        }
     }
 }
 
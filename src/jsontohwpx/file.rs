@@ -0,0 +1,206 @@
+use std::io::Read;
+use std::path::Path;
+
+use crate::hwpx::{HwpxTable, HwpxWriter};
+
+use super::error::{JsonToHwpxError, Result};
+use super::model::{FileRender, TextNormalization};
+use super::table;
+use super::text;
+
+/// file 콘텐츠로 읽을 수 있는 파일 크기 상한 (바이트)
+pub const MAX_FILE_BYTES: u64 = 2 * 1024 * 1024;
+
+/// 텍스트 파일(.txt, .csv)을 읽어 본문에 넣는다
+///
+/// - `Text`: 줄마다 한 단락 ([`text::add_text_paragraphs`], `normalization` 적용)
+/// - `Table`: CSV로 읽어 표 하나. 짧은 행은 빈 칸으로 채운다
+///
+/// 파일은 UTF-8(BOM 허용)로 읽고, 아니면 EUC-KR로 읽는다. 둘 다 아니거나
+/// [`MAX_FILE_BYTES`]보다 크면 `Conversion` 에러. 표의 셀 수가 `max_table_cells`를
+/// 넘으면 `LimitExceeded` 에러.
+pub fn add_file(
+    writer: &mut HwpxWriter,
+    url: &str,
+    render: FileRender,
+    base_path: &Path,
+    max_table_cells: usize,
+    normalization: &TextNormalization,
+) -> Result<()> {
+    let bytes = load_file_bytes(url, base_path)?;
+    let value = decode_text(&bytes).ok_or_else(|| {
+        JsonToHwpxError::Conversion(format!(
+            "파일 인코딩을 알 수 없습니다 (UTF-8, EUC-KR이 아님): {}",
+            url
+        ))
+    })?;
+
+    match render {
+        FileRender::Text => {
+            let value = value.replace("\r\n", "\n");
+            let value = value.strip_suffix('\n').unwrap_or(&value);
+            text::add_text_paragraphs(writer, value, normalization)
+        }
+        FileRender::Table => {
+            let mut rows = parse_csv(&value);
+            let cols = rows.iter().map(Vec::len).max().unwrap_or(0);
+            if cols == 0 {
+                return Err(JsonToHwpxError::Conversion(format!(
+                    "CSV에 행이 없습니다: {}",
+                    url
+                )));
+            }
+            table::check_cells(rows.len(), cols, max_table_cells)?;
+            for row in &mut rows {
+                row.resize(cols, String::new());
+            }
+            let data = rows
+                .iter()
+                .map(|row| row.iter().map(String::as_str).collect())
+                .collect();
+            writer.add_table(HwpxTable::from_data(data))?;
+            Ok(())
+        }
+    }
+}
+
+/// URL 또는 base_path 기준 경로에서 파일 바이트를 읽는다 (크기 상한 확인)
+fn load_file_bytes(url: &str, base_path: &Path) -> Result<Vec<u8>> {
+    let too_large = || {
+        JsonToHwpxError::Conversion(format!(
+            "파일이 너무 큽니다 (최대 {} MB): {}",
+            MAX_FILE_BYTES / 1024 / 1024,
+            url
+        ))
+    };
+
+    let bytes = if url.starts_with("http://") || url.starts_with("https://") {
+        download_file(url)?
+    } else {
+        let path = base_path.join(url);
+        let read_error = |e: std::io::Error| {
+            JsonToHwpxError::Conversion(format!("파일 읽기 실패: {} ({})", path.display(), e))
+        };
+        let file = std::fs::File::open(&path).map_err(read_error)?;
+        let mut bytes = Vec::new();
+        file.take(MAX_FILE_BYTES + 1)
+            .read_to_end(&mut bytes)
+            .map_err(read_error)?;
+        bytes
+    };
+    if bytes.len() as u64 > MAX_FILE_BYTES {
+        return Err(too_large());
+    }
+    Ok(bytes)
+}
+
+/// 외부 URL에서 파일 다운로드 (타임아웃 60초, 크기 상한까지만 읽음)
+fn download_file(url: &str) -> Result<Vec<u8>> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(60))
+        .build()
+        .map_err(|e| JsonToHwpxError::Conversion(format!("HTTP 클라이언트 생성 실패: {}", e)))?;
+
+    let response = client
+        .get(url)
+        .send()
+        .map_err(|e| JsonToHwpxError::Conversion(format!("파일 다운로드 실패: {} ({})", url, e)))?;
+    if !response.status().is_success() {
+        return Err(JsonToHwpxError::Conversion(format!(
+            "파일 다운로드 실패: {} (HTTP {})",
+            url,
+            response.status()
+        )));
+    }
+
+    let mut bytes = Vec::new();
+    response
+        .take(MAX_FILE_BYTES + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| {
+            JsonToHwpxError::Conversion(format!("파일 데이터 읽기 실패: {} ({})", url, e))
+        })?;
+    Ok(bytes)
+}
+
+/// UTF-8(BOM 허용), 안 되면 EUC-KR로 읽는다. 둘 다 아니면 `None`
+fn decode_text(bytes: &[u8]) -> Option<String> {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    if let Ok(value) = std::str::from_utf8(bytes) {
+        return Some(value.to_string());
+    }
+    encoding_rs::EUC_KR
+        .decode_without_bom_handling_and_without_replacement(bytes)
+        .map(|value| value.into_owned())
+}
+
+/// CSV를 행과 칸으로 (RFC 4180: `"`로 감싼 칸 안의 쉼표, 줄 바꿈, `""`)
+///
+/// 빈 줄은 버린다.
+fn parse_csv(value: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    cell.push('"');
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if cell.is_empty() => quoted = true,
+            ',' if !quoted => row.push(std::mem::take(&mut cell)),
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {}
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut cell));
+                if !(row.len() == 1 && row[0].is_empty()) {
+                    rows.push(std::mem::take(&mut row));
+                }
+                row.clear();
+            }
+            c => cell.push(c),
+        }
+    }
+    if !cell.is_empty() || !row.is_empty() {
+        row.push(cell);
+        rows.push(row);
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv() {
+        let rows = parse_csv("이름,설명\r\n\"홍, 길동\",\"줄\n바꿈 \"\"인용\"\"\"\n\n끝,\n");
+        assert_eq!(
+            rows,
+            vec![
+                vec!["이름", "설명"],
+                vec!["홍, 길동", "줄\n바꿈 \"인용\""],
+                vec!["끝", ""],
+            ]
+        );
+        assert_eq!(parse_csv("a,b"), vec![vec!["a", "b"]]);
+        assert!(parse_csv("").is_empty());
+    }
+
+    #[test]
+    fn test_decode_text() {
+        assert_eq!(
+            decode_text("\u{FEFF}한글".as_bytes()).as_deref(),
+            Some("한글")
+        );
+        let (euc_kr, _, _) = encoding_rs::EUC_KR.encode("한글 문서");
+        assert_eq!(decode_text(&euc_kr).as_deref(), Some("한글 문서"));
+        assert_eq!(decode_text(&[0xFF, 0xFF, 0xFF]), None);
+    }
+}
@@ -0,0 +1,358 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use scraper::{ElementRef, Html, Node};
+
+use crate::hwpx::HwpxWriter;
+
+use super::error::Result;
+use super::image::{self, ImageOptions};
+use super::inline::{dropped_link_warning, html_text, link_url_error, InlineStyle, RunBuilder};
+use super::model::{ConvertLimits, ListItem};
+use super::table;
+use super::text;
+
+/// HTML 본문을 단락, 표, 그림으로 변환하여 추가
+///
+/// - `p`, `br` → 단락 나눔
+/// - `b`/`strong`, `i`/`em`, `u` → 굵게, 기울임, 밑줄
+/// - `a href` → 하이퍼링크 (http, https, mailto만. 그 밖의 스킴은 텍스트만 남기고 `LINK_DROPPED` 경고)
+/// - `ul`/`ol`/`li` → 목록 ([`text::add_list`], 하위 목록은 바깥 목록의 번호 방식을 따름)
+/// - `img src` → 그림 (경로, URL 또는 `data:` base64). `skip_images`면 "[이미지: URL]" 단락
+/// - `table` → 표 ([`table::add_table_from_html`], 셀 안의 `img`도 위와 같이)
+/// - `blockquote` → 인용문 ([`text::add_quote`], 서식 없이 텍스트만, `p`/`br`마다 줄 나눔)
+///
+/// 텍스트의 문자 참조는 파서가 풀고, NBSP는 보통 공백으로 바꾼다
+/// ([`html_text`](super::inline::html_text)). 그 밖의 태그는 텍스트만 남기고 풀어내며,
+/// 태그 이름별 경고를 한 번씩 돌려준다.
+/// `script`, `style`, `head`의 내용은 버린다. 표와 그림에는 `limits`의 한도를 적용한다.
+///
+/// 경고는 (코드, 메시지)로 돌려준다. 모르는 태그는 `UNSUPPORTED_HTML_TAG`, 버린 링크는
+/// `LINK_DROPPED`, 표의 경고는 [`table::add_table_from_html`]과 같다.
+pub fn add_html(
+    writer: &mut HwpxWriter,
+    html: &str,
+    base_path: &Path,
+    body_size_pt: u32,
+    skip_images: bool,
+    limits: &ConvertLimits,
+) -> Result<Vec<(&'static str, String)>> {
+    let fragment = Html::parse_fragment(html);
+    let mut converter = HtmlConverter {
+        writer,
+        base_path,
+        body_size_pt,
+        skip_images,
+        limits,
+        runs: RunBuilder::new(),
+        unsupported: BTreeSet::new(),
+        warnings: Vec::new(),
+    };
+    converter.visit_children(fragment.root_element())?;
+    converter.flush_paragraph()?;
+
+    let unsupported = converter.unsupported.into_iter().map(|tag| {
+        let message = format!("지원하지 않는 HTML 태그 <{}>: 텍스트만 변환했습니다", tag);
+        ("UNSUPPORTED_HTML_TAG", message)
+    });
+    Ok(unsupported.chain(converter.warnings).collect())
+}
+
+struct HtmlConverter<'a> {
+    writer: &'a mut HwpxWriter,
+    base_path: &'a Path,
+    body_size_pt: u32,
+    skip_images: bool,
+    limits: &'a ConvertLimits,
+    runs: RunBuilder,
+    unsupported: BTreeSet<String>,
+    /// 버린 링크와 표의 경고 (코드, 메시지)
+    warnings: Vec<(&'static str, String)>,
+}
+
+impl HtmlConverter<'_> {
+    fn visit_element(&mut self, element: ElementRef<'_>) -> Result<()> {
+        let name = element.value().name();
+        match name {
+            "p" => {
+                self.flush_paragraph()?;
+                self.visit_children(element)?;
+                self.flush_paragraph()
+            }
+            "br" => self.flush_paragraph(),
+            "b" | "strong" => self.with_style(element, |s| s.bold = true),
+            "i" | "em" => self.with_style(element, |s| s.italic = true),
+            "u" => self.with_style(element, |s| s.underline = true),
+            "a" => {
+                let href = element.value().attr("href");
+                if let Some(url) = href.filter(|url| link_url_error(url).is_some()) {
+                    self.warnings.push(dropped_link_warning(url));
+                    return self.visit_children(element);
+                }
+                let href = href.map(String::from);
+                self.with_style(element, |s| s.link = href)
+            }
+            "ul" | "ol" => {
+                self.flush_paragraph()?;
+                let items = list_items(element);
+                text::add_list(self.writer, name == "ol", &items)
+            }
+            "img" => {
+                self.flush_paragraph()?;
+                match element.value().attr("src") {
+                    Some(src) => self.add_image(src),
+                    None => Ok(()),
+                }
+            }
+            "table" => {
+                self.flush_paragraph()?;
+                let images = table::CellImageOptions {
+                    base_path: self.base_path,
+                    skip: self.skip_images,
+                    max_total_bytes: self.limits.max_total_image_bytes,
+                };
+                let warnings = table::add_table_from_html(
+                    self.writer,
+                    &element.html(),
+                    self.limits.max_table_cells,
+                    Some(&images),
+                )?;
+                self.warnings.extend(warnings);
+                Ok(())
+            }
+            "blockquote" => {
+                self.flush_paragraph()?;
+                let value = quote_text(element);
+                text::add_quote(self.writer, &value, self.body_size_pt)
+            }
+            "script" | "style" | "head" => Ok(()),
+            _ => {
+                self.unsupported.insert(name.to_string());
+                self.visit_children(element)
+            }
+        }
+    }
+
+    fn visit_children(&mut self, element: ElementRef<'_>) -> Result<()> {
+        for child in element.children() {
+            match ElementRef::wrap(child) {
+                Some(child) => self.visit_element(child)?,
+                None => {
+                    if let Node::Text(text) = child.value() {
+                        self.runs.push_text(&html_text(text));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn with_style(
+        &mut self,
+        element: ElementRef<'_>,
+        apply: impl FnOnce(&mut InlineStyle),
+    ) -> Result<()> {
+        let saved = self.runs.style.clone();
+        apply(&mut self.runs.style);
+        let result = self.visit_children(element);
+        self.runs.style = saved;
+        result
+    }
+
+    fn flush_paragraph(&mut self) -> Result<()> {
+        self.runs.flush(self.writer)
+    }
+
+    fn add_image(&mut self, src: &str) -> Result<()> {
+        if self.skip_images {
+            let text = image::skipped_image_text(Some(src), false);
+            self.writer.add_paragraph(&text)?;
+            return Ok(());
+        }
+        let options = ImageOptions {
+            max_total_bytes: self.limits.max_total_image_bytes,
+            ..Default::default()
+        };
+        // data:image/png;base64,....
+        if let Some(data_url) = src.strip_prefix("data:") {
+            if let Some((meta, data)) = data_url.split_once(',') {
+                if let Some(mime) = meta.strip_suffix(";base64") {
+                    let format = mime.strip_prefix("image/");
+                    image::add_image_from_base64(self.writer, data, format, &options)?;
+                    return Ok(());
+                }
+            }
+        }
+        image::add_image_from_url(self.writer, src, self.base_path, &options)?;
+        Ok(())
+    }
+}
+
+/// `blockquote`의 텍스트. `p`, `br`마다 줄을 나누고 빈 줄은 버린다.
+fn quote_text(quote: ElementRef<'_>) -> String {
+    fn collect(element: ElementRef<'_>, lines: &mut Vec<String>) {
+        for child in element.children() {
+            match ElementRef::wrap(child) {
+                Some(el) if matches!(el.value().name(), "p" | "br") => {
+                    lines.push(String::new());
+                    collect(el, lines);
+                    lines.push(String::new());
+                }
+                Some(el) => collect(el, lines),
+                None => {
+                    if let Node::Text(text) = child.value() {
+                        let line = lines.last_mut().expect("at least one line");
+                        line.push_str(&html_text(text));
+                    }
+                }
+            }
+        }
+    }
+
+    let mut lines = vec![String::new()];
+    collect(quote, &mut lines);
+    lines
+        .iter()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `ul`/`ol`의 `li`를 목록 항목으로. 항목 안의 하위 목록은 children이 된다.
+fn list_items(list: ElementRef<'_>) -> Vec<ListItem> {
+    list.children()
+        .filter_map(ElementRef::wrap)
+        .filter(|li| li.value().name() == "li")
+        .map(|li| {
+            let mut text = String::new();
+            let mut children = Vec::new();
+            for child in li.children() {
+                match ElementRef::wrap(child) {
+                    Some(el) if matches!(el.value().name(), "ul" | "ol") => {
+                        children.extend(list_items(el));
+                    }
+                    Some(el) => text.extend(el.text().map(html_text)),
+                    None => {
+                        if let Node::Text(t) = child.value() {
+                            text.push_str(&html_text(t));
+                        }
+                    }
+                }
+            }
+            ListItem {
+                text: text.split_whitespace().collect::<Vec<_>>().join(" "),
+                children,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsupported_tags_are_unwrapped_with_warning() {
+        let mut writer = HwpxWriter::new();
+        let warnings = add_html(
+            &mut writer,
+            "<div><p>앞 <span>감싼</span> 글</p><section>인용</section><span>또</span></div>",
+            Path::new("."),
+            10,
+            false,
+            &ConvertLimits::DEFAULT,
+        )
+        .unwrap();
+        let messages: Vec<&str> = warnings
+            .iter()
+            .map(|(code, message)| {
+                assert_eq!(*code, "UNSUPPORTED_HTML_TAG");
+                message.as_str()
+            })
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "지원하지 않는 HTML 태그 <div>: 텍스트만 변환했습니다",
+                "지원하지 않는 HTML 태그 <section>: 텍스트만 변환했습니다",
+                "지원하지 않는 HTML 태그 <span>: 텍스트만 변환했습니다",
+            ]
+        );
+
+        let bytes = writer.to_bytes().unwrap();
+        let text = crate::HwpxReader::from_bytes(&bytes)
+            .unwrap()
+            .extract_text();
+        assert_eq!(text, "앞 감싼 글\n인용또\n");
+    }
+
+    #[test]
+    fn test_unsafe_links_are_dropped_with_warning() {
+        let mut writer = HwpxWriter::new();
+        let warnings = add_html(
+            &mut writer,
+            r#"<p><a href="javascript:alert(1)">누르기</a> <a href="https://example.com">사이트</a></p>"#,
+            Path::new("."),
+            10,
+            false,
+            &ConvertLimits::DEFAULT,
+        )
+        .unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].0, "LINK_DROPPED");
+        assert!(warnings[0].1.contains("javascript:alert(1)"));
+
+        let bytes = writer.to_bytes().unwrap();
+        let links = crate::HwpxReader::extract_hyperlinks(&bytes).unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].url, "https://example.com");
+        let text = crate::HwpxReader::from_bytes(&bytes)
+            .unwrap()
+            .extract_text();
+        assert!(text.starts_with("누르기"), "{}", text);
+    }
+
+    #[test]
+    fn test_entities_are_decoded_once() {
+        let mut writer = HwpxWriter::new();
+        add_html(
+            &mut writer,
+            "<p>가&nbsp;나 &amp;amp; &#x2022; &amp;lt;b&amp;gt;</p><ul><li>R&amp;D</li></ul>",
+            Path::new("."),
+            10,
+            false,
+            &ConvertLimits::DEFAULT,
+        )
+        .unwrap();
+
+        let bytes = writer.to_bytes().unwrap();
+        let text = crate::HwpxReader::from_bytes(&bytes)
+            .unwrap()
+            .extract_text();
+        assert!(
+            text.starts_with("가 나 &amp; \u{2022} &lt;b&gt;\n"),
+            "{}",
+            text
+        );
+        assert!(text.contains("R&D"), "{}", text);
+        assert!(!text.contains('\u{A0}'), "{}", text);
+    }
+
+    #[test]
+    fn test_list_items_with_nested_list() {
+        let fragment = Html::parse_fragment(
+            "<ul><li>첫째 <b>항목</b><ol><li>하위</li></ol></li><li>둘째</li></ul>",
+        );
+        let list = fragment
+            .root_element()
+            .children()
+            .find_map(ElementRef::wrap)
+            .unwrap();
+        let items = list_items(list);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].text, "첫째 항목");
+        assert_eq!(items[0].children[0].text, "하위");
+        assert_eq!(items[1].text, "둘째");
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+
+use base64::Engine;
+use serde::Serialize;
+
+use crate::hwpx::{ExtractedImage, ExtractedTable, HwpxReader};
+
+use super::error::Result;
+use super::model::{Article, Content, Data, TableContent};
+
+/// HWPX에서 되돌린 ApiResponse 형태의 문서
+///
+/// `serde_json`으로 직렬화하면 jsontohwpx 입력 JSON과 같은 구조가 된다.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArticleDocumentExport {
+    pub response_code: String,
+    pub data: Data,
+}
+
+/// HWPX 바이트를 jsontohwpx JSON 구조로 역변환
+///
+/// - 빈 단락 사이의 연속된 텍스트 단락 → `text` 한 항목 (`\n`으로 연결)
+/// - 표 → `table` (colspan/rowspan을 포함한 HTML)
+/// - 그림 → `image` (base64)
+/// - 구분선, 체크박스, 표 캡션은 복원하지 않음
+/// - 본문 첫머리에서 문서 제목과 같은 단락은 subject로 보고 contents에서 뺌
+/// - atclId는 HWPX에 저장되지 않으므로 빈 문자열
+pub fn convert(bytes: &[u8]) -> Result<ArticleDocumentExport> {
+    let document = HwpxReader::from_bytes(bytes)?;
+    let metadata = HwpxReader::metadata_from_bytes(bytes)?;
+
+    let mut tables: HashMap<usize, Vec<ExtractedTable>> = HashMap::new();
+    for table in HwpxReader::extract_tables(bytes)? {
+        tables.entry(table.paragraph_index).or_default().push(table);
+    }
+    let mut images: HashMap<usize, Vec<ExtractedImage>> = HashMap::new();
+    for image in HwpxReader::extract_images(bytes)? {
+        images.entry(image.paragraph_index).or_default().push(image);
+    }
+
+    let mut contents = Vec::new();
+    let mut lines: Vec<&str> = Vec::new();
+    let paragraphs = document.sections().flat_map(|s| &s.paragraphs);
+    for (para_idx, para) in paragraphs.enumerate() {
+        let text = para.text.as_ref().map_or("", |t| t.content.as_str());
+        let para_tables = tables.remove(&para_idx).unwrap_or_default();
+        let para_images = images.remove(&para_idx).unwrap_or_default();
+
+        if text.is_empty() || !para_tables.is_empty() || !para_images.is_empty() {
+            flush_text(&mut lines, &mut contents);
+        }
+        if !text.is_empty() {
+            lines.push(text);
+        }
+        contents.extend(para_tables.iter().map(|table| {
+            Content::Table(TableContent::Html {
+                value: table_to_html(table),
+            })
+        }));
+        contents.extend(para_images.iter().map(image_content));
+    }
+    flush_text(&mut lines, &mut contents);
+
+    // 본문 첫머리의 제목 단락(titleStyle)은 subject와 겹치므로 뺀다
+    let title = Content::Text {
+        value: metadata.title.as_str().into(),
+        links: Vec::new(),
+        style: None,
+    };
+    if !metadata.title.is_empty() && contents.first() == Some(&title) {
+        contents.remove(0);
+    }
+
+    // 작성자는 변환 시 "이름 (부서)"로 기록된다
+    let (reg_emp_name, reg_dept_name) = match metadata.creator.rsplit_once(" (") {
+        Some((name, dept)) if dept.ends_with(')') => (
+            Some(name.to_string()),
+            Some(dept.trim_end_matches(')').to_string()),
+        ),
+        _ => (non_empty(metadata.creator), None),
+    };
+
+    Ok(ArticleDocumentExport {
+        response_code: "0".to_string(),
+        data: Data {
+            article: Article {
+                atcl_id: String::new(),
+                subject: metadata.title,
+                contents,
+                content_html: None,
+                reg_dt: non_empty(metadata.created_date),
+                reg_emp_name,
+                reg_dept_name,
+                board_name: non_empty(metadata.subject),
+                tags: metadata.keywords,
+                attachments: Vec::new(),
+                comments: Vec::new(),
+                extra: HashMap::new(),
+            },
+        },
+    })
+}
+
+/// 모아 둔 텍스트 단락을 `text` 항목 하나로 추가
+fn flush_text(lines: &mut Vec<&str>, contents: &mut Vec<Content>) {
+    if !lines.is_empty() {
+        contents.push(Content::Text {
+            value: lines.join("\n").into(),
+            links: Vec::new(),
+            style: None,
+        });
+        lines.clear();
+    }
+}
+
+/// 병합 정보를 colspan/rowspan으로 옮긴 HTML 표
+fn table_to_html(table: &ExtractedTable) -> String {
+    let mut html = String::from("<table>");
+    for row in &table.rows {
+        html.push_str("<tr>");
+        for cell in row {
+            html.push_str("<td");
+            if cell.span.col_span > 1 {
+                html.push_str(&format!(" colspan=\"{}\"", cell.span.col_span));
+            }
+            if cell.span.row_span > 1 {
+                html.push_str(&format!(" rowspan=\"{}\"", cell.span.row_span));
+            }
+            html.push('>');
+            html.push_str(&escape_html(&cell.text));
+            html.push_str("</td>");
+        }
+        html.push_str("</tr>");
+    }
+    html.push_str("</table>");
+    html
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn image_content(image: &ExtractedImage) -> Content {
+    Content::Image {
+        url: None,
+        base64: Some(base64::engine::general_purpose::STANDARD.encode(image.data.as_slice())),
+        format: Some(image.format.extension().to_string()),
+        width: None,
+        height: None,
+        align: None,
+        caption: None,
+    }
+}
+
+fn non_empty(value: String) -> Option<String> {
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hwpx::{CellSpan, ExtractedCell};
+
+    #[test]
+    fn test_table_to_html_with_spans() {
+        let cell = |row, col, col_span, row_span, text: &str| ExtractedCell {
+            row,
+            col,
+            span: CellSpan { col_span, row_span },
+            text: text.to_string(),
+        };
+        let table = ExtractedTable {
+            paragraph_index: 0,
+            row_count: 2,
+            col_count: 2,
+            rows: vec![
+                vec![cell(0, 0, 2, 1, "A&B")],
+                vec![cell(1, 0, 1, 1, "<1>"), cell(1, 1, 1, 1, "2")],
+            ],
+        };
+        assert_eq!(
+            table_to_html(&table),
+            "<table><tr><td colspan=\"2\">A&amp;B</td></tr>\
+             <tr><td>&lt;1&gt;</td><td>2</td></tr></table>"
+        );
+    }
+}
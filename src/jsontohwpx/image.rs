@@ -1,40 +1,224 @@
 use std::path::Path;
 
-use crate::hwpx::{HwpxImage, HwpxWriter};
+use crate::hwpx::{
+    HwpxAlignment, HwpxImage, HwpxParagraphStyle, HwpxTextStyle, HwpxWriter, ImageHandle,
+};
+
+use super::error::{ConvertLimit, JsonToHwpxError, Result};
+use super::model::{ContentAlign, ConvertLimits, ImageLength};
+
+/// 해상도 정보가 없는 이미지의 DPI
+const DEFAULT_DPI: f64 = 96.0;
+
+/// image 콘텐츠의 크기, 정렬, 캡션 (지정하지 않으면 원본 크기로 정렬 없이)
+#[derive(Debug, Clone, Copy)]
+pub struct ImageOptions<'a> {
+    /// `"60%"`(본문 너비 대비), `"80mm"`, `"300px"`, `"300"`
+    pub width: Option<&'a str>,
+    /// `"80mm"`, `"300px"`, `"300"`. 한쪽만 주면 원본 비율을 유지한다.
+    pub height: Option<&'a str>,
+    pub align: Option<ContentAlign>,
+    /// 그림 아래 단락으로 넣을 캡션
+    pub caption: Option<&'a str>,
+    /// 본문 글자 크기 (pt). 캡션은 이보다 1pt 작게 쓴다.
+    pub body_size_pt: u32,
+    /// 이 그림을 넣은 뒤 문서의 그림 바이트 합계 상한
+    pub max_total_bytes: usize,
+}
+
+impl Default for ImageOptions<'_> {
+    fn default() -> Self {
+        Self {
+            width: None,
+            height: None,
+            align: None,
+            caption: None,
+            body_size_pt: 10,
+            max_total_bytes: ConvertLimits::DEFAULT.max_total_image_bytes,
+        }
+    }
+}
 
-use super::error::{JsonToHwpxError, Result};
+/// skipImages일 때 그림 대신 넣는 단락 텍스트. base64와 `data:` URL은 내용을 생략한다
+pub fn skipped_image_text(url: Option<&str>, base64: bool) -> String {
+    match url.filter(|url| !base64 && !url.starts_with("data:")) {
+        Some(url) => format!("[이미지: {}]", url),
+        None => "[이미지: base64 생략]".to_string(),
+    }
+}
 
 /// 이미지 URL/경로에서 이미지를 로드하여 HwpxWriter에 추가
-pub fn add_image_from_url(writer: &mut HwpxWriter, url: &str, base_path: &Path) -> Result<()> {
+pub fn add_image_from_url(
+    writer: &mut HwpxWriter,
+    url: &str,
+    base_path: &Path,
+    options: &ImageOptions<'_>,
+) -> Result<ImageHandle> {
+    let image = image_from_url(url, base_path)?;
+    add_image(writer, image, options)
+}
+
+/// HTML `img src`(경로, URL 또는 `data:` base64)의 이미지를 읽는다
+///
+/// 문서에 넣지는 않는다. 표 셀 그림처럼 호출한 쪽에서 넣은 뒤
+/// [`check_total_bytes`]로 용량을 확인한다.
+pub fn load_image(src: &str, base_path: &Path) -> Result<HwpxImage> {
+    // data:image/png;base64,....
+    if let Some(data_url) = src.strip_prefix("data:") {
+        let (meta, data) = data_url.split_once(',').unwrap_or((data_url, ""));
+        let mime = meta.strip_suffix(";base64").ok_or_else(|| {
+            JsonToHwpxError::Conversion("base64가 아닌 data: URL 이미지".to_string())
+        })?;
+        let bytes = convert_if_needed_by_format(decode_base64(data)?, mime.strip_prefix("image/"))?;
+        return HwpxImage::from_bytes(bytes).ok_or_else(|| {
+            JsonToHwpxError::Conversion("Base64 이미지 포맷 인식 실패".to_string())
+        });
+    }
+    image_from_url(src, base_path)
+}
+
+fn image_from_url(url: &str, base_path: &Path) -> Result<HwpxImage> {
     let image_bytes = load_image_bytes(url, base_path)?;
     let image_bytes = convert_if_needed(image_bytes, url)?;
 
-    let image = HwpxImage::from_bytes(image_bytes).ok_or_else(|| {
-        JsonToHwpxError::Conversion(format!("지원하지 않는 이미지 포맷: {}", url))
-    })?;
+    HwpxImage::from_bytes(image_bytes)
+        .ok_or_else(|| JsonToHwpxError::Conversion(format!("지원하지 않는 이미지 포맷: {}", url)))
+}
 
-    writer.add_image(image)?;
+/// 문서의 그림 바이트 합계가 `max_total_bytes`를 넘으면 `LimitExceeded` 에러
+pub fn check_total_bytes(writer: &HwpxWriter, max_total_bytes: usize) -> Result<()> {
+    let total = writer.embedded_image_bytes();
+    if total > max_total_bytes {
+        return Err(JsonToHwpxError::LimitExceeded(
+            ConvertLimit::TotalImageBytes,
+            format!(
+                "그림 용량 합계가 너무 큽니다 ({} 바이트, 최대 {} 바이트)",
+                total, max_total_bytes
+            ),
+        ));
+    }
     Ok(())
 }
 
+/// image 콘텐츠의 base64 데이터를 디코딩
+pub fn decode_base64(data: &str) -> Result<Vec<u8>> {
+    use base64::Engine;
+    base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .map_err(|e| JsonToHwpxError::Conversion(format!("Base64 디코딩 실패: {}", e)))
+}
+
 /// Base64 인코딩된 이미지를 디코딩하여 HwpxWriter에 추가
 pub fn add_image_from_base64(
     writer: &mut HwpxWriter,
     data: &str,
     format: Option<&str>,
-) -> Result<()> {
-    use base64::Engine;
-    let bytes = base64::engine::general_purpose::STANDARD
-        .decode(data)
-        .map_err(|e| JsonToHwpxError::Conversion(format!("Base64 디코딩 실패: {}", e)))?;
+    options: &ImageOptions<'_>,
+) -> Result<ImageHandle> {
+    let bytes = decode_base64(data)?;
 
     let bytes = convert_if_needed_by_format(bytes, format)?;
 
     let image = HwpxImage::from_bytes(bytes)
         .ok_or_else(|| JsonToHwpxError::Conversion("Base64 이미지 포맷 인식 실패".to_string()))?;
 
-    writer.add_image(image)?;
-    Ok(())
+    add_image(writer, image, options)
+}
+
+/// 크기, 정렬을 적용하여 그림 단락을 추가하고 캡션 단락을 잇는다
+///
+/// - `%`는 본문(단) 너비 대비, px는 이미지에 기록된 해상도(없으면 96 DPI)로 mm 변환
+/// - 형식은 [`ApiResponse::validate`](super::ApiResponse::validate)에서 검증된다
+/// - 캡션은 그림과 같은 정렬(지정하지 않으면 가운데)로 쓴다
+/// - 넣은 뒤 그림 바이트 합계가 `max_total_bytes`를 넘으면 `LimitExceeded` 에러
+fn add_image(
+    writer: &mut HwpxWriter,
+    mut image: HwpxImage,
+    options: &ImageOptions<'_>,
+) -> Result<ImageHandle> {
+    let dpi = detect_dpi(&image.data).unwrap_or(DEFAULT_DPI);
+    let column_mm = writer.column_width_mm();
+    let to_mm = |value: Option<&str>| {
+        value
+            .and_then(ImageLength::parse)
+            .map(|length| match length {
+                ImageLength::Percent(percent) => column_mm * percent / 100.0,
+                ImageLength::Mm(mm) => mm,
+                ImageLength::Px(px) => px * 25.4 / dpi,
+            })
+    };
+
+    let ratio = match (image.width_mm, image.height_mm) {
+        (Some(w), Some(h)) if w > 0 => h as f64 / w as f64,
+        _ => 1.0,
+    };
+    let size = match (to_mm(options.width), to_mm(options.height)) {
+        (Some(w), Some(h)) => Some((w, h)),
+        (Some(w), None) => Some((w, w * ratio)),
+        (None, Some(h)) => Some((h / ratio, h)),
+        (None, None) => None,
+    };
+    if let Some((w, h)) = size {
+        image = image.with_size((w.round() as u32).max(1), (h.round() as u32).max(1));
+    }
+
+    let handle = match options.align {
+        Some(align) => {
+            let para_style = HwpxParagraphStyle::new().alignment(alignment(align));
+            writer.add_image_with_style(image, &para_style)?
+        }
+        None => writer.add_image(image)?,
+    };
+    check_total_bytes(writer, options.max_total_bytes)?;
+
+    if let Some(caption) = options.caption.filter(|c| !c.trim().is_empty()) {
+        let align = options.align.map_or(HwpxAlignment::Center, alignment);
+        let para_style = HwpxParagraphStyle::new().alignment(align);
+        let style = HwpxTextStyle::new().size(options.body_size_pt.saturating_sub(1).max(1));
+        writer.add_styled_paragraph_with_style(caption, style, &para_style)?;
+    }
+    Ok(handle)
+}
+
+fn alignment(align: ContentAlign) -> HwpxAlignment {
+    match align {
+        ContentAlign::Left => HwpxAlignment::Left,
+        ContentAlign::Center => HwpxAlignment::Center,
+        ContentAlign::Right => HwpxAlignment::Right,
+        ContentAlign::Justify => HwpxAlignment::Justify,
+    }
+}
+
+/// PNG `pHYs`, JPEG JFIF 헤더에 기록된 가로 해상도 (DPI)
+fn detect_dpi(bytes: &[u8]) -> Option<f64> {
+    let be_u16 = |at: usize| Some(u16::from_be_bytes(bytes.get(at..at + 2)?.try_into().ok()?));
+    let be_u32 = |at: usize| Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?));
+
+    let dpi = if bytes.starts_with(&[0x89, 0x50, 0x4E, 0x47]) {
+        // 청크: 길이(4) + 종류(4) + 데이터 + CRC(4). pHYs는 IDAT 앞에 온다.
+        let mut at = 8;
+        loop {
+            let len = be_u32(at)? as usize;
+            match bytes.get(at + 4..at + 8)? {
+                b"pHYs" if bytes.get(at + 16) == Some(&1) => {
+                    // 단위 1 = 미터당 픽셀
+                    break be_u32(at + 8)? as f64 * 0.0254;
+                }
+                b"IDAT" | b"IEND" => return None,
+                _ => at += 12 + len,
+            }
+        }
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF, 0xE0]) && bytes.get(6..11) == Some(b"JFIF\0") {
+        let density = be_u16(14)? as f64;
+        match bytes.get(13)? {
+            1 => density,
+            2 => density * 2.54,
+            _ => return None,
+        }
+    } else {
+        return None;
+    };
+    (dpi > 0.0).then_some(dpi)
 }
 
 /// URL 또는 로컬 경로에서 이미지 바이트를 로드
@@ -275,7 +459,7 @@ mod tests {
         // 1x1 투명 PNG의 Base64
         let b64 = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNk+M9QDwADhgGAWjR9awAAAABJRU5ErkJggg==";
         let mut writer = HwpxWriter::new();
-        add_image_from_base64(&mut writer, b64, Some("png")).unwrap();
+        add_image_from_base64(&mut writer, b64, Some("png"), &ImageOptions::default()).unwrap();
         let bytes = writer.to_bytes().unwrap();
         assert!(!bytes.is_empty());
     }
@@ -283,16 +467,60 @@ mod tests {
     #[test]
     fn test_base64_invalid_data() {
         let mut writer = HwpxWriter::new();
-        let result = add_image_from_base64(&mut writer, "!!!invalid!!!", Some("png"));
+        let result = add_image_from_base64(
+            &mut writer,
+            "!!!invalid!!!",
+            Some("png"),
+            &ImageOptions::default(),
+        );
         assert!(result.is_err());
     }
 
+    // --- 해상도 감지 테스트 ---
+
+    #[test]
+    fn test_detect_dpi_png_phys() {
+        let mut png = vec![0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+        // IHDR (13바이트 데이터)
+        png.extend_from_slice(&13u32.to_be_bytes());
+        png.extend_from_slice(b"IHDR");
+        png.extend_from_slice(&[0; 13 + 4]);
+        // pHYs: 5669 px/m (144 DPI), 단위 미터
+        png.extend_from_slice(&9u32.to_be_bytes());
+        png.extend_from_slice(b"pHYs");
+        png.extend_from_slice(&5669u32.to_be_bytes());
+        png.extend_from_slice(&5669u32.to_be_bytes());
+        png.extend_from_slice(&[1, 0, 0, 0, 0]);
+
+        let dpi = detect_dpi(&png).unwrap();
+        assert!((dpi - 144.0).abs() < 0.1, "{}", dpi);
+        assert_eq!(detect_dpi(&png[..33]), None);
+    }
+
+    #[test]
+    fn test_detect_dpi_jfif() {
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10];
+        jpeg.extend_from_slice(b"JFIF\0");
+        jpeg.extend_from_slice(&[1, 1, 1, 0, 72, 0, 72]);
+        assert_eq!(detect_dpi(&jpeg), Some(72.0));
+
+        // 단위 0 = 비율만 기록
+        jpeg[13] = 0;
+        assert_eq!(detect_dpi(&jpeg), None);
+    }
+
     // --- 로컬 파일 로딩 테스트 ---
 
     #[test]
     fn test_load_local_png() {
         let mut writer = HwpxWriter::new();
-        add_image_from_url(&mut writer, "./test_img.png", &examples_path()).unwrap();
+        add_image_from_url(
+            &mut writer,
+            "./test_img.png",
+            &examples_path(),
+            &ImageOptions::default(),
+        )
+        .unwrap();
         let bytes = writer.to_bytes().unwrap();
         assert!(!bytes.is_empty());
     }
@@ -300,7 +528,13 @@ mod tests {
     #[test]
     fn test_load_local_jpg() {
         let mut writer = HwpxWriter::new();
-        add_image_from_url(&mut writer, "./test_img.jpg", &examples_path()).unwrap();
+        add_image_from_url(
+            &mut writer,
+            "./test_img.jpg",
+            &examples_path(),
+            &ImageOptions::default(),
+        )
+        .unwrap();
         let bytes = writer.to_bytes().unwrap();
         assert!(!bytes.is_empty());
     }
@@ -308,7 +542,13 @@ mod tests {
     #[test]
     fn test_load_local_webp_converts_to_png() {
         let mut writer = HwpxWriter::new();
-        add_image_from_url(&mut writer, "./test_img.webp", &examples_path()).unwrap();
+        add_image_from_url(
+            &mut writer,
+            "./test_img.webp",
+            &examples_path(),
+            &ImageOptions::default(),
+        )
+        .unwrap();
         let bytes = writer.to_bytes().unwrap();
         assert!(!bytes.is_empty());
     }
@@ -316,7 +556,13 @@ mod tests {
     #[test]
     fn test_load_local_gif_extracts_first_frame() {
         let mut writer = HwpxWriter::new();
-        add_image_from_url(&mut writer, "./test_img.gif", &examples_path()).unwrap();
+        add_image_from_url(
+            &mut writer,
+            "./test_img.gif",
+            &examples_path(),
+            &ImageOptions::default(),
+        )
+        .unwrap();
         let bytes = writer.to_bytes().unwrap();
         assert!(!bytes.is_empty());
     }
@@ -324,7 +570,12 @@ mod tests {
     #[test]
     fn test_load_nonexistent_file_fails() {
         let mut writer = HwpxWriter::new();
-        let result = add_image_from_url(&mut writer, "./nonexistent.png", &examples_path());
+        let result = add_image_from_url(
+            &mut writer,
+            "./nonexistent.png",
+            &examples_path(),
+            &ImageOptions::default(),
+        );
         assert!(result.is_err());
     }
 
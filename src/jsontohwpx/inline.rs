@@ -0,0 +1,154 @@
+use std::borrow::Cow;
+
+use crate::hwpx::{HwpxTextStyle, HwpxWriter, StyledText};
+
+use super::error::Result;
+
+/// 코드(`code`, 코드 블록)에 쓰는 고정폭 글꼴
+pub(crate) const MONOSPACE_FONT: &str = "굴림체";
+
+/// 링크 URL에 허용하는 스킴
+const LINK_SCHEMES: [&str; 3] = ["http://", "https://", "mailto:"];
+
+/// 링크 URL이 허용하는 스킴(http, https, mailto)이 아니면 그 이유
+///
+/// link, text 콘텐츠는 검증에서 거부하고, HTML·Markdown 안의 링크는
+/// [`dropped_link_warning`]으로 경고하고 텍스트만 남긴다.
+pub(crate) fn link_url_error(url: &str) -> Option<String> {
+    let lower = url.trim().to_ascii_lowercase();
+    if LINK_SCHEMES.iter().any(|scheme| lower.starts_with(scheme)) {
+        None
+    } else {
+        Some(format!(
+            "링크 URL은 http, https, mailto만 허용됩니다 (url={})",
+            url
+        ))
+    }
+}
+
+/// 허용하지 않는 스킴이라 버린 링크의 경고 (코드, 메시지)
+pub(crate) fn dropped_link_warning(url: &str) -> (&'static str, String) {
+    let message = format!(
+        "링크를 버리고 텍스트만 변환했습니다: http, https, mailto만 허용됩니다 (url={})",
+        url
+    );
+    ("LINK_DROPPED", message)
+}
+
+/// HTML 텍스트 노드를 문서 텍스트로
+///
+/// 문자 참조는 파서가 이미 풀었으므로 다시 풀지 않는다 (`&amp;lt;`는 `&lt;` 그대로).
+/// NBSP(U+00A0)만 보통 공백으로 바꾼다. 한글에서 NBSP는 빈 네모로 보이기 때문이다.
+pub(crate) fn html_text(text: &str) -> Cow<'_, str> {
+    if text.contains('\u{A0}') {
+        Cow::Owned(text.replace('\u{A0}', " "))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// 현재 적용 중인 인라인 서식
+#[derive(Clone, Default)]
+pub(crate) struct InlineStyle {
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub code: bool,
+    pub link: Option<String>,
+}
+
+impl InlineStyle {
+    fn text_style(&self) -> HwpxTextStyle {
+        HwpxTextStyle {
+            font_name: self.code.then(|| MONOSPACE_FONT.to_string()),
+            bold: self.bold,
+            italic: self.italic,
+            underline: self.underline,
+            ..Default::default()
+        }
+    }
+
+    fn is_plain(&self) -> bool {
+        !self.bold && !self.italic && !self.underline && !self.code && self.link.is_none()
+    }
+}
+
+/// 서식이 다른 텍스트 조각(run)을 모아 한 단락으로 내보낸다
+///
+/// HTML, Markdown 콘텐츠가 같은 서식 매핑을 쓰도록 공유한다.
+#[derive(Default)]
+pub(crate) struct RunBuilder {
+    /// 아직 단락으로 내보내지 않은 run과 그 서식
+    runs: Vec<(String, InlineStyle)>,
+    pub style: InlineStyle,
+}
+
+impl RunBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 현재 서식으로 텍스트를 추가. 연속된 공백은 한 칸으로 줄인다.
+    pub fn push_text(&mut self, text: &str) {
+        let mut collapsed = String::with_capacity(text.len());
+        for (idx, word) in text.split_whitespace().enumerate() {
+            if idx > 0 {
+                collapsed.push(' ');
+            }
+            collapsed.push_str(word);
+        }
+        if text.starts_with(char::is_whitespace) {
+            collapsed.insert(0, ' ');
+        }
+        if text.ends_with(char::is_whitespace) && !collapsed.ends_with(' ') {
+            collapsed.push(' ');
+        }
+        if !collapsed.is_empty() {
+            self.runs.push((collapsed, self.style.clone()));
+        }
+    }
+
+    /// 서식을 버린 텍스트만 꺼낸다 (제목, 목록 항목, 표 셀용)
+    pub fn take_text(&mut self) -> String {
+        self.take_runs().into_iter().map(|(t, _)| t).collect()
+    }
+
+    /// 모아 둔 run을 한 단락으로 추가 (공백뿐이면 버림)
+    pub fn flush(&mut self, writer: &mut HwpxWriter) -> Result<()> {
+        let runs = self.take_runs();
+        if runs.is_empty() {
+            return Ok(());
+        }
+
+        if runs.iter().all(|(_, style)| style.is_plain()) {
+            let text: String = runs.iter().map(|(t, _)| t.as_str()).collect();
+            writer.add_paragraph(&text)?;
+        } else {
+            let runs = runs
+                .iter()
+                .map(|(text, style)| {
+                    let run = StyledText::with_style(text, style.text_style());
+                    match &style.link {
+                        Some(url) => run.link(url),
+                        None => run,
+                    }
+                })
+                .collect();
+            writer.add_mixed_styled_paragraph(runs)?;
+        }
+        Ok(())
+    }
+
+    /// 단락 앞뒤 공백을 걷어낸 run 목록
+    fn take_runs(&mut self) -> Vec<(String, InlineStyle)> {
+        let mut runs = std::mem::take(&mut self.runs);
+        if let Some((first, _)) = runs.first_mut() {
+            *first = first.trim_start().to_string();
+        }
+        if let Some((last, _)) = runs.last_mut() {
+            *last = last.trim_end().to_string();
+        }
+        runs.retain(|(text, _)| !text.is_empty());
+        runs
+    }
+}
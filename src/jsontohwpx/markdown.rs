@@ -0,0 +1,356 @@
+use crate::hwpx::HwpxWriter;
+
+use super::error::Result;
+use super::inline::{dropped_link_warning, link_url_error, RunBuilder};
+use super::model::ListItem;
+use super::table;
+use super::text;
+
+/// Markdown 본문을 제목, 단락, 목록, 표로 변환하여 추가
+///
+/// 위키 내보내기에 쓰이는 범위만 다루는 간단한 파서다.
+///
+/// - `#` ~ `######` → 제목 ([`text::add_heading`])
+/// - 빈 줄로 나뉜 문단 → 단락 (줄바꿈은 공백, 줄 끝 공백 두 칸이나 `\`는 단락 나눔)
+/// - `-`/`*`/`+`, `1.` 목록 → 목록 ([`text::add_list`], 더 들여쓴 항목은 하위 목록)
+/// - ```` ``` ```` 코드 블록 → 코드 블록 ([`text::add_code_block`])
+/// - 둘째 줄이 `|---|` 인 `|` 표 → 표 ([`table::add_table_from_html`])
+/// - 인라인 `**굵게**`, `*기울임*`, `` `코드` ``, `[텍스트](URL)` (URL 안의 괄호는 짝이 맞아야 함)
+///
+/// 제목, 목록 항목, 표 셀의 인라인 서식은 텍스트만 남긴다. 표마다 셀 수가
+/// `max_table_cells`를 넘으면 `LimitExceeded` 에러.
+///
+/// 링크 스킴이 http, https, mailto가 아니면 텍스트만 남기고 `LINK_DROPPED` 경고를
+/// (코드, 메시지)로 돌려준다.
+pub fn add_markdown(
+    writer: &mut HwpxWriter,
+    value: &str,
+    body_size_pt: u32,
+    max_table_cells: usize,
+) -> Result<Vec<(&'static str, String)>> {
+    let lines: Vec<&str> = value.lines().collect();
+    let mut paragraph = RunBuilder::new();
+    let mut warnings = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() {
+            paragraph.flush(writer)?;
+            i += 1;
+        } else if let Some(fence) = ["```", "~~~"].into_iter().find(|f| trimmed.starts_with(f)) {
+            paragraph.flush(writer)?;
+            i += 1;
+            let mut code = Vec::new();
+            while i < lines.len() && !lines[i].trim().starts_with(fence) {
+                code.push(lines[i].trim_end());
+                i += 1;
+            }
+            text::add_code_block(writer, &code.join("\n"), body_size_pt)?;
+            i += 1;
+        } else if let Some((level, text)) = heading(trimmed) {
+            paragraph.flush(writer)?;
+            text::add_heading(writer, &inline_text(text), level, body_size_pt)?;
+            i += 1;
+        } else if let Some((_, ordered, _)) = list_marker(line) {
+            paragraph.flush(writer)?;
+            let mut entries: Vec<(usize, String)> = Vec::new();
+            while i < lines.len() {
+                if let Some((indent, _, text)) = list_marker(lines[i]) {
+                    entries.push((indent, text.to_string()));
+                } else if lines[i].trim().is_empty() {
+                    // 빈 줄 뒤에 항목이 이어지면 같은 목록
+                    if lines.get(i + 1).is_none_or(|l| list_marker(l).is_none()) {
+                        break;
+                    }
+                } else if lines[i].starts_with(char::is_whitespace) {
+                    // 들여쓴 줄은 앞 항목의 이어지는 텍스트
+                    let (_, last) = entries.last_mut().expect("list has an item");
+                    last.push(' ');
+                    last.push_str(lines[i].trim());
+                } else {
+                    break;
+                }
+                i += 1;
+            }
+            text::add_list(writer, ordered, &build_list(&entries))?;
+        } else if trimmed.starts_with('|') && lines.get(i + 1).is_some_and(|l| is_delimiter_row(l))
+        {
+            paragraph.flush(writer)?;
+            let mut html = String::from("<table>");
+            push_table_row(&mut html, "th", trimmed);
+            i += 2;
+            while i < lines.len() && lines[i].trim().starts_with('|') {
+                push_table_row(&mut html, "td", lines[i].trim());
+                i += 1;
+            }
+            html.push_str("</table>");
+            warnings.extend(table::add_table_from_html(
+                writer,
+                &html,
+                max_table_cells,
+                None,
+            )?);
+        } else if let Some(text) = line.strip_suffix("  ").or_else(|| line.strip_suffix('\\')) {
+            push_inline(&mut paragraph, text, &mut warnings);
+            paragraph.flush(writer)?;
+            i += 1;
+        } else {
+            push_inline(&mut paragraph, line, &mut warnings);
+            paragraph.push_text(" ");
+            i += 1;
+        }
+    }
+    paragraph.flush(writer)?;
+    Ok(warnings)
+}
+
+/// `## 제목 ##` → (2, "제목")
+fn heading(line: &str) -> Option<(u8, &str)> {
+    let level = line.len() - line.trim_start_matches('#').len();
+    let rest = &line[level..];
+    if !(1..=6).contains(&level) || !(rest.is_empty() || rest.starts_with(' ')) {
+        return None;
+    }
+    let rest = rest.trim();
+    // 닫는 #은 앞에 공백이 있을 때만 버린다 ("C#"은 그대로)
+    let without_closing = rest.trim_end_matches('#');
+    let text = if without_closing.is_empty() || without_closing.ends_with(' ') {
+        without_closing.trim_end()
+    } else {
+        rest
+    };
+    Some((level as u8, text))
+}
+
+/// 목록 항목 줄 → (들여쓰기 폭, 번호 목록 여부, 텍스트)
+fn list_marker(line: &str) -> Option<(usize, bool, &str)> {
+    let body = line.trim_start();
+    let indent = line[..line.len() - body.len()]
+        .chars()
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum();
+
+    let digits = body.len() - body.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let (ordered, rest) = if digits > 0 {
+        (true, body[digits..].strip_prefix(['.', ')'])?)
+    } else {
+        (false, body.strip_prefix(['-', '*', '+'])?)
+    };
+    rest.strip_prefix(' ')
+        .map(|text| (indent, ordered, text.trim()))
+}
+
+/// 들여쓰기 폭으로 항목 트리를 만든다
+fn build_list(entries: &[(usize, String)]) -> Vec<ListItem> {
+    let mut items = Vec::new();
+    let mut rest = entries;
+    while let Some(((indent, text), tail)) = rest.split_first() {
+        let nested = tail.iter().take_while(|(i, _)| i > indent).count();
+        items.push(ListItem {
+            text: inline_text(text),
+            children: build_list(&tail[..nested]),
+        });
+        rest = &tail[nested..];
+    }
+    items
+}
+
+/// `|---|:---:|` 형태의 표 구분 줄
+fn is_delimiter_row(line: &str) -> bool {
+    let line = line.trim();
+    line.starts_with('|')
+        && line.contains('-')
+        && line.chars().all(|c| matches!(c, '|' | '-' | ':' | ' '))
+}
+
+fn push_table_row(html: &mut String, tag: &str, line: &str) {
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line.strip_suffix('|').unwrap_or(line);
+    html.push_str("<tr>");
+    for cell in line.split('|') {
+        let text = inline_text(cell.trim())
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;");
+        html.push_str(&format!("<{tag}>{text}</{tag}>"));
+    }
+    html.push_str("</tr>");
+}
+
+/// 인라인 서식을 버린 텍스트 (링크도 버리므로 스킴 경고는 내지 않는다)
+fn inline_text(text: &str) -> String {
+    let mut runs = RunBuilder::new();
+    push_inline(&mut runs, text, &mut Vec::new());
+    runs.take_text()
+}
+
+/// 한 줄의 인라인 서식을 해석하여 run으로 추가
+///
+/// 여는 `*`, `**`는 같은 줄에 닫는 기호가 있을 때만 서식으로 본다. 허용하지 않는
+/// 스킴의 링크는 텍스트만 남기고 `warnings`에 적는다.
+fn push_inline(runs: &mut RunBuilder, text: &str, warnings: &mut Vec<(&'static str, String)>) {
+    let saved = runs.style.clone();
+    let mut plain = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let after = &rest[c.len_utf8()..];
+        match c {
+            '\\' if after.starts_with(|n: char| n.is_ascii_punctuation()) => {
+                plain.push_str(&after[..1]);
+                rest = &after[1..];
+                continue;
+            }
+            '*' if after.starts_with('*') => {
+                let after = &after[1..];
+                if runs.style.bold || after.contains("**") {
+                    runs.push_text(&std::mem::take(&mut plain));
+                    runs.style.bold = !runs.style.bold;
+                    rest = after;
+                    continue;
+                }
+            }
+            '*' if runs.style.italic || after.contains('*') => {
+                runs.push_text(&std::mem::take(&mut plain));
+                runs.style.italic = !runs.style.italic;
+                rest = after;
+                continue;
+            }
+            '`' => {
+                if let Some(end) = after.find('`') {
+                    runs.push_text(&std::mem::take(&mut plain));
+                    runs.style.code = true;
+                    runs.push_text(&after[..end]);
+                    runs.style.code = false;
+                    rest = &after[end + 1..];
+                    continue;
+                }
+            }
+            '[' => {
+                if let Some((label, url, tail)) = link(after) {
+                    runs.push_text(&std::mem::take(&mut plain));
+                    let outer = runs.style.link.clone();
+                    if link_url_error(url).is_some() {
+                        warnings.push(dropped_link_warning(url));
+                    } else {
+                        runs.style.link = Some(url.to_string());
+                    }
+                    push_inline(runs, label, warnings);
+                    runs.style.link = outer;
+                    rest = tail;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        plain.push(c);
+        rest = after;
+    }
+    runs.push_text(&plain);
+    runs.style = saved;
+}
+
+/// `텍스트](URL)나머지` → (텍스트, URL, 나머지)
+///
+/// URL은 짝이 맞는 괄호를 포함할 수 있다 (`https://a.b/x_(y)`).
+fn link(text: &str) -> Option<(&str, &str, &str)> {
+    let close = text.find("](")?;
+    let rest = &text[close + 2..];
+    let mut depth = 0usize;
+    let end = rest.char_indices().find_map(|(idx, c)| match c {
+        '(' => {
+            depth += 1;
+            None
+        }
+        ')' if depth == 0 => Some(idx),
+        ')' => {
+            depth -= 1;
+            None
+        }
+        _ => None,
+    })?;
+    Some((&text[..close], rest[..end].trim(), &rest[end + 1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_helpers() {
+        assert_eq!(heading("## 개요 ##"), Some((2, "개요")));
+        assert_eq!(heading("# C#"), Some((1, "C#")));
+        assert_eq!(heading("#해시태그"), None);
+        assert_eq!(heading("####### 일곱"), None);
+
+        assert_eq!(list_marker("- 항목"), Some((0, false, "항목")));
+        assert_eq!(list_marker("    2) 둘째"), Some((4, true, "둘째")));
+        assert_eq!(list_marker("**굵게**"), None);
+        assert_eq!(list_marker("---"), None);
+
+        assert!(is_delimiter_row("| --- | :-: |"));
+        assert!(!is_delimiter_row("| a | b |"));
+    }
+
+    #[test]
+    fn test_inline_text_strips_markers() {
+        assert_eq!(
+            inline_text(r"**굵게** *기울임* `code` [링크](https://a.b) 2 \* 3 * 4"),
+            "굵게 기울임 code 링크 2 * 3 * 4"
+        );
+    }
+
+    #[test]
+    fn test_link_url_with_parentheses() {
+        assert_eq!(
+            link("위키](https://ko.wikipedia.org/wiki/A_(B)) 뒤"),
+            Some(("위키", "https://ko.wikipedia.org/wiki/A_(B)", " 뒤"))
+        );
+        assert_eq!(
+            link("z](javascript:alert(3))"),
+            Some(("z", "javascript:alert(3)", ""))
+        );
+        assert_eq!(link("z](https://a.b/(열림"), None);
+    }
+
+    #[test]
+    fn test_unsafe_links_are_dropped_with_warning() {
+        let mut writer = HwpxWriter::new();
+        let warnings = add_markdown(
+            &mut writer,
+            "[z](javascript:alert(3)) 그리고 [위키](https://a.b/A_(B))",
+            10,
+            usize::MAX,
+        )
+        .unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].0, "LINK_DROPPED");
+        assert!(
+            warnings[0].1.contains("(url=javascript:alert(3))"),
+            "{}",
+            warnings[0].1
+        );
+
+        let bytes = writer.to_bytes().unwrap();
+        let links = crate::HwpxReader::extract_hyperlinks(&bytes).unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].url, "https://a.b/A_(B)");
+        assert_eq!(links[0].text, "위키");
+    }
+
+    #[test]
+    fn test_build_list_nests_by_indent() {
+        let entries: Vec<(usize, String)> = [(0, "a"), (2, "a1"), (4, "a1x"), (2, "a2"), (0, "b")]
+            .iter()
+            .map(|(i, t)| (*i, t.to_string()))
+            .collect();
+        let items = build_list(&entries);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].children.len(), 2);
+        assert_eq!(items[0].children[0].children[0].text, "a1x");
+        assert_eq!(items[1].text, "b");
+    }
+}
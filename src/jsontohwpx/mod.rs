@@ -1,11 +1,23 @@
 pub mod api;
+pub mod batch;
 pub mod converter;
 pub mod error;
+pub mod file;
+pub mod html;
+pub mod hwpxtojson;
 pub mod image;
+mod inline;
+pub mod markdown;
 pub mod model;
 pub mod table;
+pub mod template;
 pub mod text;
 
-pub use converter::{convert, convert_to_file};
-pub use error::{JsonToHwpxError, Result};
-pub use model::ApiResponse;
+pub use batch::{convert_batch, BatchMode, BatchOutput};
+pub use converter::{
+    convert, convert_into, convert_to_file, convert_to_output, convert_with_text,
+    convert_with_warnings, ConvertOutput, ConvertWarning,
+};
+pub use error::{ConvertLimit, JsonToHwpxError, Result};
+pub use hwpxtojson::ArticleDocumentExport;
+pub use model::{ApiResponse, ConvertLimits, TextNormalization};
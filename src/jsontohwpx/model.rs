@@ -1,24 +1,177 @@
-use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::{Deserialize, Serialize};
+
+use crate::model::PaperSize;
 
 use super::error::{JsonToHwpxError, Result};
+use super::inline::link_url_error;
+use super::{image, table, template};
 
 /// API 응답 최상위 구조
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
+///
+/// 두 가지 입력 모양을 받아 같은 구조로 맞춘다.
+///
+/// - 게시판 API 응답: `{"responseCode": "0", "data": {"article": {"atclId", "subject", ...}}}`
+/// - 평평한 문서: `{"article_id", "title", "metadata": {"author", "department", "created_at",
+///   "comments"}, "contents", "options"}`. `article_id` → atclId, `title` → subject, `metadata`의
+///   author/department/created_at/comments → regEmpName/regDeptName/regDt/comments로 옮기고
+///   responseCode는 "0"
+///
+/// `data`가 없고 `article_id`가 있으면 평평한 문서로 읽는다.
+#[derive(Debug)]
 pub struct ApiResponse {
+    /// 입력 스키마 버전 ("1.2"). 없으면 [`SCHEMA_VERSION`]으로 본다
+    pub schema_version: Option<String>,
     pub response_code: String,
     pub response_text: Option<String>,
-    #[serde(default)]
     pub options: Options,
     pub data: Data,
 }
 
+impl<'de> Deserialize<'de> for ApiResponse {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let flat = value.get("data").is_none() && value.get("article_id").is_some();
+        let input = if flat {
+            serde_json::from_value::<ArticleDocument>(value).map(ApiResponse::from)
+        } else {
+            serde_json::from_value::<ResponseEnvelope>(value).map(ApiResponse::from)
+        };
+        input.map_err(de::Error::custom)
+    }
+}
+
+/// 게시판 API 응답 모양
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ResponseEnvelope {
+    #[serde(default)]
+    schema_version: Option<String>,
+    response_code: String,
+    response_text: Option<String>,
+    #[serde(default)]
+    options: Options,
+    data: Data,
+}
+
+impl From<ResponseEnvelope> for ApiResponse {
+    fn from(envelope: ResponseEnvelope) -> Self {
+        Self {
+            schema_version: envelope.schema_version,
+            response_code: envelope.response_code,
+            response_text: envelope.response_text,
+            options: envelope.options,
+            data: envelope.data,
+        }
+    }
+}
+
+/// 평평한 문서 모양
+#[derive(Deserialize)]
+struct ArticleDocument {
+    #[serde(default, rename = "schemaVersion")]
+    schema_version: Option<String>,
+    article_id: String,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    metadata: DocumentMetadata,
+    #[serde(default)]
+    contents: Vec<Content>,
+    #[serde(default)]
+    options: Options,
+}
+
+#[derive(Default, Deserialize)]
+struct DocumentMetadata {
+    #[serde(default)]
+    author: Option<String>,
+    #[serde(default)]
+    department: Option<String>,
+    #[serde(default)]
+    created_at: Option<String>,
+    #[serde(default)]
+    comments: Vec<Comment>,
+}
+
+impl From<ArticleDocument> for ApiResponse {
+    fn from(document: ArticleDocument) -> Self {
+        Self {
+            schema_version: document.schema_version,
+            response_code: "0".to_string(),
+            response_text: None,
+            options: document.options,
+            data: Data {
+                article: Article {
+                    atcl_id: document.article_id,
+                    subject: document.title,
+                    contents: document.contents,
+                    content_html: None,
+                    reg_dt: document.metadata.created_at,
+                    reg_emp_name: document.metadata.author,
+                    reg_dept_name: document.metadata.department,
+                    board_name: None,
+                    tags: Vec::new(),
+                    attachments: Vec::new(),
+                    comments: document.metadata.comments,
+                    extra: HashMap::new(),
+                },
+            },
+        }
+    }
+}
+
 impl ApiResponse {
+    /// 출력 파일 이름 (`options.filenameTemplate`, 없으면 `{article_id}.hwpx`)
+    pub fn output_filename(&self) -> String {
+        let template = self
+            .options
+            .filename_template
+            .as_deref()
+            .unwrap_or(template::DEFAULT_FILENAME_TEMPLATE);
+        template::render_filename(template, &self.data.article)
+    }
+
     /// 입력 데이터 검증
     ///
+    /// [`validate_document`](Self::validate_document)를 통과하면 [`content_issues`](Self::content_issues)로
+    /// 콘텐츠를 모두 확인하고, 문제가 있으면 전부 `; `로 이어 `Input` 에러로 돌려준다.
+    pub fn validate(&self) -> Result<()> {
+        self.validate_with(&self.options)
+    }
+
+    /// 입력의 `options` 대신 `options`로 [`validate`](Self::validate)와 같이 검증
+    ///
+    /// 일괄 변환이나 `convert_into`처럼 다른 옵션으로 변환할 때 쓴다. 입력의
+    /// `options`는 확인하지 않고, onError, skipImages, limits도 `options`의 값을 따른다.
+    pub fn validate_with(&self, options: &Options) -> Result<()> {
+        self.validate_article()?;
+        options.validate()?;
+
+        let issues = self.content_issues_with(options);
+        if !issues.is_empty() {
+            let messages: Vec<String> = issues.iter().map(ToString::to_string).collect();
+            return Err(JsonToHwpxError::Input(messages.join("; ")));
+        }
+        Ok(())
+    }
+
+    /// contents를 뺀 문서 단위 검증
+    ///
     /// - responseCode == "0" 확인
+    /// - schemaVersion 주 버전 확인
     /// - atclId 비어있지 않음 확인
-    pub fn validate(&self) -> Result<()> {
+    /// - pageSize, marginsMm, dateFormat, labelLocale 옵션 확인
+    pub fn validate_document(&self) -> Result<()> {
+        self.validate_article()?;
+        self.options.validate()
+    }
+
+    /// options를 뺀 문서 단위 검증 (responseCode, schemaVersion, atclId)
+    fn validate_article(&self) -> Result<()> {
         if self.response_code != "0" {
             return Err(JsonToHwpxError::Input(format!(
                 "responseCode가 '0'이 아닙니다: code='{}', text='{}'",
@@ -27,32 +180,643 @@ impl ApiResponse {
             )));
         }
 
-        if self.data.article.atcl_id.trim().is_empty() {
+        self.schema_version()?;
+
+        let atcl_id = &self.data.article.atcl_id;
+        if atcl_id.trim().is_empty() {
             return Err(JsonToHwpxError::Input("atclId가 비어있습니다".to_string()));
         }
-
+        if template::sanitize_article_id(atcl_id).is_empty() {
+            return Err(JsonToHwpxError::Input(format!(
+                "atclId에 파일 이름으로 쓸 수 있는 글자가 없습니다 (atclId={:?})",
+                atcl_id
+            )));
+        }
         Ok(())
     }
+
+    /// contents의 문제를 모두 모은다 (없으면 빈 목록)
+    ///
+    /// - 콘텐츠 종류별 최소 schemaVersion
+    /// - heading level이 1~6 범위인지
+    /// - 링크 URL 스킴, text 색 형식
+    /// - image에 url 또는 base64가 있는지, width/height 형식
+    /// - rows 표의 header_rows, merges 범위
+    ///
+    /// onError가 fail이면 변환하다 실패할 문제도 미리 찾는다: 행이 없는 HTML 표,
+    /// 디코딩할 수 없는 base64 그림(skipImages면 확인하지 않음). skip이면 이런
+    /// 콘텐츠는 변환 때 자리표시 단락으로 바뀐다. HTML 표는 `limits`의 셀 수 한도를
+    /// 넘으면 격자를 만들지 않고 넘어가며, 한도 에러는 변환할 때 낸다.
+    pub fn content_issues(&self) -> Vec<ContentIssue> {
+        self.content_issues_with(&self.options)
+    }
+
+    /// `options`의 onError, skipImages, limits로 찾은 [`content_issues`](Self::content_issues)
+    fn content_issues_with(&self, options: &Options) -> Vec<ContentIssue> {
+        let version = self.schema_version().unwrap_or(SCHEMA_VERSION);
+        self.data
+            .article
+            .contents
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, content)| {
+                content_errors(content, version, options)
+                    .into_iter()
+                    .map(move |message| ContentIssue {
+                        content_index: idx,
+                        message,
+                    })
+            })
+            .collect()
+    }
+
+    /// 입력 스키마 버전. schemaVersion이 없으면 [`SCHEMA_VERSION`]
+    ///
+    /// 주 버전이 [`SCHEMA_VERSION`]과 다르거나 형식이 잘못되면 `Input` 에러.
+    /// 부 버전이 더 높은 1.x는 받아들인다.
+    pub fn schema_version(&self) -> Result<SchemaVersion> {
+        let Some(value) = &self.schema_version else {
+            return Ok(SCHEMA_VERSION);
+        };
+        match SchemaVersion::parse(value) {
+            Some(version) if version.major == SCHEMA_VERSION.major => Ok(version),
+            _ => Err(JsonToHwpxError::Input(format!(
+                "지원하지 않는 schemaVersion입니다: '{}' (지원하는 버전: {})",
+                value,
+                supported_schema_versions()
+            ))),
+        }
+    }
+}
+
+/// 현재 입력 스키마 버전
+pub const SCHEMA_VERSION: SchemaVersion = SchemaVersion { major: 1, minor: 2 };
+
+/// 입력 스키마 버전 (`주.부`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SchemaVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl SchemaVersion {
+    const fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
+    }
+
+    /// "1", "1.2", "1.2.0" 형태. 세 번째 자리는 무시한다
+    pub fn parse(value: &str) -> Option<Self> {
+        let parts = value
+            .trim()
+            .split('.')
+            .map(|part| part.parse::<u32>().ok())
+            .collect::<Option<Vec<_>>>()?;
+        if parts.len() > 3 {
+            return None;
+        }
+        Some(Self::new(parts[0], parts.get(1).copied().unwrap_or(0)))
+    }
+}
+
+impl fmt::Display for SchemaVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// "1.0, 1.1, 1.2"
+fn supported_schema_versions() -> String {
+    (0..=SCHEMA_VERSION.minor)
+        .map(|minor| SchemaVersion::new(SCHEMA_VERSION.major, minor).to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn color_error(color: &str) -> Option<String> {
+    match parse_color(color) {
+        Some(_) => None,
+        None => Some(format!("색은 #RRGGBB 형식이어야 합니다 (color={})", color)),
+    }
+}
+
+/// 콘텐츠 검증 문제 (`contents[n]: 메시지`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentIssue {
+    pub content_index: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ContentIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "contents[{}]: {}", self.content_index, self.message)
+    }
+}
+
+/// 콘텐츠 하나의 문제 목록. [`ApiResponse::content_issues`] 참고
+fn content_errors(content: &Content, version: SchemaVersion, options: &Options) -> Vec<String> {
+    let mut errors = Vec::new();
+    // 변환하다 실패할 문제는 onError가 fail일 때만 본다
+    let conversion_checks = options.on_error == OnError::Fail;
+
+    let (type_name, since) = content.min_schema_version();
+    if version < since {
+        errors.push(format!(
+            "{} 콘텐츠는 schemaVersion {} 이상에서 쓸 수 있습니다 (schemaVersion={})",
+            type_name, since, version
+        ));
+    }
+
+    match content {
+        Content::Heading { level, .. } if !(1..=MAX_HEADING_LEVEL).contains(level) => {
+            errors.push(format!(
+                "heading level은 1~{} 사이여야 합니다 (level={})",
+                MAX_HEADING_LEVEL, level
+            ));
+        }
+        Content::Link { url, .. } => errors.extend(link_url_error(url)),
+        Content::File { url, .. } if url.trim().is_empty() => {
+            errors.push("file에는 url이 필요합니다".to_string());
+        }
+        Content::Image {
+            url,
+            base64,
+            width,
+            height,
+            ..
+        } => {
+            if url.is_none() && base64.is_none() {
+                errors.push("image에는 url 또는 base64가 필요합니다".to_string());
+            }
+            if let Some(data) = base64.as_deref() {
+                if conversion_checks && !options.skip_images {
+                    if let Err(e) = image::decode_base64(data) {
+                        errors.push(error_message(e));
+                    }
+                }
+            }
+            if let Some(width) = width {
+                if ImageLength::parse(width).is_none() {
+                    errors.push(format!(
+                        "이미지 width 형식이 잘못되었습니다 (width={}). 예: \"60%\", \"80mm\", \"300px\"",
+                        width
+                    ));
+                }
+            }
+            if let Some(height) = height {
+                if !matches!(
+                    ImageLength::parse(height),
+                    Some(ImageLength::Mm(_) | ImageLength::Px(_))
+                ) {
+                    errors.push(format!(
+                        "이미지 height 형식이 잘못되었습니다 (height={}). 예: \"80mm\", \"300px\"",
+                        height
+                    ));
+                }
+            }
+        }
+        Content::Text {
+            value,
+            links,
+            style,
+        } => {
+            errors.extend(links.iter().filter_map(|link| link_url_error(&link.url)));
+            if let Some(color) = style.as_ref().and_then(|s| s.color.as_deref()) {
+                errors.extend(color_error(color));
+            }
+            if let TextValue::Runs(runs) = value {
+                if !links.is_empty() {
+                    errors.push("links는 문자열 value에만 쓸 수 있습니다".to_string());
+                }
+                errors.extend(
+                    runs.iter()
+                        .filter_map(|run| run.color.as_deref())
+                        .filter_map(color_error),
+                );
+            }
+        }
+        Content::Table(TableContent::Html { value }) if conversion_checks => {
+            match table::validate_html(value, options.limits.max_table_cells) {
+                // 한도는 변환할 때 같은 자리에서 그 에러 코드로 멈춘다
+                Ok(()) | Err(JsonToHwpxError::LimitExceeded(..)) => {}
+                Err(e) => errors.push(error_message(e)),
+            }
+        }
+        Content::Table(TableContent::Rows(rows)) => {
+            if let Err(e) = table::validate_rows(rows) {
+                errors.push(error_message(e));
+            }
+        }
+        _ => {}
+    }
+    errors
+}
+
+/// 에러 종류 접두어("입력 에러: ")를 뺀 메시지
+fn error_message(e: JsonToHwpxError) -> String {
+    match e {
+        JsonToHwpxError::Input(msg)
+        | JsonToHwpxError::Conversion(msg)
+        | JsonToHwpxError::Hwpx(msg)
+        | JsonToHwpxError::Encrypted(msg) => msg,
+        e => e.to_string(),
+    }
 }
 
+/// heading 콘텐츠의 최대 level
+pub const MAX_HEADING_LEVEL: u8 = 6;
+
 /// 변환 옵션
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Options {
     #[serde(default)]
     pub include_header: bool,
     #[serde(default)]
     pub header_fields: Vec<String>,
+    /// 콘텐츠 사이를 빈 단락 대신 문단 위 간격으로 구분
+    #[serde(default)]
+    pub separator_spacing: bool,
+    /// 본문 기본 글꼴 (지정하지 않으면 맑은 고딕)
+    #[serde(default)]
+    pub default_font: Option<String>,
+    /// 본문 기본 글자 크기 (pt, 지정하지 않으면 10)
+    #[serde(default)]
+    pub default_font_size: Option<u32>,
+    /// 줄 번호 표시 (지정하지 않으면 표시하지 않음)
+    #[serde(default)]
+    pub line_numbers: Option<LineNumberOptions>,
+    /// 본문 끝에 첨부파일 목록 표 삽입
+    #[serde(default)]
+    pub include_attachments: bool,
+    /// 본문 끝에 댓글 목록 삽입
+    #[serde(default)]
+    pub include_comments: bool,
+    /// 문서 끝에 생성 시각과 hwpers 버전을 남기고, ModifiedDate를 생성 시각으로
+    #[serde(default)]
+    pub include_provenance: bool,
+    /// contents가 비어 있으면 contentHtml을 HTML 콘텐츠로 변환 (기본 켜짐)
+    #[serde(default = "default_true")]
+    pub use_content_html_fallback: bool,
+    /// 용지 크기 (A4, A3, Letter, 지정하지 않으면 A4)
+    #[serde(default)]
+    pub page_size: Option<String>,
+    /// 가로 방향
+    #[serde(default)]
+    pub landscape: bool,
+    /// 쪽 여백 (mm, 지정하지 않은 변은 기본값)
+    #[serde(default)]
+    pub margins_mm: Option<MarginsMm>,
+    /// 꼬리말 가운데에 쪽 번호 표시
+    #[serde(default)]
+    pub footer_page_numbers: Option<PageNumberStyle>,
+    /// 꼬리말 텍스트 (쪽 번호가 있으면 그 앞에 표시)
+    #[serde(default)]
+    pub footer_text: Option<String>,
+    /// 머리말 템플릿 (예: "{board_name} - {title}")
+    #[serde(default)]
+    pub header_template: Option<String>,
+    /// 꼬리말 템플릿. 있으면 footerText 대신 쓴다
+    #[serde(default)]
+    pub footer_template: Option<String>,
+    /// 템플릿 `{date}`의 strftime 형식 (예: "%Y.%m.%d")
+    #[serde(default)]
+    pub date_format: Option<String>,
+    /// 출력 파일 이름 템플릿 (예: "{date}_{department}_{article_id}.hwpx")
+    #[serde(default)]
+    pub filename_template: Option<String>,
+    /// 본문 첫머리 제목 모양. 없으면 includeHeader가 꺼져 있을 때 [`TitleStyle::DEFAULT`]
+    #[serde(default)]
+    pub title_style: Option<TitleStyle>,
+    /// 헤더 라벨 언어 ("ko", "en")
+    #[serde(default = "default_label_locale")]
+    pub label_locale: String,
+    /// 헤더 필드 키별 라벨 (labelLocale보다 우선)
+    #[serde(default)]
+    pub label_overrides: HashMap<String, String>,
+    /// 콘텐츠 하나의 변환이 실패했을 때의 처리
+    #[serde(default)]
+    pub on_error: OnError,
+    /// 그림을 읽지 않고 "[이미지: URL]" 단락으로 대신한다 (네트워크, 파일 접근 없음)
+    #[serde(default)]
+    pub skip_images: bool,
+    /// 문자열 text 콘텐츠와 file 텍스트의 정규화 (기본은 입력 그대로)
+    #[serde(default)]
+    pub text_normalization: TextNormalization,
+    /// 바탕 문서로 쓸 HWPX 템플릿 경로 (basePath 기준)
+    #[serde(default)]
+    pub template_path: Option<String>,
+    /// 바탕 문서로 쓸 HWPX 템플릿 바이트 (라이브러리용, templatePath보다 우선)
+    #[serde(skip)]
+    pub template_bytes: Option<Vec<u8>>,
+    /// 변환 한도 (입력 JSON으로는 바꿀 수 없음, 서버 설정이나 라이브러리용)
+    #[serde(skip)]
+    pub limits: ConvertLimits,
 }
 
-/// data 필드
+/// 입력 텍스트 정규화. 모두 끄면(기본) 입력을 그대로 쓴다
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextNormalization {
+    /// `\r\n`과 `\r`을 `\n`으로
+    #[serde(default)]
+    pub crlf_to_lf: bool,
+    /// 폭 없는 공백(U+200B, U+2060, U+FEFF)과 `\n`, `\t` 밖의 제어 문자를 지운다
+    #[serde(default)]
+    pub strip_invisible: bool,
+    /// 연이은 빈 줄을 이 개수까지만 남긴다
+    #[serde(default)]
+    pub max_blank_lines: Option<usize>,
+    /// 유니코드 NFC 정규화 (풀어 쓴 한글 자모를 완성형으로 합치는 등)
+    #[serde(default)]
+    pub nfc: bool,
+}
+
+/// 변환 한도. 큰 입력이 워커를 오래 붙잡지 않도록, 넘으면
+/// [`JsonToHwpxError::LimitExceeded`]로 변환을 멈춘다
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConvertLimits {
+    /// 글 하나의 contents 개수
+    pub max_contents: usize,
+    /// 표 하나의 셀 수 (행 수 × 열 수, 병합된 칸 포함)
+    pub max_table_cells: usize,
+    /// 문서에 넣는 그림 바이트 합계 (같은 그림은 한 번만 셈)
+    pub max_total_image_bytes: usize,
+    /// 본문 단락 수 (표 셀 안의 단락 제외)
+    pub max_paragraphs: usize,
+}
+
+impl ConvertLimits {
+    /// contents 2,000개, 표 셀 20,000개, 그림 100 MB, 단락 50,000개
+    pub const DEFAULT: Self = Self {
+        max_contents: 2_000,
+        max_table_cells: 20_000,
+        max_total_image_bytes: 100 * 1024 * 1024,
+        max_paragraphs: 50_000,
+    };
+}
+
+impl Default for ConvertLimits {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            include_header: false,
+            header_fields: Vec::new(),
+            separator_spacing: false,
+            default_font: None,
+            default_font_size: None,
+            line_numbers: None,
+            include_attachments: false,
+            include_comments: false,
+            include_provenance: false,
+            use_content_html_fallback: true,
+            page_size: None,
+            landscape: false,
+            margins_mm: None,
+            footer_page_numbers: None,
+            footer_text: None,
+            header_template: None,
+            footer_template: None,
+            date_format: None,
+            title_style: None,
+            filename_template: None,
+            label_locale: default_label_locale(),
+            label_overrides: HashMap::new(),
+            on_error: OnError::Fail,
+            skip_images: false,
+            text_normalization: TextNormalization::default(),
+            template_path: None,
+            template_bytes: None,
+            limits: ConvertLimits::DEFAULT,
+        }
+    }
+}
+
+impl Options {
+    /// 용지 크기, 여백, 날짜 형식, 라벨 언어 확인
+    pub fn validate(&self) -> Result<()> {
+        if !LABEL_LOCALES.contains(&self.label_locale.as_str()) {
+            return Err(JsonToHwpxError::Input(format!(
+                "labelLocale은 ko, en 중 하나여야 합니다 (labelLocale={})",
+                self.label_locale
+            )));
+        }
+        if let Some(format) = &self.date_format {
+            if !template::is_valid_date_format(format) {
+                return Err(JsonToHwpxError::Input(format!(
+                    "dateFormat 형식이 잘못되었습니다 (dateFormat={})",
+                    format
+                )));
+            }
+        }
+        if let Some(size) = &self.page_size {
+            if parse_page_size(size).is_none() {
+                return Err(JsonToHwpxError::Input(format!(
+                    "pageSize는 A4, A3, Letter 중 하나여야 합니다 (pageSize={})",
+                    size
+                )));
+            }
+        }
+        if let Some(template) = &self.filename_template {
+            if template.contains(['/', '\\']) {
+                return Err(JsonToHwpxError::Input(format!(
+                    "filenameTemplate에 경로 구분자를 쓸 수 없습니다 (filenameTemplate={})",
+                    template
+                )));
+            }
+            if let Some(name) = template::unknown_filename_placeholders(template).first() {
+                return Err(JsonToHwpxError::Input(format!(
+                    "filenameTemplate의 알 수 없는 자리표시자 {{{}}} (article_id, title, date, department 중 하나)",
+                    name
+                )));
+            }
+        }
+        if let Some(TitleStyle::Heading { size_pt: 0, .. }) = self.title_style {
+            return Err(JsonToHwpxError::Input(
+                "titleStyle.heading.sizePt는 1 이상이어야 합니다".to_string(),
+            ));
+        }
+        if let Some(margins) = &self.margins_mm {
+            let sides = [
+                ("top", margins.top),
+                ("bottom", margins.bottom),
+                ("left", margins.left),
+                ("right", margins.right),
+            ];
+            for (name, value) in sides {
+                if let Some(value) = value.filter(|v| !(v.is_finite() && *v >= 0.0)) {
+                    return Err(JsonToHwpxError::Input(format!(
+                        "marginsMm.{}는 0 이상이어야 합니다 ({}={})",
+                        name, name, value
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 실제로 쓸 제목 모양. titleStyle이 없으면 includeHeader가 켜져 있을 때는
+    /// 헤더에 제목이 있으므로 넣지 않는다.
+    pub fn effective_title_style(&self) -> TitleStyle {
+        match self.title_style {
+            Some(style) => style,
+            None if self.include_header => TitleStyle::None,
+            None => TitleStyle::DEFAULT,
+        }
+    }
+}
+
+/// 본문 첫머리에 subject를 넣는 방식
+///
+/// JSON으로는 `"none"` 또는 `{"heading": {"sizePt": 20, "bold": true, "align": "left"}}`.
+/// heading에서 빠진 항목은 [`TitleStyle::DEFAULT`]의 값을 쓴다.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum TitleStyle {
+    /// 넣지 않음 (문서 정보에만 저장)
+    None,
+    /// 한 단락으로 넣음
+    Heading {
+        /// 글자 크기 (pt)
+        #[serde(default = "default_title_size")]
+        size_pt: u32,
+        #[serde(default = "default_true")]
+        bold: bool,
+        #[serde(default = "default_title_align")]
+        align: ContentAlign,
+    },
+}
+
+impl TitleStyle {
+    /// 가운데 정렬 굵은 16pt
+    pub const DEFAULT: Self = Self::Heading {
+        size_pt: 16,
+        bold: true,
+        align: ContentAlign::Center,
+    };
+}
+
+fn default_title_size() -> u32 {
+    16
+}
+
+fn default_title_align() -> ContentAlign {
+    ContentAlign::Center
+}
+
+/// pageSize 옵션 값 → 용지 크기 (대소문자 무시)
+pub fn parse_page_size(value: &str) -> Option<PaperSize> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "a4" => Some(PaperSize::A4),
+        "a3" => Some(PaperSize::A3),
+        "letter" => Some(PaperSize::Letter),
+        _ => None,
+    }
+}
+
+/// 콘텐츠 변환 실패 처리
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+pub enum OnError {
+    /// 변환 전체를 실패로 돌린다
+    #[default]
+    #[serde(rename = "fail")]
+    Fail,
+    /// 실패한 콘텐츠를 "[이미지 로드 실패: URL]" 같은 단락으로 바꾸고 경고를 남긴다
+    #[serde(rename = "skip")]
+    SkipWithPlaceholder,
+}
+
+impl OnError {
+    /// `"fail"`, `"skip"` 옵션 값 (API 쿼리용)
+    pub fn parse(value: &str) -> Option<Self> {
+        serde_json::from_value(serde_json::Value::String(value.trim().to_string())).ok()
+    }
+}
+
+/// 꼬리말 쪽 번호 모양
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PageNumberStyle {
+    /// 1, 2, 3
+    Numeric,
+    /// - 1 -, - 2 -
+    Dashed,
+    /// i, ii, iii
+    RomanLower,
+    /// I, II, III
+    RomanUpper,
+}
+
+impl PageNumberStyle {
+    /// `"dashed"`, `"romanLower"` 같은 옵션 값 (CLI 인자용)
+    pub fn parse(value: &str) -> Option<Self> {
+        serde_json::from_value(serde_json::Value::String(value.trim().to_string())).ok()
+    }
+}
+
+/// 쪽 여백 옵션 (mm)
+#[derive(Debug, Default, Deserialize)]
+pub struct MarginsMm {
+    #[serde(default)]
+    pub top: Option<f32>,
+    #[serde(default)]
+    pub bottom: Option<f32>,
+    #[serde(default)]
+    pub left: Option<f32>,
+    #[serde(default)]
+    pub right: Option<f32>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_label_locale() -> String {
+    "ko".to_string()
+}
+
+/// labelLocale에 쓸 수 있는 값
+pub const LABEL_LOCALES: [&str; 2] = ["ko", "en"];
+
+/// 줄 번호 옵션
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LineNumberOptions {
+    /// 번호를 매길 줄 간격 (1이면 모든 줄)
+    #[serde(default = "default_count_by")]
+    pub count_by: u16,
+    /// 시작 번호
+    #[serde(default = "default_start")]
+    pub start: u16,
+    /// 본문과 줄 번호 사이 간격 (mm)
+    #[serde(default = "default_distance_mm")]
+    pub distance_mm: u32,
+}
+
+fn default_count_by() -> u16 {
+    1
+}
+
+fn default_start() -> u16 {
+    1
+}
+
+fn default_distance_mm() -> u32 {
+    5
+}
+
+/// data 필드
+#[derive(Debug, Deserialize, Serialize)]
 pub struct Data {
     pub article: Article,
 }
 
 /// article 구조 (메타데이터 + 본문)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Article {
     pub atcl_id: String,
@@ -60,31 +824,443 @@ pub struct Article {
     pub subject: String,
     #[serde(default)]
     pub contents: Vec<Content>,
-    #[serde(default)]
+    /// HTML 본문 (contents가 없는 예전 글)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_html: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub reg_dt: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub reg_emp_name: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub reg_dept_name: Option<String>,
+    /// 게시판 이름 (문서 주제로 사용)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub board_name: Option<String>,
+    /// 태그 목록 (문서 키워드로 사용)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// 첨부파일 목록 (includeAttachments 옵션이 켜져 있으면 본문 끝에 표로 삽입)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<Attachment>,
+    /// 댓글 목록 (includeComments 옵션이 켜져 있으면 본문 끝에 삽입)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<Comment>,
+    /// 그 밖의 필드 (문서번호, 보안등급 등). headerFields에 키를 넣으면 헤더에 표시
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// 첨부파일 정보. 빠진 항목은 표에서 빈 칸이 된다.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Attachment {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+    /// 파일 크기 (바이트)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_size: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reg_dt: Option<String>,
+}
+
+/// 댓글. 작성자와 작성일시는 굵은 한 줄, 본문은 그 아래 단락이 된다.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Comment {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, alias = "created_at", skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub body: String,
 }
 
 /// contents 배열의 각 요소
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum Content {
     #[serde(rename = "text")]
-    Text { value: String },
+    Text {
+        value: TextValue,
+        /// value 안에서 하이퍼링크로 만들 텍스트
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        links: Vec<TextLink>,
+        /// 이 콘텐츠에만 적용할 정렬, 글자 모양
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        style: Option<ContentStyle>,
+    },
+    /// 제목 (level 1이 가장 큰 제목)
+    #[serde(rename = "heading")]
+    Heading { value: String, level: u8 },
     #[serde(rename = "image")]
     Image {
-        #[serde(default)]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         url: Option<String>,
-        #[serde(default)]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         base64: Option<String>,
-        #[serde(default)]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         format: Option<String>,
+        /// 너비: `"60%"`(본문 너비 대비), `"80mm"`, `"300px"` 또는 픽셀 수
+        #[serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            deserialize_with = "deserialize_length"
+        )]
+        width: Option<String>,
+        /// 높이: `"80mm"`, `"300px"` 또는 픽셀 수. 한쪽만 주면 비율을 유지한다.
+        #[serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            deserialize_with = "deserialize_length"
+        )]
+        height: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        align: Option<ContentAlign>,
+        /// 그림 아래에 넣을 캡션 (예: "그림 1. 분기 매출")
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        caption: Option<String>,
     },
+    /// 표: HTML 문자열(`value`) 또는 행 배열(`rows`)
     #[serde(rename = "table")]
-    Table { value: String },
+    Table(TableContent),
+    #[serde(rename = "divider")]
+    Divider,
+    /// 인용문 (왼쪽 세로줄이 있는 들여쓴 회색 단락)
+    #[serde(rename = "quote")]
+    Quote { value: String },
+    /// 코드 블록 (고정폭 글꼴, 공백 유지)
+    #[serde(rename = "code")]
+    Code {
+        value: String,
+        /// 언어 이름 (기록용, 구문 강조는 하지 않음)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        language: Option<String>,
+    },
+    /// 하이퍼링크 한 단락
+    #[serde(rename = "link")]
+    Link { text: String, url: String },
+    /// 다음 콘텐츠를 새 쪽에서 시작
+    #[serde(rename = "pagebreak")]
+    PageBreak,
+    #[serde(rename = "checkbox")]
+    Checkbox {
+        label: String,
+        #[serde(default)]
+        checked: bool,
+    },
+    /// 번호(ordered) 또는 글머리표 목록
+    #[serde(rename = "list")]
+    List {
+        #[serde(default)]
+        ordered: bool,
+        items: Vec<ListItem>,
+    },
+    /// HTML 본문 (지원하지 않는 태그는 텍스트만 변환)
+    #[serde(rename = "html")]
+    Html { value: String },
+    /// Markdown 본문 (제목, 목록, 코드 블록, 표 지원)
+    #[serde(rename = "markdown")]
+    Markdown { value: String },
+    /// 텍스트 파일(.txt, .csv) 내용 (경로 또는 HTTP URL)
+    #[serde(rename = "file")]
+    File {
+        url: String,
+        #[serde(default)]
+        render: FileRender,
+    },
+}
+
+/// file 콘텐츠를 넣는 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileRender {
+    /// 줄마다 한 단락
+    #[default]
+    Text,
+    /// CSV로 읽어 표 하나
+    Table,
+}
+
+impl Content {
+    /// JSON의 `type` 값 (예: "table")
+    pub fn type_name(&self) -> &'static str {
+        self.min_schema_version().0
+    }
+
+    /// 콘텐츠 type 이름과 그 type을 쓸 수 있는 최소 schemaVersion
+    ///
+    /// - 1.0: text, image, table
+    /// - 1.1: divider, checkbox
+    /// - 1.2: heading, quote, code, link, pagebreak, list, html, markdown, file
+    pub fn min_schema_version(&self) -> (&'static str, SchemaVersion) {
+        match self {
+            Content::Text { .. } => ("text", SchemaVersion::new(1, 0)),
+            Content::Image { .. } => ("image", SchemaVersion::new(1, 0)),
+            Content::Table(_) => ("table", SchemaVersion::new(1, 0)),
+            Content::Divider => ("divider", SchemaVersion::new(1, 1)),
+            Content::Checkbox { .. } => ("checkbox", SchemaVersion::new(1, 1)),
+            Content::Heading { .. } => ("heading", SchemaVersion::new(1, 2)),
+            Content::Quote { .. } => ("quote", SchemaVersion::new(1, 2)),
+            Content::Code { .. } => ("code", SchemaVersion::new(1, 2)),
+            Content::Link { .. } => ("link", SchemaVersion::new(1, 2)),
+            Content::PageBreak => ("pagebreak", SchemaVersion::new(1, 2)),
+            Content::List { .. } => ("list", SchemaVersion::new(1, 2)),
+            Content::Html { .. } => ("html", SchemaVersion::new(1, 2)),
+            Content::Markdown { .. } => ("markdown", SchemaVersion::new(1, 2)),
+            Content::File { .. } => ("file", SchemaVersion::new(1, 2)),
+        }
+    }
+}
+
+/// table 콘텐츠의 두 형태
+///
+/// `{"value": "<table>…</table>"}`는 HTML 표, `{"rows": [[…]], "header_rows": 1,
+/// "merges": […]}`는 행 배열로 적은 표로 읽는다. 둘 다 있거나 둘 다 없으면 에러.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum TableContent {
+    Html { value: String },
+    Rows(TableRows),
+}
+
+impl<'de> Deserialize<'de> for TableContent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct RawTable {
+            value: Option<String>,
+            rows: Option<Vec<Vec<String>>>,
+            #[serde(default, alias = "headerRows")]
+            header_rows: Option<usize>,
+            #[serde(default)]
+            merges: Option<Vec<TableMerge>>,
+        }
+
+        let raw = RawTable::deserialize(deserializer)?;
+        match (raw.value, raw.rows) {
+            (Some(_), Some(_)) => Err(de::Error::custom(
+                "table에는 value와 rows 중 하나만 쓸 수 있습니다",
+            )),
+            (Some(value), None) => {
+                if raw.header_rows.is_some() || raw.merges.is_some() {
+                    return Err(de::Error::custom(
+                        "header_rows, merges는 rows와 함께만 쓸 수 있습니다",
+                    ));
+                }
+                Ok(TableContent::Html { value })
+            }
+            (None, Some(rows)) => Ok(TableContent::Rows(TableRows {
+                rows,
+                header_rows: raw.header_rows.unwrap_or(0),
+                merges: raw.merges.unwrap_or_default(),
+            })),
+            (None, None) => Err(de::Error::custom(
+                "table에는 value(HTML) 또는 rows가 필요합니다",
+            )),
+        }
+    }
+}
+
+/// 행 배열로 적은 표
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TableRows {
+    /// 행마다 셀 텍스트. 짧은 행은 가장 긴 행에 맞춰 빈 셀로 채운다
+    pub rows: Vec<Vec<String>>,
+    /// 앞에서부터 머리글로 쓸 행 수
+    pub header_rows: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub merges: Vec<TableMerge>,
+}
+
+/// rows 표의 셀 병합. (`row`, `col`) 셀이 오른쪽으로 `colspan`, 아래로 `rowspan`칸을 차지한다
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TableMerge {
+    pub row: usize,
+    pub col: usize,
+    #[serde(default = "default_span")]
+    pub colspan: u32,
+    #[serde(default = "default_span")]
+    pub rowspan: u32,
+}
+
+fn default_span() -> u32 {
+    1
+}
+
+/// text 콘텐츠의 value: 문자열 또는 서식이 있는 run 배열
+///
+/// 문자열은 `\n`마다 단락을 나누고, run 배열은 이어 붙여 한 단락으로 만든다
+/// (run 텍스트 안의 `\n`에서 단락을 나눈다).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum TextValue {
+    Plain(String),
+    Runs(Vec<TextRun>),
+}
+
+impl From<&str> for TextValue {
+    fn from(value: &str) -> Self {
+        Self::Plain(value.to_string())
+    }
+}
+
+impl From<String> for TextValue {
+    fn from(value: String) -> Self {
+        Self::Plain(value)
+    }
+}
+
+impl<'de> Deserialize<'de> for TextValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct TextValueVisitor;
+
+        impl<'de> Visitor<'de> for TextValueVisitor {
+            type Value = TextValue;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("문자열 또는 run 객체 배열")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> std::result::Result<TextValue, E> {
+                Ok(TextValue::Plain(value.to_string()))
+            }
+
+            fn visit_string<E: de::Error>(
+                self,
+                value: String,
+            ) -> std::result::Result<TextValue, E> {
+                Ok(TextValue::Plain(value))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> std::result::Result<TextValue, A::Error> {
+                let mut runs = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(run) = seq.next_element()? {
+                    runs.push(run);
+                }
+                Ok(TextValue::Runs(runs))
+            }
+        }
+
+        deserializer.deserialize_any(TextValueVisitor)
+    }
+}
+
+/// text 콘텐츠의 서식이 있는 텍스트 조각
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct TextRun {
+    pub text: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub bold: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub italic: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub underline: bool,
+    /// 글자 크기 (pt, 지정하지 않으면 본문 크기)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u32>,
+    /// 글자 색 (`#RRGGBB`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+/// `#RRGGBB` → 0xRRGGBB (형식이 틀리면 None)
+pub fn parse_color(color: &str) -> Option<u32> {
+    let hex = color.strip_prefix('#')?;
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(hex, 16).ok()
+}
+
+/// text 콘텐츠의 style (지정한 항목만 본문 기본값을 덮어씀)
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentStyle {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub align: Option<ContentAlign>,
+    /// 글자 크기 (pt)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub font_size: Option<u32>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub bold: bool,
+    /// 글자 색 (`#RRGGBB`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// 알 수 없는 키. 변환 시 경고만 하고 무시한다.
+    #[serde(flatten, skip_serializing)]
+    pub unknown: BTreeMap<String, serde_json::Value>,
+}
+
+/// 단락 정렬
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentAlign {
+    Left,
+    Center,
+    Right,
+    Justify,
+}
+
+/// image 콘텐츠의 width/height
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageLength {
+    /// 본문(단) 너비 대비 %
+    Percent(f64),
+    Mm(f64),
+    /// 이미지 해상도(DPI)로 mm 변환
+    Px(f64),
+}
+
+impl ImageLength {
+    /// `"60%"`, `"80mm"`, `"300px"`, `"300"` (단위가 없으면 px)
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let (number, length): (&str, fn(f64) -> Self) = if let Some(n) = value.strip_suffix('%') {
+            (n, Self::Percent)
+        } else if let Some(n) = value.strip_suffix("mm") {
+            (n, Self::Mm)
+        } else {
+            (value.strip_suffix("px").unwrap_or(value), Self::Px)
+        };
+        let number: f64 = number.trim().parse().ok()?;
+        (number.is_finite() && number > 0.0).then(|| length(number))
+    }
+}
+
+/// 숫자(픽셀 수)도 받도록 width/height를 문자열로
+fn deserialize_length<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Length {
+        Text(String),
+        Pixels(f64),
+    }
+
+    Ok(
+        Option::<Length>::deserialize(deserializer)?.map(|length| match length {
+            Length::Text(text) => text,
+            Length::Pixels(px) => format!("{}px", px),
+        }),
+    )
+}
+
+/// text 콘텐츠의 링크 (value에서 text를 찾아 url로 연결)
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TextLink {
+    pub text: String,
+    pub url: String,
+}
+
+/// list 콘텐츠의 항목 (children은 한 단계 아래 목록)
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ListItem {
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<ListItem>,
 }
 
 #[cfg(test)]
@@ -138,6 +1314,62 @@ mod tests {
         assert_eq!(err.exit_code(), 1);
     }
 
+    #[test]
+    fn test_schema_version_validation() {
+        let parse = |version: Option<&str>, content: &str| -> ApiResponse {
+            let version = version
+                .map(|v| format!(r#""schemaVersion": "{}","#, v))
+                .unwrap_or_default();
+            serde_json::from_str(&format!(
+                r#"{{
+                    {}
+                    "responseCode": "0",
+                    "data": {{ "article": {{ "atclId": "T1", "contents": [
+                        {{ "type": "text", "value": "본문" }},
+                        {}
+                    ] }} }}
+                }}"#,
+                version, content
+            ))
+            .unwrap()
+        };
+        let heading = r#"{ "type": "heading", "value": "제목", "level": 1 }"#;
+
+        // 없으면 현재 버전
+        let response = parse(None, heading);
+        assert!(response.validate().is_ok());
+        assert_eq!(response.schema_version().unwrap(), SCHEMA_VERSION);
+
+        let response = parse(Some("1.2"), heading);
+        assert!(response.validate().is_ok());
+        assert_eq!(response.schema_version().unwrap().to_string(), "1.2");
+        // 부 버전이 더 높아도 1.x는 받는다
+        assert!(parse(Some("1.5.1"), heading).validate().is_ok());
+
+        // 새 콘텐츠 종류는 최소 버전부터
+        let err = parse(Some("1.0"), heading).validate().unwrap_err();
+        assert!(matches!(err, JsonToHwpxError::Input(_)));
+        assert!(
+            err.to_string()
+                .contains("contents[1]: heading 콘텐츠는 schemaVersion 1.2 이상"),
+            "{}",
+            err
+        );
+        assert!(parse(Some("1.1"), r#"{ "type": "divider" }"#)
+            .validate()
+            .is_ok());
+
+        for version in ["2.0", "0.9", "v1", "1.x"] {
+            let err = parse(Some(version), heading).validate().unwrap_err();
+            assert!(matches!(err, JsonToHwpxError::Input(_)));
+            assert!(
+                err.to_string().contains("지원하는 버전: 1.0, 1.1, 1.2"),
+                "{}",
+                err
+            );
+        }
+    }
+
     #[test]
     fn test_parse_image_url() {
         let json = r#"{
@@ -203,6 +1435,315 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_parse_divider() {
+        let json = r#"{
+            "responseCode": "0",
+            "data": {
+                "article": {
+                    "atclId": "T1",
+                    "subject": "S",
+                    "contents": [
+                        { "type": "divider" }
+                    ]
+                }
+            }
+        }"#;
+
+        let response: ApiResponse = serde_json::from_str(json).unwrap();
+        assert!(matches!(
+            response.data.article.contents[0],
+            Content::Divider
+        ));
+    }
+
+    #[test]
+    fn test_parse_checkbox() {
+        let json = r#"{
+            "responseCode": "0",
+            "data": {
+                "article": {
+                    "atclId": "T1",
+                    "contents": [
+                        { "type": "checkbox", "label": "검토 완료", "checked": true },
+                        { "type": "checkbox", "label": "반려" }
+                    ]
+                }
+            }
+        }"#;
+
+        let response: ApiResponse = serde_json::from_str(json).unwrap();
+        let contents = &response.data.article.contents;
+        assert!(matches!(
+            &contents[0],
+            Content::Checkbox { label, checked: true } if label == "검토 완료"
+        ));
+        assert!(matches!(
+            &contents[1],
+            Content::Checkbox { checked: false, .. }
+        ));
+    }
+
+    #[test]
+    fn test_parse_heading() {
+        let json = r#"{
+            "responseCode": "0",
+            "data": {
+                "article": {
+                    "atclId": "T1",
+                    "contents": [
+                        { "type": "heading", "value": "1. 개요", "level": 2 }
+                    ]
+                }
+            }
+        }"#;
+
+        let response: ApiResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            response.data.article.contents[0],
+            Content::Heading {
+                value: "1. 개요".to_string(),
+                level: 2
+            }
+        );
+        assert!(response.validate().is_ok());
+    }
+
+    #[test]
+    fn test_heading_level_validation() {
+        for level in [0, 7] {
+            let json = format!(
+                r#"{{
+                    "responseCode": "0",
+                    "data": {{
+                        "article": {{
+                            "atclId": "T1",
+                            "contents": [
+                                {{ "type": "text", "value": "본문" }},
+                                {{ "type": "heading", "value": "제목", "level": {} }}
+                            ]
+                        }}
+                    }}
+                }}"#,
+                level
+            );
+
+            let response: ApiResponse = serde_json::from_str(&json).unwrap();
+            let err = response.validate().unwrap_err();
+            assert!(matches!(err, JsonToHwpxError::Input(_)));
+            assert!(err.to_string().contains("contents[1]"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_parse_text_value_shapes() {
+        let json = r##"{
+            "responseCode": "0",
+            "data": {
+                "article": {
+                    "atclId": "T1",
+                    "contents": [
+                        { "type": "text", "value": "문자열" },
+                        { "type": "text", "value": [{ "text": "굵게", "bold": true, "color": "#00ff7f" }] }
+                    ]
+                }
+            }
+        }"##;
+
+        let response: ApiResponse = serde_json::from_str(json).unwrap();
+        let contents = &response.data.article.contents;
+        assert!(
+            matches!(&contents[0], Content::Text { value: TextValue::Plain(v), .. } if v == "문자열")
+        );
+        let Content::Text {
+            value: TextValue::Runs(runs),
+            ..
+        } = &contents[1]
+        else {
+            panic!("Expected text runs");
+        };
+        assert!(runs[0].bold && !runs[0].italic);
+        assert_eq!(
+            runs[0].color.as_deref().and_then(parse_color),
+            Some(0x00FF7F)
+        );
+        assert!(response.validate().is_ok());
+
+        // 숫자 같은 다른 모양은 파싱 에러
+        let bad = json.replace(r#""value": "문자열""#, r#""value": 3"#);
+        assert!(serde_json::from_str::<ApiResponse>(&bad).is_err());
+    }
+
+    #[test]
+    fn test_parse_table_shapes() {
+        let contents: Vec<Content> = serde_json::from_str(
+            r#"[
+                { "type": "table", "value": "<table><tr><td>A</td></tr></table>" },
+                { "type": "table", "rows": [["이름", "나이"], ["홍길동", "30"]], "header_rows": 1,
+                  "merges": [{ "row": 0, "col": 0, "colspan": 2 }] }
+            ]"#,
+        )
+        .unwrap();
+        assert!(
+            matches!(&contents[0], Content::Table(TableContent::Html { value }) if value.starts_with("<table>"))
+        );
+        let Content::Table(TableContent::Rows(rows)) = &contents[1] else {
+            panic!("Expected rows table");
+        };
+        assert_eq!(rows.rows[1], vec!["홍길동", "30"]);
+        assert_eq!(rows.header_rows, 1);
+        assert_eq!(
+            rows.merges,
+            vec![TableMerge {
+                row: 0,
+                col: 0,
+                colspan: 2,
+                rowspan: 1
+            }]
+        );
+
+        // 직렬화해도 같은 모양으로 돌아온다
+        let json = serde_json::to_string(&contents).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Vec<Content>>(&json).unwrap(),
+            contents
+        );
+
+        for bad in [
+            r#"{ "type": "table" }"#,
+            r#"{ "type": "table", "value": "<table></table>", "rows": [["A"]] }"#,
+            r#"{ "type": "table", "value": "<table></table>", "header_rows": 1 }"#,
+        ] {
+            assert!(serde_json::from_str::<Content>(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_content_issues() {
+        let json = r#"{
+            "responseCode": "0",
+            "data": {
+                "article": {
+                    "atclId": "T1",
+                    "contents": [
+                        { "type": "table", "value": "<table></table>" },
+                        { "type": "image", "width": "넓게" },
+                        { "type": "image", "base64": "!!!" },
+                        { "type": "text", "value": "정상" },
+                        { "type": "link", "text": "링크", "url": "javascript:alert(1)" },
+                        { "type": "table", "rows": [["A"]], "merges": [{ "row": 0, "col": 0, "colspan": 2 }] }
+                    ]
+                }
+            }
+        }"#;
+        let mut response: ApiResponse = serde_json::from_str(json).unwrap();
+
+        let issues = response.content_issues();
+        let indexes: Vec<usize> = issues.iter().map(|i| i.content_index).collect();
+        assert_eq!(indexes, vec![0, 1, 1, 2, 4, 5]);
+        assert_eq!(issues[0].message, "테이블에 행이 없습니다");
+        assert_eq!(issues[1].message, "image에는 url 또는 base64가 필요합니다");
+        assert!(issues[2].message.starts_with("이미지 width 형식"));
+        assert!(issues[3].message.starts_with("Base64 디코딩 실패"));
+        assert!(issues[4].message.starts_with("링크 URL은"));
+        assert!(issues[5].message.starts_with("merges[0]: 병합 범위가"));
+
+        // validate는 문제를 모두 이어서 돌려준다
+        let err = response.validate().unwrap_err().to_string();
+        assert!(
+            err.contains("contents[0]: 테이블에 행이 없습니다; contents[1]:"),
+            "{}",
+            err
+        );
+        assert!(err.contains("contents[5]: merges[0]"), "{}", err);
+
+        // onError가 skip이면 변환 때 자리표시로 바뀌는 문제는 빼고,
+        // skipImages면 base64를 디코딩하지 않는다
+        response.options.on_error = OnError::SkipWithPlaceholder;
+        let indexes: Vec<usize> = response
+            .content_issues()
+            .iter()
+            .map(|i| i.content_index)
+            .collect();
+        assert_eq!(indexes, vec![1, 1, 4, 5]);
+        response.options.on_error = OnError::Fail;
+        response.options.skip_images = true;
+        assert!(response
+            .content_issues()
+            .iter()
+            .all(|i| i.content_index != 2));
+    }
+
+    #[test]
+    fn test_parse_flat_document() {
+        let json = r#"{
+            "schemaVersion": "1.2",
+            "article_id": "FLAT001",
+            "title": "평평한 문서",
+            "metadata": { "author": "홍길동", "department": "개발팀", "created_at": "2025-01-24" },
+            "options": { "includeHeader": true },
+            "contents": [{ "type": "text", "value": "본문" }]
+        }"#;
+
+        let response: ApiResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.response_code, "0");
+        assert_eq!(response.schema_version.as_deref(), Some("1.2"));
+        assert!(response.options.include_header);
+        let article = &response.data.article;
+        assert_eq!(article.atcl_id, "FLAT001");
+        assert_eq!(article.subject, "평평한 문서");
+        assert_eq!(article.reg_emp_name.as_deref(), Some("홍길동"));
+        assert_eq!(article.reg_dept_name.as_deref(), Some("개발팀"));
+        assert_eq!(article.reg_dt.as_deref(), Some("2025-01-24"));
+        assert_eq!(article.contents.len(), 1);
+        assert!(response.validate().is_ok());
+
+        // 빈 article_id는 envelope 모양과 같은 검증 에러
+        let blank = json.replace(r#""FLAT001""#, r#"" ""#);
+        let response: ApiResponse = serde_json::from_str(&blank).unwrap();
+        assert!(response.validate().is_err());
+
+        // data도 article_id도 없으면 envelope 모양의 에러
+        let err = serde_json::from_str::<ApiResponse>(r#"{ "title": "제목" }"#).unwrap_err();
+        assert!(err.to_string().contains("responseCode"), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_nested_list() {
+        let json = r#"{
+            "responseCode": "0",
+            "data": {
+                "article": {
+                    "atclId": "T1",
+                    "contents": [
+                        {
+                            "type": "list",
+                            "ordered": true,
+                            "items": [
+                                { "text": "준비", "children": [{ "text": "자료 수집" }] },
+                                { "text": "실행" }
+                            ]
+                        },
+                        { "type": "list", "items": [{ "text": "메모" }] }
+                    ]
+                }
+            }
+        }"#;
+
+        let response: ApiResponse = serde_json::from_str(json).unwrap();
+        let contents = &response.data.article.contents;
+        match &contents[0] {
+            Content::List { ordered, items } => {
+                assert!(ordered);
+                assert_eq!(items.len(), 2);
+                assert_eq!(items[0].children[0].text, "자료 수집");
+                assert!(items[1].children.is_empty());
+            }
+            _ => panic!("Expected List content"),
+        }
+        assert!(matches!(&contents[1], Content::List { ordered: false, .. }));
+    }
+
     #[test]
     fn test_missing_atcl_id() {
         let json = r#"{
@@ -235,6 +1776,37 @@ mod tests {
         let response: ApiResponse = serde_json::from_str(json).unwrap();
         let err = response.validate().unwrap_err();
         assert_eq!(err.exit_code(), 1);
+
+        // 파일 이름으로 거르면 남는 글자가 없는 atclId
+        let json = json.replace(r#""  ""#, r#"" ... ""#);
+        let response: ApiResponse = serde_json::from_str(&json).unwrap();
+        let err = response.validate().unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("파일 이름으로 쓸 수 있는 글자가 없습니다"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_parse_board_name_and_tags() {
+        let json = r#"{
+            "responseCode": "0",
+            "data": {
+                "article": {
+                    "atclId": "T1",
+                    "subject": "S",
+                    "boardName": "공지사항",
+                    "tags": ["보안", "점검"]
+                }
+            }
+        }"#;
+
+        let response: ApiResponse = serde_json::from_str(json).unwrap();
+        let article = &response.data.article;
+        assert_eq!(article.board_name.as_deref(), Some("공지사항"));
+        assert_eq!(article.tags, vec!["보안", "점검"]);
     }
 
     #[test]
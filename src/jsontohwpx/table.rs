@@ -1,23 +1,240 @@
-use crate::hwpx::{HwpxTable, HwpxWriter};
+use std::path::Path;
 
-use super::error::{JsonToHwpxError, Result};
+use crate::hwpx::{CaptionPosition, CellLink, CellStyle, HwpxTable, HwpxWriter, TableHandle};
+
+use super::error::{ConvertLimit, JsonToHwpxError, Result};
+use super::image;
+use super::inline::{dropped_link_warning, html_text, link_url_error};
+use super::model::TableRows;
+
+/// 셀 안 그림(`img src`)을 읽는 방식
+#[derive(Debug, Clone, Copy)]
+pub struct CellImageOptions<'a> {
+    /// 상대 경로 src의 기준 디렉토리
+    pub base_path: &'a Path,
+    /// 그림 대신 "[이미지: URL]" 줄을 셀 텍스트 앞에 넣는다 (skipImages)
+    pub skip: bool,
+    /// 표를 넣은 뒤 문서의 그림 바이트 합계 상한
+    pub max_total_bytes: usize,
+}
 
 /// HTML 테이블 문자열을 파싱하여 HwpxWriter에 추가
-pub fn add_table_from_html(writer: &mut HwpxWriter, html: &str) -> Result<()> {
-    let table = parse_html_table(html)?;
+///
+/// 셀이 `max_cells`보다 많으면 표를 만들기 전에 `LimitExceeded` 에러. 셀 안의 그림은
+/// `images`가 있을 때만 읽어 셀 텍스트 앞에 넣고, 없으면 버린다. 그림을 읽지 못하면
+/// `Conversion` 에러, 넣은 뒤 그림 용량 합계가 상한을 넘으면 `LimitExceeded` 에러.
+///
+/// 경고는 (코드, 메시지)로 돌려준다. 병합이 어긋나 격자를 늘렸으면 `TABLE_GRID_ADJUSTED`,
+/// 셀 안 링크의 스킴이 http, https, mailto가 아니라 버렸으면 `LINK_DROPPED`.
+pub fn add_table_from_html(
+    writer: &mut HwpxWriter,
+    html: &str,
+    max_cells: usize,
+    images: Option<&CellImageOptions<'_>>,
+) -> Result<Vec<(&'static str, String)>> {
+    let mut warnings = Vec::new();
+    let table = parse_html_table_with(html, max_cells, images, &mut warnings)?;
     writer.add_table(table)?;
+    if let Some(images) = images {
+        image::check_total_bytes(writer, images.max_total_bytes)?;
+    }
+    Ok(warnings)
+}
+
+/// HTML 표를 writer 없이 파싱만 해서 검증 (행이 없으면 `Conversion` 에러)
+///
+/// 셀이 `max_cells`보다 많으면 격자를 만들기 전에 `LimitExceeded` 에러.
+pub(super) fn validate_html(html: &str, max_cells: usize) -> Result<()> {
+    parse_html_table(html, max_cells).map(|_| ())
+}
+
+/// 행 배열로 적은 표를 HwpxWriter에 추가 (셀 수 한도는 [`add_table_from_html`]과 같다)
+pub fn add_table_from_rows(
+    writer: &mut HwpxWriter,
+    table: &TableRows,
+    max_cells: usize,
+) -> Result<TableHandle> {
+    check_cells(table.rows.len(), column_count(table), max_cells)?;
+    let table = build_rows_table(table)?;
+    Ok(writer.add_table(table)?)
+}
+
+/// 행 수 × 열 수가 `max_cells`를 넘으면 `LimitExceeded` 에러
+pub(super) fn check_cells(rows: usize, cols: usize, max_cells: usize) -> Result<()> {
+    let cells = rows.saturating_mul(cols);
+    if cells > max_cells {
+        return Err(JsonToHwpxError::LimitExceeded(
+            ConvertLimit::TableCells,
+            format!(
+                "표 셀이 너무 많습니다 ({}행 {}열 = {}칸, 최대 {}칸)",
+                rows, cols, cells, max_cells
+            ),
+        ));
+    }
     Ok(())
 }
 
+/// rows 표 검증: 셀이 있는지, header_rows와 merges가 표 안에 있고 병합끼리 겹치지 않는지
+///
+/// 잘못된 병합은 몇 번째인지(`merges[n]`)와 함께 `Input` 에러를 돌려준다.
+pub(super) fn validate_rows(table: &TableRows) -> Result<()> {
+    let row_count = table.rows.len();
+    let col_count = column_count(table);
+    if col_count == 0 {
+        return Err(JsonToHwpxError::Input(
+            "table rows에 셀이 없습니다".to_string(),
+        ));
+    }
+    if table.header_rows > row_count {
+        return Err(JsonToHwpxError::Input(format!(
+            "header_rows가 행 수보다 많습니다 (header_rows={}, 행 {}개)",
+            table.header_rows, row_count
+        )));
+    }
+
+    for (idx, merge) in table.merges.iter().enumerate() {
+        if merge.colspan == 0 || merge.rowspan == 0 {
+            return Err(JsonToHwpxError::Input(format!(
+                "merges[{}]: colspan, rowspan은 1 이상이어야 합니다",
+                idx
+            )));
+        }
+        let end_row = merge.row.saturating_add(merge.rowspan as usize);
+        let end_col = merge.col.saturating_add(merge.colspan as usize);
+        if end_row > row_count || end_col > col_count {
+            return Err(JsonToHwpxError::Input(format!(
+                "merges[{}]: 병합 범위가 표({}행 {}열)를 벗어납니다 (row={}, col={}, colspan={}, rowspan={})",
+                idx, row_count, col_count, merge.row, merge.col, merge.colspan, merge.rowspan
+            )));
+        }
+        let overlapped = table.merges[..idx].iter().position(|other| {
+            merge.row < other.row + other.rowspan as usize
+                && other.row < end_row
+                && merge.col < other.col + other.colspan as usize
+                && other.col < end_col
+        });
+        if let Some(other) = overlapped {
+            return Err(JsonToHwpxError::Input(format!(
+                "merges[{}]: merges[{}]와 병합 범위가 겹칩니다",
+                idx, other
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn column_count(table: &TableRows) -> usize {
+    table.rows.iter().map(Vec::len).max().unwrap_or(0)
+}
+
+/// rows 표로 HwpxTable 생성. 병합에 덮인 셀의 텍스트는 버린다
+fn build_rows_table(table: &TableRows) -> Result<HwpxTable> {
+    validate_rows(table)?;
+    let col_count = column_count(table);
+    let data: Vec<Vec<&str>> = table
+        .rows
+        .iter()
+        .map(|row| {
+            (0..col_count)
+                .map(|col| row.get(col).map_or("", |cell| cell.trim()))
+                .collect()
+        })
+        .collect();
+    let mut hwpx_table = HwpxTable::from_data(data).with_header_rows(table.header_rows);
+
+    for merge in &table.merges {
+        hwpx_table.set_cell_span(merge.row, merge.col, merge.colspan, merge.rowspan);
+    }
+    for row in 0..table.rows.len() {
+        for col in 0..col_count {
+            if hwpx_table.is_covered(row, col) {
+                hwpx_table.set_cell(row, col, "");
+            }
+        }
+    }
+    Ok(hwpx_table)
+}
+
 /// Parsed cell info from HTML
 struct ParsedCell {
     text: String,
     col_span: u32,
     row_span: u32,
+    /// `<th>` 셀인지
+    header: bool,
+    /// `width` 속성 또는 style의 너비
+    width: Option<HtmlWidth>,
+    /// 배경색, 글자 색 ([`cell_style`])
+    style: CellStyle,
+    /// 셀 텍스트 안의 링크 ([`cell_text`])
+    links: Vec<CellLink>,
+    /// 셀 안 `img`의 src
+    images: Vec<String>,
+}
+
+/// HTML의 너비 값 (`20%`, `120px`, `120`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum HtmlWidth {
+    Percent(f64),
+    Px(f64),
+}
+
+impl HtmlWidth {
+    fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let (number, percent) = match value.strip_suffix('%') {
+            Some(number) => (number, true),
+            None => (value.strip_suffix("px").unwrap_or(value), false),
+        };
+        let number: f64 = number.trim().parse().ok()?;
+        if !number.is_finite() || number <= 0.0 {
+            return None;
+        }
+        Some(if percent {
+            Self::Percent(number)
+        } else {
+            Self::Px(number)
+        })
+    }
+
+    /// `width` 속성, 없으면 `style`의 `width`
+    fn of(element: &scraper::node::Element) -> Option<Self> {
+        if let Some(width) = element.attr("width") {
+            return Self::parse(width);
+        }
+        element
+            .attr("style")?
+            .split(';')
+            .filter_map(|decl| decl.split_once(':'))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("width"))
+            .and_then(|(_, value)| Self::parse(value))
+    }
 }
 
 /// HTML <table> 태그를 파싱하여 HwpxTable 생성 (colspan/rowspan 지원)
-fn parse_html_table(html: &str) -> Result<HwpxTable> {
+///
+/// 셀의 배경색과 글자 색은 style(`background-color`, `background`, `color`)과
+/// `bgcolor` 속성에서 읽는다 ([`cell_style`]). 셀과 캡션 텍스트의 NBSP는 보통 공백으로
+/// 바꾼다 ([`html_text`]). 셀 안의 `<br>`은 줄 나눔이 되어 셀 안에서 단락을 나눈다
+/// ([`cell_text`]). 열 너비는 `<col>`, 없으면
+/// 셀의 `width` 속성이나 style을 비율로 따른다 ([`relative_col_widths`]).
+/// 셀 안의 그림과 격자 조정 경고는 버린다.
+fn parse_html_table(html: &str, max_cells: usize) -> Result<HwpxTable> {
+    parse_html_table_with(html, max_cells, None, &mut Vec::new())
+}
+
+/// [`parse_html_table`]에 더해 `images`가 있으면 셀 안의 그림을 읽어 셀에 넣는다
+/// ([`CellImageOptions`]).
+///
+/// rowspan이 마지막 행을 넘으면 그 행까지 표를 늘리고, 위 행의 rowspan에 밀린 셀이
+/// 열 수를 넘으면 열을 늘려 다시 놓는다 ([`place_cells`]). 늘렸거나 셀 안 링크를 버렸으면
+/// `warnings`에 (코드, 메시지)로 적는다.
+fn parse_html_table_with(
+    html: &str,
+    max_cells: usize,
+    images: Option<&CellImageOptions<'_>>,
+    warnings: &mut Vec<(&'static str, String)>,
+) -> Result<HwpxTable> {
     let document = scraper::Html::parse_fragment(html);
     let tr_selector = scraper::Selector::parse("tr")
         .map_err(|_| JsonToHwpxError::Conversion("tr 셀렉터 파싱 실패".to_string()))?;
@@ -30,7 +247,7 @@ fn parse_html_table(html: &str) -> Result<HwpxTable> {
     for tr in document.select(&tr_selector) {
         let mut row: Vec<ParsedCell> = Vec::new();
         for cell in tr.select(&cell_selector) {
-            let text = cell.text().collect::<Vec<_>>().join("");
+            let (text, links, images) = cell_text(cell, warnings);
             let col_span = cell
                 .value()
                 .attr("colspan")
@@ -44,9 +261,14 @@ fn parse_html_table(html: &str) -> Result<HwpxTable> {
                 .unwrap_or(1)
                 .max(1);
             row.push(ParsedCell {
-                text: text.trim().to_string(),
+                text,
                 col_span,
                 row_span,
+                header: cell.value().name() == "th",
+                width: HtmlWidth::of(cell.value()),
+                style: cell_style(cell.value()),
+                links,
+                images,
             });
         }
         if !row.is_empty() {
@@ -60,79 +282,488 @@ fn parse_html_table(html: &str) -> Result<HwpxTable> {
         ));
     }
 
-    // Determine grid dimensions
-    // Calculate effective column count considering colspan
+    // 열 수는 행마다 colspan 합의 최댓값, 행 수는 rowspan이 닿는 마지막 행까지
     let row_count = parsed_rows.len();
-    let mut col_count: usize = 0;
-    for parsed_row in &parsed_rows {
-        let row_cols: usize = parsed_row.iter().map(|c| c.col_span as usize).sum();
-        if row_cols > col_count {
-            col_count = row_cols;
-        }
+    let mut col_count: usize = parsed_rows
+        .iter()
+        .map(|row| row.iter().map(|c| c.col_span as usize).sum())
+        .max()
+        .unwrap_or(0);
+    let max_row = parsed_rows
+        .iter()
+        .enumerate()
+        .flat_map(|(r, row)| row.iter().map(move |c| r + c.row_span as usize))
+        .fold(row_count, usize::max);
+    if max_row > row_count {
+        let message = format!(
+            "rowspan이 마지막 행을 넘어 표를 {}행에서 {}행으로 늘렸습니다",
+            row_count, max_row
+        );
+        warnings.push(("TABLE_GRID_ADJUSTED", message));
     }
-    // Also account for rowspan pushing cells into later rows
-    // We'll use a grid-based approach to handle this correctly
 
-    // Build the grid: track which positions are occupied
-    // We may need to expand row_count if rowspan extends beyond parsed rows
-    let mut max_row = row_count;
-    for (r, parsed_row) in parsed_rows.iter().enumerate() {
-        for cell in parsed_row {
-            let end_row = r + cell.row_span as usize;
-            if end_row > max_row {
-                max_row = end_row;
-            }
+    // 위 행의 rowspan에 밀려 열 수를 넘는 셀이 있으면 열을 늘려 다시 놓는다
+    let first_col_count = col_count;
+    let placed = loop {
+        check_cells(max_row, col_count, max_cells)?;
+        match place_cells(&parsed_rows, max_row, col_count) {
+            Ok(placed) => break placed,
+            Err(needed) => col_count = needed,
         }
+    };
+    if col_count > first_col_count {
+        let message = format!(
+            "병합된 셀이 표 범위를 넘어 열 수를 {}개에서 {}개로 늘렸습니다",
+            first_col_count, col_count
+        );
+        warnings.push(("TABLE_GRID_ADJUSTED", message));
     }
 
     let mut grid: Vec<Vec<String>> = vec![vec![String::new(); col_count]; max_row];
-    let mut occupied: Vec<Vec<bool>> = vec![vec![false; col_count]; max_row];
     let mut spans: Vec<(usize, usize, u32, u32)> = Vec::new(); // (row, col, col_span, row_span)
+    let col_selector = scraper::Selector::parse("col")
+        .map_err(|_| JsonToHwpxError::Conversion("col 셀렉터 파싱 실패".to_string()))?;
+    // `<col>`의 너비, 없으면 병합하지 않은 셀 중 열마다 처음 나온 너비
+    let mut widths: Vec<Option<HtmlWidth>> = document
+        .select(&col_selector)
+        .flat_map(|col| {
+            let span = col
+                .value()
+                .attr("span")
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(1)
+                .clamp(1, col_count);
+            std::iter::repeat_n(HtmlWidth::of(col.value()), span)
+        })
+        .collect();
+    let from_cols = widths.len() == col_count;
+    if !from_cols {
+        widths = vec![None; col_count];
+    }
+
+    for &(row_idx, col_idx, cell) in &placed {
+        grid[row_idx][col_idx] = cell.text.clone();
+        if !from_cols && cell.col_span == 1 && widths[col_idx].is_none() {
+            widths[col_idx] = cell.width;
+        }
+        if cell.col_span > 1 || cell.row_span > 1 {
+            spans.push((row_idx, col_idx, cell.col_span, cell.row_span));
+        }
+    }
+
+    // Build HwpxTable
+    let data: Vec<Vec<&str>> = grid
+        .iter()
+        .map(|r| r.iter().map(|s| s.as_str()).collect())
+        .collect();
+    // 앞쪽의 `<th>`로만 된 행은 머리글 행
+    let header_rows = parsed_rows
+        .iter()
+        .take_while(|row| row.iter().all(|cell| cell.header))
+        .count();
+    let mut table = HwpxTable::from_data(data).with_header_rows(header_rows);
+    if let Some(col_widths) = relative_col_widths(&widths) {
+        table = table.with_col_widths(col_widths);
+    }
 
+    for (row, col, cs, rs) in spans {
+        table.set_cell_span(row, col, cs, rs);
+    }
+    for (row, col, cell) in placed {
+        table.set_cell_style(row, col, cell.style);
+        let Some(options) = images.filter(|_| !cell.images.is_empty()) else {
+            table.set_cell_links(row, col, cell.links.clone());
+            continue;
+        };
+        if options.skip {
+            // 그림 자리 줄을 셀 텍스트 앞에 넣고 링크 범위를 그만큼 민다
+            let mut prefix: Vec<String> = cell
+                .images
+                .iter()
+                .map(|src| image::skipped_image_text(Some(src), false))
+                .collect();
+            if !cell.text.is_empty() {
+                prefix.push(String::new());
+            }
+            let prefix = prefix.join("\n");
+            let links = cell
+                .links
+                .iter()
+                .map(|link| CellLink {
+                    range: link.range.start + prefix.len()..link.range.end + prefix.len(),
+                    url: link.url.clone(),
+                })
+                .collect();
+            table.rows[row][col] = prefix + &cell.text;
+            table.set_cell_links(row, col, links);
+        } else {
+            for src in &cell.images {
+                table.add_cell_image(row, col, image::load_image(src, options.base_path)?);
+            }
+            table.set_cell_links(row, col, cell.links.clone());
+        }
+    }
+
+    // 바깥 표의 <caption> → 표 캡션 (CSS caption-side: bottom이면 표 아래).
+    // 셀 안에 든 표의 캡션은 쓰지 않는다
+    let table_selector = scraper::Selector::parse("table")
+        .map_err(|_| JsonToHwpxError::Conversion("table 셀렉터 파싱 실패".to_string()))?;
+    let caption = document.select(&table_selector).next().and_then(|outer| {
+        outer
+            .children()
+            .filter_map(scraper::ElementRef::wrap)
+            .find(|child| child.value().name() == "caption")
+    });
+    if let Some(caption) = caption {
+        let text = caption.text().collect::<Vec<_>>().join("");
+        let text = html_text(&text);
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !text.is_empty() {
+            let below = caption
+                .value()
+                .attr("style")
+                .is_some_and(|s| s.replace(' ', "").contains("caption-side:bottom"));
+            let position = if below {
+                CaptionPosition::Below
+            } else {
+                CaptionPosition::Above
+            };
+            table = table.with_caption(&text, position);
+        }
+    }
+
+    Ok(table)
+}
+
+/// 셀을 행 순서대로 그 행의 비어 있는 첫 칸부터 놓는다 (위 행의 rowspan이 차지한 칸은 건너뜀)
+///
+/// 병합이 `col_count`를 넘는 셀이 있으면 모두 들어가는 데 필요한 열 수를 `Err`로 돌려준다.
+/// 그 열 수로 다시 놓아도 넘칠 수 있으므로 `Ok`가 나올 때까지 되풀이한다.
+fn place_cells(
+    parsed_rows: &[Vec<ParsedCell>],
+    row_count: usize,
+    col_count: usize,
+) -> std::result::Result<Vec<(usize, usize, &ParsedCell)>, usize> {
+    let mut occupied = vec![vec![false; col_count]; row_count];
+    let mut placed = Vec::new();
+    let mut needed = col_count;
     for (row_idx, parsed_row) in parsed_rows.iter().enumerate() {
-        let mut col_cursor: usize = 0;
+        let mut col_cursor = 0;
         for cell in parsed_row {
-            // Find next available column in this row
             while col_cursor < col_count && occupied[row_idx][col_cursor] {
                 col_cursor += 1;
             }
-            if col_cursor >= col_count {
-                break;
+            let end = col_cursor + cell.col_span as usize;
+            needed = needed.max(end);
+            for row in occupied
+                .iter_mut()
+                .skip(row_idx)
+                .take(cell.row_span as usize)
+            {
+                for col in row.iter_mut().skip(col_cursor).take(cell.col_span as usize) {
+                    *col = true;
+                }
             }
+            placed.push((row_idx, col_cursor, cell));
+            col_cursor = end;
+        }
+    }
+    if needed > col_count {
+        Err(needed)
+    } else {
+        Ok(placed)
+    }
+}
 
-            // Place cell at (row_idx, col_cursor)
-            grid[row_idx][col_cursor] = cell.text.clone();
-
-            // Mark occupied cells and record span
-            let cs = cell.col_span.min(col_count as u32 - col_cursor as u32);
-            let rs = cell.row_span.min(max_row as u32 - row_idx as u32);
+/// 셀의 배경색과 글자 색
+///
+/// style의 `background-color`(없으면 `background`), `color`를 쓰고, 배경색이 없으면
+/// `bgcolor` 속성을 쓴다. 읽을 수 없는 색은 무시한다 ([`css_color`]).
+fn cell_style(element: &scraper::node::Element) -> CellStyle {
+    let mut style = CellStyle::default();
+    let mut background = None;
+    let declarations = element
+        .attr("style")
+        .into_iter()
+        .flat_map(|s| s.split(';'))
+        .filter_map(|decl| decl.split_once(':'));
+    for (name, value) in declarations {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "background-color" => style.background = css_color(value),
+            // 단축 속성은 색만 있을 때
+            "background" => background = css_color(value),
+            "color" => style.text_color = css_color(value),
+            _ => {}
+        }
+    }
+    style.background = style
+        .background
+        .or(background)
+        .or_else(|| element.attr("bgcolor").and_then(css_color));
+    style
+}
 
-            if cs > 1 || rs > 1 {
-                spans.push((row_idx, col_cursor, cs, rs));
+/// CSS 색 (`#RGB`, `#RRGGBB`, `rgb(r, g, b)`, 자주 쓰는 색 이름)을 0xRRGGBB로
+fn css_color(value: &str) -> Option<u32> {
+    let value = value.trim().trim_end_matches("!important").trim();
+    let value = value.to_ascii_lowercase();
+    if let Some(hex) = value.strip_prefix('#') {
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        return match hex.len() {
+            3 => {
+                let expanded: String = hex.chars().flat_map(|c| [c, c]).collect();
+                u32::from_str_radix(&expanded, 16).ok()
             }
+            6 => u32::from_str_radix(hex, 16).ok(),
+            _ => None,
+        };
+    }
+    if let Some(args) = value
+        .strip_prefix("rgb(")
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        let channels: Vec<u8> = args
+            .split(',')
+            .map(|c| c.trim().parse().ok())
+            .collect::<Option<_>>()?;
+        return match channels[..] {
+            [r, g, b] => Some(u32::from_be_bytes([0, r, g, b])),
+            _ => None,
+        };
+    }
+    let named = match value.as_str() {
+        "black" => 0x000000,
+        "white" => 0xFFFFFF,
+        "red" => 0xFF0000,
+        "green" => 0x008000,
+        "blue" => 0x0000FF,
+        "yellow" => 0xFFFF00,
+        "orange" => 0xFFA500,
+        "gray" | "grey" => 0x808080,
+        "lightgray" | "lightgrey" => 0xD3D3D3,
+        "darkgray" | "darkgrey" => 0xA9A9A9,
+        "silver" => 0xC0C0C0,
+        "maroon" => 0x800000,
+        "brown" => 0xA52A2A,
+        "pink" => 0xFFC0CB,
+        "purple" => 0x800080,
+        "fuchsia" | "magenta" => 0xFF00FF,
+        "lime" => 0x00FF00,
+        "olive" => 0x808000,
+        "navy" => 0x000080,
+        "teal" => 0x008080,
+        "aqua" | "cyan" => 0x00FFFF,
+        _ => return None,
+    };
+    Some(named)
+}
 
-            for row in occupied.iter_mut().skip(row_idx).take(rs as usize) {
-                for col in row.iter_mut().skip(col_cursor).take(cs as usize) {
-                    *col = true;
+/// 열 너비들을 HwpxTable의 상대 너비로
+///
+/// 단위가 모두 같아야 한다. 퍼센트는 빠진 열에 남은 비율을 고르게 나누고, px는
+/// 빠진 열이 없어야 한다. 너비가 없거나 맞지 않으면 `None` (열을 고르게 나눔).
+fn relative_col_widths(widths: &[Option<HtmlWidth>]) -> Option<Vec<u32>> {
+    let given: Vec<HtmlWidth> = widths.iter().flatten().copied().collect();
+    let percent = match given.first()? {
+        HtmlWidth::Percent(_) => true,
+        HtmlWidth::Px(_) => false,
+    };
+    let mut total = 0.0;
+    for width in &given {
+        match (width, percent) {
+            (HtmlWidth::Percent(value), true) | (HtmlWidth::Px(value), false) => total += value,
+            _ => return None,
+        }
+    }
+
+    let missing = widths.len() - given.len();
+    let fill = if missing == 0 {
+        0.0
+    } else if percent && total < 100.0 {
+        (100.0 - total) / missing as f64
+    } else {
+        return None;
+    };
+    // 소수 둘째 자리까지 살려 정수로
+    Some(
+        widths
+            .iter()
+            .map(|width| match width {
+                Some(HtmlWidth::Percent(value) | HtmlWidth::Px(value)) => *value,
+                None => fill,
+            })
+            .map(|value| (value * 100.0).round() as u32)
+            .collect(),
+    )
+}
+
+/// 셀 텍스트와 그 안의 링크. `<br>`마다 줄을 나누고, 줄마다 공백(원본의 줄 바꿈 포함)을
+/// 한 칸으로 줄인다. 앞뒤의 빈 줄과 공백은 버린다. 셀 안의 `ul`/`ol`은 항목마다 "• ",
+/// "1. "로 시작하는 줄이 되고, `a href`는 그 텍스트 범위의 링크가 된다. 스킴이 http,
+/// https, mailto가 아닌 링크는 텍스트만 남기고 `warnings`에 `LINK_DROPPED`로 적는다.
+/// `img`는 텍스트에서 빠지고 src만 모은다.
+fn cell_text(
+    cell: scraper::ElementRef<'_>,
+    warnings: &mut Vec<(&'static str, String)>,
+) -> (String, Vec<CellLink>, Vec<String>) {
+    let mut collector = CellLines {
+        lines: vec![Vec::new()],
+        urls: Vec::new(),
+        link: None,
+        images: Vec::new(),
+        dropped_links: Vec::new(),
+    };
+    collector.collect(cell);
+    warnings.extend(
+        collector
+            .dropped_links
+            .iter()
+            .map(|url| dropped_link_warning(url)),
+    );
+
+    let lines: Vec<Vec<(char, Option<usize>)>> = collector
+        .lines
+        .iter()
+        .map(|line| collapse_whitespace(line))
+        .collect();
+    let first = lines.iter().position(|line| !line.is_empty());
+    let last = lines.iter().rposition(|line| !line.is_empty());
+    let (Some(first), Some(last)) = (first, last) else {
+        return (String::new(), Vec::new(), collector.images);
+    };
+
+    let mut text = String::new();
+    // (링크 번호, 링크). 같은 `a`에서 이어지는 글자는 한 범위로
+    let mut links: Vec<(usize, CellLink)> = Vec::new();
+    for (idx, line) in lines[first..=last].iter().enumerate() {
+        if idx > 0 {
+            text.push('\n');
+        }
+        for &(c, link) in line {
+            if let Some(id) = link {
+                match links.last_mut() {
+                    Some((last_id, last)) if *last_id == id && last.range.end == text.len() => {
+                        last.range.end += c.len_utf8();
+                    }
+                    _ => links.push((
+                        id,
+                        CellLink {
+                            range: text.len()..text.len() + c.len_utf8(),
+                            url: collector.urls[id].clone(),
+                        },
+                    )),
                 }
             }
+            text.push(c);
+        }
+    }
+    let links = links.into_iter().map(|(_, link)| link).collect();
+    (text, links, collector.images)
+}
+
+/// 셀의 줄들. 줄은 (텍스트, 링크 번호) 조각의 목록
+struct CellLines {
+    lines: Vec<Vec<(String, Option<usize>)>>,
+    /// 링크 번호별 URL
+    urls: Vec<String>,
+    /// 지금 안에 있는 `a`의 링크 번호
+    link: Option<usize>,
+    /// `img`의 src
+    images: Vec<String>,
+    /// 스킴이 허용되지 않아 버린 링크의 URL
+    dropped_links: Vec<String>,
+}
 
-            col_cursor += cs as usize;
+impl CellLines {
+    fn collect(&mut self, element: scraper::ElementRef<'_>) {
+        for child in element.children() {
+            if let scraper::Node::Text(text) = child.value() {
+                let segment = (html_text(text).into_owned(), self.link);
+                self.current().push(segment);
+                continue;
+            }
+            let Some(child) = scraper::ElementRef::wrap(child) else {
+                continue;
+            };
+            match child.value().name() {
+                "br" => self.lines.push(Vec::new()),
+                "img" => self
+                    .images
+                    .extend(child.value().attr("src").map(String::from)),
+                name @ ("ul" | "ol") => {
+                    let items = child
+                        .children()
+                        .filter_map(scraper::ElementRef::wrap)
+                        .filter(|li| li.value().name() == "li");
+                    for (idx, li) in items.enumerate() {
+                        self.start_line();
+                        let marker = match name {
+                            "ol" => format!("{}. ", idx + 1),
+                            _ => "\u{2022} ".to_string(),
+                        };
+                        self.current().push((marker, None));
+                        self.collect(li);
+                    }
+                    self.start_line();
+                }
+                "a" => match child.value().attr("href") {
+                    Some(href) if link_url_error(href).is_some() => {
+                        self.dropped_links.push(href.to_string());
+                        self.collect(child);
+                    }
+                    Some(href) => {
+                        let outer = self.link;
+                        self.urls.push(href.to_string());
+                        self.link = Some(self.urls.len() - 1);
+                        self.collect(child);
+                        self.link = outer;
+                    }
+                    None => self.collect(child),
+                },
+                _ => self.collect(child),
+            }
         }
     }
 
-    // Build HwpxTable
-    let data: Vec<Vec<&str>> = grid
-        .iter()
-        .map(|r| r.iter().map(|s| s.as_str()).collect())
-        .collect();
-    let mut table = HwpxTable::from_data(data);
+    fn current(&mut self) -> &mut Vec<(String, Option<usize>)> {
+        self.lines.last_mut().expect("at least one line")
+    }
 
-    for (row, col, cs, rs) in spans {
-        table.set_cell_span(row, col, cs, rs);
+    /// 새 줄을 시작한다. 지금 줄이 비어 있으면(공백뿐이면) 그 줄을 쓴다.
+    fn start_line(&mut self) {
+        let line = self.current();
+        if line.iter().all(|(text, _)| text.trim().is_empty()) {
+            line.clear();
+        } else {
+            self.lines.push(Vec::new());
+        }
     }
+}
 
-    Ok(table)
+/// 한 줄의 조각을 글자와 링크 번호로 풀면서 공백을 한 칸으로 줄이고 앞뒤 공백을 버린다.
+/// 링크 경계의 공백은 링크 밖으로 둔다.
+fn collapse_whitespace(segments: &[(String, Option<usize>)]) -> Vec<(char, Option<usize>)> {
+    let mut chars = Vec::new();
+    let mut pending_space = false;
+    let mut prev_link = None;
+    for (text, link) in segments {
+        for c in text.chars() {
+            if c.is_whitespace() {
+                pending_space = !chars.is_empty();
+                continue;
+            }
+            if pending_space {
+                let space_link = if prev_link == *link { *link } else { None };
+                chars.push((' ', space_link));
+                pending_space = false;
+            }
+            chars.push((c, *link));
+            prev_link = *link;
+        }
+    }
+    chars
 }
 
 #[cfg(test)]
@@ -142,7 +773,7 @@ mod tests {
     #[test]
     fn test_simple_table() {
         let html = "<table><tr><td>A</td><td>B</td></tr><tr><td>C</td><td>D</td></tr></table>";
-        let table = parse_html_table(html).unwrap();
+        let table = parse_html_table(html, usize::MAX).unwrap();
         assert_eq!(table.rows.len(), 2);
         assert_eq!(table.rows[0], vec!["A", "B"]);
         assert_eq!(table.rows[1], vec!["C", "D"]);
@@ -151,22 +782,46 @@ mod tests {
     #[test]
     fn test_table_with_thead() {
         let html = "<table><thead><tr><th>헤더1</th><th>헤더2</th></tr></thead><tbody><tr><td>값1</td><td>값2</td></tr></tbody></table>";
-        let table = parse_html_table(html).unwrap();
+        let table = parse_html_table(html, usize::MAX).unwrap();
         assert_eq!(table.rows.len(), 2);
         assert_eq!(table.rows[0], vec!["헤더1", "헤더2"]);
     }
 
+    #[test]
+    fn test_caption_element() {
+        let html = "<table><caption> 분기별 매출 </caption><tr><td>A</td></tr></table>";
+        let table = parse_html_table(html, usize::MAX).unwrap();
+        assert_eq!(table.caption.as_deref(), Some("분기별 매출"));
+        assert_eq!(table.caption_position, CaptionPosition::Above);
+        assert_eq!(table.rows, vec![vec!["A"]]);
+
+        let html = r#"<table><caption style="caption-side: bottom">출처</caption><tr><td>A</td></tr></table>"#;
+        let table = parse_html_table(html, usize::MAX).unwrap();
+        assert_eq!(table.caption_position, CaptionPosition::Below);
+
+        let table = parse_html_table("<table><tr><td>A</td></tr></table>", usize::MAX).unwrap();
+        assert!(table.caption.is_none());
+
+        // 여러 줄로 쓴 캡션은 한 줄로, 셀 안에 든 표의 캡션은 버린다
+        let html = "<table><caption>\n  2024년\n  실적\n</caption><tr><td>A</td></tr></table>";
+        let table = parse_html_table(html, usize::MAX).unwrap();
+        assert_eq!(table.caption.as_deref(), Some("2024년 실적"));
+        let html = "<table><tr><td><table><caption>안쪽</caption><tr><td>B</td></tr></table></td></tr></table>";
+        let table = parse_html_table(html, usize::MAX).unwrap();
+        assert!(table.caption.is_none());
+    }
+
     #[test]
     fn test_empty_table() {
         let html = "<table></table>";
-        let result = parse_html_table(html);
+        let result = parse_html_table(html, usize::MAX);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_uneven_columns() {
         let html = "<table><tr><td>A</td><td>B</td><td>C</td></tr><tr><td>D</td></tr></table>";
-        let table = parse_html_table(html).unwrap();
+        let table = parse_html_table(html, usize::MAX).unwrap();
         assert_eq!(table.rows[1].len(), 3);
         assert_eq!(table.rows[1][1], "");
     }
@@ -175,7 +830,7 @@ mod tests {
     fn test_add_table_to_writer() {
         let mut writer = HwpxWriter::new();
         let html = "<table><tr><td>A</td><td>B</td></tr></table>";
-        add_table_from_html(&mut writer, html).unwrap();
+        add_table_from_html(&mut writer, html, usize::MAX, None).unwrap();
 
         let bytes = writer.to_bytes().unwrap();
         assert!(!bytes.is_empty());
@@ -184,7 +839,7 @@ mod tests {
     #[test]
     fn test_colspan() {
         let html = r#"<table><tr><th colspan="3">합계</th></tr><tr><td>A</td><td>B</td><td>C</td></tr></table>"#;
-        let table = parse_html_table(html).unwrap();
+        let table = parse_html_table(html, usize::MAX).unwrap();
         assert_eq!(table.rows.len(), 2);
         assert_eq!(table.rows[0].len(), 3);
         assert_eq!(table.rows[0][0], "합계");
@@ -204,7 +859,7 @@ mod tests {
     fn test_rowspan() {
         let html =
             r#"<table><tr><td rowspan="2">병합</td><td>A</td></tr><tr><td>B</td></tr></table>"#;
-        let table = parse_html_table(html).unwrap();
+        let table = parse_html_table(html, usize::MAX).unwrap();
         assert_eq!(table.rows.len(), 2);
         assert_eq!(table.rows[0][0], "병합");
         assert_eq!(table.rows[0][1], "A");
@@ -218,6 +873,49 @@ mod tests {
         assert_eq!(table.rows[1][1], "B");
     }
 
+    #[test]
+    fn test_spans_past_grid_expand_with_warning() {
+        // 둘째 행의 colspan 합이 첫 행보다 넓다 (위 행의 rowspan 칸 포함)
+        let html = r#"<table><tr><td rowspan="2">A</td><td>B</td></tr><tr><td colspan="2">C</td></tr></table>"#;
+        let mut warnings = Vec::new();
+        let table = parse_html_table_with(html, usize::MAX, None, &mut warnings).unwrap();
+        assert_eq!(table.rows, vec![vec!["A", "B", ""], vec!["", "C", ""]]);
+        assert!(table.is_covered(1, 0));
+        assert_eq!(table.get_cell_span(1, 1).col_span, 2);
+        assert!(table.is_covered(1, 2));
+        assert_eq!(
+            warnings,
+            vec![(
+                "TABLE_GRID_ADJUSTED",
+                "병합된 셀이 표 범위를 넘어 열 수를 2개에서 3개로 늘렸습니다".to_string()
+            )]
+        );
+
+        // rowspan이 마지막 행을 넘는다
+        let html = r#"<table><tr><td rowspan="3">A</td><td>B</td></tr><tr><td>C</td></tr></table>"#;
+        let mut warnings = Vec::new();
+        let table = parse_html_table_with(html, usize::MAX, None, &mut warnings).unwrap();
+        assert_eq!(
+            table.rows,
+            vec![vec!["A", "B"], vec!["", "C"], vec!["", ""]]
+        );
+        assert_eq!(table.get_cell_span(0, 0).row_span, 3);
+        assert!(table.is_covered(2, 0));
+        assert_eq!(
+            warnings,
+            vec![(
+                "TABLE_GRID_ADJUSTED",
+                "rowspan이 마지막 행을 넘어 표를 2행에서 3행으로 늘렸습니다".to_string()
+            )]
+        );
+
+        // 어긋나지 않은 표는 경고가 없다
+        let html = r#"<table><tr><td rowspan="2">A</td><td>B</td></tr><tr><td>C</td></tr></table>"#;
+        let mut warnings = Vec::new();
+        parse_html_table_with(html, usize::MAX, None, &mut warnings).unwrap();
+        assert!(warnings.is_empty());
+    }
+
     #[test]
     fn test_colspan_and_rowspan_combined() {
         let html = r#"<table>
@@ -225,7 +923,7 @@ mod tests {
             <tr><td>F</td></tr>
             <tr><td>G</td><td>H</td><td>I</td></tr>
         </table>"#;
-        let table = parse_html_table(html).unwrap();
+        let table = parse_html_table(html, usize::MAX).unwrap();
         assert_eq!(table.rows.len(), 3);
         assert_eq!(table.rows[0][0], "병합");
         let span = table.get_cell_span(0, 0);
@@ -249,7 +947,7 @@ mod tests {
             <tr><td colspan="2">AB</td><td colspan="2">CD</td></tr>
             <tr><td>A</td><td>B</td><td>C</td><td>D</td></tr>
         </table>"#;
-        let table = parse_html_table(html).unwrap();
+        let table = parse_html_table(html, usize::MAX).unwrap();
         assert_eq!(table.rows[0].len(), 4);
         assert_eq!(table.rows[0][0], "AB");
         assert_eq!(table.rows[0][2], "CD");
@@ -270,7 +968,7 @@ mod tests {
             <tr><td rowspan="2">G2</td><td>C</td></tr>
             <tr><td>D</td></tr>
         </table>"#;
-        let table = parse_html_table(html).unwrap();
+        let table = parse_html_table(html, usize::MAX).unwrap();
         assert_eq!(table.rows.len(), 4);
         assert_eq!(table.rows[0][0], "G1");
         assert_eq!(table.rows[2][0], "G2");
@@ -288,7 +986,7 @@ mod tests {
             <tr><td>백엔드</td><td>8</td></tr>
             <tr><td>인프라</td><td>3</td></tr>
         </table>"#;
-        let table = parse_html_table(html).unwrap();
+        let table = parse_html_table(html, usize::MAX).unwrap();
         assert_eq!(table.rows.len(), 3);
         assert_eq!(table.rows[0][0], "개발본부");
         let span = table.get_cell_span(0, 0);
@@ -310,7 +1008,7 @@ mod tests {
             <tr><td colspan="2">중간</td></tr>
             <tr><td colspan="5">하단</td></tr>
         </table>"#;
-        let table = parse_html_table(html).unwrap();
+        let table = parse_html_table(html, usize::MAX).unwrap();
         assert_eq!(table.rows.len(), 4);
         assert_eq!(table.rows[0].len(), 5);
 
@@ -341,26 +1039,184 @@ mod tests {
 
     #[test]
     fn test_inline_style_ignored() {
-        // 인라인 스타일은 무시하고 텍스트만 추출
+        // 셀 텍스트는 스타일과 상관없이 텍스트만 추출 (색 밖의 스타일은 무시)
         let html = r#"<table><tr><td style="color:red; font-weight:bold;">스타일</td><td class="highlight">클래스</td></tr></table>"#;
-        let table = parse_html_table(html).unwrap();
+        let table = parse_html_table(html, usize::MAX).unwrap();
         assert_eq!(table.rows[0][0], "스타일");
         assert_eq!(table.rows[0][1], "클래스");
     }
 
+    #[test]
+    fn test_cell_colors() {
+        let html = r#"<table><tr><th style="background-color:#EEE;color:#C00">머리글</th><td bgcolor="gray" style="COLOR: rgb(0, 128, 255)">값</td><td style="background: yellow; color: none">노랑</td><td>없음</td></tr></table>"#;
+        let table = parse_html_table(html, usize::MAX).unwrap();
+        let style = |col| table.cell_styles.get(&(0, col)).copied();
+        assert_eq!(
+            style(0),
+            Some(CellStyle {
+                background: Some(0xEEEEEE),
+                text_color: Some(0xCC0000),
+            })
+        );
+        assert_eq!(
+            style(1),
+            Some(CellStyle {
+                background: Some(0x808080),
+                text_color: Some(0x0080FF),
+            })
+        );
+        assert_eq!(
+            style(2),
+            Some(CellStyle {
+                background: Some(0xFFFF00),
+                text_color: None,
+            })
+        );
+        assert_eq!(style(3), None);
+    }
+
     #[test]
     fn test_th_treated_as_text() {
         // th는 td와 동일하게 텍스트만 추출 (HwpxTable이 셀별 스타일 미지원)
         let html = "<table><tr><th>헤더</th></tr><tr><td>데이터</td></tr></table>";
-        let table = parse_html_table(html).unwrap();
+        let table = parse_html_table(html, usize::MAX).unwrap();
         assert_eq!(table.rows[0][0], "헤더");
         assert_eq!(table.rows[1][0], "데이터");
     }
 
+    #[test]
+    fn test_entities_in_cells() {
+        let html = "<table><caption>A&amp;amp;B</caption><tr><td>가&nbsp;나</td><td>R&amp;D</td><td>&#x2022; 항목</td></tr><tr><td>&amp;lt;b&amp;gt;</td><td>&amp;#8226;</td><td>&unknown; &amp</td></tr></table>";
+        let table = parse_html_table(html, usize::MAX).unwrap();
+        assert_eq!(table.rows[0], vec!["가 나", "R&D", "\u{2022} 항목"]);
+        // 파서가 푼 텍스트를 다시 풀지 않는다
+        assert_eq!(table.rows[1], vec!["&lt;b&gt;", "&#8226;", "&unknown; &"]);
+        assert_eq!(table.caption.as_deref(), Some("A&amp;B"));
+    }
+
+    #[test]
+    fn test_br_in_cell_becomes_line_break() {
+        let html = "<table><tr><td>서울시<br>강남구</td><td><br>앞<br/><br>\n  뒤 <br></td><td>한\n줄</td></tr></table>";
+        let table = parse_html_table(html, usize::MAX).unwrap();
+        assert_eq!(table.rows[0], vec!["서울시\n강남구", "앞\n\n뒤", "한 줄"]);
+    }
+
+    #[test]
+    fn test_col_widths() {
+        let colgroup = r#"<table><colgroup><col width="20%"><col style="width: 80%"></colgroup><tr><td>A</td><td>B</td></tr></table>"#;
+        let table = parse_html_table(colgroup, usize::MAX).unwrap();
+        assert_eq!(table.col_widths, vec![2000, 8000]);
+
+        let span = r#"<table><col span="2" width="100px"><col width="50"><tr><td>A</td><td>B</td><td>C</td></tr></table>"#;
+        let table = parse_html_table(span, usize::MAX).unwrap();
+        assert_eq!(table.col_widths, vec![10000, 10000, 5000]);
+
+        // 병합한 셀은 건너뛰고 아래 행의 셀 너비를 쓴다. 빠진 열은 남은 비율
+        let cells = r#"<table><tr><td colspan="2" width="90%">제목</td><td>C</td></tr><tr><td width="50%">A</td><td width="30%">B</td><td>C</td></tr></table>"#;
+        let table = parse_html_table(cells, usize::MAX).unwrap();
+        assert_eq!(table.col_widths, vec![5000, 3000, 2000]);
+
+        // 단위가 섞이거나 px가 빠지면 기본값(고르게)
+        for html in [
+            r#"<table><tr><td width="20%">A</td><td width="100px">B</td></tr></table>"#,
+            r#"<table><tr><td width="100px">A</td><td>B</td></tr></table>"#,
+            r#"<table><tr><td width="auto">A</td><td>B</td></tr></table>"#,
+        ] {
+            let table = parse_html_table(html, usize::MAX).unwrap();
+            assert_eq!(table.col_widths, vec![8390, 8390], "{}", html);
+        }
+    }
+
+    #[test]
+    fn test_list_in_cell_becomes_lines() {
+        let html = "<table><tr><td>준비물: <ul>\n  <li>항목1</li>\n  <li><b>항목</b>2</li>\n</ul></td><td><ol><li>첫째</li><li>둘째<ul><li>하위</li></ul></li></ol>끝</td></tr></table>";
+        let table = parse_html_table(html, usize::MAX).unwrap();
+        assert_eq!(
+            table.rows[0],
+            vec![
+                "준비물:\n\u{2022} 항목1\n\u{2022} 항목2",
+                "1. 첫째\n2. 둘째\n\u{2022} 하위\n끝",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_links_in_cell() {
+        let html = r#"<table><tr><td>보기: <a href="https://example.com/a"> 첫 <b>문서</b> </a>와<br><a href="https://example.com/b">둘째</a></td><td><a>주소 없음</a></td></tr></table>"#;
+        let table = parse_html_table(html, usize::MAX).unwrap();
+        let text = &table.rows[0][0];
+        assert_eq!(text, "보기: 첫 문서 와\n둘째");
+
+        let links = &table.cell_links[&(0, 0)];
+        let linked: Vec<(&str, &str)> = links
+            .iter()
+            .map(|link| (&text[link.range.clone()], link.url.as_str()))
+            .collect();
+        assert_eq!(
+            linked,
+            vec![
+                ("첫 문서", "https://example.com/a"),
+                ("둘째", "https://example.com/b"),
+            ]
+        );
+        assert!(!table.cell_links.contains_key(&(0, 1)));
+    }
+
+    #[test]
+    fn test_unsafe_links_in_cell_are_dropped() {
+        let html = r#"<table><tr><td><a href="javascript:alert(1)">누르기</a> <a href="MAILTO:a@example.com">메일</a></td></tr></table>"#;
+        let mut warnings = Vec::new();
+        let table = parse_html_table_with(html, usize::MAX, None, &mut warnings).unwrap();
+        assert_eq!(table.rows[0][0], "누르기 메일");
+        let links = &table.cell_links[&(0, 0)];
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].url, "MAILTO:a@example.com");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].0, "LINK_DROPPED");
+        assert!(
+            warnings[0].1.contains("javascript:alert(1)"),
+            "{}",
+            warnings[0].1
+        );
+    }
+
+    #[test]
+    fn test_images_in_cell() {
+        let html = r#"<table><tr><td><img src="a.png"> 정면 <a href="https://example.com">상세</a></td><td><img src="data:image/png;base64,AAAA"></td></tr></table>"#;
+
+        // images 옵션이 없으면 그림은 버린다
+        let table = parse_html_table(html, usize::MAX).unwrap();
+        assert_eq!(table.rows[0], vec!["정면 상세", ""]);
+        assert!(table.cell_images.is_empty());
+
+        // skipImages면 그림 자리 줄을 앞에 넣고 링크 범위를 민다
+        let options = CellImageOptions {
+            base_path: Path::new("."),
+            skip: true,
+            max_total_bytes: usize::MAX,
+        };
+        let table =
+            parse_html_table_with(html, usize::MAX, Some(&options), &mut Vec::new()).unwrap();
+        let text = &table.rows[0][0];
+        assert_eq!(text, "[이미지: a.png]\n정면 상세");
+        let link = &table.cell_links[&(0, 0)][0];
+        assert_eq!(&text[link.range.clone()], "상세");
+        assert_eq!(table.rows[0][1], "[이미지: base64 생략]");
+        assert!(table.cell_images.is_empty());
+
+        // 읽지 못한 그림은 에러
+        let options = CellImageOptions {
+            skip: false,
+            ..options
+        };
+        let result = parse_html_table_with(html, usize::MAX, Some(&options), &mut Vec::new());
+        assert!(matches!(result, Err(JsonToHwpxError::Conversion(_))));
+    }
+
     #[test]
     fn test_whitespace_trimming() {
         let html = "<table><tr><td>  공백  </td><td>\n줄바꿈\n</td></tr></table>";
-        let table = parse_html_table(html).unwrap();
+        let table = parse_html_table(html, usize::MAX).unwrap();
         assert_eq!(table.rows[0][0], "공백");
         assert_eq!(table.rows[0][1], "줄바꿈");
     }
@@ -369,7 +1225,7 @@ mod tests {
     fn test_nested_html_elements_text_only() {
         // 셀 내부 HTML 태그는 무시하고 텍스트만 추출
         let html = "<table><tr><td><b>굵게</b> 일반</td><td><a href='#'>링크</a></td></tr></table>";
-        let table = parse_html_table(html).unwrap();
+        let table = parse_html_table(html, usize::MAX).unwrap();
         assert_eq!(table.rows[0][0], "굵게 일반");
         assert_eq!(table.rows[0][1], "링크");
     }
@@ -377,18 +1233,96 @@ mod tests {
     #[test]
     fn test_empty_cells() {
         let html = "<table><tr><td></td><td>값</td></tr><tr><td>A</td><td></td></tr></table>";
-        let table = parse_html_table(html).unwrap();
+        let table = parse_html_table(html, usize::MAX).unwrap();
         assert_eq!(table.rows[0][0], "");
         assert_eq!(table.rows[0][1], "값");
         assert_eq!(table.rows[1][0], "A");
         assert_eq!(table.rows[1][1], "");
     }
 
+    fn rows_table(value: serde_json::Value) -> TableRows {
+        match serde_json::from_value(value).unwrap() {
+            super::super::model::TableContent::Rows(rows) => rows,
+            other => panic!("rows 표가 아님: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_rows_table_matches_html() {
+        let rows = rows_table(serde_json::json!({
+            "rows": [["이름", "나이"], ["홍길동", "30"], ["김철수"]],
+            "header_rows": 1,
+            "merges": [{ "row": 1, "col": 0, "rowspan": 2 }]
+        }));
+        let table = build_rows_table(&rows).unwrap();
+        let html = parse_html_table(
+            r#"<table><tr><th>이름</th><th>나이</th></tr><tr><td rowspan="2">홍길동</td><td>30</td></tr><tr><td></td></tr></table>"#,
+            usize::MAX,
+        )
+        .unwrap();
+
+        // 덮인 셀(2,0)의 "김철수"는 버린다
+        assert_eq!(table.rows, html.rows);
+        assert_eq!(table.rows[2], vec!["", ""]);
+        assert_eq!(table.header_rows, 1);
+        assert_eq!(html.header_rows, 1);
+        assert_eq!(table.get_cell_span(1, 0), html.get_cell_span(1, 0));
+        assert!(table.is_covered(2, 0));
+    }
+
+    #[test]
+    fn test_rows_table_validation() {
+        let err = |value: serde_json::Value| validate_rows(&rows_table(value)).unwrap_err();
+
+        let e = err(serde_json::json!({
+            "rows": [["A", "B"], ["C", "D"]],
+            "merges": [{ "row": 0, "col": 0 }, { "row": 0, "col": 1, "colspan": 2 }]
+        }));
+        assert!(matches!(e, JsonToHwpxError::Input(_)));
+        assert!(
+            e.to_string()
+                .contains("merges[1]: 병합 범위가 표(2행 2열)를 벗어납니다"),
+            "{}",
+            e
+        );
+
+        let e = err(serde_json::json!({
+            "rows": [["A", "B"], ["C", "D"]],
+            "merges": [{ "row": 0, "col": 0, "rowspan": 2 }, { "row": 1, "col": 0, "colspan": 2 }]
+        }));
+        assert!(
+            e.to_string()
+                .contains("merges[1]: merges[0]와 병합 범위가 겹칩니다"),
+            "{}",
+            e
+        );
+
+        let e = err(
+            serde_json::json!({ "rows": [["A"]], "merges": [{ "row": 0, "col": 0, "colspan": 0 }] }),
+        );
+        assert!(
+            e.to_string()
+                .contains("merges[0]: colspan, rowspan은 1 이상"),
+            "{}",
+            e
+        );
+
+        let e = err(serde_json::json!({ "rows": [["A"]], "header_rows": 2 }));
+        assert!(
+            e.to_string().contains("header_rows가 행 수보다 많습니다"),
+            "{}",
+            e
+        );
+
+        let e = err(serde_json::json!({ "rows": [[]] }));
+        assert!(e.to_string().contains("셀이 없습니다"), "{}", e);
+    }
+
     #[test]
     fn test_table_with_hwpx_reader_verification() {
         let mut writer = HwpxWriter::new();
         let html = "<table><thead><tr><th>이름</th><th>나이</th></tr></thead><tbody><tr><td>홍길동</td><td>30</td></tr></tbody></table>";
-        add_table_from_html(&mut writer, html).unwrap();
+        add_table_from_html(&mut writer, html, usize::MAX, None).unwrap();
 
         let bytes = writer.to_bytes().unwrap();
         // HwpxReader가 생성된 HWPX를 정상적으로 읽을 수 있는지 확인
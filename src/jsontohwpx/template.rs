@@ -0,0 +1,368 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+
+use super::model::Article;
+
+/// dateFormat 옵션이 없을 때 쓰는 날짜 형식
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// regDt로 받는 날짜 형식 (ISO 8601 외)
+const DATE_INPUT_FORMATS: [&str; 4] = [
+    "%Y-%m-%d %p %I:%M:%S",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%d %H:%M",
+];
+
+/// filenameTemplate 옵션이 없을 때 쓰는 출력 파일 이름
+pub const DEFAULT_FILENAME_TEMPLATE: &str = "{article_id}.hwpx";
+
+/// 파일 이름 템플릿 자리표시자
+pub const FILENAME_PLACEHOLDERS: [&str; 4] = ["article_id", "title", "date", "department"];
+
+/// 파일 이름 `{date}`의 형식
+const FILENAME_DATE_FORMAT: &str = "%Y%m%d";
+
+/// 파일 이름에 넣는 값 하나의 최대 글자 수
+const MAX_FILENAME_VALUE_CHARS: usize = 100;
+
+/// `.hwpx`를 뺀 파일 이름의 최대 바이트 수
+///
+/// 파일 이름 한도(NAME_MAX) 255바이트에서 비동기 작업이 앞에 붙이는
+/// `{작업 ID}_`(UUID 36바이트와 `_`)와 `.hwpx`를 뺀 값
+const MAX_FILENAME_STEM_BYTES: usize = 255 - 37 - ".hwpx".len();
+
+/// Windows에서 파일 이름으로 쓸 수 없는 이름 (확장자와 관계없이)
+const RESERVED_FILENAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// 머리말/꼬리말 템플릿의 `{이름}`을 article 값으로 바꾼다
+///
+/// - `{title}`, `{board_name}`, `{author}`, `{dept}`, `{date}`, `{atcl_id}`
+/// - 그 밖의 이름은 article 추가 필드에서 찾는다
+/// - 값이 없으면 빈 문자열, 모르는 이름은 빈 문자열과 경고
+/// - `{{`, `}}`는 중괄호 그대로
+///
+/// `{date}`는 regDt를 날짜로 읽을 수 있으면 `date_format`(strftime 형식)으로,
+/// 아니면 regDt 그대로 쓴다.
+pub fn render(template: &str, article: &Article, date_format: &str) -> (String, Vec<String>) {
+    let (out, unknown) = substitute(template, |name| value_of(name, article, date_format));
+    let warnings = unknown
+        .into_iter()
+        .map(|name| format!("알 수 없는 자리표시자 {{{}}}: 빈 값으로 바꿨습니다", name))
+        .collect();
+    (out, warnings)
+}
+
+/// 출력 파일 이름 템플릿의 `{이름}`을 article 값으로 바꾼다
+///
+/// - `{article_id}`, `{title}`, `{department}`, `{date}`(regDt를 `YYYYMMDD`로)
+/// - 넣는 값은 [`sanitize_filename`]으로 거른다. 템플릿 자체는
+///   [`Options::validate`](super::model::Options::validate)에서 검증한다
+/// - `.hwpx`로 끝나지 않으면 붙이고, 이름이 비면 atclId를 쓴다
+/// - `.hwpx` 앞부분은 [`MAX_FILENAME_STEM_BYTES`]바이트로 자른다 (글자 중간에서 자르지 않음)
+pub fn render_filename(template: &str, article: &Article) -> String {
+    let (name, _) = substitute(template, |name| {
+        let value = match name {
+            "article_id" => Some(article.atcl_id.clone()),
+            "title" => Some(article.subject.clone()),
+            "department" => article.reg_dept_name.clone(),
+            "date" => article.reg_dt.as_deref().map(|date| {
+                format_date(date, FILENAME_DATE_FORMAT).unwrap_or_else(|| date.to_string())
+            }),
+            _ => return None,
+        };
+        Some(value.map(|value| sanitize_filename(&value)))
+    });
+
+    let stem = name.strip_suffix(".hwpx").unwrap_or(&name);
+    if stem.trim_matches(['.', ' ']).is_empty() {
+        let id = sanitize_article_id(&article.atcl_id);
+        return format!("{}.hwpx", truncate_filename_stem(&id));
+    }
+    format!("{}.hwpx", truncate_filename_stem(stem))
+}
+
+/// `stem`을 [`MAX_FILENAME_STEM_BYTES`]바이트 안으로 자른다. 자른 끝의 `.`과 공백은 걷어낸다
+fn truncate_filename_stem(stem: &str) -> &str {
+    if stem.len() <= MAX_FILENAME_STEM_BYTES {
+        return stem;
+    }
+    let mut end = MAX_FILENAME_STEM_BYTES;
+    while !stem.is_char_boundary(end) {
+        end -= 1;
+    }
+    stem[..end].trim_end_matches(['.', ' '])
+}
+
+/// 파일 이름과 Content-Disposition에 쓸 atclId
+///
+/// [`sanitize_filename`]과 같이 거르므로 `../`, 따옴표, 제어 문자가 빠지고 길이가 잘린다.
+/// 거른 결과가 비는 atclId는 [`ApiResponse::validate`](super::ApiResponse::validate)에서 거부한다.
+pub fn sanitize_article_id(id: &str) -> String {
+    sanitize_filename(id)
+}
+
+/// 파일 이름에 넣을 수 있게 값을 거른다
+///
+/// - 경로 구분자와 Windows에서 못 쓰는 글자(`:*?"<>|`)는 `_`로, 제어 문자는 버림
+/// - 앞뒤의 `.`과 공백을 걷어내고 [`MAX_FILENAME_VALUE_CHARS`]자로 자름
+/// - `CON`, `NUL` 같은 예약된 이름이면 앞에 `_`를 붙임
+pub fn sanitize_filename(value: &str) -> String {
+    let cleaned: String = value
+        .chars()
+        .filter(|c| !c.is_control())
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c => c,
+        })
+        .collect();
+    let cleaned: String = cleaned
+        .trim_matches(|c: char| c == '.' || c.is_whitespace())
+        .chars()
+        .take(MAX_FILENAME_VALUE_CHARS)
+        .collect();
+
+    let base = cleaned.split('.').next().unwrap_or_default().trim_end();
+    if RESERVED_FILENAMES
+        .iter()
+        .any(|name| base.eq_ignore_ascii_case(name))
+    {
+        format!("_{}", cleaned)
+    } else {
+        cleaned
+    }
+}
+
+/// 템플릿의 `{이름}`을 `value`로 바꾸고, `value`가 모르는 이름(`None`) 목록을 함께 돌려준다
+///
+/// `value`가 `Some(None)`이면 빈 문자열. `{{`, `}}`는 중괄호 그대로.
+fn substitute(
+    template: &str,
+    mut value: impl FnMut(&str) -> Option<Option<String>>,
+) -> (String, Vec<String>) {
+    let mut out = String::with_capacity(template.len());
+    let mut unknown = Vec::new();
+    let mut rest = template;
+
+    while let Some(pos) = rest.find(['{', '}']) {
+        out.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        let close = tail.find('}').filter(|_| tail.starts_with('{'));
+        let Some(close) = close else {
+            // 짝이 없는 중괄호는 그대로
+            out.push_str(&tail[..1]);
+            rest = &tail[1..];
+            continue;
+        };
+
+        let name = tail[1..close].trim();
+        match value(name) {
+            Some(value) => out.push_str(&value.unwrap_or_default()),
+            None => unknown.push(name.to_string()),
+        }
+        rest = &tail[close + 1..];
+    }
+    out.push_str(rest);
+
+    (out, unknown)
+}
+
+/// 파일 이름 템플릿에서 모르는 자리표시자 이름
+pub fn unknown_filename_placeholders(template: &str) -> Vec<String> {
+    substitute(template, |name| {
+        FILENAME_PLACEHOLDERS.contains(&name).then_some(None)
+    })
+    .1
+}
+
+/// 자리표시자 값. 모르는 이름이면 `None`, 값이 없으면 `Some(None)`
+fn value_of(name: &str, article: &Article, date_format: &str) -> Option<Option<String>> {
+    let value = match name {
+        "title" => Some(article.subject.clone()).filter(|s| !s.is_empty()),
+        "board_name" => article.board_name.clone(),
+        "author" => article.reg_emp_name.clone(),
+        "dept" => article.reg_dept_name.clone(),
+        "date" => article
+            .reg_dt
+            .as_deref()
+            .map(|date| format_date(date, date_format).unwrap_or_else(|| date.to_string())),
+        "atcl_id" => Some(article.atcl_id.clone()),
+        _ => {
+            return article.extra.get(name).map(|value| match value {
+                serde_json::Value::Null => None,
+                serde_json::Value::String(s) => Some(s.clone()),
+                other => Some(other.to_string()),
+            })
+        }
+    };
+    Some(value)
+}
+
+/// 날짜 문자열을 `date_format`으로. 날짜로 읽을 수 없거나 형식이 잘못되면 `None`
+///
+/// ISO 8601(`2025-01-30T10:00:00+09:00`, 시간대는 버리고 그 시각 그대로)과
+/// `2025-01-24 AM 10:00:00`, `2025-01-24` 형태를 읽는다.
+pub fn format_date(value: &str, date_format: &str) -> Option<String> {
+    if !is_valid_date_format(date_format) {
+        return None;
+    }
+    parse_date(value).map(|date| date.format(date_format).to_string())
+}
+
+fn parse_date(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim();
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|date| date.naive_local())
+        .or_else(|| {
+            DATE_INPUT_FORMATS
+                .iter()
+                .find_map(|f| NaiveDateTime::parse_from_str(value, f).ok())
+        })
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+        })
+}
+
+/// chrono가 해석할 수 있는 strftime 형식인지
+pub fn is_valid_date_format(format: &str) -> bool {
+    !StrftimeItems::new(format).any(|item| matches!(item, Item::Error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn article() -> Article {
+        serde_json::from_str(
+            r#"{
+                "atclId": "T001",
+                "subject": "주간 보고",
+                "boardName": "공지",
+                "regEmpName": "홍길동",
+                "regDt": "2026-01-25 PM 02:30:00",
+                "문서번호": "DOC-7"
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_render_placeholders() {
+        let article = article();
+        let (text, warnings) = render(
+            "{board_name} - {title} ({author}, {date}) {문서번호}",
+            &article,
+            DEFAULT_DATE_FORMAT,
+        );
+        assert_eq!(text, "공지 - 주간 보고 (홍길동, 2026-01-25 14:30) DOC-7");
+        assert!(warnings.is_empty());
+
+        let (text, _) = render("{date}", &article, "%Y.%m.%d %H:%M");
+        assert_eq!(text, "2026.01.25 14:30");
+    }
+
+    #[test]
+    fn test_render_missing_and_unknown() {
+        let article = article();
+        let (text, warnings) = render(
+            "[{dept}] {nope} {{literal}} {",
+            &article,
+            DEFAULT_DATE_FORMAT,
+        );
+        assert_eq!(text, "[]  {literal} {");
+        assert_eq!(
+            warnings,
+            vec!["알 수 없는 자리표시자 {nope}: 빈 값으로 바꿨습니다"]
+        );
+    }
+
+    #[test]
+    fn test_render_filename() {
+        let mut article = article();
+        article.reg_dept_name = Some("개발팀".to_string());
+        assert_eq!(
+            render_filename("{date}_{department}_{article_id}.hwpx", &article),
+            "20260125_개발팀_T001.hwpx"
+        );
+        assert_eq!(render_filename("{title}", &article), "주간 보고.hwpx");
+        assert_eq!(
+            render_filename(DEFAULT_FILENAME_TEMPLATE, &article),
+            "T001.hwpx"
+        );
+
+        // 값 안의 경로는 파일 이름 하나로 무력화
+        article.subject = "../../etc/passwd".to_string();
+        assert_eq!(
+            render_filename("{title}.hwpx", &article),
+            "_.._etc_passwd.hwpx"
+        );
+
+        // 값이 비면 atclId
+        article.reg_dept_name = None;
+        assert_eq!(render_filename("{department}", &article), "T001.hwpx");
+
+        // 긴 한글 제목도 작업 ID를 붙여 255바이트 안에 들어간다
+        article.subject = "가".repeat(MAX_FILENAME_VALUE_CHARS);
+        article.reg_dept_name = Some("개발팀".to_string());
+        let name = render_filename("{date}_{department}_{article_id}_{title}", &article);
+        assert!(name.starts_with("20260125_개발팀_T001_가"));
+        assert!(name.ends_with("가.hwpx"));
+        assert_eq!(name.len(), 255 - 37);
+    }
+
+    #[test]
+    fn test_sanitize_article_id() {
+        assert_eq!(sanitize_article_id("../../etc/x"), "_.._etc_x");
+        assert_eq!(sanitize_article_id(" DOC\"1\" "), "DOC_1_");
+        assert_eq!(sanitize_article_id("..\u{0}.."), "");
+
+        let long = "A".repeat(300);
+        assert_eq!(
+            sanitize_article_id(&long),
+            "A".repeat(MAX_FILENAME_VALUE_CHARS)
+        );
+    }
+
+    #[test]
+    fn test_sanitize_filename() {
+        assert_eq!(sanitize_filename("보고서: 1/2\t분기?"), "보고서_ 1_2분기_");
+        assert_eq!(sanitize_filename("  ..숨김.  "), "숨김");
+        assert_eq!(sanitize_filename("con"), "_con");
+        assert_eq!(sanitize_filename("LPT1.txt"), "_LPT1.txt");
+        assert_eq!(sanitize_filename("CONSOLE"), "CONSOLE");
+        assert_eq!(sanitize_filename(&"가".repeat(150)).chars().count(), 100);
+        assert_eq!(
+            unknown_filename_placeholders("{article_id}_{nope}_{{x}}"),
+            vec!["nope"]
+        );
+    }
+
+    #[test]
+    fn test_format_date() {
+        assert_eq!(
+            format_date("2025-01-30T10:00:00+09:00", DEFAULT_DATE_FORMAT).as_deref(),
+            Some("2025-01-30 10:00")
+        );
+        assert_eq!(
+            format_date("2025-01-24 AM 10:00:00", DEFAULT_DATE_FORMAT).as_deref(),
+            Some("2025-01-24 10:00")
+        );
+        assert_eq!(
+            format_date("2026-01-25", "%Y년 %m월 %d일").as_deref(),
+            Some("2026년 01월 25일")
+        );
+        assert_eq!(format_date("어제", "%Y"), None);
+        assert!(!is_valid_date_format("%Q"));
+    }
+}
@@ -1,12 +1,30 @@
-use crate::hwpx::HwpxWriter;
+use std::borrow::Cow;
+
+use icu_normalizer::ComposingNormalizerBorrowed;
+
+use crate::hwpx::{
+    HwpxAlignment, HwpxParagraphStyle, HwpxTextStyle, HwpxWriter, ParagraphBorder, StyledText,
+};
 
 use super::error::Result;
+use super::inline::MONOSPACE_FONT;
+use super::model::{
+    self, ContentAlign, ContentStyle, ListItem, TextLink, TextNormalization, TextRun, TextValue,
+};
 
 /// 텍스트 value를 \n 기준으로 분리하여 단락으로 추가
 ///
 /// - `\n` = 새 단락 생성
 /// - `\n\n` = 빈 단락 포함 (빈 줄 추가)
-pub fn add_text_paragraphs(writer: &mut HwpxWriter, value: &str) -> Result<()> {
+/// - `\t` = 탭 (hp:tab으로 출력)
+///
+/// 나누기 전에 `normalization`을 적용한다 ([`normalize_text`]).
+pub fn add_text_paragraphs(
+    writer: &mut HwpxWriter,
+    value: &str,
+    normalization: &TextNormalization,
+) -> Result<()> {
+    let value = normalize_text(value, normalization);
     let lines: Vec<&str> = value.split('\n').collect();
 
     for line in &lines {
@@ -16,9 +34,326 @@ pub fn add_text_paragraphs(writer: &mut HwpxWriter, value: &str) -> Result<()> {
     Ok(())
 }
 
-/// 연속된 text 요소 사이 빈 단락 추가
-pub fn add_separator_paragraph(writer: &mut HwpxWriter) -> Result<()> {
-    writer.add_paragraph("")?;
+/// 입력 텍스트 정규화. 켜진 항목만 이 순서로 적용한다
+///
+/// 1. `crlf_to_lf`: `\r\n`, `\r` → `\n`
+/// 2. `strip_invisible`: 폭 없는 공백(U+200B, U+2060, U+FEFF)과 `\n`, `\t` 밖의 제어 문자 삭제.
+///    이모지와 일부 문자에 필요한 ZWJ/ZWNJ(U+200D, U+200C)는 남긴다
+/// 3. `nfc`: 유니코드 NFC 정규화
+/// 4. `max_blank_lines`: 공백만 있는 줄도 빈 줄로 보고, 연이은 빈 줄을 그 개수까지만 남김
+///
+/// 바꿀 것이 없으면 빌린 값을 그대로 돌려준다.
+pub fn normalize_text<'a>(value: &'a str, normalization: &TextNormalization) -> Cow<'a, str> {
+    let mut value = Cow::Borrowed(value);
+    if normalization.crlf_to_lf && value.contains('\r') {
+        value = Cow::Owned(value.replace("\r\n", "\n").replace('\r', "\n"));
+    }
+    if normalization.strip_invisible && value.chars().any(is_invisible) {
+        value = Cow::Owned(value.chars().filter(|&c| !is_invisible(c)).collect());
+    }
+    if normalization.nfc {
+        if let Cow::Owned(normalized) = ComposingNormalizerBorrowed::new_nfc().normalize(&value) {
+            value = Cow::Owned(normalized);
+        }
+    }
+    if let Some(max) = normalization.max_blank_lines {
+        let mut blank_run = 0;
+        let mut changed = false;
+        let lines: Vec<&str> = value
+            .split('\n')
+            .filter(|line| {
+                if line.trim().is_empty() {
+                    blank_run += 1;
+                    changed |= blank_run > max;
+                    blank_run <= max
+                } else {
+                    blank_run = 0;
+                    true
+                }
+            })
+            .collect();
+        if changed {
+            value = Cow::Owned(lines.join("\n"));
+        }
+    }
+    value
+}
+
+fn is_invisible(c: char) -> bool {
+    matches!(c, '\u{200B}' | '\u{2060}' | '\u{FEFF}') || (c.is_control() && c != '\n' && c != '\t')
+}
+
+/// 링크가 있는 텍스트 value를 단락으로 추가
+///
+/// 줄 나눔은 [`add_text_paragraphs`]와 같다. 링크는 순서대로 value에서 찾아
+/// 하이퍼링크 run으로 만들며, 찾지 못한 링크와 그 뒤의 링크는 무시한다.
+pub fn add_linked_text_paragraphs(
+    writer: &mut HwpxWriter,
+    value: &str,
+    links: &[TextLink],
+) -> Result<()> {
+    for runs in linked_lines(value, links, &HwpxTextStyle::default()) {
+        if runs.iter().all(|run| run.url.is_none()) {
+            let line: String = runs.iter().map(|run| run.text.as_str()).collect();
+            writer.add_paragraph(&line)?;
+        } else {
+            writer.add_mixed_styled_paragraph(runs)?;
+        }
+    }
+    Ok(())
+}
+
+/// 서식이 있는 run을 이어 붙여 단락으로 추가
+///
+/// run 텍스트 안의 `\n`에서 단락을 나눈다. 크기를 지정하지 않은 run은 본문
+/// 크기(`body_size_pt`)를 쓰고, 색은 [`ApiResponse::validate`](super::ApiResponse::validate)에서
+/// 검증된다.
+pub fn add_text_runs(writer: &mut HwpxWriter, runs: &[TextRun], body_size_pt: u32) -> Result<()> {
+    for runs in run_lines(runs, &HwpxTextStyle::new().size(body_size_pt)) {
+        if runs.is_empty() {
+            writer.add_paragraph("")?;
+        } else {
+            writer.add_mixed_styled_paragraph(runs)?;
+        }
+    }
+    Ok(())
+}
+
+/// style이 있는 text 콘텐츠를 단락으로 추가
+///
+/// 글자 모양은 style을 바탕으로 하고, run 배열이면 run의 서식을 그 위에 덮어쓴다.
+/// 줄 나눔과 링크는 style이 없을 때와 같다.
+pub fn add_styled_text(
+    writer: &mut HwpxWriter,
+    value: &TextValue,
+    links: &[TextLink],
+    style: &ContentStyle,
+    body_size_pt: u32,
+) -> Result<()> {
+    let mut base = HwpxTextStyle::new().size(style.font_size.unwrap_or(body_size_pt));
+    if style.bold {
+        base = base.bold();
+    }
+    if let Some(color) = style.color.as_deref().and_then(model::parse_color) {
+        base = base.color(color);
+    }
+    let para_style = style.align.map(|align| {
+        HwpxParagraphStyle::new().alignment(match align {
+            ContentAlign::Left => HwpxAlignment::Left,
+            ContentAlign::Center => HwpxAlignment::Center,
+            ContentAlign::Right => HwpxAlignment::Right,
+            ContentAlign::Justify => HwpxAlignment::Justify,
+        })
+    });
+
+    let lines = match value {
+        TextValue::Plain(value) => linked_lines(value, links, &base),
+        TextValue::Runs(runs) => run_lines(runs, &base),
+    };
+    for mut runs in lines {
+        if runs.is_empty() {
+            // 빈 줄도 같은 글자 크기로 두어 줄 간격을 맞춘다
+            runs.push(StyledText::with_style("", base.clone()));
+        }
+        match &para_style {
+            Some(para_style) => writer.add_mixed_styled_paragraph_with_style(runs, para_style)?,
+            None => writer.add_mixed_styled_paragraph(runs)?,
+        }
+    }
+    Ok(())
+}
+
+/// value를 줄별 run으로 (링크는 순서대로 찾아 하이퍼링크 run)
+fn linked_lines(value: &str, links: &[TextLink], style: &HwpxTextStyle) -> Vec<Vec<StyledText>> {
+    let mut pending = links.iter().filter(|l| !l.text.is_empty()).peekable();
+    let mut lines = Vec::new();
+
+    for line in value.split('\n') {
+        let mut runs = Vec::new();
+        let mut rest = line;
+        while let Some(start) = pending.peek().and_then(|l| rest.find(&l.text)) {
+            let link = pending.next().expect("peeked link");
+            if start > 0 {
+                runs.push(StyledText::with_style(&rest[..start], style.clone()));
+            }
+            runs.push(StyledText::with_style(&link.text, style.clone()).link(&link.url));
+            rest = &rest[start + link.text.len()..];
+        }
+        if !rest.is_empty() {
+            runs.push(StyledText::with_style(rest, style.clone()));
+        }
+        lines.push(runs);
+    }
+    lines
+}
+
+/// run 배열을 줄별 run으로 (run 텍스트의 `\n`에서 줄을 나눔)
+fn run_lines(runs: &[TextRun], base: &HwpxTextStyle) -> Vec<Vec<StyledText>> {
+    let mut lines = vec![Vec::new()];
+    for run in runs {
+        let mut style = base.clone();
+        if let Some(size) = run.size {
+            style = style.size(size);
+        }
+        if run.bold {
+            style = style.bold();
+        }
+        if run.italic {
+            style = style.italic();
+        }
+        if run.underline {
+            style = style.underline();
+        }
+        if let Some(color) = run.color.as_deref().and_then(model::parse_color) {
+            style = style.color(color);
+        }
+
+        for (idx, piece) in run.text.split('\n').enumerate() {
+            if idx > 0 {
+                lines.push(Vec::new());
+            }
+            if !piece.is_empty() {
+                let line = lines.last_mut().expect("at least one line");
+                line.push(StyledText::with_style(piece, style.clone()));
+            }
+        }
+    }
+    lines
+}
+
+/// 제목 글자 크기 (본문 크기 대비 %, level 1부터)
+const HEADING_SCALE_PERCENT: [u32; 6] = [200, 170, 150, 130, 120, 110];
+
+/// 제목을 굵고 큰 글자의 단락으로 추가
+///
+/// 글자 크기는 본문 크기(`body_size_pt`)에 level별 배율을 곱한 값이다.
+/// level은 [`ApiResponse::validate`](super::ApiResponse::validate)에서 1~6으로 검증된다.
+pub fn add_heading(
+    writer: &mut HwpxWriter,
+    value: &str,
+    level: u8,
+    body_size_pt: u32,
+) -> Result<()> {
+    let scale = HEADING_SCALE_PERCENT[(level.clamp(1, 6) - 1) as usize];
+    let style = HwpxTextStyle::new()
+        .bold()
+        .size((body_size_pt * scale).div_ceil(100));
+    for line in value.split('\n') {
+        writer.add_styled_paragraph(line, style.clone())?;
+    }
+    Ok(())
+}
+
+/// 인용문 들여쓰기 (mm)
+const QUOTE_INDENT_MM: f32 = 5.0;
+
+/// 인용문 왼쪽 세로줄 색
+const QUOTE_BAR_COLOR: u32 = 0xAAAAAA;
+
+/// 인용문 글자 색
+const QUOTE_TEXT_COLOR: u32 = 0x666666;
+
+/// 인용문을 왼쪽 세로줄이 있는 들여쓴 회색 단락으로 추가
+///
+/// 줄 나눔은 [`add_text_paragraphs`]와 같다. 글자 크기는 본문 크기(`body_size_pt`)를 따른다.
+pub fn add_quote(writer: &mut HwpxWriter, value: &str, body_size_pt: u32) -> Result<()> {
+    let border = ParagraphBorder::new()
+        .left_only()
+        .color(QUOTE_BAR_COLOR)
+        .offsets_mm(2.0, 0.0, 0.0, 0.0);
+    let para_style = HwpxParagraphStyle::new()
+        .left_margin(QUOTE_INDENT_MM)
+        .border(border);
+    let style = HwpxTextStyle::new()
+        .size(body_size_pt)
+        .color(QUOTE_TEXT_COLOR);
+    for line in value.split('\n') {
+        writer.add_styled_paragraph_with_style(line, style.clone(), &para_style)?;
+    }
+    Ok(())
+}
+
+/// 코드 블록 배경색
+const CODE_BACKGROUND: u32 = 0xF2F2F2;
+
+/// 코드 블록을 회색 배경의 고정폭 글꼴 단락으로 추가
+///
+/// 한 줄이 한 단락이며, 앞 공백과 빈 줄을 그대로 둔다. 양쪽 정렬로 글자 사이가
+/// 벌어지지 않도록 왼쪽 정렬한다.
+pub fn add_code_block(writer: &mut HwpxWriter, value: &str, body_size_pt: u32) -> Result<()> {
+    let para_style = HwpxParagraphStyle::new()
+        .alignment(HwpxAlignment::Left)
+        .border(ParagraphBorder::shading(CODE_BACKGROUND));
+    let style = HwpxTextStyle {
+        font_name: Some(MONOSPACE_FONT.to_string()),
+        ..HwpxTextStyle::new().size(body_size_pt)
+    };
+    for line in value.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        writer.add_styled_paragraph_with_style(line, style.clone(), &para_style)?;
+    }
+    Ok(())
+}
+
+/// 목록 한 단계당 들여쓰기 (mm)
+const LIST_INDENT_MM: f32 = 5.0;
+
+/// 단계별 글머리표. 더 깊은 단계는 마지막 기호를 쓴다.
+const BULLETS: [&str; 3] = ["•", "◦", "▪"];
+
+const HANGUL_NUMBERS: [&str; 14] = [
+    "가", "나", "다", "라", "마", "바", "사", "아", "자", "차", "카", "타", "파", "하",
+];
+
+/// 목록을 번호 또는 글머리표가 붙은 단락으로 추가
+///
+/// - 번호 형식: 1단계 `1.`, 2단계 `가.`, 3단계 이하 `1)`
+/// - 단계마다 [`LIST_INDENT_MM`]씩 들여쓴다. 3단계보다 깊어도 에러 없이 들여쓰기만 늘어난다.
+pub fn add_list(writer: &mut HwpxWriter, ordered: bool, items: &[ListItem]) -> Result<()> {
+    add_list_level(writer, ordered, items, 0)
+}
+
+fn add_list_level(
+    writer: &mut HwpxWriter,
+    ordered: bool,
+    items: &[ListItem],
+    depth: usize,
+) -> Result<()> {
+    let style = HwpxParagraphStyle::new().left_margin(depth as f32 * LIST_INDENT_MM);
+    for (idx, item) in items.iter().enumerate() {
+        let marker = if ordered {
+            list_number(idx + 1, depth)
+        } else {
+            BULLETS[depth.min(BULLETS.len() - 1)].to_string()
+        };
+        writer.add_paragraph_with_style(&format!("{} {}", marker, item.text), &style)?;
+        add_list_level(writer, ordered, &item.children, depth + 1)?;
+    }
+    Ok(())
+}
+
+/// 단계별 번호 표기
+fn list_number(n: usize, depth: usize) -> String {
+    match depth {
+        0 => format!("{}.", n),
+        1 => format!("{}.", HANGUL_NUMBERS[(n - 1) % HANGUL_NUMBERS.len()]),
+        _ => format!("{})", n),
+    }
+}
+
+/// 구분 간격(pt). 10pt 본문 한 줄(줄간격 160%) 높이와 같다.
+const SEPARATOR_SPACING_PT: u32 = 16;
+
+/// 연속된 콘텐츠 사이 구분 추가
+///
+/// - `use_spacing = false`: 빈 단락 추가
+/// - `use_spacing = true`: 빈 단락 대신 다음 단락의 문단 위 간격으로 처리
+pub fn add_separator_paragraph(writer: &mut HwpxWriter, use_spacing: bool) -> Result<()> {
+    if use_spacing {
+        writer.add_vertical_space(SEPARATOR_SPACING_PT)?;
+    } else {
+        writer.add_empty_paragraphs(1)?;
+    }
     Ok(())
 }
 
@@ -26,10 +361,46 @@ pub fn add_separator_paragraph(writer: &mut HwpxWriter) -> Result<()> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_list_number_by_depth() {
+        assert_eq!(list_number(3, 0), "3.");
+        assert_eq!(list_number(2, 1), "나.");
+        assert_eq!(list_number(15, 1), "가.");
+        assert_eq!(list_number(4, 2), "4)");
+        assert_eq!(list_number(4, 5), "4)");
+    }
+
+    #[test]
+    fn test_normalize_text() {
+        let input = "가\r\n\u{200B}나\u{0007}\r\n\n \n\n\n\u{1100}\u{1161}";
+        assert_eq!(
+            normalize_text(input, &TextNormalization::default()),
+            Cow::Borrowed(input)
+        );
+
+        let all = TextNormalization {
+            crlf_to_lf: true,
+            strip_invisible: true,
+            max_blank_lines: Some(1),
+            nfc: true,
+        };
+        assert_eq!(normalize_text(input, &all), "가\n나\n\n가");
+
+        let keep_two = TextNormalization {
+            max_blank_lines: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(normalize_text("a\n\n\n\n\nb", &keep_two), "a\n\n\nb");
+        assert!(matches!(
+            normalize_text("a\n\nb", &keep_two),
+            Cow::Borrowed(_)
+        ));
+    }
+
     #[test]
     fn test_single_line() {
         let mut writer = HwpxWriter::new();
-        add_text_paragraphs(&mut writer, "안녕하세요").unwrap();
+        add_text_paragraphs(&mut writer, "안녕하세요", &TextNormalization::default()).unwrap();
 
         let bytes = writer.to_bytes().unwrap();
         assert!(!bytes.is_empty());
@@ -38,7 +409,12 @@ mod tests {
     #[test]
     fn test_multiple_lines() {
         let mut writer = HwpxWriter::new();
-        add_text_paragraphs(&mut writer, "첫 줄\n둘째 줄\n셋째 줄").unwrap();
+        add_text_paragraphs(
+            &mut writer,
+            "첫 줄\n둘째 줄\n셋째 줄",
+            &TextNormalization::default(),
+        )
+        .unwrap();
 
         let bytes = writer.to_bytes().unwrap();
         assert!(!bytes.is_empty());
@@ -47,7 +423,12 @@ mod tests {
     #[test]
     fn test_empty_line_between() {
         let mut writer = HwpxWriter::new();
-        add_text_paragraphs(&mut writer, "첫 단락\n\n셋째 단락").unwrap();
+        add_text_paragraphs(
+            &mut writer,
+            "첫 단락\n\n셋째 단락",
+            &TextNormalization::default(),
+        )
+        .unwrap();
 
         let bytes = writer.to_bytes().unwrap();
         assert!(!bytes.is_empty());
@@ -56,18 +437,32 @@ mod tests {
     #[test]
     fn test_separator_paragraph() {
         let mut writer = HwpxWriter::new();
-        add_text_paragraphs(&mut writer, "첫 텍스트").unwrap();
-        add_separator_paragraph(&mut writer).unwrap();
-        add_text_paragraphs(&mut writer, "둘째 텍스트").unwrap();
+        add_text_paragraphs(&mut writer, "첫 텍스트", &TextNormalization::default()).unwrap();
+        add_separator_paragraph(&mut writer, false).unwrap();
+        add_text_paragraphs(&mut writer, "둘째 텍스트", &TextNormalization::default()).unwrap();
 
         let bytes = writer.to_bytes().unwrap();
         assert!(!bytes.is_empty());
     }
 
+    #[test]
+    fn test_separator_spacing() {
+        let mut writer = HwpxWriter::new();
+        add_text_paragraphs(&mut writer, "첫 텍스트", &TextNormalization::default()).unwrap();
+        add_separator_paragraph(&mut writer, true).unwrap();
+        add_text_paragraphs(&mut writer, "둘째 텍스트", &TextNormalization::default()).unwrap();
+
+        let bytes = writer.to_bytes().unwrap();
+        let doc = crate::HwpxReader::from_bytes(&bytes).unwrap();
+        let paragraphs = &doc.body_texts[0].sections[0].paragraphs;
+        assert_eq!(paragraphs.len(), 2);
+        assert_eq!(paragraphs[1].para_shape_id, 1);
+    }
+
     #[test]
     fn test_empty_value() {
         let mut writer = HwpxWriter::new();
-        add_text_paragraphs(&mut writer, "").unwrap();
+        add_text_paragraphs(&mut writer, "", &TextNormalization::default()).unwrap();
 
         let bytes = writer.to_bytes().unwrap();
         assert!(!bytes.is_empty());
@@ -76,7 +471,12 @@ mod tests {
     #[test]
     fn test_special_characters() {
         let mut writer = HwpxWriter::new();
-        add_text_paragraphs(&mut writer, "특수문자: <tag> & \"quotes\" 'apos'").unwrap();
+        add_text_paragraphs(
+            &mut writer,
+            "특수문자: <tag> & \"quotes\" 'apos'",
+            &TextNormalization::default(),
+        )
+        .unwrap();
 
         let bytes = writer.to_bytes().unwrap();
         assert!(!bytes.is_empty());
@@ -89,7 +489,12 @@ mod tests {
     #[test]
     fn test_text_with_hwpx_reader_verification() {
         let mut writer = HwpxWriter::new();
-        add_text_paragraphs(&mut writer, "검증용 텍스트\n두번째 줄").unwrap();
+        add_text_paragraphs(
+            &mut writer,
+            "검증용 텍스트\n두번째 줄",
+            &TextNormalization::default(),
+        )
+        .unwrap();
 
         let bytes = writer.to_bytes().unwrap();
         let doc = crate::HwpxReader::from_bytes(&bytes).unwrap();
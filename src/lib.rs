@@ -42,10 +42,15 @@ impl HwpReader {
         let header = FileHeader::parse(header_data)?;
 
         if header.is_encrypted() {
-            return Err(HwpError::UnsupportedVersion(
+            return Err(HwpError::Encrypted(
                 "Password-encrypted documents are not supported".to_string(),
             ));
         }
+        if header.is_drm() {
+            return Err(HwpError::Encrypted(
+                "DRM-protected documents are not supported".to_string(),
+            ));
+        }
 
         let distribution_record = if header.is_distribute() {
             Some(Self::read_distribution_record(
@@ -102,6 +107,7 @@ impl HwpReader {
             preview_text,
             preview_image,
             summary_info,
+            warnings: Vec::new(),
         })
     }
 
@@ -158,6 +164,51 @@ impl HwpReader {
     }
 }
 
+/// Convert a binary HWP document to an HWPX package.
+///
+/// Paragraph text, char shapes and summary information are carried over.
+/// The binary parser does not rebuild tables, so table cells come through as
+/// plain paragraphs in document order.
+pub fn convert_hwp_to_hwpx(input: &[u8]) -> Result<Vec<u8>> {
+    let mut document = HwpReader::from_bytes(input)?;
+    let summary = document.summary_info.take();
+
+    for paragraph in document
+        .body_texts
+        .iter_mut()
+        .flat_map(|b| &mut b.sections)
+        .flat_map(|s| &mut s.paragraphs)
+    {
+        // Only paraPr/style 0 are emitted in header.xml
+        paragraph.para_shape_id = 0;
+        paragraph.style_id = 0;
+        if let Some(text) = paragraph.text.as_mut() {
+            let trimmed = text.content.trim_end_matches('\r').len();
+            text.content.truncate(trimmed);
+        }
+    }
+
+    let mut writer = HwpxWriter::from_document(document);
+    if let Some(summary) = summary {
+        writer.set_metadata(hwpx::HwpxMetadata {
+            title: summary.title.unwrap_or_default(),
+            creator: summary.author.unwrap_or_default(),
+            subject: summary.subject.unwrap_or_default(),
+            description: summary.comments.unwrap_or_default(),
+            keywords: summary
+                .keywords
+                .iter()
+                .flat_map(|k| k.split(','))
+                .map(str::trim)
+                .filter(|k| !k.is_empty())
+                .map(String::from)
+                .collect(),
+            ..Default::default()
+        });
+    }
+    writer.to_bytes()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
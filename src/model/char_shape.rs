@@ -17,6 +17,7 @@ pub struct CharShape {
     pub shade_color: u32,
     pub shadow_color: u32,
     pub border_fill_id: u16,
+    pub strikethrough_color: u32,
 }
 
 impl CharShape {
@@ -50,6 +51,20 @@ impl CharShape {
         ((self.properties >> 11) & 0x3) as u8
     }
 
+    pub fn is_outline(&self) -> bool {
+        self.get_outline_type() != 0
+    }
+
+    pub fn is_emboss(&self) -> bool {
+        // Emboss is bit 13 of properties
+        self.properties & (1 << 13) != 0
+    }
+
+    pub fn is_engrave(&self) -> bool {
+        // Engrave is bit 14 of properties
+        self.properties & (1 << 14) != 0
+    }
+
     pub fn from_record(record: &Record) -> Result<Self> {
         let mut reader = record.data_reader();
 
@@ -83,21 +98,38 @@ impl CharShape {
             *item = reader.read_u8()? as i8;
         }
 
+        let base_size = reader.read_i32()?;
+        let properties = reader.read_u32()?;
+        let shadow_gap_x = reader.read_u8()? as i8;
+        let shadow_gap_y = reader.read_u8()? as i8;
+        let text_color = reader.read_u32()?;
+        let underline_color = reader.read_u32()?;
+        let shade_color = reader.read_u32()?;
+        let shadow_color = reader.read_u32()?;
+        let border_fill_id = reader.read_u16()?;
+        // Strikethrough color was added in 5.0.3.0; older files strike in the text color
+        let strikethrough_color = if reader.remaining() >= 4 {
+            reader.read_u32()?
+        } else {
+            text_color
+        };
+
         Ok(Self {
             face_name_ids,
             ratios,
             char_spaces,
             relative_sizes,
             char_offsets,
-            base_size: reader.read_i32()?,
-            properties: reader.read_u32()?,
-            shadow_gap_x: reader.read_u8()? as i8,
-            shadow_gap_y: reader.read_u8()? as i8,
-            text_color: reader.read_u32()?,
-            underline_color: reader.read_u32()?,
-            shade_color: reader.read_u32()?,
-            shadow_color: reader.read_u32()?,
-            border_fill_id: reader.read_u16()?,
+            base_size,
+            properties,
+            shadow_gap_x,
+            shadow_gap_y,
+            text_color,
+            underline_color,
+            shade_color,
+            shadow_color,
+            border_fill_id,
+            strikethrough_color,
         })
     }
 }
@@ -119,6 +151,7 @@ impl CharShape {
             shade_color: 0xFFFFFF,  // White shade
             shadow_color: 0x808080, // Gray shadow
             border_fill_id: 0,
+            strikethrough_color: 0x000000,
         }
     }
 }
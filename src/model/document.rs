@@ -13,6 +13,9 @@ pub struct HwpDocument {
     pub preview_text: Option<PreviewText>,
     pub preview_image: Option<PreviewImage>,
     pub summary_info: Option<SummaryInfo>,
+    /// Problems skipped over while reading leniently, e.g. by
+    /// [`HwpxReader::from_bytes_lenient`](crate::HwpxReader::from_bytes_lenient)
+    pub warnings: Vec<String>,
 }
 
 impl HwpDocument {
@@ -20,6 +23,20 @@ impl HwpDocument {
         self.body_texts.iter().flat_map(|bt| bt.sections.iter())
     }
 
+    /// Number of sections across all body texts
+    pub fn section_count(&self) -> usize {
+        self.sections().count()
+    }
+
+    /// Page size, orientation and margins of a section, if the file records them
+    pub fn page_layout(&self, section_index: usize) -> Option<crate::model::PageLayout> {
+        self.sections()
+            .nth(section_index)?
+            .page_def
+            .as_ref()
+            .map(|page_def| page_def.get_layout())
+    }
+
     pub fn extract_text(&self) -> String {
         let mut result = String::new();
 
@@ -142,6 +159,10 @@ impl HwpDocument {
         self.summary_info.as_ref()
     }
 
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
     pub fn title(&self) -> Option<&str> {
         self.summary_info.as_ref().and_then(|s| s.title.as_deref())
     }
@@ -82,7 +82,7 @@ impl Hyperlink {
             hyperlink_type: HyperlinkType::Url,
             display_text: display_text.to_string(),
             target_url: url.to_string(),
-            length: display_text.chars().count() as u32,
+            length: display_text.encode_utf16().count() as u32,
             ..Default::default()
         }
     }
@@ -99,7 +99,7 @@ impl Hyperlink {
             hyperlink_type: HyperlinkType::Email,
             display_text: display_text.to_string(),
             target_url: mailto_url,
-            length: display_text.chars().count() as u32,
+            length: display_text.encode_utf16().count() as u32,
             ..Default::default()
         }
     }
@@ -110,7 +110,7 @@ impl Hyperlink {
             hyperlink_type: HyperlinkType::File,
             display_text: display_text.to_string(),
             target_url: file_path.to_string(),
-            length: display_text.chars().count() as u32,
+            length: display_text.encode_utf16().count() as u32,
             ..Default::default()
         }
     }
@@ -121,7 +121,7 @@ impl Hyperlink {
             hyperlink_type: HyperlinkType::Bookmark,
             display_text: display_text.to_string(),
             target_url: format!("#{}", bookmark_name),
-            length: display_text.chars().count() as u32,
+            length: display_text.encode_utf16().count() as u32,
             ..Default::default()
         }
     }
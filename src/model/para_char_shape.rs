@@ -8,6 +8,7 @@ pub struct ParaCharShape {
 
 #[derive(Debug, Clone)]
 pub struct CharPositionShape {
+    /// Offset into the paragraph text in UTF-16 code units
     pub position: u32,
     pub char_shape_id: u16,
 }
@@ -62,6 +62,11 @@ impl SectionDef {
         (self.properties & 0x04) != 0
     }
 
+    pub fn text_direction(&self) -> u8 {
+        // Text direction is bits 16-18: 0 = horizontal, 1 = vertical
+        ((self.properties >> 16) & 0x7) as u8
+    }
+
     /// Create a new default SectionDef for writing
     pub fn new_default() -> Self {
         Self {
@@ -65,6 +65,7 @@ impl HwpWriter {
                 preview_text: None,
                 preview_image: None,
                 summary_info: None,
+                warnings: Vec::new(),
             },
             current_section_idx: 0,
             next_instance_id: 1,
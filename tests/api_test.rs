@@ -4,7 +4,7 @@ use axum::Router;
 use http_body_util::BodyExt;
 use tower::ServiceExt;
 
-use hwpers::jsontohwpx::api::{create_router, ServerConfig};
+use hwpers::jsontohwpx::api::{build_state, create_router, create_router_with_state, ServerConfig};
 
 fn test_config() -> ServerConfig {
     ServerConfig {
@@ -79,7 +79,12 @@ async fn test_convert_success() {
     assert_eq!(resp.status(), StatusCode::OK);
 
     // Content-Type 확인
-    let content_type = resp.headers().get("content-type").unwrap().to_str().unwrap();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .unwrap()
+        .to_str()
+        .unwrap();
     assert_eq!(content_type, "application/vnd.hancom.hwpx");
 
     // Content-Disposition 확인
@@ -97,6 +102,59 @@ async fn test_convert_success() {
     assert_eq!(&body[0..2], &[0x50, 0x4B], "유효한 ZIP 파일이어야 함");
 }
 
+#[tokio::test]
+async fn test_convert_accepts_both_input_shapes() {
+    let envelope = r#"{
+        "responseCode": "0",
+        "options": { "includeHeader": true },
+        "data": {
+            "article": {
+                "atclId": "SHAPE001",
+                "subject": "입력 모양",
+                "regEmpName": "홍길동",
+                "regDeptName": "개발팀",
+                "regDt": "2025-01-24 10:00:00",
+                "contents": [{ "type": "text", "value": "본문" }]
+            }
+        }
+    }"#;
+    let flat = r#"{
+        "article_id": "SHAPE001",
+        "title": "입력 모양",
+        "metadata": {
+            "author": "홍길동",
+            "department": "개발팀",
+            "created_at": "2025-01-24 10:00:00"
+        },
+        "options": { "includeHeader": true },
+        "contents": [{ "type": "text", "value": "본문" }]
+    }"#;
+
+    let app = create_router(&test_config());
+    let mut texts = Vec::new();
+    for json in [envelope, flat] {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/api/v1/convert")
+            .header("content-type", "application/json")
+            .body(Body::from(json))
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let disposition = resp.headers()["content-disposition"].to_str().unwrap();
+        assert!(disposition.contains("SHAPE001.hwpx"), "{}", disposition);
+
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        texts.push(
+            hwpers::HwpxReader::from_bytes(&body)
+                .unwrap()
+                .extract_text(),
+        );
+    }
+    assert!(texts[0].contains("홍길동"), "{}", texts[0]);
+    assert_eq!(texts[0], texts[1]);
+}
+
 #[tokio::test]
 async fn test_convert_invalid_json() {
     let app = create_router(&test_config());
@@ -171,12 +229,188 @@ async fn test_convert_empty_table_error() {
         .body(Body::from(json))
         .unwrap();
 
+    // 빈 표는 변환 전 입력 검증에서 걸린다
     let resp = app.oneshot(req).await.unwrap();
-    assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
 
     let body = resp.into_body().collect().await.unwrap().to_bytes();
     let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
-    assert!(json["error"]["code"].as_str().is_some());
+    assert_eq!(json["error"]["code"], "INPUT_ERROR");
+    let details = json["error"]["details"].as_array().unwrap();
+    assert_eq!(details.len(), 1);
+    assert_eq!(details[0]["path"], "contents/0");
+    assert_eq!(details[0]["message"], "테이블에 행이 없습니다");
+}
+
+#[tokio::test]
+async fn test_convert_on_error_skip() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "SKIP001",
+                "contents": [
+                    { "type": "text", "value": "본문" },
+                    { "type": "table", "value": "<table></table>" }
+                ]
+            }
+        }
+    }"#;
+    let request = |uri: &str| {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(json))
+            .unwrap()
+    };
+
+    let app = create_router(&test_config());
+    let resp = app
+        .clone()
+        .oneshot(request("/api/v1/convert?on_error=skip"))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let header = resp
+        .headers()
+        .get("x-conversion-warnings")
+        .expect("경고 헤더 없음")
+        .to_str()
+        .unwrap();
+    let warnings: Vec<serde_json::Value> = serde_json::from_str(header).unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0]["contentIndex"], 1);
+    assert_eq!(warnings[0]["code"], "CONTENT_SKIPPED");
+
+    let resp = app
+        .clone()
+        .oneshot(request("/api/v1/convert?on_error=ignore"))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_convert_skip_images_query() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "SKIPIMG001",
+                "contents": [
+                    { "type": "image", "url": "http://invalid.example.test/nonexistent.png" }
+                ]
+            }
+        }
+    }"#;
+
+    let app = create_router(&test_config());
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/v1/convert?skip_images=true")
+                .header("content-type", "application/json")
+                .body(Body::from(json))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let text = hwpers::HwpxReader::from_bytes(&body)
+        .unwrap()
+        .extract_text();
+    assert!(
+        text.contains("[이미지: http://invalid.example.test/nonexistent.png]"),
+        "{}",
+        text
+    );
+}
+
+#[tokio::test]
+async fn test_convert_include_provenance_query() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "PROV002",
+                "contents": [{ "type": "text", "value": "본문" }]
+            }
+        }
+    }"#;
+
+    let app = create_router(&test_config());
+    for (uri, expected) in [
+        ("/api/v1/convert?include_provenance=true", true),
+        ("/api/v1/convert", false),
+    ] {
+        let resp = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(uri)
+                    .header("content-type", "application/json")
+                    .body(Body::from(json))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let body = resp.into_body().collect().await.unwrap().to_bytes();
+        let text = hwpers::HwpxReader::from_bytes(&body)
+            .unwrap()
+            .extract_text();
+        let version = format!("hwpers v{}로 생성되었습니다", env!("CARGO_PKG_VERSION"));
+        assert_eq!(text.contains(&version), expected, "{}: {}", uri, text);
+    }
+}
+
+#[tokio::test]
+async fn test_convert_limits_from_config() {
+    let config = ServerConfig {
+        limits: hwpers::jsontohwpx::ConvertLimits {
+            max_table_cells: 2,
+            ..Default::default()
+        },
+        ..test_config()
+    };
+    let app = create_router(&config);
+
+    let json = r#"{
+        "responseCode": "0",
+        "options": { "onError": "skip" },
+        "data": {
+            "article": {
+                "atclId": "LIMIT002",
+                "contents": [
+                    { "type": "text", "value": "본문" },
+                    { "type": "table", "value": "<table><tr><td>A</td><td>B</td><td>C</td></tr></table>" }
+                ]
+            }
+        }
+    }"#;
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/api/v1/convert")
+                .header("content-type", "application/json")
+                .body(Body::from(json))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(value["error"]["code"], "TABLE_CELLS_LIMIT_EXCEEDED");
+    assert_eq!(value["error"]["details"][0]["path"], "contents/1");
 }
 
 #[tokio::test]
@@ -231,6 +465,7 @@ async fn test_validate_success() {
     let body = resp.into_body().collect().await.unwrap().to_bytes();
     let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
     assert_eq!(json["valid"], true);
+    assert_eq!(json["schemaVersion"], "1.2");
     // errors는 비어있으면 생략됨
     let errors = json["errors"].as_array().map(|a| a.len()).unwrap_or(0);
     assert_eq!(errors, 0);
@@ -286,6 +521,64 @@ async fn test_validate_bad_response_code() {
     assert_eq!(json["valid"], false);
 }
 
+#[tokio::test]
+async fn test_validate_reports_content_issues() {
+    let app = create_router(&test_config());
+
+    // test_convert_empty_table_error의 빈 표와 그 밖의 변환 실패 원인
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "TBL_ERR",
+                "contents": [
+                    { "type": "table", "value": "<table></table>" },
+                    { "type": "text", "value": "정상" },
+                    { "type": "image" },
+                    { "type": "image", "base64": "%%%", "format": "png" },
+                    { "type": "link", "text": "링크", "url": "ftp://example.com" }
+                ]
+            }
+        }
+    }"#;
+
+    let req = Request::builder()
+        .method("POST")
+        .uri("/api/v1/validate")
+        .header("content-type", "application/json")
+        .body(Body::from(json))
+        .unwrap();
+
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["valid"], false);
+    let errors: Vec<&str> = json["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e.as_str().unwrap())
+        .collect();
+    assert_eq!(errors.len(), 4, "{:?}", errors);
+    assert_eq!(errors[0], "contents[0]: 테이블에 행이 없습니다");
+    assert_eq!(
+        errors[1],
+        "contents[2]: image에는 url 또는 base64가 필요합니다"
+    );
+    assert!(
+        errors[2].starts_with("contents[3]: Base64 디코딩 실패"),
+        "{}",
+        errors[2]
+    );
+    assert!(
+        errors[3].starts_with("contents[4]: 링크 URL은"),
+        "{}",
+        errors[3]
+    );
+}
+
 // --- health 핸들러 테스트 ---
 
 #[tokio::test]
@@ -383,7 +676,8 @@ async fn test_swagger_ui_redirect() {
     let resp = app.oneshot(req).await.unwrap();
     // Swagger UI는 /swagger-ui/ 로 리다이렉트하거나 200 반환
     assert!(
-        resp.status() == StatusCode::OK || resp.status() == StatusCode::MOVED_PERMANENTLY
+        resp.status() == StatusCode::OK
+            || resp.status() == StatusCode::MOVED_PERMANENTLY
             || resp.status() == StatusCode::TEMPORARY_REDIRECT
             || resp.status() == StatusCode::SEE_OTHER,
     );
@@ -457,6 +751,38 @@ async fn test_convert_async_success() {
     assert!(result["downloadUrl"].as_str().is_some());
 }
 
+#[tokio::test]
+async fn test_convert_async_keeps_text() {
+    let tmp = tempfile::tempdir().unwrap();
+    let config = test_config_with_output(tmp.path().to_path_buf());
+    let state = build_state(&config);
+    let app = create_router_with_state(state.clone(), config.max_request_size);
+
+    let req = Request::builder()
+        .method("POST")
+        .uri("/api/v1/convert/async")
+        .header("content-type", "application/json")
+        .body(Body::from(simple_json()))
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let job_id = json["jobId"].as_str().unwrap();
+    poll_job_completed(&app, job_id).await;
+
+    let job = state.job_store.get_job(job_id).await.unwrap();
+    let bytes = std::fs::read(job.file_path.unwrap()).unwrap();
+    let expected = hwpers::HwpxReader::extract_text_with_options(
+        &bytes,
+        &hwpers::hwpx::ExtractOptions {
+            include_tables: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(job.text.as_deref(), Some(expected.as_str()));
+}
+
 #[tokio::test]
 async fn test_convert_async_invalid_json() {
     let app = create_router(&test_config());
@@ -520,6 +846,156 @@ async fn test_get_job_not_found() {
     assert_eq!(json["error"]["code"], "NOT_FOUND");
 }
 
+#[tokio::test]
+async fn test_convert_async_download_filename_template() {
+    let tmp = tempfile::tempdir().unwrap();
+    let app = create_router(&test_config_with_output(tmp.path().to_path_buf()));
+
+    let json = r#"{
+        "responseCode": "0",
+        "options": { "filenameTemplate": "{title}_{article_id}" },
+        "data": {
+            "article": {
+                "atclId": "NAME001",
+                "subject": "주간 보고",
+                "contents": [{ "type": "text", "value": "본문" }]
+            }
+        }
+    }"#;
+    let req = Request::builder()
+        .method("POST")
+        .uri("/api/v1/convert/async")
+        .header("content-type", "application/json")
+        .body(Body::from(json))
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let job_id = json["jobId"].as_str().unwrap().to_string();
+
+    let result = poll_job_completed(&app, &job_id).await;
+    assert_eq!(result["status"], "completed");
+
+    // 작업 파일은 작업 ID를 붙인 템플릿 이름
+    assert!(tmp
+        .path()
+        .join(format!("{}_주간 보고_NAME001.hwpx", job_id))
+        .exists());
+
+    let req = Request::builder()
+        .method("GET")
+        .uri(result["downloadUrl"].as_str().unwrap())
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let disposition = resp.headers()["content-disposition"].to_str().unwrap();
+    assert_eq!(
+        disposition,
+        "attachment; filename=\"__ ___NAME001.hwpx\"; \
+         filename*=UTF-8''%EC%A3%BC%EA%B0%84%20%EB%B3%B4%EA%B3%A0_NAME001.hwpx"
+    );
+}
+
+#[tokio::test]
+async fn test_convert_content_failure_details() {
+    let app = create_router(&test_config());
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "FAIL001",
+                "contents": [
+                    { "type": "text", "value": "하나" },
+                    { "type": "text", "value": "둘" },
+                    { "type": "text", "value": "셋" },
+                    { "type": "image", "url": "./nonexistent_image.png" }
+                ]
+            }
+        }
+    }"#;
+    let req = Request::builder()
+        .method("POST")
+        .uri("/api/v1/convert")
+        .header("content-type", "application/json")
+        .body(Body::from(json))
+        .unwrap();
+
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["error"]["code"], "CONVERSION_ERROR");
+    let message = json["error"]["message"].as_str().unwrap();
+    assert!(message.contains("contents[3] (image): "), "{}", message);
+    let details = json["error"]["details"].as_array().unwrap();
+    assert_eq!(details.len(), 1);
+    assert_eq!(details[0]["path"], "contents/3");
+    assert_eq!(details[0]["message"], message);
+}
+
+#[tokio::test]
+async fn test_convert_sanitizes_article_id() {
+    let long_id = "A".repeat(300);
+    let cases = [
+        ("../../etc/x", "attachment; filename=\"_.._etc_x.hwpx\""),
+        (r#"DOC"1""#, "attachment; filename=\"DOC_1_.hwpx\""),
+        (long_id.as_str(), ""),
+    ];
+
+    let app = create_router(&test_config());
+    for (id, expected) in cases {
+        let json = serde_json::json!({
+            "responseCode": "0",
+            "data": { "article": { "atclId": id, "contents": [{ "type": "text", "value": "본문" }] } }
+        });
+        let req = Request::builder()
+            .method("POST")
+            .uri("/api/v1/convert")
+            .header("content-type", "application/json")
+            .body(Body::from(json.to_string()))
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK, "{}", id);
+
+        let disposition = resp.headers()["content-disposition"].to_str().unwrap();
+        if expected.is_empty() {
+            let filename = format!("{}.hwpx", "A".repeat(100));
+            assert_eq!(
+                disposition,
+                format!("attachment; filename=\"{}\"", filename)
+            );
+        } else {
+            assert_eq!(disposition, expected);
+        }
+    }
+
+    // 비동기 작업 파일도 출력 디렉터리 안에 만든다
+    let tmp = tempfile::tempdir().unwrap();
+    let output_dir = tmp.path().join("out");
+    let app = create_router(&test_config_with_output(output_dir.clone()));
+    let json = serde_json::json!({
+        "responseCode": "0",
+        "data": { "article": { "atclId": "../../escape", "contents": [] } }
+    });
+    let req = Request::builder()
+        .method("POST")
+        .uri("/api/v1/convert/async")
+        .header("content-type", "application/json")
+        .body(Body::from(json.to_string()))
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let job_id = json["jobId"].as_str().unwrap().to_string();
+    let result = poll_job_completed(&app, &job_id).await;
+    assert_eq!(result["status"], "completed");
+    assert!(output_dir
+        .join(format!("{}__.._escape.hwpx", job_id))
+        .exists());
+    assert!(!tmp.path().join("escape.hwpx").exists());
+}
+
 #[tokio::test]
 async fn test_download_job_not_found() {
     let app = create_router(&test_config());
@@ -567,7 +1043,12 @@ async fn test_convert_async_download() {
     let resp = app.clone().oneshot(req).await.unwrap();
     assert_eq!(resp.status(), StatusCode::OK);
 
-    let content_type = resp.headers().get("content-type").unwrap().to_str().unwrap();
+    let content_type = resp
+        .headers()
+        .get("content-type")
+        .unwrap()
+        .to_str()
+        .unwrap();
     assert_eq!(content_type, "application/vnd.hancom.hwpx");
 
     let disposition = resp
@@ -588,7 +1069,7 @@ async fn test_convert_async_failed_job() {
     let tmp = tempfile::tempdir().unwrap();
     let app = create_router(&test_config_with_output(tmp.path().to_path_buf()));
 
-    // 빈 테이블로 변환 실패 유도
+    // 내려받을 수 없는 이미지로 변환 실패 유도
     let json_body = r#"{
         "responseCode": "0",
         "data": {
@@ -596,7 +1077,7 @@ async fn test_convert_async_failed_job() {
                 "atclId": "FAIL001",
                 "subject": "실패테스트",
                 "contents": [
-                    { "type": "table", "value": "<table></table>" }
+                    { "type": "image", "url": "http://invalid.example.test/nonexistent.png" }
                 ]
             }
         }
@@ -622,6 +1103,43 @@ async fn test_convert_async_failed_job() {
     assert!(result["error"].as_str().is_some());
 }
 
+#[tokio::test]
+async fn test_convert_async_on_error_skip_records_warnings() {
+    let tmp = tempfile::tempdir().unwrap();
+    let app = create_router(&test_config_with_output(tmp.path().to_path_buf()));
+
+    let json_body = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "SKIP002",
+                "contents": [
+                    { "type": "table", "value": "<table></table>" }
+                ]
+            }
+        }
+    }"#;
+
+    let req = Request::builder()
+        .method("POST")
+        .uri("/api/v1/convert/async?on_error=skip")
+        .header("content-type", "application/json")
+        .body(Body::from(json_body))
+        .unwrap();
+
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::ACCEPTED);
+
+    let body = resp.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let job_id = json["jobId"].as_str().unwrap();
+
+    let result = poll_job_completed(&app, job_id).await;
+    assert_eq!(result["status"], "completed");
+    assert_eq!(result["warnings"].as_array().map(Vec::len), Some(1));
+    assert_eq!(result["warnings"][0]["code"], "CONTENT_SKIPPED");
+}
+
 #[tokio::test]
 async fn test_health_with_queue_info() {
     let app = create_router(&test_config());
@@ -1,4 +1,6 @@
-use hwpers::hwpx::writer::{HwpxHyperlink, HwpxImage, HwpxTable, HwpxTextStyle, StyledText};
+use hwpers::hwpx::writer::{
+    CaptionPosition, HwpxHyperlink, HwpxImage, HwpxTable, HwpxTextStyle, StyledText, TextBoxStyle,
+};
 use hwpers::{HwpxReader, HwpxWriter};
 use std::path::PathBuf;
 use tempfile::TempDir;
@@ -394,6 +396,7 @@ fn test_hwpx_hyperlink_basic() {
 }
 
 #[test]
+#[allow(deprecated)]
 fn test_hwpx_multiple_hyperlinks() {
     let mut writer = HwpxWriter::new();
 
@@ -514,3 +517,2618 @@ fn test_hwpx_odd_even_headers() {
     let bytes = writer.to_bytes().unwrap();
     assert!(!bytes.is_empty());
 }
+
+fn assert_matches_to_bytes(writer: &HwpxWriter, write: impl FnOnce() -> Vec<u8>) {
+    assert!(
+        write() == writer.to_bytes().unwrap(),
+        "write_to output should be identical to to_bytes()"
+    );
+}
+
+#[test]
+fn test_hwpx_write_to_cursor() {
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("스트리밍 출력").unwrap();
+
+    assert_matches_to_bytes(&writer, || {
+        let mut cursor = std::io::Cursor::new(Vec::new());
+        writer.write_to(&mut cursor).unwrap();
+        cursor.into_inner()
+    });
+}
+
+#[test]
+fn test_hwpx_write_to_tempfile() {
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("파일 스트리밍").unwrap();
+    writer
+        .add_table(HwpxTable::from_data(vec![vec!["A", "B"]]))
+        .unwrap();
+
+    let (_temp_dir, output_path) = create_temp_file("write_to.hwpx");
+    assert_matches_to_bytes(&writer, || {
+        let file = std::fs::File::create(&output_path).unwrap();
+        writer.write_to(file).unwrap();
+        std::fs::read(&output_path).unwrap()
+    });
+
+    let document = HwpxReader::from_file(&output_path).unwrap();
+    assert!(document.extract_text().contains("파일 스트리밍"));
+}
+
+#[test]
+fn test_hwpx_write_to_non_seekable_writer() {
+    /// Seek 없이 Write만 되는 출력 (소켓, 파이프 등)
+    struct Sink(Vec<u8>);
+    impl std::io::Write for Sink {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("스트림 출력").unwrap();
+    writer
+        .add_image(HwpxImage::from_bytes(tiny_png()).unwrap())
+        .unwrap();
+
+    let mut sink = Sink(Vec::new());
+    writer.write_to_writer(&mut sink).unwrap();
+    let streamed = sink.0;
+    // mimetype을 포함해 모든 항목의 로컬 헤더에 CRC와 크기가 들어가 to_bytes()와 같다
+    assert_matches_to_bytes(&writer, || streamed.clone());
+
+    let document = HwpxReader::from_bytes(&streamed).unwrap();
+    assert!(document.extract_text().contains("스트림 출력"));
+}
+
+fn tiny_png() -> Vec<u8> {
+    vec![
+        0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44,
+        0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x00, 0x00, 0x00, 0x90,
+        0x77, 0x53, 0xDE, 0x00, 0x00, 0x00, 0x0C, 0x49, 0x44, 0x41, 0x54, 0x08, 0xD7, 0x63, 0xF8,
+        0xFF, 0xFF, 0x3F, 0x00, 0x05, 0xFE, 0x02, 0xFE, 0xDC, 0xCC, 0x59, 0xE7, 0x00, 0x00, 0x00,
+        0x00, 0x49, 0x45, 0x4E, 0x44, 0xAE, 0x42, 0x60, 0x82,
+    ]
+}
+
+fn zip_entry_names(bytes: &[u8]) -> Vec<String> {
+    let archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+    archive.file_names().map(String::from).collect()
+}
+
+fn zip_entry_text(bytes: &[u8], name: &str) -> String {
+    use std::io::Read;
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+    let mut text = String::new();
+    archive
+        .by_name(name)
+        .unwrap()
+        .read_to_string(&mut text)
+        .unwrap();
+    text
+}
+
+#[test]
+fn test_hwpx_from_hwpx_bytes_appends() {
+    let mut template = HwpxWriter::new();
+    template
+        .add_styled_paragraph("레터헤드", HwpxTextStyle::new().size(16).bold())
+        .unwrap();
+    template
+        .add_image(HwpxImage::from_bytes(tiny_png()).unwrap().with_size(20, 10))
+        .unwrap();
+    template
+        .add_table(HwpxTable::from_data(vec![vec!["부서", "담당"]]))
+        .unwrap();
+    let template_bytes = template.to_bytes().unwrap();
+
+    let mut writer = HwpxWriter::from_hwpx_bytes(&template_bytes).unwrap();
+    writer.add_paragraph("첫 번째 추가 문단").unwrap();
+    writer
+        .add_styled_paragraph("두 번째 추가 문단", HwpxTextStyle::new().italic())
+        .unwrap();
+    let bytes = writer.to_bytes().unwrap();
+
+    let document = HwpxReader::from_bytes(&bytes).unwrap();
+    let text = document.extract_text();
+    assert!(text.contains("레터헤드"));
+    assert!(text.contains("첫 번째 추가 문단"));
+    assert!(text.contains("두 번째 추가 문단"));
+    assert!(text.find("레터헤드") < text.find("첫 번째 추가 문단"));
+
+    // Original image is re-serialized into BinData and still referenced
+    assert!(zip_entry_names(&bytes).contains(&"BinData/image1.png".to_string()));
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    assert!(section.contains(r#"binaryItemIDRef="image1""#));
+    assert!(section.contains("부서"));
+
+    // Original bold charPr keeps its size; the appended italic one gets a new id
+    let header = zip_entry_text(&bytes, "Contents/header.xml");
+    assert!(header.contains(r#"<hh:charPr id="0" height="1600" bold="1""#));
+    assert!(header.contains(r#"<hh:charPr id="1" height="1000" italic="1""#));
+}
+
+#[test]
+fn test_hwpx_from_hwpx_bytes_keeps_paragraph_shapes() {
+    use hwpers::hwpx::{HwpxAlignment, HwpxParagraphStyle, TabAlignment};
+
+    let mut template = HwpxWriter::new();
+    template
+        .add_paragraph_with_style(
+            "가운데 제목",
+            &HwpxParagraphStyle {
+                left_margin_mm: 10.0,
+                ..HwpxParagraphStyle::new()
+                    .alignment(HwpxAlignment::Center)
+                    .tab_stop(40.0, TabAlignment::Left)
+                    .keep_with_next()
+            },
+        )
+        .unwrap();
+    template.add_paragraph("본문").unwrap();
+    // Hanging indent and 200% line spacing on every source paraPr
+    let template_bytes = rebuild_package(&template.to_bytes().unwrap(), |name, data| {
+        let data = match name {
+            "Contents/header.xml" => String::from_utf8(data)
+                .unwrap()
+                .replace(r#"<hc:intent value="0""#, r#"<hc:intent value="-850""#)
+                .replace(
+                    r#"type="PERCENT" value="160""#,
+                    r#"type="PERCENT" value="200""#,
+                )
+                .into_bytes(),
+            _ => data,
+        };
+        Some((name.to_string(), data))
+    });
+
+    let mut writer = HwpxWriter::from_hwpx_bytes(&template_bytes).unwrap();
+    writer.add_paragraph("추가 문단").unwrap();
+    let bytes = writer.to_bytes().unwrap();
+
+    // Source paraPr 0 and 1 are renumbered after the writer's own default
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    let refs: Vec<&str> = section
+        .split(r#"paraPrIDRef=""#)
+        .skip(1)
+        .map(|rest| &rest[..rest.find('"').unwrap()])
+        .collect();
+    assert_eq!(refs, ["2", "1", "0"]);
+
+    let header = zip_entry_text(&bytes, "Contents/header.xml");
+    let para_pr = |id: u32| {
+        let start = header.find(&format!(r#"<hh:paraPr id="{id}""#)).unwrap();
+        let end = start + header[start..].find("</hh:paraPr>").unwrap();
+        &header[start..end]
+    };
+    assert!(para_pr(0).contains(r#"<hc:intent value="0""#));
+    assert!(para_pr(0).contains(r#"type="PERCENT" value="160""#));
+    for id in [1, 2] {
+        assert!(para_pr(id).contains(r#"<hc:intent value="-850""#));
+        assert!(para_pr(id).contains(r#"type="PERCENT" value="200""#));
+        // tab stops are not carried over
+        assert!(para_pr(id).contains(r#"tabPrIDRef="0""#));
+    }
+    assert!(para_pr(2).contains(r#"horizontal="CENTER""#));
+    assert!(para_pr(2).contains(r#"keepWithNext="1""#));
+    assert!(!para_pr(2).contains(r#"<hc:left value="0""#));
+    assert!(para_pr(1).contains(r#"horizontal="JUSTIFY""#));
+}
+
+#[test]
+fn test_hwpx_reader_char_shape_size_in_hwp_units() {
+    // hh:charPr height is already in HWP units (1/100 pt), like CharShape::base_size
+    let mut writer = HwpxWriter::new();
+    writer
+        .add_styled_paragraph("16pt", HwpxTextStyle::new().size(16))
+        .unwrap();
+    let document = HwpxReader::from_bytes(&writer.to_bytes().unwrap()).unwrap();
+    assert_eq!(document.doc_info.char_shapes[0].base_size, 1600);
+}
+
+#[test]
+fn test_hwpx_from_hwpx_file() {
+    let mut template = HwpxWriter::new();
+    template.add_paragraph("템플릿 본문").unwrap();
+    let (_temp_dir, path) = create_temp_file("template.hwpx");
+    template.save_to_file(&path).unwrap();
+
+    let mut writer = HwpxWriter::from_hwpx_file(&path).unwrap();
+    writer.add_paragraph("추가 본문").unwrap();
+
+    let document = HwpxReader::from_bytes(&writer.to_bytes().unwrap()).unwrap();
+    let text = document.extract_text();
+    assert!(text.contains("템플릿 본문"));
+    assert!(text.contains("추가 본문"));
+}
+
+#[test]
+fn test_hwpx_insert_at_marker() {
+    let mut template = HwpxWriter::new();
+    template.add_header("머리말");
+    template.add_paragraph("앞").unwrap();
+    template.add_paragraph("{{BODY}}").unwrap();
+    template
+        .add_table(HwpxTable::from_data(vec![vec!["뒤 표"]]))
+        .unwrap();
+    template.add_paragraph("뒤").unwrap();
+    let template_bytes = template.to_bytes().unwrap();
+
+    let mut writer = HwpxWriter::from_hwpx_bytes(&template_bytes).unwrap();
+    let inserted = writer
+        .insert_at_marker("{{BODY}}", |w| {
+            w.add_paragraph("가운데 1")?;
+            w.add_table(HwpxTable::from_data(vec![vec!["가운데 표"]]))?;
+            w.add_paragraph("가운데 2")
+        })
+        .unwrap();
+    assert!(inserted);
+    assert!(!writer
+        .insert_at_marker("{{BODY}}", |_| -> hwpers::Result<()> {
+            panic!("no marker left")
+        })
+        .unwrap());
+    let bytes = writer.to_bytes().unwrap();
+
+    let text = HwpxReader::from_bytes(&bytes).unwrap().extract_text();
+    let lines: Vec<&str> = text.lines().filter(|l| !l.is_empty()).collect();
+    assert_eq!(lines, ["앞", "가운데 1", "가운데 2", "뒤"]);
+    let tables = HwpxReader::extract_tables(&bytes).unwrap();
+    let cells: Vec<&str> = tables.iter().map(|t| t.rows[0][0].text.as_str()).collect();
+    assert_eq!(cells, ["가운데 표", "뒤 표"]);
+    assert!(tables[0].paragraph_index < tables[1].paragraph_index);
+
+    let headers = HwpxReader::extract_headers(&bytes).unwrap();
+    assert_eq!(headers.len(), 1);
+    assert_eq!(headers[0].text, "머리말");
+}
+
+#[test]
+fn test_hwpx_from_hwpx_bytes_invalid() {
+    assert!(HwpxWriter::from_hwpx_bytes(b"not a zip").is_err());
+}
+
+#[test]
+fn test_hwpx_merge_documents() {
+    let mut first = HwpxWriter::new();
+    first
+        .add_styled_paragraph("첫 번째 문서", HwpxTextStyle::new().bold())
+        .unwrap();
+    first
+        .add_image(HwpxImage::from_bytes(tiny_png()).unwrap())
+        .unwrap();
+
+    let mut second = HwpxWriter::new();
+    second.add_paragraph("두 번째 문서").unwrap();
+    second
+        .add_table(HwpxTable::from_data(vec![vec!["셀1", "셀2"]]))
+        .unwrap();
+
+    let mut third = HwpxWriter::new();
+    third.add_paragraph("세 번째 문서").unwrap();
+    third
+        .add_image(HwpxImage::from_bytes(tiny_png()).unwrap())
+        .unwrap();
+
+    let merged = hwpers::hwpx::merge(&[
+        first.to_bytes().unwrap(),
+        second.to_bytes().unwrap(),
+        third.to_bytes().unwrap(),
+    ])
+    .unwrap();
+
+    let document = HwpxReader::from_bytes(&merged).unwrap();
+    let text = document.extract_text();
+    assert!(text.contains("첫 번째 문서"));
+    assert!(text.contains("두 번째 문서"));
+    assert!(text.contains("세 번째 문서"));
+
+    let names = zip_entry_names(&merged);
+    for idx in 0..3 {
+        assert!(names.contains(&format!("Contents/section{}.xml", idx)));
+    }
+    assert!(!names.contains(&"Contents/section3.xml".to_string()));
+    // The first and third documents embed the same picture, which is stored once
+    assert!(names.contains(&"BinData/image1.png".to_string()));
+    assert!(!names.contains(&"BinData/image2.png".to_string()));
+
+    assert!(zip_entry_text(&merged, "Contents/header.xml").contains(r#"secCnt="3""#));
+    let hpf = zip_entry_text(&merged, "Contents/content.hpf");
+    assert!(hpf.contains(r#"href="Contents/section2.xml""#));
+    assert!(zip_entry_text(&merged, "META-INF/container.rdf").contains("Contents/section2.xml"));
+
+    // Image references still resolve after renumbering
+    assert!(
+        zip_entry_text(&merged, "Contents/section0.xml").contains(r#"binaryItemIDRef="image1""#)
+    );
+    let section1 = zip_entry_text(&merged, "Contents/section1.xml");
+    assert!(section1.contains("셀2"));
+    // The bold charPr of the first document must not leak into the second one
+    assert!(section1.contains(r#"<hp:run charPrIDRef="1"><hp:t>두 번째 문서</hp:t>"#));
+    assert!(
+        zip_entry_text(&merged, "Contents/section2.xml").contains(r#"binaryItemIDRef="image1""#)
+    );
+}
+
+#[test]
+fn test_hwpx_text_box() {
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("공지 사항").unwrap();
+    writer
+        .add_text_box(
+            "점검 시간: 02:00~04:00\n서비스가 일시 중단됩니다",
+            TextBoxStyle::new().width(120).fill(0xFFF2CC).padding(3),
+        )
+        .unwrap();
+    writer.add_paragraph("이상입니다").unwrap();
+    let bytes = writer.to_bytes().unwrap();
+
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    let box_start = section.find(r#"borderFillIDRef="4""#).unwrap();
+    let box_end = section[box_start..].find("</hp:tbl>").unwrap() + box_start;
+    let box_xml = &section[box_start..box_end];
+    assert!(box_xml.contains(r#"treatAsChar="1""#));
+    assert!(box_xml.contains("<hp:subList"));
+    assert!(box_xml.contains("<hp:t>점검 시간: 02:00~04:00</hp:t>"));
+    assert!(box_xml.contains("<hp:t>서비스가 일시 중단됩니다</hp:t>"));
+
+    let header = zip_entry_text(&bytes, "Contents/header.xml");
+    assert!(header.contains(r#"<hh:borderFills itemCnt="4">"#));
+    assert!(header.contains(r#"<hh:borderFill id="4""#));
+    assert!(header.contains(r##"faceColor="#FFF2CC""##));
+
+    let document = HwpxReader::from_bytes(&bytes).unwrap();
+    let text = document.extract_text();
+    assert!(text.contains("공지 사항"));
+    assert!(text.contains("이상입니다"));
+}
+
+#[test]
+fn test_hwpx_text_box_and_table_ids_are_unique() {
+    let mut writer = HwpxWriter::new();
+    writer
+        .add_table(HwpxTable::from_data(vec![vec!["A", "B"]]))
+        .unwrap();
+    writer.add_text_box("첫 번째", TextBoxStyle::new()).unwrap();
+    writer
+        .add_table(HwpxTable::from_data(vec![vec!["C"]]))
+        .unwrap();
+    writer.add_text_box("두 번째", TextBoxStyle::new()).unwrap();
+    let bytes = writer.to_bytes().unwrap();
+
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    let ids: Vec<&str> = section
+        .split("<hp:tbl id=\"")
+        .skip(1)
+        .map(|t| &t[..t.find('"').unwrap()])
+        .collect();
+    assert_eq!(ids, ["1", "3", "2", "4"]);
+}
+
+#[test]
+fn test_hwpx_horizontal_rule() {
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("위").unwrap();
+    writer.add_horizontal_rule(50, 0.5, 0x336699).unwrap();
+    writer.add_paragraph("아래").unwrap();
+    let bytes = writer.to_bytes().unwrap();
+
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    assert!(section.contains("<hp:line "));
+    assert!(section.contains(r#"<hc:endPt x="21260" y="0"/>"#));
+    assert!(section.contains(r##"<hp:lineShape color="#336699" width="142""##));
+
+    let text = HwpxReader::from_bytes(&bytes).unwrap().extract_text();
+    assert!(text.contains("위"));
+    assert!(text.contains("아래"));
+}
+
+#[test]
+fn test_hwpx_two_column_layout() {
+    use hwpers::model::PageLayout;
+
+    let layout = PageLayout::a4_portrait().with_columns(2, 8.0);
+    let gap = layout.column_spacing;
+    let column_width = layout.column_width();
+
+    let mut writer = HwpxWriter::new();
+    writer.set_page_layout(layout).unwrap();
+    writer.add_paragraph("소식지 본문").unwrap();
+    writer
+        .add_table(HwpxTable::from_data(vec![vec!["A", "B", "C"]]))
+        .unwrap();
+    writer
+        .add_image(
+            HwpxImage::from_bytes(tiny_png())
+                .unwrap()
+                .with_size(150, 50),
+        )
+        .unwrap();
+    let bytes = writer.to_bytes().unwrap();
+
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    assert!(section.contains(r#"colCount="2""#));
+    assert!(section.contains(&format!(r#"sameGap="{}""#, gap)));
+
+    // Wide content is clamped to the column width
+    let table_width = column_width / 3 * 3;
+    assert!(section.contains(&format!(
+        r#"<hp:sz width="{}" widthRelTo="ABSOLUTE""#,
+        table_width
+    )));
+    assert!(section.contains(&format!(r#"<hp:curSz width="{}""#, column_width)));
+
+    let document = HwpxReader::from_bytes(&bytes).unwrap();
+    assert!(document.extract_text().contains("소식지 본문"));
+}
+
+#[test]
+fn test_hwpx_layout_per_section() {
+    use hwpers::model::PageLayout;
+
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("1단 구역").unwrap();
+    writer.add_section().unwrap();
+    writer
+        .set_page_layout(PageLayout::a4_landscape().with_columns(3, 5.0))
+        .unwrap();
+    writer.add_paragraph("3단 구역").unwrap();
+    let bytes = writer.to_bytes().unwrap();
+
+    let first = zip_entry_text(&bytes, "Contents/section0.xml");
+    assert!(first.contains(r#"colCount="1""#));
+    assert!(first.contains(r#"landscape="WIDELY" width="59528" height="84186""#));
+
+    let second = zip_entry_text(&bytes, "Contents/section1.xml");
+    assert!(second.contains(r#"colCount="3""#));
+    assert!(second.contains(r#"landscape="NARROWLY" width="84188" height="59528""#));
+
+    let text = HwpxReader::from_bytes(&bytes).unwrap().extract_text();
+    assert!(text.contains("1단 구역"));
+    assert!(text.contains("3단 구역"));
+}
+
+#[test]
+fn test_hwpx_page_setup_inspection() {
+    use hwpers::model::{PageLayout, PageMargins, PageOrientation, PaperSize};
+
+    // Defaults: one A4 portrait section
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("기본 용지").unwrap();
+    let document = HwpxReader::from_bytes(&writer.to_bytes().unwrap()).unwrap();
+    assert_eq!(document.section_count(), 1);
+    let layout = document.page_layout(0).unwrap();
+    assert_eq!((layout.width, layout.height), (59528, 84186));
+    assert_eq!(layout.orientation, PageOrientation::Portrait);
+    assert_eq!(layout.paper_size, PaperSize::A4);
+    assert_eq!(layout.margins.top, 5668);
+    assert_eq!(layout.margins.left, 8504);
+    assert!(document.page_layout(1).is_none());
+
+    // A custom second section
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("세로").unwrap();
+    writer.add_section().unwrap();
+    writer
+        .set_page_layout(
+            PageLayout::a4_landscape()
+                .with_margins(PageMargins::new_mm(10.0, 12.0, 15.0, 20.0).with_gutter_mm(5.0)),
+        )
+        .unwrap();
+    writer.add_paragraph("가로").unwrap();
+    let document = HwpxReader::from_bytes(&writer.to_bytes().unwrap()).unwrap();
+    assert_eq!(document.section_count(), 2);
+
+    let first = document.page_layout(0).unwrap();
+    assert_eq!(first.orientation, PageOrientation::Portrait);
+
+    let second = document.page_layout(1).unwrap();
+    assert_eq!((second.width, second.height), (84188, 59528));
+    assert_eq!(second.orientation, PageOrientation::Landscape);
+    assert_eq!(second.paper_size, PaperSize::A4);
+    let expected = PageMargins::new_mm(10.0, 12.0, 15.0, 20.0).with_gutter_mm(5.0);
+    assert_eq!(second.margins.left, expected.left);
+    assert_eq!(second.margins.right, expected.right);
+    assert_eq!(second.margins.top, expected.top);
+    assert_eq!(second.margins.bottom, expected.bottom);
+    assert_eq!(second.margins.header, expected.header);
+    assert_eq!(second.margins.gutter, expected.gutter);
+}
+
+#[test]
+fn test_hwpx_metadata_roundtrip() {
+    use hwpers::hwpx::HwpxMetadata;
+
+    let metadata = HwpxMetadata {
+        title: "보고서 <초안>".to_string(),
+        creator: "홍길동 (개발팀)".to_string(),
+        created_date: "2026-01-25 PM 12:00:00".to_string(),
+        subject: "공지사항 & 안내".to_string(),
+        description: "\"분기\" 실적 요약".to_string(),
+        keywords: vec!["실적".to_string(), "2026".to_string()],
+        modified_date: "2026-01-26 AM 09:00:00".to_string(),
+    };
+
+    let mut writer = HwpxWriter::new();
+    writer.set_metadata(metadata.clone());
+    writer.add_paragraph("본문").unwrap();
+    let bytes = writer.to_bytes().unwrap();
+
+    let hpf = zip_entry_text(&bytes, "Contents/content.hpf");
+    assert!(
+        hpf.contains(r#"<opf:meta name="subject" content="text">공지사항 &amp; 안내</opf:meta>"#)
+    );
+    assert!(hpf.contains(r#"<opf:meta name="keyword" content="text">실적, 2026</opf:meta>"#));
+
+    assert_eq!(HwpxReader::metadata_from_bytes(&bytes).unwrap(), metadata);
+
+    // The parsed document exposes the same fields through its summary
+    let document = HwpxReader::from_bytes(&bytes).unwrap();
+    assert_eq!(document.title(), Some("보고서 <초안>"));
+    assert_eq!(document.author(), Some("홍길동 (개발팀)"));
+    assert_eq!(document.subject(), Some("공지사항 & 안내"));
+    assert_eq!(document.keywords(), Some("실적, 2026"));
+    assert_eq!(
+        document.summary_info().unwrap().comments.as_deref(),
+        Some("\"분기\" 실적 요약")
+    );
+}
+
+#[test]
+fn test_hwpx_metadata_defaults() {
+    use hwpers::hwpx::HwpxMetadata;
+
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("본문").unwrap();
+    let bytes = writer.to_bytes().unwrap();
+
+    let hpf = zip_entry_text(&bytes, "Contents/content.hpf");
+    assert!(hpf.contains(r#"<opf:meta name="keyword" content="text"/>"#));
+    assert_eq!(
+        HwpxReader::metadata_from_bytes(&bytes).unwrap(),
+        HwpxMetadata::default()
+    );
+    let document = HwpxReader::from_bytes(&bytes).unwrap();
+    assert_eq!(document.title(), None);
+    assert_eq!(document.keywords(), None);
+}
+
+#[test]
+fn test_hwpx_suppress_first_page_header_footer() {
+    use hwpers::hwpx::{HwpxFooter, HwpxHeader};
+
+    let mut writer = HwpxWriter::new();
+    writer.add_header_config(HwpxHeader::new("Report").suppress_first());
+    writer.add_footer_config(HwpxFooter::new("Page ").with_page_number().suppress_first());
+    writer.add_paragraph("Cover").unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    assert!(section.contains(r#"hideFirstHeader="1" hideFirstFooter="1""#));
+}
+
+#[test]
+fn test_hwpx_first_page_header_only_in_first_section() {
+    use hwpers::hwpx::HwpxHeader;
+
+    let mut writer = HwpxWriter::new();
+    writer.add_header_config(HwpxHeader::for_first_page("Cover Header"));
+    writer.add_paragraph("Cover").unwrap();
+    writer.add_section().unwrap();
+    writer.add_paragraph("Body").unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+    let first = zip_entry_text(&bytes, "Contents/section0.xml");
+    let second = zip_entry_text(&bytes, "Contents/section1.xml");
+    assert!(first.contains(r#"<hp:header id="1" applyPageType="BOTH">"#));
+    assert!(first.contains(r#"hideFirstHeader="0" hideFirstFooter="0""#));
+    assert!(!second.contains("Cover Header"));
+}
+
+#[test]
+fn test_hwpx_styled_footer_with_page_number() {
+    use hwpers::hwpx::HwpxFooter;
+
+    let mut writer = HwpxWriter::new();
+    writer.add_footer_config(
+        HwpxFooter::styled(vec![
+            StyledText::with_style("문서번호 A-17 ", HwpxTextStyle::new().bold()),
+            StyledText::new("| "),
+        ])
+        .with_page_number(),
+    );
+    writer.add_paragraph("본문").unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    let start = section.find("<hp:footer ").unwrap();
+    let end = section.find("</hp:footer>").unwrap();
+    let footer = &section[start..end];
+
+    // charPr 0 is the plain default; the bold and plain runs get 1 and 2
+    assert!(footer.contains(r#"<hp:run charPrIDRef="1"><hp:t>문서번호 A-17 </hp:t></hp:run>"#));
+    assert!(footer.contains(r#"<hp:run charPrIDRef="2"><hp:t>| </hp:t></hp:run>"#));
+    assert!(
+        footer.contains(r#"<hp:run charPrIDRef="0"><hp:ctrl><hp:autoNum num="1" numType="PAGE">"#)
+    );
+
+    let header = zip_entry_text(&bytes, "Contents/header.xml");
+    assert!(header.contains(r#"<hh:charProperties itemCnt="3">"#));
+}
+
+#[test]
+fn test_hwpx_header_image_listed_in_bin_data() {
+    use hwpers::hwpx::HwpxHeader;
+
+    let mut writer = HwpxWriter::new();
+    writer
+        .add_image(HwpxImage::from_bytes(tiny_png()).unwrap())
+        .unwrap();
+    writer.add_header_config(
+        HwpxHeader::styled(vec![StyledText::with_style(
+            "사내 문서",
+            HwpxTextStyle::new().bold(),
+        )])
+        .with_image(HwpxImage::from_bytes(synthetic_png(64, 1)).unwrap()),
+    );
+
+    let bytes = writer.to_bytes().unwrap();
+    let names = zip_entry_names(&bytes);
+    assert!(names.contains(&"BinData/image1.png".to_string()));
+    assert!(names.contains(&"BinData/image2.png".to_string()));
+
+    let header = zip_entry_text(&bytes, "Contents/header.xml");
+    assert!(header.contains(r#"<hh:binDataItems itemCnt="2">"#));
+    let hpf = zip_entry_text(&bytes, "Contents/content.hpf");
+    assert!(hpf.contains(r#"href="BinData/image2.png""#));
+
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    let start = section.find("<hp:header ").unwrap();
+    let end = section.find("</hp:header>").unwrap();
+    assert!(section[start..end].contains(r#"binaryItemIDRef="image2""#));
+    assert!(section[start..end].contains("사내 문서"));
+}
+
+#[test]
+fn test_hwpx_start_page_number() {
+    let mut writer = HwpxWriter::new();
+    writer.set_start_page(5).unwrap();
+    writer.add_footer_with_page_number("");
+    writer.add_paragraph("Chapter 2").unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+    let header = zip_entry_text(&bytes, "Contents/header.xml");
+    assert!(header.contains(r#"<hh:beginNum page="5""#));
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    assert!(section.contains(r#"<hp:startNum pageStartsOn="BOTH" page="5""#));
+}
+
+#[test]
+fn test_hwpx_centered_page_number_footer() {
+    use hwpers::hwpx::{HwpxAlignment, HwpxFooter, PageNumberFormat};
+
+    let mut writer = HwpxWriter::new();
+    writer.add_footer_config(
+        HwpxFooter::new("")
+            .with_page_number_format(PageNumberFormat::Numeric)
+            .with_page_number_chars('-', '-')
+            .with_alignment(HwpxAlignment::Center),
+    );
+    writer.add_paragraph("본문").unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+    let header = zip_entry_text(&bytes, "Contents/header.xml");
+    assert!(header.contains(r#"<hh:paraProperties itemCnt="2">"#));
+    assert!(header.contains(r#"<hh:paraPr id="1" "#));
+    assert!(header.contains(r#"<hh:align horizontal="CENTER" vertical="BASELINE"/>"#));
+
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    let start = section.find("<hp:footer ").unwrap();
+    let end = section.find("</hp:footer>").unwrap();
+    let footer = &section[start..end];
+    assert!(footer.contains(r#"<hp:p id="0" paraPrIDRef="1" "#));
+    assert!(footer.contains(r#"type="DIGIT" userChar="" prefixChar="-" suffixChar="-""#));
+    // No empty text run before a standalone page number
+    assert!(!footer.contains("<hp:t></hp:t>"));
+}
+
+#[test]
+fn test_hwpx_to_bytes_is_deterministic() {
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("재현 가능한 출력").unwrap();
+    writer
+        .add_table(HwpxTable::from_data(vec![vec!["A", "B"]]))
+        .unwrap();
+    writer
+        .add_image(HwpxImage::from_bytes(tiny_png()).unwrap())
+        .unwrap();
+
+    let first = writer.to_bytes().unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(2100));
+    assert_eq!(first, writer.to_bytes().unwrap());
+
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(&first)).unwrap();
+    let entry = archive.by_name("Contents/section0.xml").unwrap();
+    assert_eq!(entry.last_modified(), Some(zip::DateTime::default()));
+}
+
+#[test]
+fn test_hwpx_manifest_lists_package_files() {
+    use quick_xml::events::Event;
+
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("매니페스트").unwrap();
+    writer
+        .add_image(HwpxImage::from_bytes(tiny_png()).unwrap())
+        .unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+    let manifest = zip_entry_text(&bytes, "META-INF/manifest.xml");
+
+    let mut listed = Vec::new();
+    let mut reader = quick_xml::Reader::from_str(&manifest);
+    loop {
+        match reader.read_event().unwrap() {
+            Event::Empty(e) if e.name().as_ref() == b"odf:file-entry" => {
+                let path = e
+                    .try_get_attribute("odf:full-path")
+                    .unwrap()
+                    .unwrap()
+                    .unescape_value()
+                    .unwrap()
+                    .to_string();
+                listed.push(path);
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    let mut files: Vec<String> = zip_entry_names(&bytes)
+        .into_iter()
+        .filter(|name| !name.ends_with('/') && name != "META-INF/manifest.xml")
+        .collect();
+    files.sort();
+    listed.sort();
+    assert_eq!(listed, files);
+    assert!(listed.contains(&"BinData/image1.png".to_string()));
+    assert!(manifest.contains(
+        r#"<odf:file-entry odf:full-path="Contents/section0.xml" odf:media-type="application/xml"/>"#
+    ));
+}
+
+const HOSTILE_STRINGS: &[&str] = &[
+    r#"" onload="x"#,
+    "<hp:p>injected</hp:p>",
+    "a & b ' c",
+    "]]><!--",
+    "bell\u{7} nul\u{0} esc\u{1b} form\u{c}",
+    "\u{FFFE}\u{FFFF}",
+];
+
+/// Every XML part must parse and contain no characters XML 1.0 forbids
+fn assert_well_formed_package(bytes: &[u8]) {
+    use quick_xml::events::Event;
+
+    for name in zip_entry_names(bytes) {
+        if !(name.ends_with(".xml") || name.ends_with(".hpf") || name.ends_with(".rdf")) {
+            continue;
+        }
+        let text = zip_entry_text(bytes, &name);
+        assert!(
+            !text
+                .chars()
+                .any(|c| (c < ' ' && !matches!(c, '\t' | '\n' | '\r'))
+                    || c == '\u{FFFE}'
+                    || c == '\u{FFFF}'),
+            "{} contains characters not allowed in XML",
+            name
+        );
+        let mut reader = quick_xml::Reader::from_str(&text);
+        loop {
+            match reader.read_event() {
+                Ok(Event::Eof) => break,
+                Ok(_) => {}
+                Err(e) => panic!("{} is not well-formed: {}", name, e),
+            }
+        }
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_hwpx_hostile_strings_stay_well_formed() {
+    use hwpers::hwpx::HwpxMetadata;
+
+    for hostile in HOSTILE_STRINGS {
+        let mut writer = HwpxWriter::new();
+        writer.set_metadata(HwpxMetadata {
+            title: hostile.to_string(),
+            creator: hostile.to_string(),
+            subject: hostile.to_string(),
+            keywords: vec![hostile.to_string()],
+            ..Default::default()
+        });
+        writer.add_paragraph(hostile).unwrap();
+        writer
+            .add_mixed_styled_paragraph(vec![StyledText::with_style(
+                hostile,
+                HwpxTextStyle::new().bold(),
+            )])
+            .unwrap();
+        writer
+            .add_table(HwpxTable::from_data(vec![vec![hostile, "ok"]]))
+            .unwrap();
+        let text = format!("{} link", hostile);
+        writer
+            .add_paragraph_with_hyperlinks(&text, vec![HwpxHyperlink::new("link", hostile)])
+            .unwrap();
+        writer.add_header(hostile);
+        writer.add_footer(hostile);
+
+        let bytes = writer.to_bytes().unwrap();
+        assert_well_formed_package(&bytes);
+        HwpxReader::from_bytes(&bytes).expect("hostile input should still be readable");
+    }
+}
+
+/// PNG signature followed by `size` bytes of a per-image pattern
+fn synthetic_png(size: usize, seed: u8) -> Vec<u8> {
+    let mut data = vec![0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+    data.extend((0..size).map(|i| (i as u8).wrapping_mul(31).wrapping_add(seed)));
+    data
+}
+
+#[test]
+fn test_hwpx_large_images_are_written_intact() {
+    use std::sync::Arc;
+
+    let mut writer = HwpxWriter::new();
+    let buffers: Vec<Arc<Vec<u8>>> = (0..4)
+        .map(|seed| Arc::new(synthetic_png(3 * 1024 * 1024, seed)))
+        .collect();
+    for buffer in &buffers {
+        let image = HwpxImage::from_shared(Arc::clone(buffer))
+            .unwrap()
+            .with_size(40, 30);
+        writer.add_image(image).unwrap();
+    }
+
+    // The writer keeps the caller's buffers instead of copies: one reference for the
+    // placement and one for its BinData item
+    for buffer in &buffers {
+        assert_eq!(Arc::strong_count(buffer), 3);
+    }
+
+    let bytes = writer.to_bytes().unwrap();
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(&bytes)).unwrap();
+    for (idx, buffer) in buffers.iter().enumerate() {
+        use std::io::Read;
+        let mut entry = archive
+            .by_name(&format!("BinData/image{}.png", idx + 1))
+            .unwrap();
+        let mut written = Vec::new();
+        entry.read_to_end(&mut written).unwrap();
+        assert!(written == **buffer, "image{} bytes differ", idx + 1);
+    }
+}
+
+#[test]
+fn test_hwpx_repeated_image_stored_once() {
+    use hwpers::hwpx::HwpxFooter;
+
+    let mut writer = HwpxWriter::new();
+    for _ in 0..3 {
+        writer
+            .add_image(HwpxImage::from_bytes(tiny_png()).unwrap())
+            .unwrap();
+    }
+    writer.add_footer_config(
+        HwpxFooter::new("").with_image(HwpxImage::from_bytes(tiny_png()).unwrap()),
+    );
+    writer
+        .add_image(HwpxImage::from_bytes(synthetic_png(64, 7)).unwrap())
+        .unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+    assert_well_formed_package(&bytes);
+
+    let bin_data: Vec<String> = zip_entry_names(&bytes)
+        .into_iter()
+        .filter(|name| name.starts_with("BinData/image"))
+        .collect();
+    assert_eq!(bin_data, ["BinData/image1.png", "BinData/image2.png"]);
+    let header = zip_entry_text(&bytes, "Contents/header.xml");
+    assert!(header.contains(r#"<hh:binDataItems itemCnt="2">"#));
+    let hpf = zip_entry_text(&bytes, "Contents/content.hpf");
+    assert_eq!(hpf.matches("href=\"BinData/").count(), 2);
+
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    assert_eq!(section.matches("<hp:pic ").count(), 5);
+    assert_eq!(section.matches(r#"binaryItemIDRef="image1""#).count(), 4);
+    assert_eq!(section.matches(r#"binaryItemIDRef="image2""#).count(), 1);
+
+    let pic_ids: std::collections::HashSet<&str> = section
+        .match_indices("<hp:pic id=\"")
+        .map(|(pos, tag)| {
+            let rest = &section[pos + tag.len()..];
+            &rest[..rest.find('"').unwrap()]
+        })
+        .collect();
+    assert_eq!(pic_ids.len(), 5, "pic ids must be distinct: {:?}", pic_ids);
+}
+
+fn char_pr_xml(header: &str, id: u32) -> &str {
+    let start = header
+        .find(&format!(r#"<hh:charPr id="{}""#, id))
+        .unwrap_or_else(|| panic!("charPr {} missing", id));
+    let end = start + header[start..].find("</hh:charPr>").unwrap();
+    &header[start..end]
+}
+
+#[test]
+fn test_hwpx_strikethrough_and_shadow_colors() {
+    let mut writer = HwpxWriter::new();
+    writer
+        .add_mixed_styled_paragraph(vec![
+            StyledText::with_style("plain ", HwpxTextStyle::new().color(0x0000FF)),
+            StyledText::with_style(
+                "struck ",
+                HwpxTextStyle::new().strikethrough_color(0xFF0000),
+            ),
+            StyledText::with_style("shadowed", HwpxTextStyle::new().shadow(15, -20, 0x00AA00)),
+        ])
+        .unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+    let header = zip_entry_text(&bytes, "Contents/header.xml");
+
+    let plain = char_pr_xml(&header, 0);
+    assert!(plain.contains(r##"<hh:underline type="NONE" shape="SOLID" color="#0000FF"/>"##));
+    assert!(plain.contains(r##"<hh:strikeout shape="NONE" color="#0000FF"/>"##));
+    assert!(
+        plain.contains(r##"<hh:shadow type="NONE" color="#808080" offsetX="10" offsetY="10"/>"##)
+    );
+
+    let struck = char_pr_xml(&header, 1);
+    assert!(struck.contains(r##"<hh:underline type="NONE""##));
+    assert!(struck.contains(r##"<hh:strikeout shape="CONTINUOUS" color="#FF0000"/>"##));
+
+    let shadowed = char_pr_xml(&header, 2);
+    assert!(shadowed.contains(r##"<hh:strikeout shape="NONE""##));
+    assert!(shadowed
+        .contains(r##"<hh:shadow type="DROP" color="#00AA00" offsetX="15" offsetY="-20"/>"##));
+}
+
+#[test]
+fn test_hwpx_default_text_style() {
+    let mut writer = HwpxWriter::new();
+    writer
+        .add_styled_paragraph("굵은 제목", HwpxTextStyle::new().bold().size(16))
+        .unwrap();
+    writer.set_default_text_style(HwpxTextStyle {
+        font_name: Some("돋움".to_string()),
+        ..HwpxTextStyle::new().size(12)
+    });
+    writer.add_paragraph("본문").unwrap();
+    writer
+        .add_table(HwpxTable::from_data(vec![vec!["셀"]]))
+        .unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+    assert_well_formed_package(&bytes);
+
+    let header = zip_entry_text(&bytes, "Contents/header.xml");
+    assert!(char_pr_xml(&header, 0).contains(r#"height="1200""#));
+    assert!(!char_pr_xml(&header, 0).contains(r#" bold="1""#));
+    // The title registered earlier moved to charPr 1 and keeps its own shape
+    let title = char_pr_xml(&header, 1);
+    assert!(title.contains(r#"height="1600""#) && title.contains(r#" bold="1""#));
+    assert!(header.contains(r#"<hh:font id="0" face="돋움""#));
+    assert!(!header.contains("맑은 고딕"));
+
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    assert!(section.contains(r#"<hp:run charPrIDRef="1"><hp:t>굵은 제목</hp:t>"#));
+    assert!(section.contains(r#"<hp:run charPrIDRef="0"><hp:t>본문</hp:t>"#));
+
+    // Setting it again replaces charPr 0 instead of shifting once more
+    writer.set_default_text_style(HwpxTextStyle::new().size(11));
+    let header = zip_entry_text(&writer.to_bytes().unwrap(), "Contents/header.xml");
+    assert!(char_pr_xml(&header, 0).contains(r#"height="1100""#));
+    assert!(char_pr_xml(&header, 1).contains(r#"height="1600""#));
+    assert!(header.contains(r#"<hh:charProperties itemCnt="2">"#));
+}
+
+#[test]
+fn test_hwpx_outline_emboss_engrave_effects() {
+    let mut writer = HwpxWriter::new();
+    writer
+        .add_mixed_styled_paragraph(vec![
+            StyledText::new("plain "),
+            StyledText::with_style("outline ", HwpxTextStyle::new().outline()),
+            StyledText::with_style("emboss ", HwpxTextStyle::new().bold().emboss()),
+            StyledText::with_style("engrave", HwpxTextStyle::new().emboss().engrave()),
+        ])
+        .unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+    let header = zip_entry_text(&bytes, "Contents/header.xml");
+
+    let plain = char_pr_xml(&header, 0);
+    assert!(plain.contains(r#"<hh:outline type="NONE"/>"#));
+    assert!(!plain.contains("<hh:emboss/>") && !plain.contains("<hh:engrave/>"));
+
+    let outline = char_pr_xml(&header, 1);
+    assert!(outline.contains(r#"<hh:outline type="SOLID"/>"#));
+
+    let emboss = char_pr_xml(&header, 2);
+    assert!(emboss.contains(r#" bold="1""#));
+    assert!(emboss.contains("<hh:emboss/>"));
+    assert!(emboss.contains(r#"<hh:outline type="NONE"/>"#));
+
+    // The later builder call wins; emboss and engrave never combine
+    let engrave = char_pr_xml(&header, 3);
+    assert!(engrave.contains("<hh:engrave/>"));
+    assert!(!engrave.contains("<hh:emboss/>"));
+
+    let document = HwpxReader::from_bytes(&bytes).unwrap();
+    let shapes = &document.doc_info.char_shapes;
+    assert!(!shapes[0].is_outline() && !shapes[0].is_emboss() && !shapes[0].is_engrave());
+    assert!(shapes[1].is_outline() && !shapes[1].is_bold());
+    assert!(shapes[2].is_bold() && shapes[2].is_emboss() && !shapes[2].is_engrave());
+    assert!(shapes[3].is_engrave() && !shapes[3].is_emboss());
+}
+
+#[test]
+fn test_hwpx_vertical_space_vs_empty_paragraphs() {
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("위").unwrap();
+    writer.add_empty_paragraphs(2).unwrap();
+    writer.add_paragraph("아래").unwrap();
+    writer.add_vertical_space(6).unwrap();
+    writer.add_vertical_space(6).unwrap();
+    writer.add_paragraph("간격 뒤").unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+    let doc = HwpxReader::from_bytes(&bytes).unwrap();
+    let ids: Vec<u16> = doc.body_texts[0].sections[0]
+        .paragraphs
+        .iter()
+        .map(|p| p.para_shape_id)
+        .collect();
+    assert_eq!(ids, vec![0, 0, 0, 0, 1]);
+
+    let header = zip_entry_text(&bytes, "Contents/header.xml");
+    assert!(header.contains(r#"<hh:paraProperties itemCnt="2">"#));
+    let start = header.find(r#"<hh:paraPr id="1""#).unwrap();
+    let para_pr = &header[start..start + header[start..].find("</hh:paraPr>").unwrap()];
+    // Both the HwpUnitChar case and the default branch carry the spacing
+    assert_eq!(
+        para_pr
+            .matches(r#"<hc:prev value="1200" unit="HWPUNIT"/>"#)
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn test_hwpx_vertical_space_rejects_overflow() {
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("위").unwrap();
+    assert!(writer.add_vertical_space(u32::MAX).is_err());
+    // 20,000,000pt fits once but not twice
+    writer.add_vertical_space(20_000_000).unwrap();
+    assert!(writer.add_vertical_space(20_000_000).is_err());
+    writer.add_paragraph("아래").unwrap();
+
+    let header = zip_entry_text(&writer.to_bytes().unwrap(), "Contents/header.xml");
+    assert!(header.contains(r#"<hc:prev value="2000000000" unit="HWPUNIT"/>"#));
+}
+
+#[test]
+fn test_hwpx_table_captions_are_numbered() {
+    let mut writer = HwpxWriter::new();
+    writer
+        .add_table(
+            HwpxTable::from_data(vec![vec!["a", "b"]]).with_caption("매출", CaptionPosition::Above),
+        )
+        .unwrap();
+    writer
+        .add_table(HwpxTable::from_data(vec![vec!["캡션 없음"]]))
+        .unwrap();
+    writer.add_paragraph("본문").unwrap();
+    writer
+        .add_table(
+            HwpxTable::from_data(vec![vec!["c"], vec!["d"]])
+                .with_caption("비용", CaptionPosition::Below),
+        )
+        .unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    let tables: Vec<&str> = section
+        .split("<hp:tbl ")
+        .skip(1)
+        .map(|t| &t[..t.find("</hp:tbl>").unwrap()])
+        .collect();
+    assert_eq!(tables.len(), 3);
+
+    // The caption sits inside hp:tbl, ahead of the rows
+    let first = tables[0];
+    assert!(first.contains(r#"<hp:caption side="TOP""#));
+    assert!(first.find("<hp:caption ").unwrap() < first.find("<hp:tr>").unwrap());
+    assert!(first.contains("<hp:t>표 1. 매출</hp:t>"));
+
+    assert!(!tables[1].contains("<hp:caption"));
+
+    let third = tables[2];
+    assert!(third.contains(r#"<hp:caption side="BOTTOM""#));
+    assert!(third.contains("<hp:t>표 2. 비용</hp:t>"));
+
+    // Captions use a centered paraPr
+    let header = zip_entry_text(&bytes, "Contents/header.xml");
+    assert!(first.contains(r#"<hp:p id="0" paraPrIDRef="1""#));
+    assert!(header.contains(r#"<hh:paraPr id="1""#));
+    assert!(header.contains(r#"<hh:align horizontal="CENTER""#));
+    HwpxReader::from_bytes(&bytes).unwrap();
+}
+
+#[test]
+fn test_hwpx_bold_hyperlink_run_in_styled_paragraph() {
+    let mut writer = HwpxWriter::new();
+    writer
+        .add_mixed_styled_paragraph(vec![
+            StyledText::new("see the "),
+            StyledText::with_style("bold link", HwpxTextStyle::new().bold())
+                .link("https://example.com/doc?a=1&b=2"),
+            StyledText::new(" here"),
+        ])
+        .unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    let header = zip_entry_text(&bytes, "Contents/header.xml");
+
+    assert!(section.contains(r#"<hp:run charPrIDRef="0"><hp:t>see the </hp:t></hp:run>"#));
+    // Bold char shape and the link control share one run
+    assert!(section.contains(concat!(
+        r#"<hp:run charPrIDRef="1"><hp:ctrl><hp:hyperlink url="https://example.com/doc?a=1&amp;b=2" "#,
+        r#"visited="0" visited_style="0" new_window="0"/></hp:ctrl><hp:t>bold link</hp:t></hp:run>"#
+    )));
+    assert!(section.contains(r#"<hp:run charPrIDRef="2"><hp:t> here</hp:t></hp:run>"#));
+    assert!(char_pr_xml(&header, 1).contains(r#" bold="1""#));
+
+    let text = HwpxReader::from_bytes(&bytes).unwrap().extract_text();
+    assert!(text.contains("see the") && text.contains("bold link"));
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_hwpx_positional_hyperlinks_after_korean_text() {
+    let mut writer = HwpxWriter::new();
+    writer
+        .add_paragraph_with_hyperlinks(
+            "자세한 내용은 Google 참고",
+            vec![HwpxHyperlink::new("Google", "https://google.com")],
+        )
+        .unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    assert!(section.contains(r#"<hp:run charPrIDRef="0"><hp:t>자세한 내용은 </hp:t></hp:run>"#));
+    assert!(section.contains(r#"new_window="0"/></hp:ctrl><hp:t>Google</hp:t></hp:run>"#));
+    assert!(section.contains(r#"<hp:run charPrIDRef="0"><hp:t> 참고</hp:t></hp:run>"#));
+}
+
+#[test]
+fn test_hwpx_vertical_text_direction() {
+    use hwpers::hwpx::{HwpxHeader, TextDirection};
+
+    let mut writer = HwpxWriter::new();
+    writer.add_header_config(HwpxHeader::new("머리말"));
+    writer
+        .set_text_direction(TextDirection::VerticalRightToLeft)
+        .unwrap();
+    writer.add_paragraph("상장").unwrap();
+    writer
+        .add_table(HwpxTable::from_data(vec![vec!["성명", "홍길동"]]))
+        .unwrap();
+    writer
+        .add_text_box("위 사람은", TextBoxStyle::default())
+        .unwrap();
+    writer.add_section().unwrap();
+    writer.add_paragraph("가로 구역").unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+    assert_well_formed_package(&bytes);
+
+    let vertical = zip_entry_text(&bytes, "Contents/section0.xml");
+    assert!(vertical.contains(r#"<hp:secPr id="" textDirection="VERTICAL""#));
+    // Both table cells and the text box follow the section; the header stays horizontal
+    assert_eq!(
+        vertical
+            .matches(r#"<hp:subList id="" textDirection="VERTICAL""#)
+            .count(),
+        3
+    );
+    let header_start = vertical.find("<hp:header ").unwrap();
+    assert!(vertical[header_start..].contains(r#"textDirection="HORIZONTAL""#));
+
+    let horizontal = zip_entry_text(&bytes, "Contents/section1.xml");
+    assert!(horizontal.contains(r#"<hp:secPr id="" textDirection="HORIZONTAL""#));
+    assert!(!horizontal.contains(r#"textDirection="VERTICAL""#));
+
+    let document = HwpxReader::from_bytes(&bytes).unwrap();
+    let text = document.extract_text();
+    assert!(text.contains("상장"));
+    assert!(text.contains("가로 구역"));
+}
+
+#[test]
+fn test_hwpx_checkbox_and_text_fields() {
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("결재 확인").unwrap();
+    writer.add_checkbox_paragraph("검토 완료", true).unwrap();
+    writer.add_checkbox_paragraph("반려 <사유>", false).unwrap();
+    writer.add_text_field("reviewer", "검토자 이름").unwrap();
+    writer.add_text_field("comment", "").unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+    assert_well_formed_package(&bytes);
+
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    assert_eq!(section.matches("<hp:checkBtn ").count(), 2);
+    assert_eq!(section.matches("<hp:edit ").count(), 2);
+    assert!(section.contains(r#"caption="검토 완료" value="CHECKED""#));
+    assert!(section.contains(r#"caption="반려 &lt;사유&gt;" value="UNCHECKED""#));
+    assert!(section.contains(r#"name="reviewer""#));
+    assert!(section.contains("<hp:text>검토자 이름</hp:text>"));
+
+    let ids: Vec<&str> = section
+        .match_indices("<hp:checkBtn id=\"")
+        .chain(section.match_indices("<hp:edit id=\""))
+        .map(|(pos, tag)| {
+            let rest = &section[pos + tag.len()..];
+            &rest[..rest.find('"').unwrap()]
+        })
+        .collect();
+    assert_eq!(ids.len(), 4);
+    let unique: std::collections::HashSet<_> = ids.iter().collect();
+    assert_eq!(unique.len(), 4, "form field ids must be unique: {:?}", ids);
+
+    let document = HwpxReader::from_bytes(&bytes).unwrap();
+    assert!(document.extract_text().contains("결재 확인"));
+    assert_eq!(document.body_texts[0].sections[0].paragraphs.len(), 5);
+}
+
+#[test]
+fn test_hwpx_line_numbers_per_section() {
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("번호 없는 구역").unwrap();
+    writer.add_section().unwrap();
+    writer.enable_line_numbers(5, 1, 10).unwrap();
+    writer.add_paragraph("제1조 목적").unwrap();
+    assert!(writer.enable_line_numbers(0, 1, 10).is_err());
+
+    let bytes = writer.to_bytes().unwrap();
+    assert_well_formed_package(&bytes);
+
+    // Disabled by default
+    let plain = zip_entry_text(&bytes, "Contents/section0.xml");
+    assert!(plain.contains(r#"showLineNumber="0""#));
+    assert!(plain.contains(
+        r#"<hp:lineNumberShape restartType="0" countBy="0" distance="0" startNumber="0"/>"#
+    ));
+
+    let numbered = zip_entry_text(&bytes, "Contents/section1.xml");
+    assert!(numbered.contains(r#"showLineNumber="1""#));
+    assert!(numbered.contains(
+        r#"<hp:lineNumberShape restartType="0" countBy="5" distance="2835" startNumber="1"/>"#
+    ));
+}
+
+#[test]
+fn test_hwpx_tab_stops_and_tab_characters() {
+    use hwpers::hwpx::{HwpxParagraphStyle, TabAlignment};
+
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("이름\t값").unwrap();
+    let style = HwpxParagraphStyle::new()
+        .tab_stop(40.0, TabAlignment::Left)
+        .tab_stop(150.0, TabAlignment::Right);
+    writer
+        .add_paragraph_with_style("항목\t내용\t1,000", &style)
+        .unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    assert!(section.contains(r#"<hp:t>이름<hp:tab width="4000" leader="0" type="1"/>값</hp:t>"#));
+    assert!(!section.contains('\t'));
+
+    let header = zip_entry_text(&bytes, "Contents/header.xml");
+    assert!(header.contains(r#"<hh:tabProperties itemCnt="2">"#));
+    assert!(header.contains(concat!(
+        r#"<hh:tabPr id="1" autoTabLeft="0" autoTabRight="0">"#,
+        r#"<hh:tabItem pos="11339" type="LEFT" leader="NONE"/>"#,
+        r#"<hh:tabItem pos="42520" type="RIGHT" leader="NONE"/></hh:tabPr>"#
+    )));
+    assert!(header.contains(r#"<hh:paraPr id="1" tabPrIDRef="1""#));
+
+    let document = HwpxReader::from_bytes(&bytes).unwrap();
+    let paragraphs = &document.body_texts[0].sections[0].paragraphs;
+    assert_eq!(paragraphs[0].text.as_ref().unwrap().content, "이름\t값");
+    assert_eq!(paragraphs[1].para_shape_id, 1);
+    assert_eq!(
+        paragraphs[1].text.as_ref().unwrap().content,
+        "항목\t내용\t1,000"
+    );
+}
+
+#[test]
+fn test_hwpx_paragraph_border_and_shading() {
+    use hwpers::hwpx::{BorderLineStyle, HwpxParagraphStyle, ParagraphBorder};
+
+    let mut writer = HwpxWriter::new();
+    writer
+        .add_text_box("글상자", TextBoxStyle::default())
+        .unwrap();
+    let notice = HwpxParagraphStyle::new().border(
+        ParagraphBorder::new()
+            .line_style(BorderLineStyle::Dash)
+            .color(0x808080)
+            .fill(0xEEEEEE)
+            .offsets_mm(2.0, 2.0, 1.0, 1.0),
+    );
+    writer
+        .add_paragraph_with_style("공지 사항", &notice)
+        .unwrap();
+    writer
+        .add_paragraph_with_style("두 번째 공지", &notice)
+        .unwrap();
+    writer
+        .add_paragraph_with_style(
+            "음영만",
+            &HwpxParagraphStyle::new().border(ParagraphBorder::shading(0xFFF2CC)),
+        )
+        .unwrap();
+    writer.add_paragraph("일반 문단").unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+    assert_well_formed_package(&bytes);
+
+    let header = zip_entry_text(&bytes, "Contents/header.xml");
+    // Fixed fills 1-3, the text box's 4, then one per distinct paragraph border
+    assert!(header.contains(r#"<hh:borderFills itemCnt="6">"#));
+
+    let fill = |id: u32| {
+        let start = header
+            .find(&format!(r#"<hh:borderFill id="{}""#, id))
+            .unwrap();
+        &header[start..start + header[start..].find("</hh:borderFill>").unwrap()]
+    };
+    let boxed = fill(5);
+    assert!(boxed.contains(r##"<hh:leftBorder type="DASH" width="0.12 mm" color="#808080"/>"##));
+    assert!(boxed.contains(r##"<hc:winBrush faceColor="#EEEEEE""##));
+    let shaded = fill(6);
+    assert!(shaded.contains(r#"<hh:topBorder type="NONE""#));
+    assert!(shaded.contains(r##"<hc:winBrush faceColor="#FFF2CC""##));
+
+    assert!(header.contains(
+        r#"<hh:border borderFillIDRef="5" offsetLeft="567" offsetRight="567" offsetTop="283" offsetBottom="283""#
+    ));
+    assert!(header.contains(r#"<hh:border borderFillIDRef="6" offsetLeft="0""#));
+
+    let document = HwpxReader::from_bytes(&bytes).unwrap();
+    let paragraphs = &document.body_texts[0].sections[0].paragraphs;
+    assert_eq!(paragraphs[1].para_shape_id, paragraphs[2].para_shape_id);
+    assert_ne!(paragraphs[1].para_shape_id, paragraphs[3].para_shape_id);
+    assert_eq!(paragraphs[4].para_shape_id, 0);
+
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    assert!(section.contains(r#"paraPrIDRef="1""#));
+    assert!(section.contains(r#"paraPrIDRef="2""#));
+}
+
+#[test]
+fn test_hwpx_keep_with_next_and_page_break_before() {
+    use hwpers::hwpx::{HwpxAlignment, HwpxParagraphStyle};
+
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("머리말 문단").unwrap();
+    writer
+        .add_paragraph_with_style("1. 개요", &HwpxParagraphStyle::new().keep_with_next())
+        .unwrap();
+    writer.add_paragraph("개요 본문").unwrap();
+    writer
+        .add_paragraph_with_style(
+            "2. 세부 내용",
+            &HwpxParagraphStyle::new()
+                .alignment(HwpxAlignment::Left)
+                .keep_with_next()
+                .keep_lines()
+                .page_break_before(),
+        )
+        .unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+    assert_well_formed_package(&bytes);
+
+    let header = zip_entry_text(&bytes, "Contents/header.xml");
+    let para_pr = |id: u32| {
+        let start = header.find(&format!(r#"<hh:paraPr id="{}""#, id)).unwrap();
+        &header[start..start + header[start..].find("</hh:paraPr>").unwrap()]
+    };
+    assert!(para_pr(0).contains(r#"keepWithNext="0" keepLines="0" pageBreakBefore="0""#));
+    assert!(para_pr(1).contains(r#"keepWithNext="1" keepLines="0" pageBreakBefore="0""#));
+    assert!(para_pr(2).contains(r#"keepWithNext="1" keepLines="1" pageBreakBefore="1""#));
+    assert!(para_pr(2).contains(r#"<hh:align horizontal="LEFT""#));
+
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    assert_eq!(section.matches(r#"paraPrIDRef="0""#).count(), 2);
+    assert_eq!(section.matches(r#"paraPrIDRef="1""#).count(), 1);
+    assert_eq!(section.matches(r#"paraPrIDRef="2""#).count(), 1);
+}
+
+#[test]
+fn test_hwpx_right_to_left_run_in_korean_text() {
+    use hwpers::hwpx::RunDirection;
+
+    let mut writer = HwpxWriter::new();
+    writer
+        .add_mixed_styled_paragraph(vec![
+            StyledText::new("가나다"),
+            StyledText::with_style(" مرحبا ", HwpxTextStyle::new().bold())
+                .direction(RunDirection::RightToLeft),
+            StyledText::new("라마"),
+        ])
+        .unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+    assert_well_formed_package(&bytes);
+
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    assert!(section.contains("<hp:t>가나다</hp:t>"));
+    assert!(section.contains("<hp:t>\u{2067} مرحبا \u{2069}</hp:t>"));
+    assert!(section.contains("<hp:t>라마</hp:t>"));
+
+    // The isolates are not part of the text, so positions survive the round trip
+    let document = HwpxReader::from_bytes(&bytes).unwrap();
+    let paragraph = &document.body_texts[0].sections[0].paragraphs[0];
+    assert_eq!(
+        paragraph.text.as_ref().unwrap().content,
+        "가나다 مرحبا 라마"
+    );
+    let positions: Vec<u32> = paragraph
+        .char_shapes
+        .as_ref()
+        .unwrap()
+        .char_positions
+        .iter()
+        .map(|p| p.position)
+        .collect();
+    // The leading charPr 0 run needs no entry
+    assert_eq!(positions, [3, 10]);
+}
+
+#[test]
+fn test_hwpx_run_boundaries_with_combining_marks_and_emoji() {
+    let mut writer = HwpxWriter::new();
+    writer
+        .add_mixed_styled_paragraph(vec![
+            StyledText::new("cafe\u{301}"),
+            StyledText::with_style("😀👍", HwpxTextStyle::new().bold()),
+            StyledText::with_style("ㅎㅏㄴ", HwpxTextStyle::new().italic()),
+            StyledText::new("끝"),
+        ])
+        .unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    assert!(section.contains("<hp:t>cafe\u{301}</hp:t>"));
+    assert!(section.contains("<hp:t>😀👍</hp:t>"));
+    assert!(section.contains("<hp:t>ㅎㅏㄴ</hp:t>"));
+
+    let document = HwpxReader::from_bytes(&bytes).unwrap();
+    let paragraph = &document.body_texts[0].sections[0].paragraphs[0];
+    let positions: Vec<u32> = paragraph
+        .char_shapes
+        .as_ref()
+        .unwrap()
+        .char_positions
+        .iter()
+        .map(|p| p.position)
+        .collect();
+    // Each emoji is a surrogate pair: two UTF-16 units
+    assert_eq!(positions, [5, 9, 12]);
+}
+
+#[test]
+fn test_hwpx_run_boundaries_after_zwj_sequence_and_link() {
+    // U+1F468 U+200D U+1F469 U+200D U+1F467: 8 UTF-16 units, one grapheme
+    let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+    let mut writer = HwpxWriter::new();
+    writer
+        .add_mixed_styled_paragraph(vec![
+            StyledText::new(family),
+            StyledText::new("𝄞링크").link("https://example.com"),
+            StyledText::with_style("🇰🇷끝", HwpxTextStyle::new().italic()),
+        ])
+        .unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+    assert_well_formed_package(&bytes);
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    assert!(section.contains(&format!("<hp:t>{}</hp:t>", family)));
+    // The link covers its whole run, including the surrogate pair
+    assert!(section.contains("<hp:t>𝄞링크</hp:t>"));
+    assert_eq!(section.matches("<hp:hyperlink ").count(), 1);
+    assert!(section.contains("<hp:t>🇰🇷끝</hp:t>"));
+
+    let document = HwpxReader::from_bytes(&bytes).unwrap();
+    let paragraph = &document.body_texts[0].sections[0].paragraphs[0];
+    let positions: Vec<u32> = paragraph
+        .char_shapes
+        .as_ref()
+        .unwrap()
+        .char_positions
+        .iter()
+        .map(|p| p.position)
+        .collect();
+    assert_eq!(positions, [8, 12]);
+}
+
+#[test]
+fn test_hwpx_table_mut_changes_table_added_earlier() {
+    use hwpers::hwpx::TableHandle;
+
+    let mut writer = HwpxWriter::new();
+    let first: TableHandle = writer
+        .add_table(HwpxTable::from_data(vec![
+            vec!["이름", "값"],
+            vec!["a", "1"],
+        ]))
+        .unwrap();
+    let second = writer
+        .add_table(HwpxTable::from_data(vec![vec!["b"]]))
+        .unwrap();
+    assert_ne!(first, second);
+    writer.add_paragraph("표 뒤 본문").unwrap();
+
+    let table = writer.table_mut(first).unwrap();
+    table.set_cell(1, 1, "42");
+    table.caption = Some("나중에 붙인 캡션".to_string());
+
+    let bytes = writer.to_bytes().unwrap();
+    assert_well_formed_package(&bytes);
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    let tables: Vec<&str> = section
+        .split("<hp:tbl ")
+        .skip(1)
+        .map(|t| &t[..t.find("</hp:tbl>").unwrap()])
+        .collect();
+    assert!(tables[0].contains("<hp:t>42</hp:t>"));
+    assert!(!tables[0].contains("<hp:t>1</hp:t>"));
+    assert!(tables[0].contains("<hp:t>표 1. 나중에 붙인 캡션</hp:t>"));
+    assert!(!tables[1].contains("<hp:caption"));
+
+    // The late caption is centered like one passed to add_table
+    let header = zip_entry_text(&bytes, "Contents/header.xml");
+    assert!(tables[0].contains(r#"<hp:p id="0" paraPrIDRef="1""#));
+    assert!(header.contains(r#"<hh:align horizontal="CENTER""#));
+}
+
+#[test]
+fn test_hwpx_table_mut_without_caption_keeps_para_properties() {
+    let para_pr_count = |writer: &HwpxWriter| {
+        let header = zip_entry_text(&writer.to_bytes().unwrap(), "Contents/header.xml");
+        header.matches("<hh:paraPr ").count()
+    };
+
+    let mut writer = HwpxWriter::new();
+    let handle = writer
+        .add_table(HwpxTable::from_data(vec![vec!["a", "1"]]))
+        .unwrap();
+    writer.add_paragraph("표 뒤 본문").unwrap();
+    let before = para_pr_count(&writer);
+
+    writer.table_mut(handle).unwrap().set_cell(0, 1, "2");
+    assert_eq!(para_pr_count(&writer), before);
+
+    // Setting a caption adds the centered caption style
+    writer.table_mut(handle).unwrap().caption = Some("캡션".to_string());
+    assert_eq!(para_pr_count(&writer), before + 1);
+}
+
+#[test]
+fn test_hwpx_image_mut_resizes_placed_picture() {
+    let mut resized = HwpxWriter::new();
+    let handle = resized
+        .add_image(HwpxImage::from_bytes(tiny_png()).unwrap())
+        .unwrap();
+    resized.add_paragraph("그림 뒤").unwrap();
+    let image = resized.image_mut(handle).unwrap();
+    image.width_mm = Some(40);
+    image.height_mm = Some(25);
+
+    let mut direct = HwpxWriter::new();
+    direct
+        .add_image(HwpxImage::from_bytes(tiny_png()).unwrap().with_size(40, 25))
+        .unwrap();
+    direct.add_paragraph("그림 뒤").unwrap();
+
+    let resized_section = zip_entry_text(&resized.to_bytes().unwrap(), "Contents/section0.xml");
+    let direct_section = zip_entry_text(&direct.to_bytes().unwrap(), "Contents/section0.xml");
+    assert_eq!(resized_section, direct_section);
+}
+
+#[test]
+fn test_hwpx_repeated_images_share_bin_data_items() {
+    let mut pngs: Vec<Vec<u8>> = (0..3u8)
+        .map(|n| {
+            let mut png = tiny_png();
+            png.push(n);
+            png
+        })
+        .collect();
+    let mut writer = HwpxWriter::new();
+    for png in pngs.iter().cycle().take(9) {
+        writer
+            .add_image(HwpxImage::from_bytes(png.clone()).unwrap())
+            .unwrap();
+    }
+    // Bytes replaced after adding get their own item
+    pngs.push(tiny_png());
+    let handle = writer
+        .add_image(HwpxImage::from_bytes(pngs[0].clone()).unwrap())
+        .unwrap();
+    writer.image_mut(handle).unwrap().data = std::sync::Arc::new(pngs[3].clone());
+
+    let bytes = writer.to_bytes().unwrap();
+    let names = zip_entry_names(&bytes);
+    let bin_data: Vec<_> = names
+        .iter()
+        .filter(|n| n.starts_with("BinData/image"))
+        .collect();
+    assert_eq!(bin_data.len(), 4);
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    let refs: Vec<&str> = section
+        .split(r#"binaryItemIDRef=""#)
+        .skip(1)
+        .map(|rest| &rest[..rest.find('"').unwrap()])
+        .collect();
+    assert_eq!(
+        refs,
+        [
+            "image1", "image2", "image3", "image1", "image2", "image3", "image1", "image2",
+            "image3", "image4"
+        ]
+    );
+}
+
+#[test]
+fn test_hwpx_extract_tables_round_trip_with_merges() {
+    let mut table = HwpxTable::from_data(vec![
+        vec!["구분", "", "비고"],
+        vec!["1분기", "120", "증가"],
+        vec!["", "95", ""],
+    ]);
+    table.set_cell_span(0, 0, 2, 1);
+    table.set_cell_span(1, 0, 1, 2);
+    table.set_cell_span(1, 2, 1, 2);
+    let expected_rows = table.rows.clone();
+    let expected_spans = table.cell_spans.clone();
+
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("표 앞").unwrap();
+    writer.add_table(table).unwrap();
+    writer
+        .add_table(HwpxTable::from_data(vec![vec!["한 줄\n두 줄"]]))
+        .unwrap();
+    let bytes = writer.to_bytes().unwrap();
+
+    let tables = HwpxReader::extract_tables(&bytes).unwrap();
+    assert_eq!(tables.len(), 2);
+
+    let merged = &tables[0];
+    assert_eq!(merged.paragraph_index, 1);
+    assert_eq!((merged.row_count, merged.col_count), (3, 3));
+    assert_eq!(merged.grid(), expected_rows);
+    // Covered cells are not written, so only origins come back
+    assert_eq!(
+        merged.rows.iter().map(Vec::len).collect::<Vec<_>>(),
+        [2, 3, 1]
+    );
+    for cell in merged.rows.iter().flatten() {
+        let expected = expected_spans
+            .get(&(cell.row, cell.col))
+            .copied()
+            .unwrap_or_default();
+        assert_eq!(cell.span, expected, "span of ({}, {})", cell.row, cell.col);
+    }
+    assert_eq!(merged.cell(2, 0).unwrap().text, "1분기");
+    assert_eq!(merged.cell(0, 1).unwrap().text, "구분");
+    assert_eq!(merged.cell(2, 1).unwrap().text, "95");
+    assert!(merged.cell(3, 0).is_none());
+
+    assert_eq!(tables[1].paragraph_index, 2);
+    assert_eq!(tables[1].grid(), [["한 줄\n두 줄"]]);
+}
+
+#[test]
+fn test_hwpx_extract_tables_caps_columns_at_col_cnt() {
+    let mut writer = HwpxWriter::new();
+    writer
+        .add_table(HwpxTable::from_data(vec![vec!["A", "B"], vec!["C", "D"]]))
+        .unwrap();
+    let bytes = writer.to_bytes().unwrap();
+    // A damaged file: the last cell claims a far-away address and a huge span
+    let damaged = rebuild_package(&bytes, |name, data| {
+        if name != "Contents/section0.xml" {
+            return Some((name.to_string(), data));
+        }
+        let xml = String::from_utf8(data).unwrap();
+        let xml = xml
+            .replace(
+                r#"<hp:cellAddr colAddr="1" rowAddr="1"/>"#,
+                r#"<hp:cellAddr colAddr="4000000000" rowAddr="1"/>"#,
+            )
+            .replace(
+                r#"<hp:cellSpan colSpan="1" rowSpan="1"/>"#,
+                r#"<hp:cellSpan colSpan="4294967295" rowSpan="1"/>"#,
+            );
+        Some((name.to_string(), xml.into_bytes()))
+    });
+
+    let tables = HwpxReader::extract_tables(&damaged).unwrap();
+    assert_eq!((tables[0].row_count, tables[0].col_count), (2, 2));
+    assert_eq!(tables[0].grid(), [["A", "B"], ["C", ""]]);
+}
+
+#[test]
+fn test_hwpx_multiline_cell_writes_paragraph_per_line() {
+    let mut writer = HwpxWriter::new();
+    writer
+        .add_table(HwpxTable::from_data(vec![vec!["서울시\n강남구", "한 줄"]]))
+        .unwrap();
+    assert_eq!(writer.extract_text(true), "서울시\n강남구\n한 줄\n");
+    let bytes = writer.to_bytes().unwrap();
+
+    let section = zip_entry_text(&bytes, "Contents/section0.xml");
+    let first_cell = section
+        .split("<hp:tc ")
+        .nth(1)
+        .and_then(|cell| cell.split("</hp:subList>").next())
+        .unwrap();
+    assert_eq!(first_cell.matches("<hp:p ").count(), 2);
+    assert!(first_cell.contains("<hp:t>서울시</hp:t>"));
+    assert!(first_cell.contains("<hp:t>강남구</hp:t>"));
+
+    let options = hwpers::hwpx::ExtractOptions {
+        include_tables: true,
+        ..Default::default()
+    };
+    let text = HwpxReader::extract_text_with_options(&bytes, &options).unwrap();
+    assert_eq!(text, writer.extract_text(true));
+    let tables = HwpxReader::extract_tables(&bytes).unwrap();
+    assert_eq!(tables[0].grid(), [["서울시\n강남구", "한 줄"]]);
+}
+
+#[test]
+fn test_hwpx_cell_links() {
+    use hwpers::hwpx::CellLink;
+
+    let mut table = HwpxTable::from_data(vec![vec!["가 링크\n둘째 줄", "그대로"]]);
+    let link = |range, url: &str| CellLink {
+        range,
+        url: url.to_string(),
+    };
+    // A link across the line break is split per paragraph; one that is not on
+    // character boundaries is written as plain text
+    table.set_cell_links(0, 0, vec![link(4..17, "https://example.com/a")]);
+    table.set_cell_links(0, 1, vec![link(1..4, "https://example.com/b")]);
+
+    let mut writer = HwpxWriter::new();
+    writer.add_table(table).unwrap();
+    let bytes = writer.to_bytes().unwrap();
+
+    let links = HwpxReader::extract_hyperlinks(&bytes).unwrap();
+    let links: Vec<(&str, &str)> = links
+        .iter()
+        .map(|l| (l.text.as_str(), l.url.as_str()))
+        .collect();
+    assert_eq!(
+        links,
+        [
+            ("링크", "https://example.com/a"),
+            ("둘째", "https://example.com/a")
+        ]
+    );
+    let tables = HwpxReader::extract_tables(&bytes).unwrap();
+    assert_eq!(tables[0].grid(), [["가 링크\n둘째 줄", "그대로"]]);
+}
+
+#[test]
+fn test_hwpx_extract_images_round_trip() {
+    use hwpers::hwpx::HwpxImageFormat;
+
+    let png = synthetic_png(32, 7);
+    let jpeg = std::fs::read("examples/jsontohwpx/test_img.jpg").unwrap();
+
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("그림 앞").unwrap();
+    writer
+        .add_image(
+            HwpxImage::from_bytes(png.clone())
+                .unwrap()
+                .with_size(40, 30),
+        )
+        .unwrap();
+    writer
+        .add_image(HwpxImage::from_bytes(jpeg.clone()).unwrap())
+        .unwrap();
+    let bytes = writer.to_bytes().unwrap();
+
+    let images = HwpxReader::extract_images(&bytes).unwrap();
+    assert_eq!(images.len(), 2);
+
+    assert_eq!(images[0].paragraph_index, 1);
+    assert_eq!(images[0].format, HwpxImageFormat::Png);
+    assert_eq!(images[0].source_name, "BinData/image1.png");
+    assert_eq!(*images[0].data, png);
+    assert_eq!(
+        (images[0].width_mm, images[0].height_mm),
+        (Some(40), Some(30))
+    );
+
+    assert_eq!(images[1].paragraph_index, 2);
+    assert_eq!(images[1].format, HwpxImageFormat::Jpeg);
+    assert_eq!(images[1].source_name, "BinData/image2.jpg");
+    assert_eq!(*images[1].data, jpeg);
+    assert!(images[1].width_mm.unwrap() > 0);
+    assert!(images[1].height_mm.unwrap() > 0);
+}
+
+#[test]
+fn test_hwpx_extract_hyperlinks_round_trip() {
+    let mut writer = HwpxWriter::new();
+    writer
+        .add_hyperlink("홈페이지", "https://example.com")
+        .unwrap();
+    writer.add_paragraph("링크 없는 문단").unwrap();
+    writer
+        .add_mixed_styled_paragraph(vec![
+            StyledText::new("자세한 내용은 "),
+            StyledText::with_style("공지 & 안내", HwpxTextStyle::new().bold())
+                .link("https://example.com/notice?a=1&b=2"),
+            StyledText::new("를 참고하세요."),
+        ])
+        .unwrap();
+    let bytes = writer.to_bytes().unwrap();
+
+    let links = HwpxReader::extract_hyperlinks(&bytes).unwrap();
+    assert_eq!(links.len(), 2);
+    assert_eq!(links[0].paragraph_index, 0);
+    assert_eq!(links[0].text, "홈페이지");
+    assert_eq!(links[0].url, "https://example.com");
+    assert_eq!(links[1].paragraph_index, 2);
+    assert_eq!(links[1].text, "공지 & 안내");
+    assert_eq!(links[1].url, "https://example.com/notice?a=1&b=2");
+}
+
+#[test]
+fn test_hwpx_extract_headers_and_footers() {
+    use hwpers::hwpx::{HeaderFooterApplyTo, HwpxFooter, HwpxHeader, PageNumberFormat};
+
+    let mut writer = HwpxWriter::new();
+    writer.add_header_config(HwpxHeader::for_odd_pages("홀수 머리글"));
+    writer.add_header_config(HwpxHeader::for_even_pages("짝수 머리글"));
+    writer.add_footer_config(
+        HwpxFooter::new("- ")
+            .with_page_number_format(PageNumberFormat::Numeric)
+            .for_odd_pages(),
+    );
+    writer.add_footer("작성: 개발팀");
+    writer.add_paragraph("본문").unwrap();
+    let bytes = writer.to_bytes().unwrap();
+
+    let headers = HwpxReader::extract_headers(&bytes).unwrap();
+    let summary: Vec<_> = headers
+        .iter()
+        .map(|h| {
+            (
+                h.section_index,
+                h.apply_to,
+                h.text.as_str(),
+                h.has_page_number,
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (0, HeaderFooterApplyTo::Odd, "홀수 머리글", false),
+            (0, HeaderFooterApplyTo::Even, "짝수 머리글", false),
+        ]
+    );
+
+    let footers = HwpxReader::extract_footers(&bytes).unwrap();
+    assert_eq!(footers.len(), 2);
+    assert_eq!(footers[0].apply_to, HeaderFooterApplyTo::Odd);
+    assert_eq!(footers[0].text, "-");
+    assert!(footers[0].has_page_number);
+    assert_eq!(footers[1].apply_to, HeaderFooterApplyTo::All);
+    assert_eq!(footers[1].text, "작성: 개발팀");
+    assert!(!footers[1].has_page_number);
+
+    // A document without them has none
+    let mut plain = HwpxWriter::new();
+    plain.add_paragraph("본문").unwrap();
+    let plain = plain.to_bytes().unwrap();
+    assert!(HwpxReader::extract_headers(&plain).unwrap().is_empty());
+    assert!(HwpxReader::extract_footers(&plain).unwrap().is_empty());
+}
+
+#[test]
+fn test_hwpx_extract_styled_paragraphs() {
+    use hwpers::hwpx::ExtractedRun;
+
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("첫 문단").unwrap();
+    writer
+        .add_mixed_styled_paragraph(vec![
+            StyledText::new("일반,"),
+            StyledText::with_style(
+                "굵은 빨강",
+                HwpxTextStyle::new().bold().color(0xFF0000).size(14),
+            ),
+            StyledText::with_style("기울임😀", HwpxTextStyle::new().italic().underline()),
+            StyledText::new("(끝)"),
+        ])
+        .unwrap();
+    let bytes = writer.to_bytes().unwrap();
+
+    let paragraphs = HwpxReader::extract_styled_paragraphs(&bytes).unwrap();
+    assert_eq!(paragraphs.len(), 2);
+    assert_eq!(paragraphs[0].len(), 1);
+    assert_eq!(paragraphs[0][0].text, "첫 문단");
+
+    let plain = |text: &str| ExtractedRun {
+        text: text.to_string(),
+        bold: false,
+        italic: false,
+        underline: false,
+        size_pt: 10.0,
+        color: 0,
+    };
+    assert_eq!(
+        paragraphs[1],
+        [
+            plain("일반,"),
+            ExtractedRun {
+                text: "굵은 빨강".to_string(),
+                bold: true,
+                size_pt: 14.0,
+                color: 0xFF0000,
+                ..plain("")
+            },
+            ExtractedRun {
+                text: "기울임😀".to_string(),
+                italic: true,
+                underline: true,
+                ..plain("")
+            },
+            plain("(끝)"),
+        ]
+    );
+}
+
+#[test]
+fn test_hwpx_extract_text_with_options() {
+    use hwpers::hwpx::{ExtractOptions, HwpxFooter};
+
+    let mut writer = HwpxWriter::new();
+    writer.add_header("머리글");
+    writer.add_footer_config(HwpxFooter::new("바닥글").with_page_number());
+    writer.add_paragraph("표 앞").unwrap();
+    writer
+        .add_table(HwpxTable::from_data(vec![
+            vec!["부서", "인원"],
+            vec!["개발", "12"],
+        ]))
+        .unwrap();
+    writer.add_paragraph("표 뒤").unwrap();
+    let bytes = writer.to_bytes().unwrap();
+
+    // The defaults reproduce extract_text
+    let default_text =
+        HwpxReader::extract_text_with_options(&bytes, &ExtractOptions::default()).unwrap();
+    let document = HwpxReader::from_bytes(&bytes).unwrap();
+    assert_eq!(default_text, document.extract_text());
+    assert!(!default_text.contains("부서"));
+    assert!(!default_text.contains("머리글"));
+
+    let with_tables = HwpxReader::extract_text_with_options(
+        &bytes,
+        &ExtractOptions {
+            include_tables: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(with_tables, "표 앞\n부서\n인원\n개발\n12\n표 뒤\n");
+
+    let everything = HwpxReader::extract_text_with_options(
+        &bytes,
+        &ExtractOptions {
+            include_tables: true,
+            include_headers_footers: true,
+            paragraph_separator: " | ".to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        everything,
+        "머리글 | 표 앞 | 부서 | 인원 | 개발 | 12 | 표 뒤 | 바닥글 | "
+    );
+}
+
+#[test]
+fn test_hwpx_from_reader_matches_from_bytes() {
+    let mut writer = HwpxWriter::new();
+    for i in 0..2000 {
+        writer
+            .add_paragraph(&format!("{}번째 문단: 스트리밍 읽기 확인용 본문", i))
+            .unwrap();
+        if i % 500 == 0 {
+            let image = HwpxImage::from_bytes(synthetic_png(1024 * 1024, i as u8)).unwrap();
+            writer.add_image(image.with_size(40, 30)).unwrap();
+        }
+    }
+    let (_temp_dir, path) = create_temp_file("large.hwpx");
+    writer.save_to_file(&path).unwrap();
+    let bytes = std::fs::read(&path).unwrap();
+    assert!(bytes.len() > 2 * 1024 * 1024, "only {} bytes", bytes.len());
+
+    let from_bytes = HwpxReader::from_bytes(&bytes).unwrap();
+    let file = std::fs::File::open(&path).unwrap();
+    let from_reader = HwpxReader::from_reader(std::io::BufReader::new(file)).unwrap();
+    let from_file = HwpxReader::from_file(&path).unwrap();
+
+    let text = from_bytes.extract_text();
+    assert!(text.contains("1999번째 문단"));
+    assert_eq!(from_reader.extract_text(), text);
+    assert_eq!(from_file.extract_text(), text);
+}
+
+/// Rebuild a package entry by entry; `edit` may rename, change or drop (None) each
+fn rebuild_package(
+    bytes: &[u8],
+    mut edit: impl FnMut(&str, Vec<u8>) -> Option<(String, Vec<u8>)>,
+) -> Vec<u8> {
+    use std::io::{Read, Write};
+
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+    let mut out = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i).unwrap();
+        if file.is_dir() {
+            continue;
+        }
+        let mut data = Vec::new();
+        file.read_to_end(&mut data).unwrap();
+        if let Some((name, data)) = edit(file.name(), data) {
+            out.start_file(name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            out.write_all(&data).unwrap();
+        }
+    }
+    out.finish().unwrap().into_inner()
+}
+
+#[test]
+fn test_hwpx_lenient_reading_of_broken_packages() {
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("첫 구역").unwrap();
+    writer.add_section().unwrap();
+    writer.add_paragraph("둘째 구역").unwrap();
+    let bytes = writer.to_bytes().unwrap();
+    let strict = HwpxReader::from_bytes(&bytes).unwrap();
+    assert!(strict.warnings().is_empty());
+    assert!(HwpxReader::from_bytes_lenient(&bytes)
+        .unwrap()
+        .warnings()
+        .is_empty());
+
+    // No version.xml and a wrong mimetype
+    let broken = rebuild_package(&bytes, |name, data| match name {
+        "version.xml" => None,
+        "mimetype" => Some((name.to_string(), b"application/zip".to_vec())),
+        _ => Some((name.to_string(), data)),
+    });
+    assert!(HwpxReader::from_bytes(&broken).is_err());
+    let document = HwpxReader::from_bytes_lenient(&broken).unwrap();
+    assert_eq!(document.extract_text(), "첫 구역\n둘째 구역\n");
+    assert_eq!(document.warnings().len(), 2);
+    assert!(document.warnings()[0].contains("application/zip"));
+    assert!(document.warnings()[1].contains("version.xml"));
+
+    // Sections numbered with a gap, listed in reverse, one of them unreadable
+    let mut entries = Vec::new();
+    rebuild_package(&bytes, |name, data| {
+        entries.push((name.to_string(), data));
+        None
+    });
+    let mut out = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    for (name, data) in entries.iter().rev() {
+        let name = name.replace("section1.xml", "section5.xml");
+        out.start_file(name, SimpleFileOptions::default()).unwrap();
+        out.write_all(data).unwrap();
+    }
+    out.start_file("Contents/section3.xml", SimpleFileOptions::default())
+        .unwrap();
+    out.write_all(b"<hs:sec><unclosed>").unwrap();
+    let shuffled = out.finish().unwrap().into_inner();
+
+    assert_eq!(
+        HwpxReader::from_bytes(&shuffled).unwrap().extract_text(),
+        "첫 구역\n"
+    );
+    let document = HwpxReader::from_bytes_lenient(&shuffled).unwrap();
+    assert_eq!(document.extract_text(), "첫 구역\n둘째 구역\n");
+    let warnings = document.warnings().join("\n");
+    assert!(warnings.contains("mimetype is not the first entry"));
+    assert!(warnings.contains("skipped Contents/section3.xml"));
+
+    // Without any readable section even lenient reading fails
+    let no_sections = rebuild_package(&bytes, |name, data| {
+        (!name.starts_with("Contents/section")).then(|| (name.to_string(), data))
+    });
+    assert!(HwpxReader::from_bytes_lenient(&no_sections).is_err());
+}
+
+#[test]
+fn test_hwpx_encrypted_package_is_reported() {
+    use hwpers::HwpError;
+
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("기밀").unwrap();
+    let bytes = writer.to_bytes().unwrap();
+
+    // Set the ZIP encryption flag on the central directory record of section0.xml
+    let mut encrypted = bytes.clone();
+    let name = b"Contents/section0.xml";
+    let record = (0..encrypted.len() - 46)
+        .find(|&i| {
+            encrypted[i..i + 4] == [0x50, 0x4B, 0x01, 0x02] && encrypted[i + 46..].starts_with(name)
+        })
+        .expect("central directory record");
+    encrypted[record + 8] |= 0x01;
+
+    assert!(matches!(
+        HwpxReader::from_bytes(&encrypted),
+        Err(HwpError::Encrypted(_))
+    ));
+    assert!(matches!(
+        HwpxReader::extract_tables(&encrypted),
+        Err(HwpError::Encrypted(_))
+    ));
+
+    // Hangul's password protection keeps the ZIP entries readable and declares
+    // the encryption in the manifest
+    let protected = rebuild_package(&bytes, |name, data| {
+        if name == "META-INF/manifest.xml" {
+            let xml = String::from_utf8(data).unwrap().replace(
+                "</odf:manifest>",
+                r#"<odf:file-entry odf:full-path="Contents/section0.xml" odf:media-type="application/xml"><odf:encryption-data odf:checksum-type="SHA1"/></odf:file-entry></odf:manifest>"#,
+            );
+            Some((name.to_string(), xml.into_bytes()))
+        } else {
+            Some((name.to_string(), data))
+        }
+    });
+    assert!(matches!(
+        HwpxReader::from_bytes(&protected),
+        Err(HwpError::Encrypted(_))
+    ));
+
+    let err = hwpers::jsontohwpx::hwpxtojson::convert(&encrypted).unwrap_err();
+    assert_eq!(err.error_code(), "DOCUMENT_ENCRYPTED");
+    assert_eq!(err.exit_code(), 1);
+
+    HwpxReader::from_bytes(&bytes).unwrap();
+}
+
+#[test]
+fn test_hwpx_document_statistics() {
+    use hwpers::hwpx::DocumentStatistics;
+
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("안녕하세요 세계").unwrap();
+    writer
+        .add_table(HwpxTable::from_data(vec![
+            vec!["가", "나"],
+            vec!["다 라", "마"],
+        ]))
+        .unwrap();
+    writer.add_paragraph("한글 문서 통계").unwrap();
+    writer
+        .add_image(HwpxImage::from_bytes(tiny_png()).unwrap())
+        .unwrap();
+    writer.add_paragraph("끝").unwrap();
+    let bytes = writer.to_bytes().unwrap();
+
+    // Characters, not UTF-8 bytes: 17 in the body and 6 in the cells, four of
+    // them spaces
+    assert_eq!(
+        HwpxReader::statistics(&bytes).unwrap(),
+        DocumentStatistics {
+            section_count: 1,
+            paragraph_count: 3,
+            char_count: 23,
+            char_count_no_spaces: 19,
+            word_count: 11,
+            table_count: 1,
+            image_count: 1,
+        }
+    );
+}
+
+#[test]
+fn test_hwpx_extract_footnotes_and_endnotes() {
+    let note = |kind: &str, paragraphs: &[&str]| {
+        let body: String = paragraphs
+            .iter()
+            .map(|t| {
+                format!(
+                    r#"<hp:p id="0" paraPrIDRef="0" styleIDRef="0"><hp:run charPrIDRef="0"><hp:t>{}</hp:t></hp:run></hp:p>"#,
+                    t
+                )
+            })
+            .collect();
+        format!(
+            r#"<hp:run charPrIDRef="0"><hp:ctrl><hp:{kind} number="1" instId="1"><hp:subList textDirection="HORIZONTAL">{body}</hp:subList></hp:{kind}></hp:ctrl></hp:run>"#
+        )
+    };
+    let text_run = |t: &str| format!(r#"<hp:run charPrIDRef="0"><hp:t>{}</hp:t></hp:run>"#, t);
+    let paragraph = |runs: String| {
+        format!(
+            r#"<hp:p id="0" paraPrIDRef="0" styleIDRef="0" pageBreak="0" columnBreak="0" merged="0">{}</hp:p>"#,
+            runs
+        )
+    };
+    let section = [
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes" ?>"#.to_string(),
+        r#"<hs:sec xmlns:hs="http://www.hancom.co.kr/hwpml/2011/section" xmlns:hp="http://www.hancom.co.kr/hwpml/2011/paragraph">"#.to_string(),
+        paragraph(text_run("첫 문단") + &note("footNote", &["첫 각주"]) + &text_run("이어지는 글")),
+        paragraph(text_run("둘째 문단")),
+        paragraph(text_run("한국어😀") + &note("footNote", &["둘째 각주 첫 줄", "둘째 줄"])),
+        paragraph(note("endNote", &["미주"]) + &text_run("끝")),
+        "</hs:sec>".to_string(),
+    ]
+    .concat();
+
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("자리").unwrap();
+    let bytes = rebuild_package(&writer.to_bytes().unwrap(), |name, data| {
+        if name == "Contents/section0.xml" {
+            Some((name.to_string(), section.clone().into_bytes()))
+        } else {
+            Some((name.to_string(), data))
+        }
+    });
+
+    let footnotes = HwpxReader::extract_footnotes(&bytes).unwrap();
+    assert_eq!(footnotes.len(), 2);
+    assert_eq!(footnotes[0].paragraph_index, 0);
+    assert_eq!(footnotes[0].anchor_offset, 4);
+    assert_eq!(footnotes[0].text, "첫 각주");
+    assert_eq!(footnotes[1].paragraph_index, 2);
+    // UTF-16 units: the emoji counts twice
+    assert_eq!(footnotes[1].anchor_offset, 5);
+    assert_eq!(footnotes[1].text, "둘째 각주 첫 줄\n둘째 줄");
+
+    let endnotes = HwpxReader::extract_endnotes(&bytes).unwrap();
+    assert_eq!(endnotes.len(), 1);
+    assert_eq!(endnotes[0].paragraph_index, 3);
+    assert_eq!(endnotes[0].anchor_offset, 0);
+    assert_eq!(endnotes[0].text, "미주");
+
+    // Note text stays out of the body text
+    let text = HwpxReader::from_bytes(&bytes).unwrap().extract_text();
+    assert!(text.contains("첫 문단이어지는 글"));
+    assert!(!text.contains("각주"));
+}
+
+#[test]
+fn test_hwpx_preview_text_reads_only_what_it_needs() {
+    let mut writer = HwpxWriter::new();
+    for i in 0..5000 {
+        writer.add_paragraph(&format!("문단 {}", i)).unwrap();
+    }
+    let bytes = writer.to_bytes().unwrap();
+    let preview = HwpxReader::preview_text(&bytes).unwrap();
+    assert!(preview.starts_with("문단 0\n문단 1\n"));
+
+    // With the preview entry present no section is parsed at all
+    let broken_section = rebuild_package(&bytes, |name, data| {
+        if name == "Contents/section0.xml" {
+            Some((name.to_string(), b"<not xml".to_vec()))
+        } else {
+            Some((name.to_string(), data))
+        }
+    });
+    assert!(HwpxReader::from_bytes(&broken_section).is_err());
+    assert_eq!(HwpxReader::preview_text(&broken_section).unwrap(), preview);
+
+    // Without it only the start of section0.xml is read: a section cut off in
+    // the middle still yields the leading paragraphs
+    let truncated = rebuild_package(&bytes, |name, mut data| match name {
+        "Preview/PrvText.txt" => None,
+        "Contents/section0.xml" => {
+            data.truncate(data.len() / 2);
+            Some((name.to_string(), data))
+        }
+        _ => Some((name.to_string(), data)),
+    });
+    assert!(HwpxReader::from_bytes(&truncated).is_err());
+    let fallback = HwpxReader::preview_text(&truncated).unwrap();
+    let expected: Vec<String> = (0..20).map(|i| format!("문단 {}", i)).collect();
+    assert_eq!(fallback, expected.join("\n"));
+}
+
+#[test]
+fn test_hwpx_validate_reports_targeted_corruption() {
+    use hwpers::hwpx::PackageIssueKind;
+
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("검증").unwrap();
+    writer
+        .add_table(HwpxTable::from_data(vec![vec!["A", "B"], vec!["C", "D"]]))
+        .unwrap();
+    writer
+        .add_image(HwpxImage::from_bytes(tiny_png()).unwrap())
+        .unwrap();
+    let bytes = writer.to_bytes().unwrap();
+    let report = HwpxReader::validate(&bytes);
+    assert!(report.is_valid(), "{:?}", report.issues);
+
+    let kinds = |bytes: &[u8]| -> Vec<(String, PackageIssueKind)> {
+        HwpxReader::validate(bytes)
+            .issues
+            .into_iter()
+            .map(|issue| (issue.part, issue.kind))
+            .collect()
+    };
+    let edit_section = |edit: &dyn Fn(String) -> String| {
+        rebuild_package(&bytes, |name, data| {
+            if name == "Contents/section0.xml" {
+                let xml = String::from_utf8(data).unwrap();
+                Some((name.to_string(), edit(xml).into_bytes()))
+            } else {
+                Some((name.to_string(), data))
+            }
+        })
+    };
+    let section = "Contents/section0.xml".to_string();
+
+    let without = |part: &str| {
+        rebuild_package(&bytes, |name, data| {
+            (name != part).then(|| (name.to_string(), data))
+        })
+    };
+    assert_eq!(
+        kinds(&without("mimetype")),
+        vec![("mimetype".to_string(), PackageIssueKind::MissingPart)]
+    );
+    assert_eq!(
+        kinds(&without("Contents/section0.xml")),
+        vec![(section.clone(), PackageIssueKind::MissingPart)]
+    );
+    assert_eq!(
+        kinds(&without("BinData/image1.png")),
+        vec![(section.clone(), PackageIssueKind::DanglingBinaryItem)]
+    );
+
+    // Only the first run gets an undefined char shape
+    let unknown_char_pr =
+        edit_section(&|xml| xml.replacen(r#"charPrIDRef="0""#, r#"charPrIDRef="99""#, 1));
+    let report = HwpxReader::validate(&unknown_char_pr);
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].kind, PackageIssueKind::UnknownCharShape);
+    assert!(report.issues[0].message.contains("charPrIDRef 99"));
+
+    // Widening the first cell makes it cover the cell to its right
+    assert!(zip_entry_text(&bytes, &section).contains(r#"<hp:cellSpan colSpan="1" rowSpan="1"/>"#));
+    let overlapping = edit_section(&|xml| {
+        xml.replacen(
+            r#"<hp:cellSpan colSpan="1" rowSpan="1"/>"#,
+            r#"<hp:cellSpan colSpan="2" rowSpan="1"/>"#,
+            1,
+        )
+    });
+    let report = HwpxReader::validate(&overlapping);
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].kind, PackageIssueKind::OverlappingCells);
+    assert!(report.issues[0].message.contains("row 0, column 1"));
+
+    // Spans far beyond the table are cut off at its edges instead of overflowing
+    let huge_span = edit_section(&|xml| {
+        xml.replacen(
+            r#"<hp:cellSpan colSpan="1" rowSpan="1"/>"#,
+            r#"<hp:cellSpan colSpan="4294967295" rowSpan="65535"/>"#,
+            1,
+        )
+    });
+    let report = HwpxReader::validate(&huge_span);
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].kind, PackageIssueKind::OverlappingCells);
+
+    // Cells without hp:cellAddr sit where they are in the XML
+    let without_addr = edit_section(&|xml| {
+        let mut xml = xml;
+        while let Some(start) = xml.find("<hp:cellAddr ") {
+            let end = start + xml[start..].find("/>").unwrap() + 2;
+            xml.replace_range(start..end, "");
+        }
+        xml
+    });
+    assert!(HwpxReader::validate(&without_addr).is_valid());
+
+    assert_eq!(
+        kinds(b"not a zip"),
+        vec![(String::new(), PackageIssueKind::InvalidArchive)]
+    );
+}
+
+#[test]
+fn test_hwpx_extract_sections() {
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("1장 개요").unwrap();
+    writer.add_paragraph("개요 본문").unwrap();
+    writer.add_section().unwrap();
+    writer.add_paragraph("2장 결과").unwrap();
+    let bytes = writer.to_bytes().unwrap();
+
+    let sections = HwpxReader::extract_sections(&bytes).unwrap();
+    assert_eq!(sections.len(), 2);
+    assert_eq!(sections[0].index, 0);
+    assert_eq!(sections[0].paragraphs, vec!["1장 개요", "개요 본문"]);
+    assert_eq!(sections[1].index, 1);
+    assert_eq!(sections[1].paragraphs, vec!["2장 결과"]);
+
+    // extract_text stays the flattened join
+    let joined: String = sections
+        .iter()
+        .flat_map(|s| &s.paragraphs)
+        .map(|p| format!("{p}\n"))
+        .collect();
+    let document = HwpxReader::from_bytes(&bytes).unwrap();
+    assert_eq!(document.extract_text(), joined);
+}
+
+#[test]
+fn test_hwpx_extract_outline_numbers_list_items() {
+    use hwpers::hwpx::ListKind;
+
+    // paraPr 1 and 2: levels 1 and 2 of the writer's numbering 1 (^1. / ^2.)
+    let list_para_prs = r#"<hh:paraPr id="1" tabPrIDRef="0"><hh:heading type="NUMBER" idRef="1" level="0"/></hh:paraPr><hh:paraPr id="2" tabPrIDRef="0"><hh:heading type="NUMBER" idRef="1" level="1"/></hh:paraPr></hh:paraProperties>"#;
+    let paragraph = |para_pr: u32, text: &str| {
+        format!(
+            r#"<hp:p id="0" paraPrIDRef="{para_pr}" styleIDRef="0"><hp:run charPrIDRef="0"><hp:t>{text}</hp:t></hp:run></hp:p>"#
+        )
+    };
+    let section = [
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes" ?>"#.to_string(),
+        r#"<hs:sec xmlns:hs="http://www.hancom.co.kr/hwpml/2011/section" xmlns:hp="http://www.hancom.co.kr/hwpml/2011/paragraph">"#.to_string(),
+        paragraph(0, "목록 앞"),
+        paragraph(1, "첫째"),
+        paragraph(1, "둘째"),
+        paragraph(2, "세부"),
+        paragraph(0, "설명"),
+        paragraph(1, "셋째"),
+        "</hs:sec>".to_string(),
+    ]
+    .concat();
+
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("자리").unwrap();
+    let bytes = rebuild_package(&writer.to_bytes().unwrap(), |name, data| match name {
+        "Contents/section0.xml" => Some((name.to_string(), section.clone().into_bytes())),
+        "Contents/header.xml" => {
+            let header = String::from_utf8(data).unwrap();
+            assert_eq!(header.matches("</hh:paraProperties>").count(), 1);
+            let header = header.replace("</hh:paraProperties>", list_para_prs);
+            Some((name.to_string(), header.into_bytes()))
+        }
+        _ => Some((name.to_string(), data)),
+    });
+
+    let outline = HwpxReader::extract_outline(&bytes).unwrap();
+    let items: Vec<_> = outline
+        .iter()
+        .map(|p| {
+            let list = p.list.as_ref();
+            (
+                p.text.as_str(),
+                list.map(|l| (l.kind, l.level, l.number, l.label.as_str())),
+            )
+        })
+        .collect();
+    assert_eq!(
+        items,
+        vec![
+            ("목록 앞", None),
+            ("첫째", Some((ListKind::Numbered, 0, Some(1), "1."))),
+            ("둘째", Some((ListKind::Numbered, 0, Some(2), "2."))),
+            ("세부", Some((ListKind::Numbered, 1, Some(1), "가."))),
+            ("설명", None),
+            ("셋째", Some((ListKind::Numbered, 0, Some(3), "3."))),
+        ]
+    );
+    assert_eq!(outline[5].paragraph_index, 5);
+
+    // Without numbering definitions every paragraph is plain
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("평문").unwrap();
+    let outline = HwpxReader::extract_outline(&writer.to_bytes().unwrap()).unwrap();
+    assert_eq!(outline.len(), 1);
+    assert_eq!(outline[0].list, None);
+}
+
+#[test]
+fn test_hwpx_unknown_bin_data_format_is_exposed() {
+    use hwpers::hwpx::HwpxImageFormat;
+
+    // EMR_HEADER record type, not an image format the reader decodes
+    let emf: Vec<u8> = [1u8, 0, 0, 0, 0x6C, 0, 0, 0]
+        .into_iter()
+        .chain(std::iter::repeat_n(0xAB, 100))
+        .collect();
+
+    let mut writer = HwpxWriter::new();
+    writer.add_paragraph("도형 앞").unwrap();
+    writer
+        .add_image(HwpxImage::from_bytes(tiny_png()).unwrap().with_size(20, 10))
+        .unwrap();
+    let bytes = rebuild_package(&writer.to_bytes().unwrap(), |name, data| match name {
+        "BinData/image1.png" => Some(("BinData/image1.emf".to_string(), emf.clone())),
+        "Contents/header.xml" | "Contents/content.hpf" => {
+            let xml = String::from_utf8(data).unwrap();
+            let xml = xml
+                .replace("BinData/image1.png", "BinData/image1.emf")
+                .replace(r#"format="PNG""#, r#"format="EMF""#);
+            Some((name.to_string(), xml.into_bytes()))
+        }
+        _ => Some((name.to_string(), data)),
+    });
+
+    let document = HwpxReader::from_bytes(&bytes).unwrap();
+    assert!(document.extract_text().contains("도형 앞"));
+    assert!(HwpxReader::validate(&bytes).is_valid());
+
+    let images = HwpxReader::extract_images(&bytes).unwrap();
+    assert_eq!(images.len(), 1);
+    assert_eq!(images[0].format, HwpxImageFormat::Other("emf".to_string()));
+    assert_eq!(images[0].source_name, "BinData/image1.emf");
+    assert_eq!(*images[0].data, emf);
+    assert_eq!(
+        (images[0].width_mm, images[0].height_mm),
+        (Some(20), Some(10))
+    );
+
+    // Editing the package carries the item over unchanged
+    let mut writer = HwpxWriter::from_hwpx_bytes(&bytes).unwrap();
+    writer.add_paragraph("추가").unwrap();
+    let images = HwpxReader::extract_images(&writer.to_bytes().unwrap()).unwrap();
+    assert_eq!(images.len(), 1);
+    assert_eq!(images[0].format, HwpxImageFormat::Other("emf".to_string()));
+    assert_eq!(*images[0].data, emf);
+}
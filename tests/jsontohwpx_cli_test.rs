@@ -64,6 +64,33 @@ fn test_cli_auto_output_filename() {
     );
 }
 
+#[test]
+fn test_cli_filename_template() {
+    let tmp = tempfile::tempdir().unwrap();
+
+    let status = Command::new(cargo_bin())
+        .arg(simple_json())
+        .arg("-b")
+        .arg(examples_path())
+        .arg("--filename-template")
+        .arg("보고서_{article_id}")
+        .current_dir(tmp.path())
+        .status()
+        .unwrap();
+
+    assert!(status.success());
+    let json = std::fs::read_to_string(simple_json()).unwrap();
+    let input: hwpers::jsontohwpx::ApiResponse = serde_json::from_str(&json).unwrap();
+    let expected_file = tmp
+        .path()
+        .join(format!("보고서_{}.hwpx", input.data.article.atcl_id));
+    assert!(
+        expected_file.exists(),
+        "템플릿 이름의 파일 없음: {}",
+        expected_file.display()
+    );
+}
+
 #[test]
 fn test_cli_validate_success() {
     let output = Command::new(cargo_bin())
@@ -77,6 +104,7 @@ fn test_cli_validate_success() {
     assert!(output.status.success());
     let stderr = String::from_utf8_lossy(&output.stderr);
     assert!(stderr.contains("검증 성공"), "stderr: {}", stderr);
+    assert!(stderr.contains("schemaVersion=1.2"), "stderr: {}", stderr);
 }
 
 #[test]
@@ -245,13 +273,20 @@ fn test_cli_validate_progress_logs() {
 }
 
 #[test]
-fn test_cli_empty_table_conversion_error() {
+fn test_cli_empty_table_input_error() {
     let tmp = tempfile::tempdir().unwrap();
     let json_file = tmp.path().join("empty_table.json");
     std::fs::write(&json_file, r#"{"responseCode":"0","data":{"article":{"atclId":"ERR001","subject":"S","contents":[{"type":"table","value":"<table></table>"}]}}}"#).unwrap();
 
     let output = Command::new(cargo_bin()).arg(&json_file).output().unwrap();
 
+    // 빈 표는 변환 전 입력 검증에서 걸린다
     assert!(!output.status.success());
-    assert_eq!(output.status.code(), Some(2)); // Conversion error
+    assert_eq!(output.status.code(), Some(1)); // Input error
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("contents[0]: 테이블에 행이 없습니다"),
+        "{}",
+        stderr
+    );
 }
@@ -101,6 +101,105 @@ fn test_image_download_failure() {
     assert!(result.is_err(), "존재하지 않는 URL이면 에러를 반환해야 함");
 }
 
+#[test]
+fn test_image_failure_error_context() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "IMG_ERR003",
+                "contents": [
+                    { "type": "text", "value": "하나" },
+                    { "type": "text", "value": "둘" },
+                    { "type": "divider" },
+                    { "type": "image", "url": "./nonexistent_image.png" },
+                    { "type": "text", "value": "다섯" }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let err = jsontohwpx::convert(&input, &base_path()).unwrap_err();
+    assert!(
+        err.to_string()
+            .starts_with("변환 에러: contents[3] (image): 이미지 파일 읽기 실패: "),
+        "{}",
+        err
+    );
+    assert_eq!(err.error_code(), "CONVERSION_ERROR");
+    assert_eq!(err.content_index(), Some(3));
+}
+
+#[test]
+fn test_image_download_failure_skipped() {
+    let json = r#"{
+        "responseCode": "0",
+        "options": { "onError": "skip" },
+        "data": {
+            "article": {
+                "atclId": "IMG_ERR003",
+                "contents": [
+                    { "type": "text", "value": "앞" },
+                    { "type": "image", "url": "http://invalid.example.test/nonexistent.png" },
+                    { "type": "text", "value": "뒤" }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let output = jsontohwpx::convert_to_output(&input, &base_path()).unwrap();
+    verify_hwpx_bytes(&output.bytes);
+
+    let text = HwpxReader::from_bytes(&output.bytes)
+        .unwrap()
+        .extract_text();
+    assert_eq!(
+        text,
+        "앞\n[이미지 로드 실패: http://invalid.example.test/nonexistent.png]\n뒤\n"
+    );
+    assert_eq!(output.warnings.len(), 1);
+    assert_eq!(output.warnings[0].content_index, Some(1));
+    assert_eq!(output.warnings[0].code, "CONTENT_SKIPPED");
+}
+
+#[test]
+fn test_skip_images_uses_placeholders() {
+    let json = r#"{
+        "responseCode": "0",
+        "options": { "skipImages": true },
+        "data": {
+            "article": {
+                "atclId": "IMG_SKIP001",
+                "contents": [
+                    { "type": "image", "url": "http://invalid.example.test/nonexistent.png" },
+                    { "type": "image", "url": "./nonexistent_image.png" },
+                    { "type": "image", "base64": "잘못된 base64", "format": "png" },
+                    { "type": "html", "value": "<p>본문</p><img src=\"http://invalid.example.test/a.png\">" }
+                ]
+            }
+        }
+    }"#;
+
+    // 내려받거나 파일을 읽지 않으므로 없는 URL, 경로, 잘못된 base64도 실패하지 않는다
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let output = jsontohwpx::convert_to_output(&input, &base_path()).unwrap();
+    verify_hwpx_bytes(&output.bytes);
+    assert!(output.warnings.is_empty());
+
+    let text = HwpxReader::from_bytes(&output.bytes)
+        .unwrap()
+        .extract_text();
+    assert_eq!(
+        text,
+        "[이미지: http://invalid.example.test/nonexistent.png]\n\
+         [이미지: ./nonexistent_image.png]\n\
+         [이미지: base64 생략]\n\
+         본문\n[이미지: http://invalid.example.test/a.png]\n"
+    );
+}
+
 #[test]
 fn test_image_local_file_not_found() {
     let json = r#"{
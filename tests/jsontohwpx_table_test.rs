@@ -82,7 +82,9 @@ fn test_table_merge_cell_structure() {
 
     let json = format!(
         r#"{{"responseCode":"0","data":{{"article":{{"atclId":"MERGE_STRUCT","subject":"구조검증","contents":[{{"type":"table","value":"{}"}}]}}}}}}"#,
-        html.replace('\n', "").replace('"', "\\\"").replace("    ", "")
+        html.replace('\n', "")
+            .replace('"', "\\\"")
+            .replace("    ", "")
     );
 
     let input: ApiResponse = serde_json::from_str(&json).unwrap();
@@ -122,7 +124,9 @@ fn test_table_merge_width_calculation() {
 
     let json = format!(
         r#"{{"responseCode":"0","data":{{"article":{{"atclId":"MERGE_WIDTH","subject":"너비검증","contents":[{{"type":"table","value":"{}"}}]}}}}}}"#,
-        html.replace('\n', "").replace('"', "\\\"").replace("    ", "")
+        html.replace('\n', "")
+            .replace('"', "\\\"")
+            .replace("    ", "")
     );
 
     let input: ApiResponse = serde_json::from_str(&json).unwrap();
@@ -140,11 +144,337 @@ fn test_table_merge_width_calculation() {
 
     // 3열 테이블: col_width = 42520/3 = 14173
     // colspan=2 셀: width = 14173*2 = 28346
-    assert!(section_xml.contains(r#"width="28346""#), "colspan=2 셀 너비");
+    assert!(
+        section_xml.contains(r#"width="28346""#),
+        "colspan=2 셀 너비"
+    );
     // 단일 셀: width = 14173
     assert!(section_xml.contains(r#"width="14173""#), "단일 셀 너비");
 }
 
+#[test]
+fn test_table_col_widths_from_html() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "TBL_WIDTH001",
+                "subject": "열 너비",
+                "contents": [
+                    { "type": "table", "value": "<table><colgroup><col width=\"20%\"><col width=\"80%\"></colgroup><tr><td colspan=\"2\">제목</td></tr><tr><td>항목</td><td>설명</td></tr></table>" }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    verify_hwpx_bytes(&bytes);
+
+    let cursor = std::io::Cursor::new(&bytes);
+    let mut archive = zip::ZipArchive::new(cursor).unwrap();
+    let mut section_xml = String::new();
+    {
+        use std::io::Read;
+        let mut file = archive.by_name("Contents/section0.xml").unwrap();
+        file.read_to_string(&mut section_xml).unwrap();
+    }
+
+    let widths: Vec<u32> = section_xml
+        .split(r#"<hp:cellSz width=""#)
+        .skip(1)
+        .map(|rest| rest.split('"').next().unwrap().parse().unwrap())
+        .collect();
+    // 본문 폭 42520을 20:80으로, 병합한 셀은 두 열의 합
+    assert_eq!(widths, vec![42520, 8504, 34016]);
+    assert!(section_xml.contains(r#"<hp:sz width="42520""#));
+}
+
+#[test]
+fn test_table_cell_colors_from_html() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "TBL_COLOR001",
+                "subject": "셀 색",
+                "contents": [
+                    { "type": "table", "value": "<table><tr><th style=\"background-color:#EEE;color:#C00\">머리글</th><th>보통</th></tr><tr><td bgcolor=\"gray\">값</td><td>값</td></tr></table>" }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    verify_hwpx_bytes(&bytes);
+
+    let cursor = std::io::Cursor::new(&bytes);
+    let mut archive = zip::ZipArchive::new(cursor).unwrap();
+    let mut read = |name: &str| {
+        use std::io::Read;
+        let mut xml = String::new();
+        archive
+            .by_name(name)
+            .unwrap()
+            .read_to_string(&mut xml)
+            .unwrap();
+        xml
+    };
+    let section_xml = read("Contents/section0.xml");
+    let header_xml = read("Contents/header.xml");
+    let attr = |xml: &str, name: &str| -> String {
+        let start = xml.find(&format!(r#" {}=""#, name)).unwrap() + name.len() + 3;
+        xml[start..].split('"').next().unwrap().to_string()
+    };
+    let cells: Vec<&str> = section_xml.split("<hp:tc ").skip(1).collect();
+    assert_eq!(cells.len(), 4);
+
+    // 머리글 셀: 배경색 borderFill과 글자 색 charPr
+    let fill_id = attr(cells[0], "borderFillIDRef");
+    let fill = header_xml
+        .split(&format!(r#"<hh:borderFill id="{}" "#, fill_id))
+        .nth(1)
+        .unwrap();
+    let fill = fill.split("</hh:borderFill>").next().unwrap();
+    assert!(fill.contains(r##"faceColor="#EEEEEE""##), "{}", fill);
+
+    let char_pr_id = attr(cells[0], "charPrIDRef");
+    let char_pr = header_xml
+        .split(&format!(r#"<hh:charPr id="{}" "#, char_pr_id))
+        .nth(1)
+        .unwrap();
+    assert_eq!(attr(char_pr, "textColor"), "#CC0000");
+
+    // 색 이름 bgcolor는 다른 borderFill, 스타일이 없는 셀은 기본 셀 테두리
+    let gray_id = attr(cells[2], "borderFillIDRef");
+    assert_ne!(gray_id, fill_id);
+    assert!(header_xml.contains(r##"faceColor="#808080""##));
+    assert_eq!(attr(cells[1], "borderFillIDRef"), "3");
+    assert_eq!(attr(cells[1], "charPrIDRef"), "0");
+    assert_eq!(attr(cells[2], "charPrIDRef"), "0");
+}
+
+#[test]
+fn test_table_cell_links_from_html() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "TBL_LINK001",
+                "subject": "셀 링크",
+                "contents": [
+                    { "type": "table", "value": "<table><tr><td>이름</td><td>자료</td></tr><tr><td>안내</td><td>첨부 <a href=\"https://example.com/doc?a=1&amp;b=2\">문서</a> 참고</td></tr></table>" }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    verify_hwpx_bytes(&bytes);
+
+    let cursor = std::io::Cursor::new(&bytes);
+    let mut archive = zip::ZipArchive::new(cursor).unwrap();
+    let mut section_xml = String::new();
+    {
+        use std::io::Read;
+        let mut file = archive.by_name("Contents/section0.xml").unwrap();
+        file.read_to_string(&mut section_xml).unwrap();
+    }
+
+    let cells: Vec<&str> = section_xml
+        .split("<hp:tc ")
+        .skip(1)
+        .map(|cell| cell.split("</hp:tc>").next().unwrap())
+        .collect();
+    assert_eq!(cells.len(), 4);
+    let url = r#"url="https://example.com/doc?a=1&amp;b=2""#;
+    assert_eq!(section_xml.matches(url).count(), 1);
+    assert!(cells[3].contains(url), "{}", cells[3]);
+    assert!(cells[3].contains("<hp:t>문서</hp:t>"));
+    assert!(cells[3].contains(r#"colAddr="1" rowAddr="1""#));
+
+    // 텍스트는 링크와 상관없이 그대로
+    let tables = HwpxReader::extract_tables(&bytes).unwrap();
+    assert_eq!(tables[0].grid()[1][1], "첨부 문서 참고");
+    let links = HwpxReader::extract_hyperlinks(&bytes).unwrap();
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].text, "문서");
+    assert_eq!(links[0].url, "https://example.com/doc?a=1&b=2");
+}
+
+#[test]
+fn test_table_cell_unsafe_link_dropped() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "TBL_LINK002",
+                "contents": [
+                    { "type": "table", "value": "<table><tr><td><a href=\"javascript:alert(1)\">누르기</a></td></tr></table>" }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let output = jsontohwpx::convert_to_output(&input, &base_path()).unwrap();
+    verify_hwpx_bytes(&output.bytes);
+    assert_eq!(output.warnings.len(), 1);
+    assert_eq!(output.warnings[0].code, "LINK_DROPPED");
+    assert_eq!(output.warnings[0].content_index, Some(0));
+
+    assert!(HwpxReader::extract_hyperlinks(&output.bytes)
+        .unwrap()
+        .is_empty());
+    let tables = HwpxReader::extract_tables(&output.bytes).unwrap();
+    assert_eq!(tables[0].grid()[0][0], "누르기");
+}
+
+#[test]
+fn test_table_cell_images_from_html() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "TBL_IMG001",
+                "subject": "셀 그림",
+                "contents": [
+                    { "type": "table", "value": "<table><tr><th>제품</th><th>사진</th></tr><tr><td>A</td><td><img src=\"./test_img.png\"> 정면</td></tr></table>" }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    verify_hwpx_bytes(&bytes);
+
+    let cursor = std::io::Cursor::new(&bytes);
+    let mut archive = zip::ZipArchive::new(cursor).unwrap();
+    assert!(archive.by_name("BinData/image1.png").is_ok());
+    let mut section_xml = String::new();
+    {
+        use std::io::Read;
+        let mut file = archive.by_name("Contents/section0.xml").unwrap();
+        file.read_to_string(&mut section_xml).unwrap();
+    }
+
+    let cells: Vec<&str> = section_xml
+        .split("<hp:tc ")
+        .skip(1)
+        .map(|cell| cell.split("</hp:tc>").next().unwrap())
+        .collect();
+    assert_eq!(cells.len(), 4);
+    assert_eq!(section_xml.matches("binaryItemIDRef=").count(), 1);
+    assert!(
+        cells[3].contains(r#"binaryItemIDRef="image1""#),
+        "{}",
+        cells[3]
+    );
+    assert!(cells[3].contains("<hp:t>정면</hp:t>"));
+
+    // 그림은 셀 너비 안으로 줄인다
+    let cell_width = |cell: &str| -> u32 {
+        let sz = cell.split("<hp:cellSz ").nth(1).unwrap();
+        sz.split("width=\"")
+            .nth(1)
+            .unwrap()
+            .split('"')
+            .next()
+            .unwrap()
+            .parse()
+            .unwrap()
+    };
+    let pic_width: u32 = cells[3]
+        .split("<hp:curSz ")
+        .nth(1)
+        .unwrap()
+        .split("width=\"")
+        .nth(1)
+        .unwrap()
+        .split('"')
+        .next()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(pic_width <= cell_width(cells[3]) - 1020, "{}", pic_width);
+}
+
+#[test]
+fn test_table_cell_image_failure_follows_on_error() {
+    let json = |on_error: &str| {
+        format!(
+            r#"{{
+                "responseCode": "0",
+                "options": {{ "onError": "{}" }},
+                "data": {{
+                    "article": {{
+                        "atclId": "TBL_IMG002",
+                        "contents": [
+                            {{ "type": "table", "value": "<table><tr><td><img src=\"./missing.png\"></td></tr></table>" }}
+                        ]
+                    }}
+                }}
+            }}"#,
+            on_error
+        )
+    };
+
+    let input: ApiResponse = serde_json::from_str(&json("fail")).unwrap();
+    let err = jsontohwpx::convert_to_output(&input, &base_path()).unwrap_err();
+    assert_eq!(err.error_code(), "CONVERSION_ERROR");
+    assert_eq!(err.content_index(), Some(0));
+
+    let input: ApiResponse = serde_json::from_str(&json("skip")).unwrap();
+    let output = jsontohwpx::convert_to_output(&input, &base_path()).unwrap();
+    verify_hwpx_bytes(&output.bytes);
+    assert_eq!(output.warnings.len(), 1);
+    assert_eq!(output.warnings[0].code, "CONTENT_SKIPPED");
+    assert!(HwpxReader::extract_tables(&output.bytes)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_table_inconsistent_spans_expand_grid() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "TBL_SPAN001",
+                "contents": [
+                    { "type": "text", "value": "앞" },
+                    { "type": "table", "value": "<table><tr><td rowspan=\"2\">A</td><td>B</td></tr><tr><td colspan=\"2\">C</td></tr></table>" }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let output = jsontohwpx::convert_to_output(&input, &base_path()).unwrap();
+    verify_hwpx_bytes(&output.bytes);
+
+    assert_eq!(output.warnings.len(), 1);
+    assert_eq!(output.warnings[0].code, "TABLE_GRID_ADJUSTED");
+    assert_eq!(output.warnings[0].content_index, Some(1));
+
+    // C가 잘리지 않고 두 칸을 차지한다
+    let tables = HwpxReader::extract_tables(&output.bytes).unwrap();
+    assert_eq!(tables[0].grid()[1][1], "C");
+    let cursor = std::io::Cursor::new(&output.bytes);
+    let mut archive = zip::ZipArchive::new(cursor).unwrap();
+    let mut section_xml = String::new();
+    {
+        use std::io::Read;
+        let mut file = archive.by_name("Contents/section0.xml").unwrap();
+        file.read_to_string(&mut section_xml).unwrap();
+    }
+    assert!(section_xml.contains(r#"colCnt="3""#));
+    assert!(section_xml.contains(r#"<hp:cellSpan colSpan="2" rowSpan="1"/>"#));
+}
+
 #[test]
 fn test_table_merge_height_calculation() {
     // rowspan 시 높이가 올바르게 계산되는지 확인
@@ -156,7 +486,9 @@ fn test_table_merge_height_calculation() {
 
     let json = format!(
         r#"{{"responseCode":"0","data":{{"article":{{"atclId":"MERGE_HEIGHT","subject":"높이검증","contents":[{{"type":"table","value":"{}"}}]}}}}}}"#,
-        html.replace('\n', "").replace('"', "\\\"").replace("    ", "")
+        html.replace('\n', "")
+            .replace('"', "\\\"")
+            .replace("    ", "")
     );
 
     let input: ApiResponse = serde_json::from_str(&json).unwrap();
@@ -173,7 +505,10 @@ fn test_table_merge_height_calculation() {
     }
 
     // rowspan=3: height = 1000*3 = 3000
-    assert!(section_xml.contains(r#"height="3000""#), "rowspan=3 셀 높이");
+    assert!(
+        section_xml.contains(r#"height="3000""#),
+        "rowspan=3 셀 높이"
+    );
     // 일반 셀: height = 1000
     assert!(section_xml.contains(r#"height="1000""#), "일반 셀 높이");
 }
@@ -270,3 +605,85 @@ fn test_large_table() {
     let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
     verify_hwpx_bytes(&bytes);
 }
+
+#[test]
+fn test_table_caption_from_html() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "TBL_CAP001",
+                "subject": "캡션",
+                "contents": [
+                    { "type": "table", "value": "<table><caption>1분기 실적</caption><tr><td>셀</td></tr></table>" },
+                    { "type": "table", "value": "<table><caption>2분기 실적</caption><tr><td>셀</td></tr></table>" }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    verify_hwpx_bytes(&bytes);
+
+    let cursor = std::io::Cursor::new(&bytes);
+    let mut archive = zip::ZipArchive::new(cursor).unwrap();
+    let mut section_xml = String::new();
+    {
+        use std::io::Read;
+        let mut file = archive.by_name("Contents/section0.xml").unwrap();
+        file.read_to_string(&mut section_xml).unwrap();
+    }
+
+    let first = section_xml.find("표 1. 1분기 실적").expect("첫 캡션");
+    let second = section_xml.find("표 2. 2분기 실적").expect("둘째 캡션");
+    assert!(first < second);
+    // 캡션 텍스트가 셀 텍스트로 들어가지 않음
+    assert_eq!(section_xml.matches("<hp:caption ").count(), 2);
+}
+
+#[test]
+fn test_table_caption_precedes_rows() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "TBL_CAP002",
+                "subject": "캡션 위치",
+                "contents": [
+                    { "type": "table", "value": "<table><tr><td>캡션 없음</td></tr></table>" },
+                    { "type": "table", "value": "<table><caption>2024년 실적</caption><tr><td>매출</td></tr></table>" }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    verify_hwpx_bytes(&bytes);
+
+    let cursor = std::io::Cursor::new(&bytes);
+    let mut archive = zip::ZipArchive::new(cursor).unwrap();
+    let mut section_xml = String::new();
+    {
+        use std::io::Read;
+        let mut file = archive.by_name("Contents/section0.xml").unwrap();
+        file.read_to_string(&mut section_xml).unwrap();
+    }
+
+    let tables: Vec<&str> = section_xml
+        .split("<hp:tbl ")
+        .skip(1)
+        .map(|table| table.split("</hp:tbl>").next().unwrap())
+        .collect();
+    assert_eq!(tables.len(), 2);
+
+    // 캡션이 없는 표는 그대로이고 번호도 세지 않는다
+    assert!(!tables[0].contains("<hp:caption "));
+    assert!(!section_xml.contains("표 2."));
+
+    let caption = tables[1].find("표 1. 2024년 실적").expect("캡션");
+    assert!(tables[1].contains(r#"<hp:caption side="TOP""#));
+    assert!(caption < tables[1].find("<hp:tr>").unwrap());
+    assert!(caption < tables[1].find("매출").unwrap());
+}
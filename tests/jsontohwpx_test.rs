@@ -20,6 +20,32 @@ fn verify_hwpx_bytes(bytes: &[u8]) -> hwpers::HwpDocument {
     HwpxReader::from_bytes(bytes).expect("HwpxReader가 HWPX 파일을 읽지 못했습니다")
 }
 
+/// HWPX 바이트에서 section0.xml 추출
+fn section_xml(bytes: &[u8]) -> String {
+    use std::io::Read;
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+    let mut xml = String::new();
+    archive
+        .by_name("Contents/section0.xml")
+        .unwrap()
+        .read_to_string(&mut xml)
+        .unwrap();
+    xml
+}
+
+/// HWPX 바이트에서 header.xml 추출
+fn header_xml(bytes: &[u8]) -> String {
+    use std::io::Read;
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes)).unwrap();
+    let mut xml = String::new();
+    archive
+        .by_name("Contents/header.xml")
+        .unwrap()
+        .read_to_string(&mut xml)
+        .unwrap();
+    xml
+}
+
 /// JSON 문자열 → HWPX 변환 → HwpxReader 검증 헬퍼
 fn convert_and_verify(json: &str) -> hwpers::HwpDocument {
     let input: ApiResponse = serde_json::from_str(json).expect("JSON 파싱 실패");
@@ -44,39 +70,1450 @@ fn test_empty_contents_generates_valid_hwpx() {
 }
 
 #[test]
-fn test_single_text_generates_valid_hwpx() {
+fn test_conversion_warnings_are_returned() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "EMPTY002",
+                "subject": "빈 문서",
+                "contents": [
+                    { "type": "text", "value": "x", "style": { "blink": true } }
+                ]
+            }
+        }
+    }"#;
+
+    // 경고는 출력하지 않고 결과에 담는다
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let output = jsontohwpx::convert_to_output(&input, &base_path()).unwrap();
+    verify_hwpx_bytes(&output.bytes);
+    assert_eq!(output.warnings.len(), 1);
+    assert_eq!(output.warnings[0].content_index, Some(0));
+    assert_eq!(output.warnings[0].code, "UNKNOWN_STYLE_KEY");
+
+    let json = json.replace(
+        r#"{ "type": "text", "value": "x", "style": { "blink": true } }"#,
+        "",
+    );
+    let input: ApiResponse = serde_json::from_str(&json).unwrap();
+    let output = jsontohwpx::convert_to_output(&input, &base_path()).unwrap();
+    verify_hwpx_bytes(&output.bytes);
+    assert_eq!(output.warnings.len(), 1);
+    let warning = &output.warnings[0];
+    assert_eq!(warning.code, "EMPTY_CONTENTS");
+    assert_eq!(warning.content_index, None);
+    assert_eq!(
+        warning.to_string(),
+        "contents가 비어있습니다. 빈 문서를 생성합니다."
+    );
+}
+
+#[test]
+fn test_content_html_fallback() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "LEGACY001",
+                "contents": [],
+                "contentHtml": "<p>예전 <b>본문</b></p><p>둘째 단락</p>"
+            }
+        }
+    }"#;
+
+    let text = convert_and_verify(json).extract_text();
+    assert_eq!(text, "예전 본문\n둘째 단락\n");
+
+    // 옵션을 끄면 contentHtml을 쓰지 않는다
+    let json = json.replace(
+        r#""responseCode": "0","#,
+        r#""responseCode": "0", "options": { "useContentHtmlFallback": false },"#,
+    );
+    let text = convert_and_verify(&json).extract_text();
+    assert!(!text.contains("본문"), "{}", text);
+}
+
+#[test]
+fn test_single_text_generates_valid_hwpx() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "TEXT001",
+                "subject": "텍스트 문서",
+                "contents": [
+                    { "type": "text", "value": "안녕하세요, HWPX 문서입니다." }
+                ]
+            }
+        }
+    }"#;
+
+    let doc = convert_and_verify(json);
+    let text = doc.extract_text();
+    assert!(
+        text.contains("안녕하세요"),
+        "문서에 '안녕하세요' 텍스트가 포함되어야 합니다. 실제: {}",
+        text
+    );
+}
+
+#[test]
+fn test_multiline_text() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "TEXT002",
+                "subject": "멀티라인",
+                "contents": [
+                    { "type": "text", "value": "첫 줄\n둘째 줄\n셋째 줄" }
+                ]
+            }
+        }
+    }"#;
+
+    let doc = convert_and_verify(json);
+    let text = doc.extract_text();
+    assert!(text.contains("첫 줄"), "첫 줄 포함 확인");
+    assert!(text.contains("둘째 줄"), "둘째 줄 포함 확인");
+    assert!(text.contains("셋째 줄"), "셋째 줄 포함 확인");
+}
+
+#[test]
+fn test_text_normalization_option() {
+    let paragraphs = |options: &str| -> Vec<String> {
+        let json = format!(
+            r#"{{
+                "responseCode": "0",
+                "data": {{
+                    "article": {{
+                        "atclId": "TEXT004",
+                        "contents": [
+                            {{ "type": "text", "value": "첫\u200B 줄\r\n둘째 줄\n\n\n\n\n\n셋째 줄" }}
+                        ]
+                    }}
+                }},
+                "options": {}
+            }}"#,
+            options
+        );
+        let input: ApiResponse = serde_json::from_str(&json).unwrap();
+        let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+        HwpxReader::extract_styled_paragraphs(&bytes)
+            .unwrap()
+            .iter()
+            .map(|runs| runs.iter().map(|run| run.text.as_str()).collect())
+            .collect()
+    };
+
+    // 기본값은 입력 그대로
+    let raw = paragraphs("{}");
+    assert_eq!(raw.len(), 8, "{:?}", raw);
+    assert!(raw[0].starts_with("첫\u{200B}"), "{:?}", raw);
+
+    let normalized = paragraphs(
+        r#"{ "textNormalization": { "crlfToLf": true, "stripInvisible": true, "maxBlankLines": 1 } }"#,
+    );
+    assert_eq!(normalized, vec!["첫 줄", "둘째 줄", "", "셋째 줄"]);
+}
+
+#[test]
+fn test_multiple_text_contents() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "TEXT003",
+                "subject": "연속 텍스트",
+                "contents": [
+                    { "type": "text", "value": "첫 번째 텍스트" },
+                    { "type": "text", "value": "두 번째 텍스트" }
+                ]
+            }
+        }
+    }"#;
+
+    let doc = convert_and_verify(json);
+    let text = doc.extract_text();
+    assert!(text.contains("첫 번째 텍스트"));
+    assert!(text.contains("두 번째 텍스트"));
+}
+
+#[test]
+fn test_tab_characters_become_hp_tab() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "TAB001",
+                "contents": [{ "type": "text", "value": "이름\t값" }]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    let xml = section_xml(&bytes);
+    assert!(xml.contains(r#"<hp:t>이름<hp:tab width="4000" leader="0" type="1"/>값</hp:t>"#));
+
+    let doc = verify_hwpx_bytes(&bytes);
+    assert!(doc.extract_text().contains("이름\t값"));
+}
+
+#[test]
+fn test_separator_spacing_option() {
+    let json = |spacing: bool| {
+        format!(
+            r#"{{"responseCode":"0","options":{{"separatorSpacing":{}}},"data":{{"article":{{"atclId":"SPACE001","contents":[{{"type":"text","value":"첫 번째"}},{{"type":"text","value":"두 번째"}}]}}}}}}"#,
+            spacing
+        )
+    };
+    let convert = |spacing: bool| {
+        let input: ApiResponse = serde_json::from_str(&json(spacing)).unwrap();
+        jsontohwpx::convert(&input, &base_path()).unwrap()
+    };
+
+    // 빈 단락 방식: 첫 번째 / (빈 단락) / 두 번째
+    let bytes = convert(false);
+    let doc = verify_hwpx_bytes(&bytes);
+    let paragraphs = &doc.body_texts[0].sections[0].paragraphs;
+    assert_eq!(paragraphs.len(), 3);
+    assert!(!header_xml(&bytes).contains(r#"<hh:paraPr id="1""#));
+
+    // 간격 방식: 빈 단락 없이 두 번째 단락에 16pt 문단 위 간격
+    let bytes = convert(true);
+    let doc = verify_hwpx_bytes(&bytes);
+    let paragraphs = &doc.body_texts[0].sections[0].paragraphs;
+    assert_eq!(paragraphs.len(), 2);
+    assert_eq!(paragraphs[0].para_shape_id, 0);
+    assert_eq!(paragraphs[1].para_shape_id, 1);
+
+    let header = header_xml(&bytes);
+    let start = header.find(r#"<hh:paraPr id="1""#).unwrap();
+    let para_pr = &header[start..start + header[start..].find("</hh:paraPr>").unwrap()];
+    assert!(para_pr.contains(r#"<hc:prev value="1600" unit="HWPUNIT"/>"#));
+    assert!(para_pr.contains(r#"<hh:align horizontal="JUSTIFY""#));
+}
+
+#[test]
+fn test_checkbox_content() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "CHECK001",
+                "subject": "결재",
+                "contents": [
+                    { "type": "text", "value": "검토 결과" },
+                    { "type": "checkbox", "label": "승인", "checked": true },
+                    { "type": "checkbox", "label": "반려" }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    verify_hwpx_bytes(&bytes);
+
+    let xml = section_xml(&bytes);
+    assert!(xml.contains(r#"caption="승인" value="CHECKED""#));
+    assert!(xml.contains(r#"caption="반려" value="UNCHECKED""#));
+}
+
+#[test]
+fn test_default_font_options() {
+    let json = r#"{
+        "responseCode": "0",
+        "options": { "defaultFont": "돋움", "defaultFontSize": 12 },
+        "data": {
+            "article": {
+                "atclId": "FONT001",
+                "contents": [{ "type": "text", "value": "본문 글자" }]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    let doc = verify_hwpx_bytes(&bytes);
+    assert_eq!(doc.doc_info.char_shapes[0].base_size, 1200);
+
+    let header = header_xml(&bytes);
+    assert!(header.contains(r#"<hh:charPr id="0" height="1200""#));
+    assert!(header.contains(r#"face="돋움""#));
+    assert!(section_xml(&bytes).contains(r#"<hp:run charPrIDRef="0"><hp:t>본문 글자</hp:t>"#));
+}
+
+#[test]
+fn test_heading_content() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "HEAD001",
+                "contents": [
+                    { "type": "heading", "value": "1. 개요", "level": 1 },
+                    { "type": "text", "value": "본문 글자" },
+                    { "type": "heading", "value": "1.1 배경", "level": 3 }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    let doc = verify_hwpx_bytes(&bytes);
+    let text = doc.extract_text();
+    assert!(text.contains("1. 개요"));
+    assert!(text.contains("1.1 배경"));
+
+    // 본문 10pt 기준: level 1은 20pt, level 3은 15pt의 굵은 글자
+    let runs = hwpers::HwpxReader::extract_styled_paragraphs(&bytes).unwrap();
+    let run_of = |needle: &str| {
+        runs.iter()
+            .flatten()
+            .find(|r| r.text == needle)
+            .unwrap_or_else(|| panic!("{} 없음", needle))
+            .clone()
+    };
+    let (h1, body, h3) = (run_of("1. 개요"), run_of("본문 글자"), run_of("1.1 배경"));
+    assert!(h1.bold && h3.bold && !body.bold, "{:?}", (&h1, &body, &h3));
+    assert_eq!((h1.size_pt, h3.size_pt, body.size_pt), (20.0, 15.0, 10.0));
+}
+
+#[test]
+fn test_title_style() {
+    let convert = |options: &str| {
+        let json = format!(
+            r#"{{
+                "responseCode": "0",
+                "options": {},
+                "data": {{
+                    "article": {{
+                        "atclId": "TITLE001",
+                        "subject": "보이는 제목",
+                        "regEmpName": "홍길동",
+                        "contents": [{{ "type": "text", "value": "본문" }}]
+                    }}
+                }}
+            }}"#,
+            options
+        );
+        let input: ApiResponse = serde_json::from_str(&json).unwrap();
+        let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+        let text = verify_hwpx_bytes(&bytes).extract_text();
+        let runs = hwpers::HwpxReader::extract_styled_paragraphs(&bytes).unwrap();
+        let title = runs.into_iter().flatten().find(|r| r.text == "보이는 제목");
+        (text, title)
+    };
+
+    // 기본: includeHeader가 꺼져 있으면 가운데 정렬 굵은 16pt 제목
+    let (text, title) = convert("{}");
+    assert_eq!(text, "보이는 제목\n본문\n");
+    let title = title.unwrap();
+    assert!(title.bold);
+    assert_eq!(title.size_pt, 16.0);
+
+    // includeHeader가 켜져 있으면 헤더의 제목만
+    let (text, _) = convert(r#"{ "includeHeader": true }"#);
+    assert_eq!(text.matches("보이는 제목").count(), 1, "{}", text);
+    assert!(text.starts_with("제목: 보이는 제목\n"), "{}", text);
+
+    // 크기와 굵기를 바꾸고, 빠진 정렬은 가운데
+    let (_, title) = convert(r#"{ "titleStyle": { "heading": { "sizePt": 24, "bold": false } } }"#);
+    let title = title.unwrap();
+    assert!(!title.bold);
+    assert_eq!(title.size_pt, 24.0);
+
+    let (text, _) = convert(r#"{ "titleStyle": "none" }"#);
+    assert_eq!(text, "본문\n");
+
+    let json = r#"{
+        "responseCode": "0",
+        "options": { "titleStyle": { "heading": { "sizePt": 0 } } },
+        "data": { "article": { "atclId": "TITLE002", "contents": [] } }
+    }"#;
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let err = jsontohwpx::convert(&input, &base_path()).unwrap_err();
+    assert!(
+        err.to_string().contains("titleStyle.heading.sizePt"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_heading_level_out_of_range_fails() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "HEAD002",
+                "contents": [{ "type": "heading", "value": "제목", "level": 0 }]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let err = jsontohwpx::convert(&input, &base_path()).unwrap_err();
+    assert_eq!(err.error_code(), "INPUT_ERROR");
+    assert!(err.to_string().contains("contents[0]"));
+}
+
+#[test]
+fn test_nested_list_content() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "LIST001",
+                "contents": [
+                    {
+                        "type": "list",
+                        "ordered": true,
+                        "items": [
+                            { "text": "준비", "children": [{ "text": "자료 수집" }, { "text": "일정 확정" }] },
+                            { "text": "실행" }
+                        ]
+                    },
+                    { "type": "list", "items": [{ "text": "참고", "children": [{ "text": "부록" }] }] }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    let doc = verify_hwpx_bytes(&bytes);
+    let lines: Vec<String> = doc
+        .extract_text()
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(String::from)
+        .collect();
+    assert_eq!(
+        lines,
+        [
+            "1. 준비",
+            "가. 자료 수집",
+            "나. 일정 확정",
+            "2. 실행",
+            "• 참고",
+            "◦ 부록"
+        ]
+    );
+
+    // 2단계 항목은 5mm(1417 HWPUNIT) 들여쓴다
+    assert!(header_xml(&bytes).contains(r#"<hc:left value="1417" unit="HWPUNIT"/>"#));
+}
+
+#[test]
+fn test_deeply_nested_list_degrades_to_indentation() {
+    let mut item = serde_json::json!({ "text": "6단계" });
+    for depth in (1..=5).rev() {
+        item = serde_json::json!({ "text": format!("{}단계", depth), "children": [item] });
+    }
+    let json = serde_json::json!({
+        "responseCode": "0",
+        "data": { "article": { "atclId": "LIST002", "contents": [
+            { "type": "list", "ordered": true, "items": [item] }
+        ] } }
+    });
+
+    let input: ApiResponse = serde_json::from_value(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    let text = verify_hwpx_bytes(&bytes).extract_text();
+    assert!(text.contains("1. 1단계"));
+    assert!(text.contains("1) 6단계"));
+    // 6단계는 25mm 들여쓴다
+    assert!(header_xml(&bytes).contains(r#"<hc:left value="7087" unit="HWPUNIT"/>"#));
+}
+
+#[test]
+fn test_line_numbers_option() {
+    let convert = |options: &str| {
+        let json = format!(
+            r#"{{"responseCode":"0","options":{},"data":{{"article":{{"atclId":"LINE001","contents":[{{"type":"text","value":"제1조"}}]}}}}}}"#,
+            options
+        );
+        let input: ApiResponse = serde_json::from_str(&json).unwrap();
+        let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+        verify_hwpx_bytes(&bytes);
+        section_xml(&bytes)
+    };
+
+    let xml = convert("{}");
+    assert!(xml.contains(r#"showLineNumber="0""#));
+
+    // 기본값: 모든 줄, 1부터, 5mm
+    let xml = convert(r#"{"lineNumbers":{}}"#);
+    assert!(xml.contains(r#"showLineNumber="1""#));
+    assert!(xml.contains(r#"countBy="1" distance="1417" startNumber="1""#));
+
+    let xml = convert(r#"{"lineNumbers":{"countBy":5,"start":10,"distanceMm":10}}"#);
+    assert!(xml.contains(r#"countBy="5" distance="2835" startNumber="10""#));
+}
+
+#[test]
+fn test_footer_page_numbers_option() {
+    let convert = |options: &str| {
+        let json = format!(
+            r#"{{"responseCode":"0","options":{},"data":{{"article":{{"atclId":"FOOT001","contents":[{{"type":"text","value":"본문"}}]}}}}}}"#,
+            options
+        );
+        let input: ApiResponse = serde_json::from_str(&json).unwrap();
+        let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+        verify_hwpx_bytes(&bytes);
+        section_xml(&bytes)
+    };
+
+    let xml = convert("{}");
+    assert!(!xml.contains("<hp:footer "));
+    assert!(!xml.contains(r#"numType="PAGE""#));
+
+    let xml = convert(r#"{"footerPageNumbers":"dashed"}"#);
+    let footer = &xml[xml.find("<hp:footer ").expect("footer 없음")..];
+    assert!(footer.contains(
+        r#"<hp:autoNum num="1" numType="PAGE"><hp:autoNumFormat type="DIGIT" userChar="" prefixChar="-" suffixChar="-""#
+    ));
+
+    let xml = convert(r#"{"footerPageNumbers":"romanUpper","footerText":"대외비"}"#);
+    let footer = &xml[xml.find("<hp:footer ").expect("footer 없음")..];
+    assert!(footer.contains("<hp:t>대외비 </hp:t>"));
+    assert!(footer.contains(r#"type="ROMAN_CAPITAL""#));
+
+    // 쪽 번호 없이 텍스트만
+    let xml = convert(r#"{"footerText":"사내 한정"}"#);
+    assert!(xml.contains("<hp:t>사내 한정</hp:t>"));
+    assert!(!xml.contains(r#"numType="PAGE""#));
+}
+
+#[test]
+fn test_header_footer_templates() {
+    let json = r#"{
+        "responseCode": "0",
+        "options": {
+            "headerTemplate": "{board_name} - {title} ({author}, {date})",
+            "footerTemplate": "{dept}{unknown}{보안등급}",
+            "footerPageNumbers": "numeric",
+            "dateFormat": "%Y.%m.%d"
+        },
+        "data": {
+            "article": {
+                "atclId": "TPL001",
+                "subject": "R&D <주간> 보고",
+                "boardName": "연구소",
+                "regEmpName": "홍길동",
+                "regDt": "2026-01-25 PM 12:00:00",
+                "보안등급": "\"대외비\""
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    verify_hwpx_bytes(&bytes);
+    let xml = section_xml(&bytes);
+
+    let header = &xml[xml.find("<hp:header ").expect("header 없음")..];
+    assert!(
+        header.contains("<hp:t>연구소 - R&amp;D &lt;주간&gt; 보고 (홍길동, 2026.01.25)</hp:t>"),
+        "{}",
+        header
+    );
+
+    // 부서는 비어 있고 모르는 자리표시자는 빈 값
+    let footer = &xml[xml.find("<hp:footer ").expect("footer 없음")..];
+    assert!(
+        footer.contains("<hp:t>&quot;대외비&quot; </hp:t>"),
+        "{}",
+        footer
+    );
+    assert!(footer.contains(r#"numType="PAGE""#));
+
+    let json = json.replace("%Y.%m.%d", "%Q");
+    let input: ApiResponse = serde_json::from_str(&json).unwrap();
+    let err = jsontohwpx::convert(&input, &base_path()).unwrap_err();
+    assert!(err.to_string().contains("dateFormat"), "{}", err);
+}
+
+#[test]
+fn test_page_setup_options() {
+    let convert = |options: &str| {
+        let json = format!(
+            r#"{{"responseCode":"0","options":{},"data":{{"article":{{"atclId":"PAGE001","contents":[{{"type":"text","value":"본문"}}]}}}}}}"#,
+            options
+        );
+        let input: ApiResponse = serde_json::from_str(&json).unwrap();
+        jsontohwpx::convert(&input, &base_path())
+    };
+
+    let bytes = convert(r#"{"pageSize":"A4","landscape":true}"#).unwrap();
+    verify_hwpx_bytes(&bytes);
+    let xml = section_xml(&bytes);
+    assert!(
+        xml.contains(r#"<hp:pagePr landscape="NARROWLY" width="84188" height="59528""#),
+        "{}",
+        xml
+    );
+
+    let bytes = convert(r#"{"pageSize":"a3","marginsMm":{"left":10,"right":10}}"#).unwrap();
+    let xml = section_xml(&bytes);
+    assert!(xml.contains(r#"landscape="WIDELY" width="84188" height="119055""#));
+    assert!(xml.contains(r#"left="2835" right="2835" top="5669" bottom="4252""#));
+
+    let err = convert(r#"{"pageSize":"B9"}"#).unwrap_err();
+    assert!(
+        matches!(err, jsontohwpx::JsonToHwpxError::Input(_)),
+        "{}",
+        err
+    );
+    assert!(err.to_string().contains("pageSize"), "{}", err);
+
+    let err = convert(r#"{"marginsMm":{"top":-5}}"#).unwrap_err();
+    assert!(err.to_string().contains("marginsMm.top"), "{}", err);
+}
+
+#[test]
+fn test_include_header_option() {
+    let json = r#"{
+        "responseCode": "0",
+        "options": {
+            "includeHeader": true
+        },
+        "data": {
+            "article": {
+                "atclId": "HDR001",
+                "subject": "헤더 포함 문서",
+                "regEmpName": "홍길동",
+                "regDeptName": "개발팀",
+                "contents": [
+                    { "type": "text", "value": "본문 내용" }
+                ]
+            }
+        }
+    }"#;
+
+    let doc = convert_and_verify(json);
+    let text = doc.extract_text();
+    assert!(text.contains("헤더 포함 문서"), "제목 포함 확인");
+    assert!(text.contains("홍길동"), "작성자 포함 확인");
+    assert!(text.contains("개발팀"), "부서 포함 확인");
+    assert!(text.contains("본문 내용"), "본문 포함 확인");
+}
+
+#[test]
+fn test_include_header_uses_line_divider() {
+    let json = r#"{
+        "responseCode": "0",
+        "options": { "includeHeader": true },
+        "data": {
+            "article": {
+                "atclId": "HDR003",
+                "subject": "구분선 문서",
+                "contents": [
+                    { "type": "text", "value": "본문" }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    verify_hwpx_bytes(&bytes);
+
+    let xml = section_xml(&bytes);
+    assert!(xml.contains("<hp:line "), "hp:line 구분선 확인");
+    assert!(!xml.contains("─"), "문자 구분선 제거 확인");
+}
+
+#[test]
+fn test_divider_content() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "DIV001",
+                "subject": "구분선",
+                "contents": [
+                    { "type": "text", "value": "위" },
+                    { "type": "divider" },
+                    { "type": "text", "value": "아래" }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    let text = verify_hwpx_bytes(&bytes).extract_text();
+    assert!(text.contains("위"));
+    assert!(text.contains("아래"));
+    assert_eq!(section_xml(&bytes).matches("<hp:line ").count(), 1);
+}
+
+#[test]
+fn test_link_content_and_text_links() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "LINK001",
+                "contents": [
+                    { "type": "link", "text": "사내 포털", "url": "https://portal.example.com" },
+                    {
+                        "type": "text",
+                        "value": "자세한 내용은 공지 참고\n문의: 담당자",
+                        "links": [
+                            { "text": "공지", "url": "https://example.com/notice" },
+                            { "text": "담당자", "url": "mailto:admin@example.com" }
+                        ]
+                    }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    let text = verify_hwpx_bytes(&bytes).extract_text();
+    assert!(text.contains("사내 포털"));
+    assert!(text.contains("문의:"));
+
+    let xml = section_xml(&bytes);
+    assert_eq!(xml.matches("<hp:hyperlink ").count(), 3);
+    assert!(xml.contains(r#"url="https://portal.example.com""#));
+    assert!(xml.contains(r#"url="mailto:admin@example.com""#));
+    assert!(xml.contains("<hp:t>자세한 내용은 </hp:t>"));
+
+    let links = HwpxReader::extract_hyperlinks(&bytes).unwrap();
+    let pairs: Vec<(&str, &str)> = links
+        .iter()
+        .map(|l| (l.text.as_str(), l.url.as_str()))
+        .collect();
+    assert_eq!(
+        pairs,
+        [
+            ("사내 포털", "https://portal.example.com"),
+            ("공지", "https://example.com/notice"),
+            ("담당자", "mailto:admin@example.com"),
+        ]
+    );
+}
+
+#[test]
+fn test_link_url_scheme_validation() {
+    for contents in [
+        r#"[{ "type": "link", "text": "x", "url": "javascript:alert(1)" }]"#,
+        r#"[{ "type": "text", "value": "a" },
+            { "type": "text", "value": "파일", "links": [{ "text": "파일", "url": "file:///etc/passwd" }] }]"#,
+    ] {
+        let json = format!(
+            r#"{{ "responseCode": "0", "data": {{ "article": {{ "atclId": "LINK002", "contents": {} }} }} }}"#,
+            contents
+        );
+        let input: ApiResponse = serde_json::from_str(&json).unwrap();
+        let err = jsontohwpx::convert(&input, &base_path()).unwrap_err();
+        assert_eq!(err.error_code(), "INPUT_ERROR");
+        assert!(err.to_string().contains("링크 URL"), "{}", err);
+    }
+
+    let json = r#"{ "responseCode": "0", "data": { "article": { "atclId": "LINK003", "contents": [
+        { "type": "text", "value": "a" },
+        { "type": "link", "text": "x", "url": "ftp://example.com" }
+    ] } } }"#;
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let err = jsontohwpx::convert(&input, &base_path()).unwrap_err();
+    assert!(err.to_string().contains("contents[1]"), "{}", err);
+}
+
+#[test]
+fn test_text_runs_content() {
+    let json = r##"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "RUNS001",
+                "contents": [
+                    { "type": "text", "value": "문자열 값" },
+                    {
+                        "type": "text",
+                        "value": [
+                            { "text": "중요: ", "bold": true, "color": "#FF0000" },
+                            { "text": "내일 점검", "italic": true, "underline": true, "size": 14 },
+                            { "text": "\n둘째 단락" }
+                        ]
+                    }
+                ]
+            }
+        }
+    }"##;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    let text = verify_hwpx_bytes(&bytes).extract_text();
+    assert!(text.contains("문자열 값\n"), "{}", text);
+    assert!(text.contains("중요: 내일 점검\n둘째 단락\n"), "{}", text);
+
+    // run마다 charPr이 다르다
+    let xml = section_xml(&bytes);
+    assert!(xml.contains("<hp:t>중요: </hp:t>"));
+    let header = header_xml(&bytes);
+    assert!(
+        header.contains(r##"bold="1" textColor="#FF0000""##),
+        "{}",
+        header
+    );
+    assert!(header.contains(r#"height="1400" italic="1""#), "{}", header);
+}
+
+#[test]
+fn test_text_run_color_validation() {
+    for color in ["red", "#FF00", "#GG0000", "FF0000"] {
+        let json = format!(
+            r#"{{ "responseCode": "0", "data": {{ "article": {{ "atclId": "RUNS002", "contents": [
+                {{ "type": "text", "value": [{{ "text": "색", "color": "{}" }}] }}
+            ] }} }} }}"#,
+            color
+        );
+        let input: ApiResponse = serde_json::from_str(&json).unwrap();
+        let err = jsontohwpx::convert(&input, &base_path()).unwrap_err();
+        assert_eq!(err.error_code(), "INPUT_ERROR");
+        assert!(err.to_string().contains("contents[0]"), "{}", err);
+        assert!(err.to_string().contains("#RRGGBB"), "{}", err);
+    }
+}
+
+#[test]
+fn test_text_content_style() {
+    let json = r##"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "STYLE001",
+                "contents": [
+                    { "type": "text", "value": "기본 단락" },
+                    {
+                        "type": "text",
+                        "value": "가운데 제목줄",
+                        "style": { "align": "center", "fontSize": 14, "bold": true, "color": "#003366", "fontFamily": "궁서" }
+                    },
+                    {
+                        "type": "text",
+                        "value": [{ "text": "오른쪽 run", "italic": true }],
+                        "style": { "align": "right" }
+                    }
+                ]
+            }
+        }
+    }"##;
+
+    // 알 수 없는 키(fontFamily)는 경고만 하고 변환한다
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    let text = verify_hwpx_bytes(&bytes).extract_text();
+    assert!(text.contains("가운데 제목줄\n"), "{}", text);
+
+    let xml = section_xml(&bytes);
+    let header = header_xml(&bytes);
+    let para_pr = |id: String| {
+        let start = header.find(&format!(r#"<hh:paraPr id="{}""#, id)).unwrap();
+        let end = start + header[start..].find("</hh:paraPr>").unwrap();
+        header[start..end].to_string()
+    };
+    assert!(para_pr(para_pr_of(&xml, "가운데 제목줄")).contains(r#"horizontal="CENTER""#));
+    assert!(para_pr(para_pr_of(&xml, "오른쪽 run")).contains(r#"horizontal="RIGHT""#));
+    assert!(
+        header.contains(r##"height="1400" bold="1" textColor="#003366""##),
+        "{}",
+        header
+    );
+
+    // style이 없으면 지금과 같이 기본 paraPr, charPr
+    assert_eq!(para_pr_of(&xml, "기본 단락"), "0");
+    assert_eq!(char_pr_of(&xml, "기본 단락").as_deref(), Some("0"));
+}
+
+/// section0.xml의 그림 크기 (hp:curSz) 목록
+fn picture_sizes(xml: &str) -> Vec<(u32, u32)> {
+    xml.split("<hp:pic ")
+        .skip(1)
+        .map(|pic| {
+            let cur = &pic[pic.find("<hp:curSz ").unwrap()..];
+            let attr = |name: &str| -> u32 {
+                let start = cur.find(&format!(r#"{}=""#, name)).unwrap() + name.len() + 2;
+                let len = cur[start..].find('"').unwrap();
+                cur[start..start + len].parse().unwrap()
+            };
+            (attr("width"), attr("height"))
+        })
+        .collect()
+}
+
+#[test]
+fn test_image_size_and_align() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "IMGSIZE001",
+                "contents": [
+                    { "type": "image", "url": "./test_img.png", "width": "100%" },
+                    { "type": "image", "url": "./test_img.png", "width": "50%", "align": "center" },
+                    { "type": "image", "url": "./test_img.png", "width": "40mm", "height": 96 }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    verify_hwpx_bytes(&bytes);
+
+    let xml = section_xml(&bytes);
+    let sizes = picture_sizes(&xml);
+    assert_eq!(sizes.len(), 3);
+    // 50%는 본문 너비의 절반 (mm 단위 반올림 오차 이내), 비율 유지
+    let (full_w, full_h) = sizes[0];
+    let (half_w, half_h) = sizes[1];
+    assert!(full_w.abs_diff(half_w * 2) <= 600, "{:?}", sizes);
+    assert!(full_h.abs_diff(half_h * 2) <= 600, "{:?}", sizes);
+    // 40mm x 96px (96 DPI = 1인치)
+    let mm = 7200.0 / 25.4;
+    assert!(sizes[2].0.abs_diff((40.0 * mm) as u32) <= 1, "{:?}", sizes);
+    assert!(sizes[2].1.abs_diff((25.0 * mm) as u32) <= 1, "{:?}", sizes);
+
+    // 가운데 정렬은 그림 단락의 paraPr로
+    let paragraphs: Vec<&str> = xml.split("<hp:p ").skip(1).collect();
+    let pic_para = |n: usize| -> String {
+        let p = paragraphs
+            .iter()
+            .filter(|p| p.contains("<hp:pic "))
+            .nth(n)
+            .unwrap();
+        let start = p.find(r#"paraPrIDRef=""#).unwrap() + r#"paraPrIDRef=""#.len();
+        p[start..start + p[start..].find('"').unwrap()].to_string()
+    };
+    assert_eq!(pic_para(0), "0");
+    let centered = pic_para(1);
+    let header = header_xml(&bytes);
+    let start = header
+        .find(&format!(r#"<hh:paraPr id="{}""#, centered))
+        .unwrap();
+    let end = start + header[start..].find("</hh:paraPr>").unwrap();
+    assert!(header[start..end].contains(r#"horizontal="CENTER""#));
+}
+
+#[test]
+fn test_image_caption() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "IMGCAP001",
+                "contents": [
+                    { "type": "text", "value": "앞 단락" },
+                    { "type": "image", "url": "./test_img.png", "caption": "그림 1. 분기 매출" },
+                    { "type": "text", "value": "뒤 단락" }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    let text = verify_hwpx_bytes(&bytes).extract_text();
+    assert_eq!(text, "앞 단락\n그림 1. 분기 매출\n뒤 단락\n");
+
+    // 캡션은 그림 단락 바로 다음 단락이며 가운데 정렬
+    let xml = section_xml(&bytes);
+    let paragraphs: Vec<&str> = xml.split("<hp:p ").skip(1).collect();
+    let pic = paragraphs
+        .iter()
+        .position(|p| p.contains("<hp:pic "))
+        .unwrap();
+    assert!(paragraphs[pic + 1].contains("<hp:t>그림 1. 분기 매출</hp:t>"));
+    let caption_pr = para_pr_of(&xml, "그림 1. 분기 매출");
+    let header = header_xml(&bytes);
+    let start = header
+        .find(&format!(r#"<hh:paraPr id="{}""#, caption_pr))
+        .unwrap();
+    let end = start + header[start..].find("</hh:paraPr>").unwrap();
+    assert!(header[start..end].contains(r#"horizontal="CENTER""#));
+}
+
+#[test]
+fn test_image_invalid_width() {
+    for (width, field) in [
+        (r#""넓게""#, "width"),
+        (r#""-10%""#, "width"),
+        (r#""abc""#, "height"),
+    ] {
+        let json = format!(
+            r#"{{ "responseCode": "0", "data": {{ "article": {{ "atclId": "IMGSIZE002", "contents": [
+                {{ "type": "text", "value": "앞" }},
+                {{ "type": "image", "url": "./test_img.png", "{}": {} }}
+            ] }} }} }}"#,
+            field, width
+        );
+        let input: ApiResponse = serde_json::from_str(&json).unwrap();
+        let err = jsontohwpx::convert(&input, &base_path()).unwrap_err();
+        assert_eq!(err.error_code(), "INPUT_ERROR");
+        assert!(err.to_string().contains("contents[1]"), "{}", err);
+        assert!(err.to_string().contains(field), "{}", err);
+    }
+}
+
+/// section0.xml에서 text를 담은 run의 charPrIDRef
+fn char_pr_of(xml: &str, text: &str) -> Option<String> {
+    let end = xml.find(&format!("<hp:t>{}</hp:t>", text))?;
+    let attr = r#"charPrIDRef=""#;
+    let start = xml[..end].rfind(attr)? + attr.len();
+    let len = xml[start..].find('"')?;
+    Some(xml[start..start + len].to_string())
+}
+
+/// section0.xml에서 text를 담은 단락의 paraPrIDRef
+fn para_pr_of(xml: &str, text: &str) -> String {
+    let paragraph = xml
+        .split("<hp:p ")
+        .find(|p| p.contains(&format!("<hp:t>{}</hp:t>", text)))
+        .unwrap_or_else(|| panic!("{} 단락 없음", text));
+    let attr = r#"paraPrIDRef=""#;
+    let start = paragraph.find(attr).unwrap() + attr.len();
+    let end = start + paragraph[start..].find('"').unwrap();
+    paragraph[start..end].to_string()
+}
+
+#[test]
+fn test_quote_content() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "QUOTE001",
+                "contents": [
+                    { "type": "text", "value": "본문" },
+                    { "type": "quote", "value": "인용 첫 줄\n인용 둘째 줄" },
+                    { "type": "html", "value": "<blockquote><p>HTML 인용</p></blockquote>" }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    let text = verify_hwpx_bytes(&bytes).extract_text();
+    assert!(text.contains("인용 첫 줄\n인용 둘째 줄\n"), "{}", text);
+    assert!(text.contains("HTML 인용"));
+
+    let xml = section_xml(&bytes);
+    let quote_pr = para_pr_of(&xml, "인용 첫 줄");
+    assert_ne!(quote_pr, "0");
+    assert_eq!(para_pr_of(&xml, "인용 둘째 줄"), quote_pr);
+    assert_eq!(para_pr_of(&xml, "HTML 인용"), quote_pr);
+    assert_eq!(para_pr_of(&xml, "본문"), "0");
+
+    // 왼쪽 세로줄만 그린다
+    let header = header_xml(&bytes);
+    assert!(header.contains(r##"<hh:leftBorder type="SOLID" width="0.12 mm" color="#AAAAAA"/>"##));
+    assert!(header.contains(r##"<hh:rightBorder type="NONE" width="0.12 mm" color="#AAAAAA"/>"##));
+}
+
+#[test]
+fn test_include_attachments_table() {
+    let json = r#"{
+        "responseCode": "0",
+        "options": { "includeAttachments": true },
+        "data": {
+            "article": {
+                "atclId": "ATTACH001",
+                "contents": [{ "type": "text", "value": "본문" }],
+                "attachments": [
+                    { "fileName": "회의록.pdf", "fileSize": 1572864, "regDt": "2025-01-24" },
+                    { "fileName": "사진.zip" }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    let text = verify_hwpx_bytes(&bytes).extract_text();
+    assert!(text.contains("본문\n첨부파일\n"), "{}", text);
+
+    let xml = section_xml(&bytes);
+    for cell in [
+        "파일명",
+        "크기",
+        "등록일",
+        "회의록.pdf",
+        "1.5 MB",
+        "2025-01-24",
+        "사진.zip",
+    ] {
+        assert!(
+            xml.contains(&format!("<hp:t>{}</hp:t>", cell)),
+            "{} 없음",
+            cell
+        );
+    }
+    assert_eq!(xml.matches("<hp:tc ").count(), 9);
+
+    // 옵션이 없으면 첨부파일 목록을 넣지 않는다
+    let json = json.replace(
+        r#""includeAttachments": true"#,
+        r#""includeAttachments": false"#,
+    );
+    let input: ApiResponse = serde_json::from_str(&json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    assert!(!section_xml(&bytes).contains("첨부파일"));
+}
+
+#[test]
+fn test_include_comments() {
+    let json = r#"{
+        "responseCode": "0",
+        "options": { "includeComments": true },
+        "data": {
+            "article": {
+                "atclId": "COMMENT001",
+                "contents": [{ "type": "text", "value": "본문" }],
+                "comments": [
+                    { "author": "홍길동", "createdAt": "2025-01-24 10:00", "body": "첫 댓글" },
+                    { "author": "김철수", "created_at": "2025-01-25 09:30", "body": "둘째 댓글\n두 줄" }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    let text = verify_hwpx_bytes(&bytes).extract_text();
+    assert!(
+        text.contains(
+            "본문\n댓글\n홍길동 · 2025-01-24 10:00\n첫 댓글\n\
+             김철수 · 2025-01-25 09:30\n둘째 댓글\n두 줄\n"
+        ),
+        "{}",
+        text
+    );
+
+    // 작성자 줄만 굵게
+    let runs = hwpers::HwpxReader::extract_styled_paragraphs(&bytes).unwrap();
+    let bold_of = |needle: &str| {
+        runs.iter()
+            .flatten()
+            .find(|r| r.text == needle)
+            .map(|r| r.bold)
+    };
+    assert_eq!(bold_of("홍길동 · 2025-01-24 10:00"), Some(true));
+    assert_eq!(bold_of("김철수 · 2025-01-25 09:30"), Some(true));
+    assert_eq!(bold_of("첫 댓글"), Some(false));
+
+    // 옵션이 없거나 댓글이 없으면 댓글 절을 넣지 않는다
+    for json in [
+        json.replace(r#""includeComments": true"#, r#""includeComments": false"#),
+        json.replace(r#""comments": ["#, r#""comments": [], "unused": ["#),
+    ] {
+        let input: ApiResponse = serde_json::from_str(&json).unwrap();
+        let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+        assert!(!section_xml(&bytes).contains("댓글"));
+    }
+}
+
+#[test]
+fn test_include_provenance() {
+    let json = r#"{
+        "responseCode": "0",
+        "options": { "includeProvenance": true },
+        "data": {
+            "article": {
+                "atclId": "PROV001",
+                "regDt": "2025-01-24 10:00:00",
+                "contents": [{ "type": "text", "value": "본문" }]
+            }
+        }
+    }"#;
+
+    let before = chrono::Local::now().format("%Y-%m-%d %H:%M").to_string();
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    let after = chrono::Local::now().format("%Y-%m-%d %H:%M").to_string();
+
+    // 마지막 단락이 작은 회색 생성 기록
+    let paragraphs = HwpxReader::extract_styled_paragraphs(&bytes).unwrap();
+    let last = paragraphs.last().unwrap();
+    assert_eq!(last.len(), 1);
+    let suffix = format!("에 hwpers v{}로 생성되었습니다", env!("CARGO_PKG_VERSION"));
+    let generated_at = last[0]
+        .text
+        .strip_prefix("본 문서는 ")
+        .and_then(|t| t.strip_suffix(&suffix))
+        .unwrap_or_else(|| panic!("{}", last[0].text));
+    assert!(
+        generated_at == before || generated_at == after,
+        "{}",
+        generated_at
+    );
+    assert_eq!(last[0].size_pt, 8.0);
+    assert!(header_xml(&bytes).contains(r##"textColor="#808080""##));
+
+    // ModifiedDate는 작성일시 대신 생성 시각
+    let metadata = HwpxReader::metadata_from_bytes(&bytes).unwrap();
+    assert_eq!(metadata.created_date, "2025-01-24 10:00");
+    assert_eq!(metadata.modified_date, generated_at);
+
+    let json = json.replace(
+        r#""includeProvenance": true"#,
+        r#""includeProvenance": false"#,
+    );
+    let input: ApiResponse = serde_json::from_str(&json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    assert!(!section_xml(&bytes).contains("생성되었습니다"));
+    let metadata = HwpxReader::metadata_from_bytes(&bytes).unwrap();
+    assert_eq!(metadata.modified_date, "2025-01-24 10:00");
+}
+
+#[test]
+fn test_convert_limits() {
+    use hwpers::jsontohwpx::ConvertLimits;
+
+    let convert = |contents: serde_json::Value, limits: ConvertLimits| {
+        let json = serde_json::json!({
+            "responseCode": "0",
+            "options": { "onError": "skip" },
+            "data": { "article": { "atclId": "LIMIT001", "contents": contents } }
+        });
+        let mut input: ApiResponse = serde_json::from_value(json).unwrap();
+        input.options.limits = limits;
+        jsontohwpx::convert(&input, &base_path())
+    };
+    let limits = ConvertLimits {
+        max_contents: 3,
+        max_table_cells: 4,
+        max_total_image_bytes: usize::MAX,
+        max_paragraphs: 20,
+    };
+    let text = |value: &str| serde_json::json!({ "type": "text", "value": value });
+
+    // 한도 안이면 변환
+    let contents = serde_json::json!([text("하나"), text("둘"), text("셋")]);
+    convert(contents, limits).unwrap();
+
+    let contents = serde_json::json!([text("하나"), text("둘"), text("셋"), text("넷")]);
+    let err = convert(contents, limits).unwrap_err();
+    assert_eq!(err.error_code(), "CONTENTS_LIMIT_EXCEEDED");
+    assert_eq!(err.exit_code(), 1);
+    assert_eq!(
+        err.to_string(),
+        "입력 에러: contents가 너무 많습니다 (4개, 최대 3개)"
+    );
+
+    // onError가 skip이어도 건너뛰지 않고 멈춘다
+    let contents = serde_json::json!([
+        text("앞"),
+        { "type": "table", "rows": [["a", "b", "c"], ["d", "e"]] }
+    ]);
+    let err = convert(contents, limits).unwrap_err();
+    assert_eq!(err.error_code(), "TABLE_CELLS_LIMIT_EXCEEDED");
+    assert_eq!(err.content_index(), Some(1));
+    assert!(
+        err.to_string()
+            .contains("contents[1] (table): 표 셀이 너무 많습니다 (2행 3열 = 6칸, 최대 4칸)"),
+        "{}",
+        err
+    );
+
+    let contents = serde_json::json!([{
+        "type": "markdown",
+        "value": "| a | b |\n|---|---|\n| c | d |\n| e | f |"
+    }]);
+    let err = convert(contents, limits).unwrap_err();
+    assert_eq!(err.error_code(), "TABLE_CELLS_LIMIT_EXCEEDED");
+
+    // 큰 colspan은 기본 한도에서 격자를 만들기 전에 거부한다
+    let contents = serde_json::json!([{
+        "type": "table",
+        "value": "<table><tr><td colspan=\"100000\">A</td></tr><tr><td>B</td></tr></table>"
+    }]);
+    let err = convert(contents, ConvertLimits::DEFAULT).unwrap_err();
+    assert_eq!(err.error_code(), "TABLE_CELLS_LIMIT_EXCEEDED");
+
+    // onError가 fail이면 검증(validate)에서도 격자를 만들기 전에 한도로 멈춘다
+    let json = serde_json::json!({
+        "responseCode": "0",
+        "data": { "article": { "atclId": "LIMIT002", "contents": [{
+            "type": "table",
+            "value": "<table><tr><td rowspan=\"200000\" colspan=\"200000\">A</td></tr></table>"
+        }] } }
+    });
+    let input: ApiResponse = serde_json::from_value(json).unwrap();
+    input.validate().unwrap();
+    assert!(input.content_issues().is_empty());
+    let err = jsontohwpx::convert(&input, &base_path()).unwrap_err();
+    assert_eq!(err.error_code(), "TABLE_CELLS_LIMIT_EXCEEDED");
+    assert_eq!(err.content_index(), Some(0));
+
+    let lines = vec!["줄"; 21].join("\n");
+    let err = convert(serde_json::json!([text(&lines)]), limits).unwrap_err();
+    assert_eq!(err.error_code(), "PARAGRAPHS_LIMIT_EXCEEDED");
+    assert_eq!(err.content_index(), Some(0));
+
+    // 그림 바이트 합계 (같은 그림은 한 번만 센다)
+    let png = std::fs::metadata(base_path().join("test_img.png"))
+        .unwrap()
+        .len() as usize;
+    let jpg = std::fs::metadata(base_path().join("test_img.jpg"))
+        .unwrap()
+        .len() as usize;
+    let image = |url: &str| serde_json::json!({ "type": "image", "url": url });
+    let limits = ConvertLimits {
+        max_total_image_bytes: png + jpg - 1,
+        ..ConvertLimits::DEFAULT
+    };
+    let contents = serde_json::json!([image("test_img.png"), image("test_img.png")]);
+    convert(contents, limits).unwrap();
+    let contents = serde_json::json!([image("test_img.png"), image("test_img.jpg")]);
+    let err = convert(contents, limits).unwrap_err();
+    assert_eq!(err.error_code(), "IMAGE_BYTES_LIMIT_EXCEEDED");
+    assert_eq!(err.content_index(), Some(1));
+}
+
+#[test]
+fn test_code_content() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "CODE001",
+                "contents": [
+                    { "type": "text", "value": "설정 예시" },
+                    {
+                        "type": "code",
+                        "language": "yaml",
+                        "value": "server:\n  port: 8080\n\n  tls:\n    enabled: true"
+                    },
+                    { "type": "markdown", "value": "```\nfn main() {\n    run();\n}\n```" }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    let text = verify_hwpx_bytes(&bytes).extract_text();
+    // 들여쓰기가 그대로 남는다 (빈 단락은 extract_text에 나오지 않음)
+    assert!(
+        text.contains("server:\n  port: 8080\n  tls:\n    enabled: true\n"),
+        "{}",
+        text
+    );
+    assert!(text.contains("fn main() {\n    run();\n}\n"), "{}", text);
+
+    let xml = section_xml(&bytes);
+    let code_pr = para_pr_of(&xml, "server:");
+    assert_ne!(code_pr, "0");
+    assert_eq!(para_pr_of(&xml, "    run();"), code_pr);
+    // 빈 줄도 한 단락 (5줄 + 3줄)
+    let code_attr = format!(r#"paraPrIDRef="{}""#, code_pr);
+    assert_eq!(xml.matches(&code_attr).count(), 8);
+
+    // 고정폭 글꼴이 글꼴 1로 등록되고 charPr이 그것을 가리킨다
+    let header = header_xml(&bytes);
+    assert!(
+        header.contains(r#"<hh:font id="1" face="굴림체""#),
+        "{}",
+        header
+    );
+    assert!(
+        header.contains(r#"<hh:fontRef hangul="1" latin="1""#),
+        "{}",
+        header
+    );
+    assert!(header.contains(r#"horizontal="LEFT""#));
+}
+
+/// section0.xml에서 pageBreak="1"인 단락의 텍스트 (본문 단락만 있는 문서용)
+fn page_break_paragraph_texts(bytes: &[u8]) -> Vec<String> {
+    section_xml(bytes)
+        .split("<hp:p ")
+        .skip(1)
+        .filter(|p| p[..p.find('>').unwrap()].contains(r#"pageBreak="1""#))
+        .map(|p| match (p.find("<hp:t>"), p.find("</hp:t>")) {
+            (Some(start), Some(end)) => p[start + "<hp:t>".len()..end].to_string(),
+            _ => String::new(),
+        })
+        .collect()
+}
+
+#[test]
+fn test_pagebreak_content() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "PB001",
+                "contents": [
+                    { "type": "text", "value": "첫 쪽" },
+                    { "type": "pagebreak" },
+                    { "type": "text", "value": "둘째 쪽" }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    let text = verify_hwpx_bytes(&bytes).extract_text();
+    // 쪽 나누기 앞뒤에는 구분용 빈 단락을 넣지 않는다
+    assert_eq!(text, "첫 쪽\n둘째 쪽\n");
+    assert_eq!(page_break_paragraph_texts(&bytes), ["둘째 쪽"]);
+}
+
+#[test]
+fn test_consecutive_pagebreaks_at_edges() {
     let json = r#"{
         "responseCode": "0",
         "data": {
             "article": {
-                "atclId": "TEXT001",
-                "subject": "텍스트 문서",
+                "atclId": "PB002",
                 "contents": [
-                    { "type": "text", "value": "안녕하세요, HWPX 문서입니다." }
+                    { "type": "pagebreak" },
+                    { "type": "text", "value": "본문" },
+                    { "type": "pagebreak" },
+                    { "type": "pagebreak" },
+                    { "type": "text", "value": "끝" },
+                    { "type": "pagebreak" }
                 ]
             }
         }
     }"#;
 
-    let doc = convert_and_verify(json);
-    let text = doc.extract_text();
-    assert!(
-        text.contains("안녕하세요"),
-        "문서에 '안녕하세요' 텍스트가 포함되어야 합니다. 실제: {}",
-        text
-    );
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    verify_hwpx_bytes(&bytes);
+    // 연속된 쪽 나누기는 빈 쪽을 만들고, 마지막 쪽 나누기는 버린다
+    assert_eq!(page_break_paragraph_texts(&bytes), ["본문", "", "끝"]);
 }
 
 #[test]
-fn test_multiline_text() {
+fn test_header_fields_filter() {
     let json = r#"{
         "responseCode": "0",
+        "options": {
+            "includeHeader": true,
+            "headerFields": ["subject"]
+        },
         "data": {
             "article": {
-                "atclId": "TEXT002",
-                "subject": "멀티라인",
+                "atclId": "HDR002",
+                "subject": "필터된 헤더",
+                "regEmpName": "숨길 이름",
                 "contents": [
-                    { "type": "text", "value": "첫 줄\n둘째 줄\n셋째 줄" }
+                    { "type": "text", "value": "본문" }
                 ]
             }
         }
@@ -84,85 +1521,159 @@ fn test_multiline_text() {
 
     let doc = convert_and_verify(json);
     let text = doc.extract_text();
-    assert!(text.contains("첫 줄"), "첫 줄 포함 확인");
-    assert!(text.contains("둘째 줄"), "둘째 줄 포함 확인");
-    assert!(text.contains("셋째 줄"), "셋째 줄 포함 확인");
+    assert!(text.contains("필터된 헤더"), "subject 포함 확인");
+    assert!(!text.contains("숨길 이름"), "regEmpName 미포함 확인");
 }
 
 #[test]
-fn test_multiple_text_contents() {
+fn test_reg_dt_date_format() {
+    let convert = |reg_dt: &str, options: &str| {
+        let json = format!(
+            r#"{{"responseCode":"0","options":{{"includeHeader":true,"headerFields":["regDt"]{}}},"data":{{"article":{{"atclId":"DATE001","regDt":"{}","contents":[]}}}}}}"#,
+            options, reg_dt
+        );
+        let input: ApiResponse = serde_json::from_str(&json).unwrap();
+        let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+        let doc = verify_hwpx_bytes(&bytes);
+        let metadata = HwpxReader::metadata_from_bytes(&bytes).unwrap();
+        (doc.extract_text(), metadata.created_date)
+    };
+
+    // ISO 8601과 예전 "AM/PM" 형태 모두 기본 형식으로
+    let (text, created) = convert("2025-01-30T10:00:00+09:00", "");
+    assert!(text.starts_with("작성일: 2025-01-30 10:00\n"), "{}", text);
+    assert_eq!(created, "2025-01-30 10:00");
+
+    let (text, _) = convert("2025-01-24 PM 03:05:00", "");
+    assert!(text.starts_with("작성일: 2025-01-24 15:05\n"), "{}", text);
+
+    let (text, created) = convert(
+        "2025-01-24 AM 10:00:00",
+        r#","dateFormat":"%Y년 %m월 %d일""#,
+    );
+    assert!(text.starts_with("작성일: 2025년 01월 24일\n"), "{}", text);
+    assert_eq!(created, "2025년 01월 24일");
+
+    // 읽을 수 없으면 그대로
+    let (text, _) = convert("지난주 금요일", "");
+    assert!(text.starts_with("작성일: 지난주 금요일\n"), "{}", text);
+}
+
+#[test]
+fn test_header_label_locale() {
     let json = r#"{
         "responseCode": "0",
+        "options": {
+            "includeHeader": true,
+            "headerFields": ["subject", "regEmpName", "보안등급"],
+            "labelLocale": "en",
+            "labelOverrides": { "보안등급": "Classification" }
+        },
         "data": {
             "article": {
-                "atclId": "TEXT003",
-                "subject": "연속 텍스트",
+                "atclId": "HDR005",
+                "subject": "Weekly report",
+                "regEmpName": "홍길동",
+                "regDeptName": "숨길 부서",
+                "보안등급": "Internal",
                 "contents": [
-                    { "type": "text", "value": "첫 번째 텍스트" },
-                    { "type": "text", "value": "두 번째 텍스트" }
+                    { "type": "text", "value": "본문" }
                 ]
             }
         }
     }"#;
 
-    let doc = convert_and_verify(json);
-    let text = doc.extract_text();
-    assert!(text.contains("첫 번째 텍스트"));
-    assert!(text.contains("두 번째 텍스트"));
+    let text = convert_and_verify(json).extract_text();
+    assert!(
+        text.starts_with("Title: Weekly report\nAuthor: 홍길동\nClassification: Internal\n"),
+        "{}",
+        text
+    );
+    assert!(!text.contains("제목") && !text.contains("작성자"));
+    assert!(!text.contains("숨길 부서"));
+
+    let json = json.replace(r#""labelLocale": "en""#, r#""labelLocale": "jp""#);
+    let input: ApiResponse = serde_json::from_str(&json).unwrap();
+    let err = jsontohwpx::convert(&input, &base_path()).unwrap_err();
+    assert!(err.to_string().contains("labelLocale"), "{}", err);
 }
 
 #[test]
-fn test_include_header_option() {
+fn test_header_fields_with_extra_keys() {
     let json = r#"{
         "responseCode": "0",
         "options": {
-            "includeHeader": true
+            "includeHeader": true,
+            "headerFields": ["보안등급", "subject", "문서번호"]
         },
         "data": {
             "article": {
-                "atclId": "HDR001",
-                "subject": "헤더 포함 문서",
-                "regEmpName": "홍길동",
-                "regDeptName": "개발팀",
+                "atclId": "HDR004",
+                "subject": "추가 필드",
+                "문서번호": "DOC-2025-001",
+                "보안등급": "대외비",
                 "contents": [
-                    { "type": "text", "value": "본문 내용" }
+                    { "type": "text", "value": "본문" }
                 ]
             }
         }
     }"#;
 
-    let doc = convert_and_verify(json);
-    let text = doc.extract_text();
-    assert!(text.contains("헤더 포함 문서"), "제목 포함 확인");
-    assert!(text.contains("홍길동"), "작성자 포함 확인");
-    assert!(text.contains("개발팀"), "부서 포함 확인");
-    assert!(text.contains("본문 내용"), "본문 포함 확인");
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    let text = verify_hwpx_bytes(&bytes).extract_text();
+    assert!(
+        text.starts_with("보안등급: 대외비\n제목: 추가 필드\n문서번호: DOC-2025-001\n"),
+        "{}",
+        text
+    );
+    assert_eq!(text.matches("대외비").count(), 1);
+    assert_eq!(text.matches("DOC-2025-001").count(), 1);
+
+    // 라벨은 기본 필드와 같은 굵은 글자
+    let runs = hwpers::HwpxReader::extract_styled_paragraphs(&bytes).unwrap();
+    let bold_of = |needle: &str| {
+        runs.iter()
+            .flatten()
+            .find(|r| r.text == needle)
+            .map(|r| r.bold)
+    };
+    assert_eq!(bold_of("보안등급: "), Some(true));
+    assert_eq!(bold_of("대외비"), Some(false));
 }
 
 #[test]
-fn test_header_fields_filter() {
+fn test_malformed_table_on_error() {
     let json = r#"{
         "responseCode": "0",
-        "options": {
-            "includeHeader": true,
-            "headerFields": ["subject"]
-        },
+        "options": { "onError": "fail" },
         "data": {
             "article": {
-                "atclId": "HDR002",
-                "subject": "필터된 헤더",
-                "regEmpName": "숨길 이름",
+                "atclId": "TBL_ERR002",
                 "contents": [
-                    { "type": "text", "value": "본문" }
+                    { "type": "table", "value": "<table></table>" },
+                    { "type": "text", "value": "표 다음" }
                 ]
             }
         }
     }"#;
 
-    let doc = convert_and_verify(json);
-    let text = doc.extract_text();
-    assert!(text.contains("필터된 헤더"), "subject 포함 확인");
-    assert!(!text.contains("숨길 이름"), "regEmpName 미포함 확인");
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    assert!(jsontohwpx::convert(&input, &base_path()).is_err());
+
+    let json = json.replace(r#""onError": "fail""#, r#""onError": "skip""#);
+    let input: ApiResponse = serde_json::from_str(&json).unwrap();
+    let output = jsontohwpx::convert_to_output(&input, &base_path()).unwrap();
+    let text = verify_hwpx_bytes(&output.bytes).extract_text();
+    assert_eq!(text, "[표 변환 실패]\n표 다음\n");
+    assert_eq!(output.warnings.len(), 1);
+    assert_eq!(output.warnings[0].code, "CONTENT_SKIPPED");
+
+    // 예전 API는 같은 경고를 문자열로 돌려준다
+    let (bytes, warnings) = jsontohwpx::convert_with_warnings(&input, &base_path()).unwrap();
+    assert_eq!(bytes.len(), output.bytes.len());
+    assert_eq!(warnings, [output.warnings[0].to_string()]);
+    assert!(warnings[0].starts_with("contents[0]: "), "{}", warnings[0]);
 }
 
 #[test]
@@ -183,6 +1694,75 @@ fn test_table_content() {
     convert_and_verify(json);
 }
 
+#[test]
+fn test_table_rows_content_matches_html() {
+    let convert_table = |table: serde_json::Value| {
+        let json = serde_json::json!({
+            "responseCode": "0",
+            "data": { "article": { "atclId": "TBL002", "contents": [table] } }
+        });
+        let input: ApiResponse = serde_json::from_value(json).unwrap();
+        let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+        verify_hwpx_bytes(&bytes);
+        bytes
+    };
+
+    let from_html = convert_table(serde_json::json!({
+        "type": "table",
+        "value": "<table><tr><th colspan=\"2\">인적 사항</th></tr><tr><td>이름</td><td>나이</td></tr><tr><td>홍길동</td><td>30</td></tr></table>"
+    }));
+    let from_rows = convert_table(serde_json::json!({
+        "type": "table",
+        "rows": [["인적 사항", ""], ["이름", "나이"], ["홍길동", "30"]],
+        "header_rows": 1,
+        "merges": [{ "row": 0, "col": 0, "colspan": 2 }]
+    }));
+
+    let html_tables = HwpxReader::extract_tables(&from_html).unwrap();
+    let rows_tables = HwpxReader::extract_tables(&from_rows).unwrap();
+    assert_eq!(rows_tables, html_tables);
+    assert_eq!(rows_tables[0].cell(0, 1).unwrap().text, "인적 사항");
+    assert_eq!(rows_tables[0].rows[2][0].text, "홍길동");
+
+    // 머리글 행의 셀만 header="1"
+    for bytes in [&from_html, &from_rows] {
+        assert_eq!(section_xml(bytes).matches(r#"header="1""#).count(), 1);
+    }
+}
+
+#[test]
+fn test_table_rows_merge_out_of_bounds_fails() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "TBL003",
+                "contents": [
+                    { "type": "text", "value": "앞" },
+                    {
+                        "type": "table",
+                        "rows": [["A", "B"], ["C", "D"]],
+                        "merges": [
+                            { "row": 0, "col": 0, "colspan": 2 },
+                            { "row": 1, "col": 1, "rowspan": 2 }
+                        ]
+                    }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let err = jsontohwpx::convert(&input, &base_path()).unwrap_err();
+    assert_eq!(err.error_code(), "INPUT_ERROR");
+    assert!(
+        err.to_string()
+            .contains("contents[1]: merges[1]: 병합 범위가 표(2행 2열)를 벗어납니다"),
+        "{}",
+        err
+    );
+}
+
 #[test]
 fn test_mixed_content_types() {
     let json = r#"{
@@ -233,6 +1813,111 @@ fn test_convert_to_file() {
     verify_hwpx_bytes(&bytes);
 }
 
+#[test]
+fn test_convert_to_file_filename_template() {
+    let json = r#"{
+        "responseCode": "0",
+        "options": { "filenameTemplate": "{date}_{department}_{title}.hwpx" },
+        "data": {
+            "article": {
+                "atclId": "FILE002",
+                "subject": "분기/보고: ../결산",
+                "regDeptName": "재무팀",
+                "regDt": "2025-01-24 AM 10:00:00",
+                "contents": [{ "type": "text", "value": "본문" }]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    assert_eq!(
+        input.output_filename(),
+        "20250124_재무팀_분기_보고_ .._결산.hwpx"
+    );
+
+    // 디렉터리에 저장하면 템플릿 이름, 그 디렉터리 밖으로 나가지 않는다
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = jsontohwpx::convert_to_file(&input, &base_path(), temp_dir.path()).unwrap();
+    assert_eq!(path, temp_dir.path().join(input.output_filename()));
+    verify_hwpx_bytes(&std::fs::read(&path).unwrap());
+
+    // 템플릿 자체의 경로와 모르는 자리표시자는 입력 에러
+    for (template, message) in [
+        ("../{article_id}.hwpx", "경로 구분자"),
+        ("{author}.hwpx", "알 수 없는 자리표시자 {author}"),
+    ] {
+        let json = json.replace("{date}_{department}_{title}.hwpx", template);
+        let input: ApiResponse = serde_json::from_str(&json).unwrap();
+        let err = jsontohwpx::convert(&input, &base_path()).unwrap_err();
+        assert!(
+            matches!(err, jsontohwpx::JsonToHwpxError::Input(_)),
+            "{:?}",
+            err
+        );
+        assert!(err.to_string().contains(message), "{}", err);
+    }
+}
+
+#[test]
+fn test_file_content() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("members.csv"),
+        "이름,부서\r\n홍길동,\"개발, 운영\"\r\n김철수\r\n",
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("note.txt"), "첫 줄\n\n셋째 줄\n").unwrap();
+    let (euc_kr, _, _) = encoding_rs::EUC_KR.encode("옛 인코딩");
+    std::fs::write(dir.path().join("legacy.txt"), euc_kr).unwrap();
+
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "FILE003",
+                "contents": [
+                    { "type": "file", "url": "members.csv", "render": "table" },
+                    { "type": "file", "url": "note.txt" },
+                    { "type": "file", "url": "legacy.txt", "render": "text" }
+                ]
+            }
+        }
+    }"#;
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, dir.path()).unwrap();
+    let text = verify_hwpx_bytes(&bytes).extract_text();
+    assert!(text.contains("첫 줄\n셋째 줄\n"), "{}", text);
+    assert!(text.contains("옛 인코딩"), "{}", text);
+
+    let tables = HwpxReader::extract_tables(&bytes).unwrap();
+    assert_eq!(tables.len(), 1);
+    assert_eq!(
+        tables[0].grid(),
+        vec![
+            vec!["이름", "부서"],
+            vec!["홍길동", "개발, 운영"],
+            vec!["김철수", ""],
+        ]
+    );
+
+    // 읽을 수 없는 파일은 위치가 붙은 변환 에러
+    std::fs::write(dir.path().join("binary.txt"), [0xFFu8, 0xFE, 0xFF]).unwrap();
+    let json = json.replace("legacy.txt", "binary.txt");
+    let input: ApiResponse = serde_json::from_str(&json).unwrap();
+    let err = jsontohwpx::convert(&input, dir.path()).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("contents[2] (file): 파일 인코딩을 알 수 없습니다"),
+        "{}",
+        err
+    );
+
+    let big = vec![b'a'; jsontohwpx::file::MAX_FILE_BYTES as usize + 1];
+    std::fs::write(dir.path().join("binary.txt"), big).unwrap();
+    let err = jsontohwpx::convert(&input, dir.path()).unwrap_err();
+    assert!(err.to_string().contains("파일이 너무 큽니다"), "{}", err);
+}
+
 #[test]
 fn test_special_characters_in_text() {
     let json = r#"{
@@ -302,3 +1987,481 @@ fn test_example_with_metadata_header() {
     assert!(text.contains("IT인프라팀"), "부서 포함");
     assert!(text.contains("2025년 2월 1일"), "본문 내용 포함");
 }
+
+#[test]
+fn test_board_name_and_tags_metadata() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "META001",
+                "subject": "점검 안내",
+                "boardName": "공지사항",
+                "tags": ["보안", "정기점검"],
+                "contents": [
+                    { "type": "text", "value": "본문" }
+                ]
+            }
+        }
+    }"#;
+
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    let metadata = HwpxReader::metadata_from_bytes(&bytes).unwrap();
+    assert_eq!(metadata.title, "점검 안내");
+    assert_eq!(metadata.subject, "공지사항");
+    assert_eq!(metadata.keywords, vec!["보안", "정기점검"]);
+}
+
+#[test]
+fn test_convert_with_text_matches_reader() {
+    let extract_options = hwpers::hwpx::ExtractOptions {
+        include_tables: true,
+        ..Default::default()
+    };
+    let json = r##"{
+        "responseCode": "0",
+        "options": { "includeHeader": true, "includeAttachments": true, "includeComments": true },
+        "data": {
+            "article": {
+                "atclId": "TEXT001",
+                "subject": "텍스트 추출",
+                "regEmpName": "홍길동",
+                "regDt": "2025-01-24 10:00:00",
+                "contents": [
+                    { "type": "text", "value": "첫 줄\t탭\n\n셋째 줄" },
+                    { "type": "text", "value": [{ "text": "굵게", "bold": true }, { "text": " 보통" }] },
+                    { "type": "text", "value": "자세한 내용은 안내를 보세요", "links": [{ "text": "안내", "url": "https://example.com" }] },
+                    { "type": "heading", "value": "소제목", "level": 2 },
+                    { "type": "quote", "value": "인용" },
+                    { "type": "code", "value": "fn main() {}" },
+                    { "type": "list", "ordered": true, "items": [{ "text": "하나", "children": [{ "text": "하위" }] }] },
+                    { "type": "table", "value": "<table><tr><th colspan=\"2\">머리</th></tr><tr><td>가</td><td></td></tr></table>" },
+                    { "type": "table", "rows": [["이름", "부서"], ["김철수", "개발\n운영"]], "merges": [] },
+                    { "type": "image", "url": "test_img.png", "caption": "그림 설명" },
+                    { "type": "checkbox", "label": "승인", "checked": true },
+                    { "type": "divider" },
+                    { "type": "pagebreak" },
+                    { "type": "html", "value": "<p>HTML <b>본문</b></p><table><tr><td>셀</td></tr></table>" },
+                    { "type": "markdown", "value": "# 제목\n\n| a | b |\n|---|---|\n| c | d |" }
+                ],
+                "attachments": [{ "fileName": "회의록.pdf", "fileSize": 1024 }],
+                "comments": [{ "author": "김철수", "body": "댓글" }]
+            }
+        }
+    }"##;
+    let full_document = std::fs::read_to_string(base_path().join("full_document.json")).unwrap();
+
+    for json in [json, &full_document] {
+        let input: ApiResponse = serde_json::from_str(json).unwrap();
+        let (bytes, text) = jsontohwpx::convert_with_text(&input, &base_path()).unwrap();
+        let expected = HwpxReader::extract_text_with_options(&bytes, &extract_options).unwrap();
+        assert_eq!(text, expected);
+        assert!(!text.is_empty());
+    }
+}
+
+#[test]
+fn test_convert_output_hash_is_stable() {
+    use sha2::{Digest, Sha256};
+
+    let json = std::fs::read_to_string(base_path().join("full_document.json")).unwrap();
+    let input: ApiResponse = serde_json::from_str(&json).unwrap();
+
+    let first = jsontohwpx::convert(&input, &base_path()).unwrap();
+    let second = jsontohwpx::convert(&input, &base_path()).unwrap();
+    assert_eq!(Sha256::digest(&first), Sha256::digest(&second));
+}
+
+#[test]
+fn test_hwpx_to_json_round_trip() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "RT001",
+                "subject": "왕복 변환",
+                "regEmpName": "홍길동",
+                "regDeptName": "개발팀",
+                "boardName": "공지사항",
+                "tags": ["보고", "분기"],
+                "contents": [
+                    { "type": "text", "value": "첫 줄\n둘째 줄" },
+                    { "type": "table", "value": "<table><tr><th colspan=\"2\">제목 &amp; 요약</th></tr><tr><td rowspan=\"2\">그룹</td><td>A</td></tr><tr><td>B</td></tr></table>" },
+                    { "type": "image", "url": "test_img.png" },
+                    { "type": "text", "value": "마지막 단락" }
+                ]
+            }
+        }
+    }"#;
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+
+    let export = jsontohwpx::hwpxtojson::convert(&bytes).unwrap();
+    let article = &export.data.article;
+    assert_eq!(article.subject, "왕복 변환");
+    assert_eq!(article.reg_emp_name.as_deref(), Some("홍길동"));
+    assert_eq!(article.reg_dept_name.as_deref(), Some("개발팀"));
+    assert_eq!(article.board_name.as_deref(), Some("공지사항"));
+    assert_eq!(article.tags, vec!["보고", "분기"]);
+    assert_eq!(article.contents.len(), 4);
+    assert_eq!(
+        article.contents[0],
+        jsontohwpx::model::Content::Text {
+            value: "첫 줄\n둘째 줄".into(),
+            links: Vec::new(),
+            style: None,
+        }
+    );
+    assert!(matches!(
+        &article.contents[2],
+        jsontohwpx::model::Content::Image { base64: Some(_), format: Some(f), .. } if f == "png"
+    ));
+    assert_eq!(
+        article.contents[3],
+        jsontohwpx::model::Content::Text {
+            value: "마지막 단락".into(),
+            links: Vec::new(),
+            style: None,
+        }
+    );
+
+    // 내보낸 JSON을 다시 변환해도 본문과 셀 값이 같다
+    let mut value = serde_json::to_value(&export).unwrap();
+    value["data"]["article"]["atclId"] = "RT001".into();
+    let reparsed: ApiResponse = serde_json::from_value(value).unwrap();
+    let bytes2 = jsontohwpx::convert(&reparsed, &base_path()).unwrap();
+
+    let first = HwpxReader::from_bytes(&bytes).unwrap().extract_text();
+    let second = HwpxReader::from_bytes(&bytes2).unwrap().extract_text();
+    assert_eq!(first, second);
+
+    let tables = HwpxReader::extract_tables(&bytes).unwrap();
+    let tables2 = HwpxReader::extract_tables(&bytes2).unwrap();
+    assert_eq!(tables2.len(), 1);
+    assert_eq!(tables[0].grid(), tables2[0].grid());
+    assert_eq!(tables2[0].cell(0, 0).unwrap().text, "제목 & 요약");
+    assert_eq!(tables2[0].cell(2, 0).unwrap().text, "그룹");
+    assert_eq!(HwpxReader::extract_images(&bytes2).unwrap().len(), 1);
+}
+
+#[test]
+fn test_html_content() {
+    let html = r#"
+        <h2>주간 소식</h2>
+        <p>이번 주 <b>핵심</b> 내용은 <a href="https://example.com/news">공지</a>를 참고하세요.<br>둘째 줄</p>
+        <ul><li>첫째</li><li>둘째<ol><li>하위</li></ol></li></ul>
+        <table><tr><th>항목</th><th>값</th></tr><tr><td>매출</td><td>120</td></tr></table>
+        <p><img src="test_img.png"></p>
+    "#;
+    let json = serde_json::json!({
+        "responseCode": "0",
+        "data": { "article": { "atclId": "HTML001", "contents": [
+            { "type": "html", "value": html }
+        ] } }
+    });
+
+    let input: ApiResponse = serde_json::from_value(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    let text = verify_hwpx_bytes(&bytes).extract_text();
+    assert!(text.contains("주간 소식"), "{}", text);
+    // 굵은 글자와 링크는 별도 run으로 나뉜다
+    let xml = section_xml(&bytes);
+    for run in ["이번 주 ", "핵심", " 내용은 ", "공지", "를 참고하세요."] {
+        assert!(xml.contains(&format!("<hp:t>{}</hp:t>", run)), "{}", run);
+    }
+    assert!(text.contains("둘째 줄"));
+    assert!(text.contains("• 첫째"));
+    assert!(text.contains("◦ 하위"));
+
+    let links = HwpxReader::extract_hyperlinks(&bytes).unwrap();
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].url, "https://example.com/news");
+    assert_eq!(links[0].text, "공지");
+
+    let tables = HwpxReader::extract_tables(&bytes).unwrap();
+    assert_eq!(tables.len(), 1);
+    assert_eq!(tables[0].rows[1][0].text, "매출");
+    assert_eq!(tables[0].rows[1][1].text, "120");
+
+    assert_eq!(HwpxReader::extract_images(&bytes).unwrap().len(), 1);
+}
+
+#[test]
+fn test_markdown_content() {
+    let markdown = "# 배포 가이드\n\
+        \n\
+        배포 전 **반드시** 아래 항목을 확인합니다.\n\
+        \n\
+        - 빌드 확인\n\
+        \x20 - 테스트 통과\n\
+        - 태그 생성\n\
+        \n\
+        | 환경 | 주소 |\n\
+        |------|------|\n\
+        | 운영 | prod.example.com |\n\
+        \n\
+        ```\n\
+        cargo build --release\n\
+        ```\n";
+    let json = serde_json::json!({
+        "responseCode": "0",
+        "data": { "article": { "atclId": "MD001", "contents": [
+            { "type": "markdown", "value": markdown }
+        ] } }
+    });
+
+    let input: ApiResponse = serde_json::from_value(json).unwrap();
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    let text = verify_hwpx_bytes(&bytes).extract_text();
+    for expected in ["배포 가이드", "• 빌드 확인", "◦ 테스트 통과", "• 태그 생성"]
+    {
+        assert!(text.contains(expected), "{} 없음:\n{}", expected, text);
+    }
+    assert!(section_xml(&bytes).contains("<hp:t>반드시</hp:t>"));
+    assert!(section_xml(&bytes).contains("<hp:t>cargo build --release</hp:t>"));
+    assert!(header_xml(&bytes).contains(r#"bold="1""#));
+
+    let tables = HwpxReader::extract_tables(&bytes).unwrap();
+    assert_eq!(tables.len(), 1);
+    assert_eq!(tables[0].rows[0][1].text, "주소");
+    assert_eq!(tables[0].rows[1][1].text, "prod.example.com");
+}
+
+#[test]
+fn test_convert_batch_modes() {
+    use hwpers::jsontohwpx::model::{OnError, Options};
+    use hwpers::jsontohwpx::{BatchMode, BatchOutput};
+
+    let inputs: Vec<ApiResponse> = ["첫째", "둘째", "셋째"]
+        .iter()
+        .enumerate()
+        .map(|(idx, name)| {
+            serde_json::from_value(serde_json::json!({
+                "responseCode": "0",
+                "data": { "article": {
+                    "atclId": format!("B{}", idx + 1),
+                    "subject": format!("{} 글", name),
+                    "contents": [{ "type": "text", "value": format!("{} 본문", name) }]
+                } }
+            }))
+            .unwrap()
+        })
+        .collect();
+    let options = Options::default();
+
+    let output = jsontohwpx::convert_batch(
+        &inputs,
+        &options,
+        &base_path(),
+        BatchMode::Combined { headings: true },
+    )
+    .unwrap();
+    let BatchOutput::Combined(output) = output else {
+        panic!("합친 문서가 아님");
+    };
+    let text = verify_hwpx_bytes(&output.bytes).extract_text();
+    let positions: Vec<usize> = [
+        "첫째 글",
+        "첫째 본문",
+        "둘째 글",
+        "둘째 본문",
+        "셋째 글",
+        "셋째 본문",
+    ]
+    .iter()
+    .map(|s| {
+        text.find(s)
+            .unwrap_or_else(|| panic!("{} 없음:\n{}", s, text))
+    })
+    .collect();
+    assert!(positions.windows(2).all(|w| w[0] < w[1]), "{}", text);
+    // 글 사이마다 쪽 나누기
+    assert_eq!(
+        section_xml(&output.bytes)
+            .matches(r#"pageBreak="1""#)
+            .count(),
+        2
+    );
+
+    let output =
+        jsontohwpx::convert_batch(&inputs, &options, &base_path(), BatchMode::Separate).unwrap();
+    let BatchOutput::Separate(documents) = output else {
+        panic!("글마다 따로 만든 문서가 아님");
+    };
+    let ids: Vec<&str> = documents.iter().map(|(id, _)| id.as_str()).collect();
+    assert_eq!(ids, ["B1", "B2", "B3"]);
+    for ((_, document), name) in documents.iter().zip(["첫째", "둘째", "셋째"]) {
+        let text = verify_hwpx_bytes(&document.bytes).extract_text();
+        assert_eq!(text, format!("{} 글\n{} 본문\n", name, name));
+    }
+
+    // 검증 오류는 몇 번째 글인지 알려 준다
+    let mut inputs = inputs;
+    inputs[1].data.article.atcl_id = " ".to_string();
+    let err = jsontohwpx::convert_batch(&inputs, &options, &base_path(), BatchMode::Separate)
+        .unwrap_err();
+    assert!(err.to_string().contains("articles[1]: atclId"), "{}", err);
+
+    // 검증도 입력의 options가 아니라 함께 넘긴 options를 따른다
+    inputs[1].data.article.atcl_id = "B2".to_string();
+    inputs[2].data.article.contents = serde_json::from_value(serde_json::json!([
+        { "type": "image", "base64": "@@@", "format": "png" }
+    ]))
+    .unwrap();
+    let err = jsontohwpx::convert_batch(&inputs, &options, &base_path(), BatchMode::Separate)
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("articles[2]: contents[0]"),
+        "{}",
+        err
+    );
+    let skip = Options {
+        on_error: OnError::SkipWithPlaceholder,
+        ..Options::default()
+    };
+    let output =
+        jsontohwpx::convert_batch(&inputs, &skip, &base_path(), BatchMode::Separate).unwrap();
+    let BatchOutput::Separate(documents) = output else {
+        panic!("글마다 따로 만든 문서가 아님");
+    };
+    assert_eq!(documents[2].1.warnings.len(), 1);
+}
+
+#[test]
+fn test_convert_into_existing_writer() {
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "EMB001",
+                "subject": "본문 글",
+                "regDt": "언젠가",
+                "contents": [
+                    { "type": "text", "value": "본문 첫 단락" },
+                    { "type": "table", "rows": [["항목", "값"]] }
+                ]
+            }
+        }
+    }"#;
+    let input: ApiResponse = serde_json::from_str(json).unwrap();
+
+    let mut writer = hwpers::HwpxWriter::new();
+    writer.add_paragraph("표지").unwrap();
+    let options = jsontohwpx::model::Options::default();
+    let warnings = jsontohwpx::convert_into(&mut writer, &input, &options, &base_path()).unwrap();
+    assert_eq!(warnings[0].code, "INVALID_DATE");
+    writer.add_paragraph("부록").unwrap();
+
+    let bytes = writer.to_bytes().unwrap();
+    let text = verify_hwpx_bytes(&bytes).extract_text();
+    let cover = text.find("표지").unwrap();
+    let body = text.find("본문 첫 단락").unwrap();
+    let appendix = text.find("부록").unwrap();
+    assert!(cover < body && body < appendix, "{}", text);
+    assert_eq!(HwpxReader::extract_tables(&bytes).unwrap().len(), 1);
+
+    // 입력 검증은 convert와 같다
+    let mut invalid = input;
+    invalid.response_code = "1".to_string();
+    let mut writer = hwpers::HwpxWriter::new();
+    let err = jsontohwpx::convert_into(&mut writer, &invalid, &options, &base_path()).unwrap_err();
+    assert_eq!(err.error_code(), "INPUT_ERROR");
+
+    // 검증은 입력의 options가 아니라 넘긴 options의 onError를 따른다
+    let mut broken_image = invalid;
+    broken_image.response_code = "0".to_string();
+    broken_image.data.article.contents = serde_json::from_value(serde_json::json!([
+        { "type": "image", "base64": "@@@", "format": "png" }
+    ]))
+    .unwrap();
+    let skip = jsontohwpx::model::Options {
+        on_error: jsontohwpx::model::OnError::SkipWithPlaceholder,
+        ..options
+    };
+    let mut writer = hwpers::HwpxWriter::new();
+    let warnings =
+        jsontohwpx::convert_into(&mut writer, &broken_image, &skip, &base_path()).unwrap();
+    assert!(warnings.iter().any(|w| w.code == "CONTENT_SKIPPED"));
+}
+
+#[test]
+fn test_template_conversion() {
+    use hwpers::hwpx::writer::{HwpxTable, HwpxTextStyle};
+    use hwpers::HwpxWriter;
+
+    let mut template = HwpxWriter::new();
+    template.add_header("회사 머리말");
+    template
+        .add_styled_paragraph("레터헤드", HwpxTextStyle::new().size(16).bold())
+        .unwrap();
+    template.add_paragraph("{{CONTENTS}}").unwrap();
+    template
+        .add_table(HwpxTable::from_data(vec![vec!["결재", "담당"]]))
+        .unwrap();
+    template.add_paragraph("끝맺음").unwrap();
+    let template = template.to_bytes().unwrap();
+
+    let json = r#"{
+        "responseCode": "0",
+        "data": {
+            "article": {
+                "atclId": "TPL001",
+                "contents": [
+                    { "type": "text", "value": "본문 첫째" },
+                    { "type": "text", "value": "본문 둘째" }
+                ]
+            }
+        }
+    }"#;
+    let mut input: ApiResponse = serde_json::from_str(json).unwrap();
+    input.options.template_bytes = Some(template.clone());
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+
+    let text = verify_hwpx_bytes(&bytes).extract_text();
+    assert!(!text.contains("{{CONTENTS}}"), "{}", text);
+    let order: Vec<usize> = ["레터헤드", "본문 첫째", "본문 둘째", "끝맺음"]
+        .iter()
+        .map(|s| {
+            text.find(s)
+                .unwrap_or_else(|| panic!("{} 없음:\n{}", s, text))
+        })
+        .collect();
+    assert!(order.windows(2).all(|w| w[0] < w[1]), "{}", text);
+
+    // 템플릿의 머리말, 글자 모양, 표가 남는다
+    let headers = HwpxReader::extract_headers(&bytes).unwrap();
+    assert_eq!(headers[0].text, "회사 머리말");
+    let runs = HwpxReader::extract_styled_paragraphs(&bytes).unwrap();
+    let letterhead = &runs[0][0];
+    assert_eq!(letterhead.text, "레터헤드");
+    assert!(letterhead.bold);
+    assert_eq!(letterhead.size_pt, 16.0);
+    let body = runs
+        .iter()
+        .flatten()
+        .find(|r| r.text == "본문 첫째")
+        .unwrap();
+    assert!(!body.bold);
+    assert_eq!(body.size_pt, 10.0);
+    let tables = HwpxReader::extract_tables(&bytes).unwrap();
+    assert_eq!(tables.len(), 1);
+    assert_eq!(tables[0].rows[0][0].text, "결재");
+
+    // templatePath로 읽고, 표시 단락이 없으면 끝에 붙인다
+    let tmp = tempfile::tempdir().unwrap();
+    let mut plain = HwpxWriter::new();
+    plain.add_paragraph("서식 문서").unwrap();
+    let path = tmp.path().join("template.hwpx");
+    std::fs::write(&path, plain.to_bytes().unwrap()).unwrap();
+    let mut input: ApiResponse = serde_json::from_str(json).unwrap();
+    input.options.template_path = Some(path.display().to_string());
+    let bytes = jsontohwpx::convert(&input, &base_path()).unwrap();
+    let text = verify_hwpx_bytes(&bytes).extract_text();
+    assert!(text.starts_with("서식 문서\n"), "{}", text);
+    assert!(text.contains("본문 둘째"), "{}", text);
+
+    input.options.template_path = Some("없는-템플릿.hwpx".to_string());
+    let err = jsontohwpx::convert(&input, &base_path()).unwrap_err();
+    assert!(err.to_string().contains("템플릿 파일 읽기 실패"), "{}", err);
+}
@@ -1,4 +1,4 @@
-use hwpers::{HwpReader, HwpWriter};
+use hwpers::{HwpReader, HwpWriter, HwpxReader};
 use std::path::PathBuf;
 
 fn test_file_path(name: &str) -> PathBuf {
@@ -138,3 +138,34 @@ fn test_minimal_document_structure() {
         }
     }
 }
+
+#[test]
+fn test_convert_hwp_to_hwpx() {
+    let mut writer = HwpWriter::new();
+    writer.add_paragraph("변환 테스트 문서").unwrap();
+    let bold = hwpers::style::TextStyle::new().bold();
+    writer.add_paragraph_with_style("굵은 문단", &bold).unwrap();
+    writer
+        .add_simple_table(&[vec!["이름", "값"], vec!["A", "1"]])
+        .unwrap();
+    writer.add_paragraph("마지막 줄").unwrap();
+    let hwp_bytes = writer.to_bytes().unwrap();
+
+    let expected = HwpReader::from_bytes(&hwp_bytes).unwrap().extract_text();
+    let hwpx_bytes = hwpers::convert_hwp_to_hwpx(&hwp_bytes).unwrap();
+    assert_eq!(&hwpx_bytes[0..2], b"PK");
+
+    let converted = HwpxReader::from_bytes(&hwpx_bytes).unwrap();
+    let text = converted.extract_text();
+    for line in ["변환 테스트 문서", "굵은 문단", "마지막 줄"] {
+        assert!(text.contains(line), "missing {line:?} in {text:?}");
+    }
+    // Table cells come through as plain paragraphs
+    let lines = |t: &str| -> Vec<String> {
+        t.lines()
+            .map(|l| l.trim_end_matches('\r').to_string())
+            .filter(|l| !l.is_empty())
+            .collect()
+    };
+    assert_eq!(lines(&text), lines(&expected));
+}